@@ -1,3 +1,4 @@
+use blackscholes::batch::{self, BatchBuffer, CalendarGrid};
 use blackscholes::OptionInputs;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -9,5 +10,34 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+/// A calendar grid of 50 strikes by 40 expiries priced as a plain batch and with the shared
+/// per-strike and per-expiry work of `price_calendar`.
+fn calendar_benchmark(c: &mut Criterion) {
+    let strikes: Vec<f64> = (0..50).map(|i| 75.0 + i as f64).collect();
+    let expiries: Vec<f64> = (1..=40).map(|i| i as f64 / 20.0).collect();
+    let vols: Vec<f64> = (0..strikes.len() * expiries.len())
+        .map(|i| 0.2 + 0.0001 * (i % 97) as f64)
+        .collect();
+
+    let mut buffer = BatchBuffer::new();
+    for (i, &k) in strikes.iter().enumerate() {
+        for (j, &t) in expiries.iter().enumerate() {
+            let option = OptionInputs::new(true, 100.0, k, 0.03, 0.01, t);
+            buffer.push(&option.with_implied_vol(vols[i * expiries.len() + j]));
+        }
+    }
+    let grid = CalendarGrid::new(true, 100.0, 0.03, 0.01, &strikes, &expiries, &vols);
+    let mut out = vec![0.0; grid.len()];
+
+    let mut group = c.benchmark_group("calendar");
+    group.bench_function("batch", |b| {
+        b.iter(|| batch::price(black_box(&buffer.as_inputs()), &mut out))
+    });
+    group.bench_function("price_calendar", |b| {
+        b.iter(|| batch::price_calendar(black_box(&grid), &mut out))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, calendar_benchmark);
 criterion_main!(benches);
@@ -0,0 +1,181 @@
+//! Price to implied vol round-trip accuracy harness.
+//!
+//! Sweeps a grid of moneyness, expiry, and vol, prices each option, solves the price back to an
+//! implied vol, and reports the largest relative error. Compile with the `accuracy` feature and
+//! run it on the target platform (e.g. WASM) to confirm the solver accuracy there.
+//!
+//! ```
+//! use blackscholes::accuracy::RoundTripGrid;
+//!
+//! let report = RoundTripGrid::default().run();
+//! assert_eq!(report.failures, 0);
+//! assert!(report.max_relative_error < 1e-9);
+//! ```
+
+use crate::OptionInputs;
+
+/// One point of the sweep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundTripCase {
+    pub is_call: bool,
+
+    /// Log-forward-moneyness `ln(K / F)`
+    pub moneyness: f64,
+    pub t: f64,
+    pub implied_vol: f64,
+    pub price: f64,
+
+    /// Price less the discounted intrinsic value on the forward
+    pub time_value: f64,
+
+    /// Implied vol recovered from the price, NaN if the solver failed
+    pub solved_vol: f64,
+}
+
+impl RoundTripCase {
+    pub fn relative_error(&self) -> f64 {
+        ((self.solved_vol - self.implied_vol) / self.implied_vol).abs()
+    }
+}
+
+/// The axes of the sweep. Calls and puts are both tested at every point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundTripGrid {
+    pub s: f64,
+    pub r: f64,
+    pub q: f64,
+    pub moneyness: Vec<f64>,
+    pub expiries: Vec<f64>,
+    pub vols: Vec<f64>,
+
+    /// Options whose time value is below this fraction of spot are skipped, since their implied
+    /// vol is not determined by a double-precision price
+    pub min_time_value: f64,
+
+    /// Options whose time value is below this fraction of their price are skipped for the same
+    /// reason, which excludes deep in-the-money options
+    pub min_time_value_fraction: f64,
+}
+
+fn linspace(lo: f64, hi: f64, n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| lo + (hi - lo) * i as f64 / (n - 1) as f64)
+        .collect()
+}
+
+impl Default for RoundTripGrid {
+    /// Moneyness from -1 to 1, expiries from a day to ten years, and vols from 1% to 300%.
+    fn default() -> Self {
+        Self {
+            s: 100.0,
+            r: 0.03,
+            q: 0.01,
+            moneyness: linspace(-1.0, 1.0, 41),
+            expiries: vec![
+                1.0 / 365.0,
+                7.0 / 365.0,
+                0.1,
+                0.25,
+                0.5,
+                1.0,
+                2.0,
+                5.0,
+                10.0,
+            ],
+            vols: vec![0.01, 0.05, 0.1, 0.2, 0.4, 0.8, 1.5, 3.0],
+            min_time_value: 1e-12,
+            min_time_value_fraction: 1e-6,
+        }
+    }
+}
+
+/// Summary of a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundTripReport {
+    /// Cases priced and solved
+    pub cases: usize,
+
+    /// Cases skipped for being priced below the threshold
+    pub skipped: usize,
+
+    /// Cases where no implied vol was returned
+    pub failures: usize,
+
+    pub max_relative_error: f64,
+    pub mean_relative_error: f64,
+
+    /// The case with the largest relative error
+    pub worst: Option<RoundTripCase>,
+}
+
+impl RoundTripGrid {
+    pub fn case(&self, is_call: bool, moneyness: f64, t: f64, implied_vol: f64) -> RoundTripCase {
+        let k = self.s * ((self.r - self.q) * t).exp() * moneyness.exp();
+        let option =
+            OptionInputs::new(is_call, self.s, k, self.r, self.q, t).with_implied_vol(implied_vol);
+        let price = option.price();
+        let forward = self.s * ((self.r - self.q) * t).exp();
+        let intrinsic = (option.sign() * (forward - k)).max(0.0) * option.rate_discount();
+        let solved_vol = OptionInputs::new(is_call, self.s, k, self.r, self.q, t)
+            .with_price(price)
+            .implied_vol();
+
+        RoundTripCase {
+            is_call,
+            moneyness,
+            t,
+            implied_vol,
+            price,
+            time_value: price - intrinsic,
+            solved_vol,
+        }
+    }
+
+    pub fn run(&self) -> RoundTripReport {
+        let mut report = RoundTripReport {
+            cases: 0,
+            skipped: 0,
+            failures: 0,
+            max_relative_error: 0.0,
+            mean_relative_error: 0.0,
+            worst: None,
+        };
+        let mut total_error = 0.0;
+
+        for &t in &self.expiries {
+            for &vol in &self.vols {
+                for &m in &self.moneyness {
+                    for is_call in [true, false] {
+                        let case = self.case(is_call, m, t, vol);
+                        if case.time_value.is_nan()
+                            || case.time_value <= self.min_time_value * self.s
+                            || case.time_value <= self.min_time_value_fraction * case.price
+                        {
+                            report.skipped += 1;
+                            continue;
+                        }
+
+                        report.cases += 1;
+                        if !case.solved_vol.is_finite() {
+                            report.failures += 1;
+                            continue;
+                        }
+
+                        let error = case.relative_error();
+                        total_error += error;
+                        if error > report.max_relative_error || report.worst.is_none() {
+                            report.max_relative_error = error;
+                            report.worst = Some(case);
+                        }
+                    }
+                }
+            }
+        }
+
+        let solved = report.cases - report.failures;
+        if solved > 0 {
+            report.mean_relative_error = total_error / solved as f64;
+        }
+        report
+    }
+}
@@ -0,0 +1,439 @@
+//! American options on underlyings paying discrete dividends.
+//!
+//! Without dividends an American call is never exercised early and is worth the same as the
+//! European. With discrete dividends the only times early exercise can pay are just before each
+//! ex-date, when the holder gives up the remaining time value to capture the dividend. Dividends
+//! are modelled as escrowed: the spot less the present value of dividends before expiry follows a
+//! lognormal process with the option's implied vol. For the spot itself to drop by each dividend
+//! on its ex-date, with puts as well as calls, see
+//! [`BinomialTree::price_with_dividends`](crate::binomial::BinomialTree::price_with_dividends).
+//!
+//! For a continuous dividend yield, [`OptionInputs::price_american`] gives the Bjerksund-Stensland
+//! (2002) approximation for calls and puts, and [`barone_adesi_whaley`] the faster, quadratic
+//! Barone-Adesi and Whaley (1987) approximation.
+
+use crate::portfolio::Position;
+use crate::solve;
+use crate::{calculate_bivariate_ncdf, calculate_ncdf, OptionInputs};
+
+/// A cash dividend paid at a known ex-date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dividend {
+    /// Time to the ex-date in years
+    pub t: f64,
+
+    /// Cash amount per share
+    pub amount: f64,
+}
+
+impl Dividend {
+    pub fn new(t: f64, amount: f64) -> Self {
+        Self { t, amount }
+    }
+}
+
+/// Present value at time `from` of the dividends with ex-dates in (`from`, `to`).
+fn pv_dividends(dividends: &[Dividend], r: f64, from: f64, to: f64) -> f64 {
+    dividends
+        .iter()
+        .filter(|d| d.t > from && d.t < to)
+        .map(|d| d.amount * (-r * (d.t - from)).exp())
+        .sum()
+}
+
+fn european_call(s: f64, k: f64, r: f64, t: f64, vol: f64) -> f64 {
+    if t <= 0.0 {
+        return (s - k).max(0.0);
+    }
+    OptionInputs::new(true, s, k, r, 0.0, t)
+        .with_implied_vol(vol)
+        .price()
+}
+
+/// European call on the escrowed spot, ignoring early exercise. The dividend yield `q` of
+/// `option` is not used; dividends are given explicitly.
+pub fn european_call_value(option: &OptionInputs, dividends: &[Dividend]) -> f64 {
+    let s = option.s - pv_dividends(dividends, option.r, 0.0, option.t);
+    european_call(s, option.k, option.r, option.t, option.implied_vol)
+}
+
+/// Cum-dividend spot just before `dividends[i]` goes ex above which exercising beats holding the
+/// ex-dividend option to expiry, or NaN if exercise there is never optimal.
+fn exercise_boundary(option: &OptionInputs, dividends: &[Dividend], i: usize) -> f64 {
+    let (k, r, vol) = (option.k, option.r, option.implied_vol);
+    let d = dividends[i];
+    let tau = option.t - d.t;
+    let later = pv_dividends(dividends, r, d.t, option.t);
+    if d.amount <= k * (1.0 - (-r * tau).exp()) - later {
+        return f64::NAN;
+    }
+
+    // holding minus exercising falls as spot rises, from K - D at zero to below zero far in the
+    // money, so there is exactly one crossing to bisect for
+    let hold_less_exercise = |s: f64| european_call(s - d.amount - later, k, r, tau, vol) - (s - k);
+    let (mut lo, mut hi) = (k, 2.0 * k);
+    while hold_less_exercise(hi) > 0.0 {
+        lo = hi;
+        hi *= 2.0;
+        if hi > 1e6 * k {
+            return f64::NAN;
+        }
+    }
+    solve::brent(hold_less_exercise, lo, hi, 1e-12 * k).unwrap_or(f64::NAN)
+}
+
+/// Roll-Geske-Whaley value of an American call with a single dividend before expiry, exact under
+/// the escrowed dividend model.
+fn roll_geske_whaley(option: &OptionInputs, d: Dividend, boundary: f64) -> f64 {
+    let (k, r, t, vol) = (option.k, option.r, option.t, option.implied_vol);
+    let s = option.s - d.amount * (-r * d.t).exp();
+    // the boundary is cum-dividend; the formula wants the ex-dividend price
+    let critical = boundary - d.amount;
+
+    let a1 = ((s / k).ln() + (r + 0.5 * vol * vol) * t) / (vol * t.sqrt());
+    let a2 = a1 - vol * t.sqrt();
+    let b1 = ((s / critical).ln() + (r + 0.5 * vol * vol) * d.t) / (vol * d.t.sqrt());
+    let b2 = b1 - vol * d.t.sqrt();
+    let rho = -(d.t / t).sqrt();
+
+    s * calculate_ncdf(b1) + s * calculate_bivariate_ncdf(a1, -b1, rho)
+        - k * (-r * t).exp() * calculate_bivariate_ncdf(a2, -b2, rho)
+        - (k - d.amount) * (-r * d.t).exp() * calculate_ncdf(b2)
+}
+
+/// Whether exercising just before one ex-date can be worth it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DividendCapture {
+    pub dividend: Dividend,
+
+    /// Interest earned by paying the strike at expiry rather than at the ex-date, net of the
+    /// later dividends that exercising also captures
+    pub interest_on_strike: f64,
+
+    /// Whether the dividend exceeds the interest on the strike, without which exercise before
+    /// this ex-date is never optimal
+    pub may_exercise: bool,
+
+    /// Cum-dividend spot just before the ex-date above which exercise is optimal, NaN if never
+    pub exercise_boundary: f64,
+}
+
+/// An American call's value split into its European part and the early-exercise premium.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PremiumDecomposition {
+    pub european: f64,
+    pub american: f64,
+
+    /// American less European value
+    pub early_exercise_premium: f64,
+
+    /// Analysis of each ex-date before expiry, in date order
+    pub dividends: Vec<DividendCapture>,
+}
+
+/// Decompose the value of an American call on a stock paying `dividends`.
+///
+/// With one dividend before expiry the American value is the Roll-Geske-Whaley closed form.
+/// With several it is Black's pseudo-American approximation: the best of holding to expiry and
+/// exercising just before each ex-date, each valued as a European, which is a lower bound on the
+/// true value.
+pub fn decompose_call(option: &OptionInputs, dividends: &[Dividend]) -> PremiumDecomposition {
+    let (k, r, t) = (option.k, option.r, option.t);
+    let mut dividends: Vec<Dividend> = dividends
+        .iter()
+        .copied()
+        .filter(|d| d.t > 0.0 && d.t < t)
+        .collect();
+    dividends.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+    let captures: Vec<DividendCapture> = (0..dividends.len())
+        .map(|i| {
+            let d = dividends[i];
+            let interest_on_strike =
+                k * (1.0 - (-r * (t - d.t)).exp()) - pv_dividends(&dividends, r, d.t, t);
+            DividendCapture {
+                dividend: d,
+                interest_on_strike,
+                may_exercise: d.amount > interest_on_strike,
+                exercise_boundary: exercise_boundary(option, &dividends, i),
+            }
+        })
+        .collect();
+
+    let european = european_call_value(option, &dividends);
+    let american = match captures.as_slice() {
+        [] => european,
+        [c] if c.exercise_boundary.is_nan() => european,
+        [c] => roll_geske_whaley(option, c.dividend, c.exercise_boundary),
+        _ => dividends.iter().fold(european, |best, d| {
+            let s = option.s - pv_dividends(&dividends, r, 0.0, d.t);
+            best.max(european_call(s, k, r, d.t, option.implied_vol))
+        }),
+    };
+
+    PremiumDecomposition {
+        european,
+        american,
+        early_exercise_premium: american - european,
+        dividends: captures,
+    }
+}
+
+/// Whether to exercise an American call just before one ex-date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExerciseAdvice {
+    pub dividend: Dividend,
+
+    /// Expected cum-dividend spot just before the ex-date, the forward of the escrowed spot plus
+    /// the dividends still to be paid
+    pub spot: f64,
+
+    /// Intrinsic value per unit from exercising at that spot
+    pub exercise_value: f64,
+
+    /// Value per unit of holding the ex-dividend option to expiry instead
+    pub hold_value: f64,
+
+    /// Whether exercising is worth more than holding
+    pub exercise: bool,
+
+    /// Exercise less hold value over all the units in the position, ignoring the sign of the
+    /// quantity. For a short position this is what the holders gain by exercising, so a positive
+    /// value flags likely assignment.
+    pub gain: f64,
+}
+
+/// Advise on early exercise of a call `position` before each ex-date of `dividends` that falls
+/// before expiry, in date order. The spot at each ex-date is taken at its forward, so the advice
+/// is most reliable for the next ex-date.
+pub fn ex_dividend_advice(position: &Position, dividends: &[Dividend]) -> Vec<ExerciseAdvice> {
+    let option = &position.option;
+    let (k, r, t) = (option.k, option.r, option.t);
+    let mut dividends: Vec<Dividend> = dividends
+        .iter()
+        .copied()
+        .filter(|d| option.is_call && d.t > 0.0 && d.t < t)
+        .collect();
+    dividends.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+    let escrowed = option.s - pv_dividends(&dividends, r, 0.0, t);
+    dividends
+        .iter()
+        .map(|&d| {
+            let later = pv_dividends(&dividends, r, d.t, t);
+            let spot = escrowed * (r * d.t).exp() + d.amount + later;
+            let exercise_value = (spot - k).max(0.0);
+            let hold_value =
+                european_call(spot - d.amount - later, k, r, t - d.t, option.implied_vol);
+            ExerciseAdvice {
+                dividend: d,
+                spot,
+                exercise_value,
+                hold_value,
+                exercise: exercise_value > hold_value,
+                gain: (exercise_value - hold_value) * position.units().abs(),
+            }
+        })
+        .collect()
+}
+
+/// `phi` of Bjerksund and Stensland: the value of receiving `s^gamma` at `t` if the spot has not
+/// by then reached the flat boundary `i`, and `h` is not crossed at expiry.
+#[allow(clippy::too_many_arguments)]
+fn bs_phi(s: f64, t: f64, gamma: f64, h: f64, i: f64, r: f64, b: f64, vol: f64) -> f64 {
+    let v2 = vol * vol;
+    let sd = vol * t.sqrt();
+    let lambda = (-r + gamma * b + 0.5 * gamma * (gamma - 1.0) * v2) * t;
+    let d = -((s / h).ln() + (b + (gamma - 0.5) * v2) * t) / sd;
+    let kappa = 2.0 * b / v2 + 2.0 * gamma - 1.0;
+    lambda.exp()
+        * s.powf(gamma)
+        * (calculate_ncdf(d) - (i / s).powf(kappa) * calculate_ncdf(d - 2.0 * (i / s).ln() / sd))
+}
+
+/// `psi` of Bjerksund and Stensland: as [`bs_phi`] with the boundary `i1` up to `t1` and `i2`
+/// from `t1` to `t2`.
+#[allow(clippy::too_many_arguments)]
+fn bs_psi(
+    s: f64,
+    t2: f64,
+    gamma: f64,
+    h: f64,
+    i2: f64,
+    i1: f64,
+    t1: f64,
+    r: f64,
+    b: f64,
+    vol: f64,
+) -> f64 {
+    let v2 = vol * vol;
+    let drift = b + (gamma - 0.5) * v2;
+    let (sd1, sd2) = (vol * t1.sqrt(), vol * t2.sqrt());
+
+    let e1 = ((s / i1).ln() + drift * t1) / sd1;
+    let e2 = ((i2 * i2 / (s * i1)).ln() + drift * t1) / sd1;
+    let e3 = ((s / i1).ln() - drift * t1) / sd1;
+    let e4 = ((i2 * i2 / (s * i1)).ln() - drift * t1) / sd1;
+
+    let f1 = ((s / h).ln() + drift * t2) / sd2;
+    let f2 = ((i2 * i2 / (s * h)).ln() + drift * t2) / sd2;
+    let f3 = ((i1 * i1 / (s * h)).ln() + drift * t2) / sd2;
+    let f4 = ((s * i1 * i1 / (h * i2 * i2)).ln() + drift * t2) / sd2;
+
+    let rho = (t1 / t2).sqrt();
+    let lambda = -r + gamma * b + 0.5 * gamma * (gamma - 1.0) * v2;
+    let kappa = 2.0 * b / v2 + 2.0 * gamma - 1.0;
+
+    (lambda * t2).exp()
+        * s.powf(gamma)
+        * (calculate_bivariate_ncdf(-e1, -f1, rho)
+            - (i2 / s).powf(kappa) * calculate_bivariate_ncdf(-e2, -f2, rho)
+            - (i1 / s).powf(kappa) * calculate_bivariate_ncdf(-e3, -f3, -rho)
+            + (i1 / i2).powf(kappa) * calculate_bivariate_ncdf(-e4, -f4, -rho))
+}
+
+/// Bjerksund-Stensland (2002) American call with cost of carry `b`, exercising at a boundary
+/// that is flat on each of two periods split at the golden ratio of the time to expiry.
+fn bjerksund_stensland_call(s: f64, k: f64, t: f64, r: f64, b: f64, vol: f64) -> f64 {
+    if b >= r {
+        // never exercised early
+        return OptionInputs::new(true, s, k, r, r - b, t)
+            .with_implied_vol(vol)
+            .price();
+    }
+
+    let v2 = vol * vol;
+    let t1 = 0.5 * (5.0_f64.sqrt() - 1.0) * t;
+    let beta = (0.5 - b / v2) + ((b / v2 - 0.5).powi(2) + 2.0 * r / v2).sqrt();
+    let b_inf = beta / (beta - 1.0) * k;
+    let b_0 = k.max(r / (r - b) * k);
+
+    let boundary = |tau: f64| {
+        let h = -(b * tau + 2.0 * vol * tau.sqrt()) * k * k / ((b_inf - b_0) * b_0);
+        b_0 + (b_inf - b_0) * (1.0 - h.exp())
+    };
+    let (i1, i2) = (boundary(t1), boundary(t));
+    if s >= i2 {
+        return s - k;
+    }
+
+    let alpha1 = (i1 - k) * i1.powf(-beta);
+    let alpha2 = (i2 - k) * i2.powf(-beta);
+    let phi = |gamma, h, i| bs_phi(s, t1, gamma, h, i, r, b, vol);
+    let psi = |gamma, h| bs_psi(s, t, gamma, h, i2, i1, t1, r, b, vol);
+
+    alpha2 * s.powf(beta) - alpha2 * phi(beta, i2, i2) + phi(1.0, i2, i2)
+        - phi(1.0, i1, i2)
+        - k * phi(0.0, i2, i2)
+        + k * phi(0.0, i1, i2)
+        + alpha1 * phi(beta, i1, i2)
+        - alpha1 * psi(beta, i1)
+        + psi(1.0, i1)
+        - psi(1.0, k)
+        - k * psi(0.0, i1)
+        + k * psi(0.0, k)
+}
+
+/// American options on an underlying with a continuous dividend yield.
+impl OptionInputs {
+    /// Price of the American option by the Bjerksund-Stensland (2002) approximation. Puts are
+    /// priced as calls through the American put-call transformation, swapping spot with strike
+    /// and the risk-free rate with the dividend yield.
+    pub fn price_american(&self) -> f64 {
+        let vol = self.implied_vol;
+        if self.t <= 0.0 {
+            let intrinsic = if self.is_call {
+                self.s - self.k
+            } else {
+                self.k - self.s
+            };
+            return intrinsic.max(0.0);
+        }
+        if self.is_call {
+            bjerksund_stensland_call(self.s, self.k, self.t, self.r, self.r - self.q, vol)
+        } else {
+            bjerksund_stensland_call(self.k, self.s, self.t, self.q, self.q - self.r, vol)
+        }
+    }
+}
+
+/// An American price approximated as the European price plus a premium for early exercise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarlyExercise {
+    pub european: f64,
+    pub american: f64,
+
+    /// American less European value
+    pub early_exercise_premium: f64,
+
+    /// Spot at or beyond which the option is exercised immediately, infinite for calls and zero
+    /// for puts that are never exercised early
+    pub critical_spot: f64,
+}
+
+/// Barone-Adesi and Whaley (1987) quadratic approximation of an American option on an underlying
+/// with a continuous dividend yield.
+pub fn barone_adesi_whaley(option: &OptionInputs) -> EarlyExercise {
+    let european = option.price();
+    let (s, k, r, t, vol) = (option.s, option.k, option.r, option.t, option.implied_vol);
+    let b = option.carry();
+    let never = EarlyExercise {
+        european,
+        american: european,
+        early_exercise_premium: 0.0,
+        critical_spot: if option.is_call { f64::INFINITY } else { 0.0 },
+    };
+    // calls are never exercised early without a dividend yield, puts without a positive rate
+    if t <= 0.0 || (option.is_call && b >= r) || (!option.is_call && r <= 0.0) {
+        return never;
+    }
+
+    let v2 = vol * vol;
+    let n = 2.0 * b / v2;
+    let m = 2.0 * r / v2;
+    let discount = 1.0 - (-r * t).exp();
+    let root = ((n - 1.0).powi(2) + 4.0 * m / discount).sqrt();
+    let sign = option.sign();
+    // q2 > 1 for calls, q1 < 0 for puts
+    let exponent = 0.5 * (1.0 - n + sign * root);
+
+    let at = |spot: f64| {
+        OptionInputs::new(option.is_call, spot, k, r, option.q, t).with_implied_vol(vol)
+    };
+    // premium coefficient that makes the value and its slope continuous at the critical spot
+    let coefficient = |spot: f64| {
+        let european = at(spot);
+        sign * spot / exponent * (1.0 - sign * european.delta())
+    };
+    let mismatch = |spot: f64| sign * (spot - k) - at(spot).price() - coefficient(spot);
+
+    let critical = if option.is_call {
+        let mut hi = 2.0 * k;
+        while mismatch(hi) < 0.0 && hi < 1e6 * k {
+            hi *= 2.0;
+        }
+        solve::brent(mismatch, k, hi, 1e-12 * k)
+    } else {
+        solve::brent(mismatch, 1e-9 * k, k, 1e-12 * k)
+    };
+    let Ok(critical) = critical else {
+        return never;
+    };
+
+    let exercised = if option.is_call {
+        s >= critical
+    } else {
+        s <= critical
+    };
+    let american = if exercised {
+        sign * (s - k)
+    } else {
+        european + coefficient(critical) * (s / critical).powf(exponent)
+    };
+    EarlyExercise {
+        european,
+        american,
+        early_exercise_premium: american - european,
+        critical_spot: critical,
+    }
+}
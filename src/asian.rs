@@ -0,0 +1,272 @@
+//! Asian options, which pay on the average of the underlying over an averaging window.
+//!
+//! The geometric average of a lognormal spot is itself lognormal, so geometric Asians have the
+//! closed form of Kemna and Vorst (1990): a Black-76 option on the average's forward at the
+//! average's vol, discounted from expiry. The average is taken continuously over the window or
+//! over equally spaced fixings ending at expiry. Besides pricing geometric Asians directly, the
+//! closed form is the usual control variate for simulating arithmetic ones.
+//!
+//! Arithmetic averages, which commodity markets mostly trade, have no closed form. The Turnbull
+//! and Wakeman (1991) approximation treats the arithmetic average as lognormal with its exact
+//! first two moments, so it prices as a Black-76 option whose forward and effective vol are the
+//! moment-matched ones, shown by [`AsianOption::arithmetic_black76`].
+//!
+//! Simulated arithmetic Asians use the geometric Asian on the same fixings as their control
+//! variate, see [`crate::mc::ControlVariate`]. The two averages are nearly perfectly correlated,
+//! so the control removes almost all of the simulation error. Continuous averaging is
+//! simulated on as many equally spaced fixings as the simulation has steps.
+
+use crate::black76::Black76Inputs;
+use crate::mc::ControlVariate;
+use crate::{quad, OptionInputs};
+
+/// `integral of e^(rate x)` over [`from`, `to`], without cancellation for small rates.
+fn exp_integral(rate: f64, from: f64, to: f64) -> f64 {
+    if rate.abs() < 1e-12 {
+        return to - from;
+    }
+    (rate * from).exp() * (rate * (to - from)).exp_m1() / rate
+}
+
+/// How the average is sampled over the averaging window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Averaging {
+    /// Continuously
+    Continuous,
+
+    /// At this many equally spaced fixings, the last at expiry
+    Discrete(usize),
+}
+
+/// The inputs to an Asian option on the average price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsianOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// Time in years at which the averaging window opens, 0 to average from today
+    pub averaging_start: f64,
+
+    pub averaging: Averaging,
+
+    /// Implied vol
+    pub implied_vol: f64,
+}
+
+impl AsianOption {
+    pub fn new(
+        is_call: bool,
+        s: f64,
+        k: f64,
+        r: f64,
+        q: f64,
+        t: f64,
+        averaging: Averaging,
+    ) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            averaging_start: 0.0,
+            averaging,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    pub fn with_averaging_start(mut self, averaging_start: f64) -> Self {
+        self.averaging_start = averaging_start;
+        self
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    /// Times of the fixings for discrete averaging, empty for continuous averaging.
+    pub fn fixing_times(&self) -> Vec<f64> {
+        match self.averaging {
+            Averaging::Continuous => Vec::new(),
+            Averaging::Discrete(n) => {
+                let step = (self.t - self.averaging_start) / n as f64;
+                (1..=n)
+                    .map(|i| self.averaging_start + i as f64 * step)
+                    .collect()
+            }
+        }
+    }
+
+    /// The mean time of the average, and its variance per unit of the underlying's variance:
+    /// the log of the geometric average has mean `ln S + (r - q - vol^2 / 2) mean_time` and
+    /// variance `vol^2 variance_time`.
+    fn geometric_times(&self) -> (f64, f64) {
+        match self.averaging {
+            Averaging::Continuous => {
+                let start = self.averaging_start;
+                (0.5 * (start + self.t), start + (self.t - start) / 3.0)
+            }
+            Averaging::Discrete(_) => {
+                let times = self.fixing_times();
+                let n = times.len() as f64;
+                // in a double sum over sorted fixings, the ith of n is the earlier in 2(n - i) + 1
+                // pairs
+                let covariance: f64 = times
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &t)| (2.0 * (n - i as f64) - 1.0) * t)
+                    .sum();
+                (times.iter().sum::<f64>() / n, covariance / (n * n))
+            }
+        }
+    }
+
+    /// Black-76 inputs on the forward of the geometric average, at the vol that gives it the
+    /// average's variance by expiry.
+    pub fn geometric_black76(&self) -> Black76Inputs {
+        let (mean_time, variance_time) = self.geometric_times();
+        let vol = self.implied_vol;
+        let log_forward = self.s.ln()
+            + (self.r - self.q - 0.5 * vol * vol) * mean_time
+            + 0.5 * vol * vol * variance_time;
+        Black76Inputs::new(self.is_call, log_forward.exp(), self.k, self.r, self.t)
+            .with_implied_vol(vol * (variance_time / self.t).sqrt())
+    }
+
+    /// Price on the geometric average.
+    pub fn geometric_price(&self) -> f64 {
+        self.geometric_black76().price()
+    }
+
+    pub fn geometric_delta(&self) -> f64 {
+        let black = self.geometric_black76();
+        black.delta() * black.f / self.s
+    }
+
+    pub fn geometric_gamma(&self) -> f64 {
+        let black = self.geometric_black76();
+        black.gamma() * (black.f / self.s).powi(2)
+    }
+
+    /// Sensitivity to the underlying's vol, which moves both the average's vol and, through the
+    /// convexity of the geometric mean, its forward.
+    pub fn geometric_vega(&self) -> f64 {
+        let (mean_time, variance_time) = self.geometric_times();
+        let black = self.geometric_black76();
+        let forward_sensitivity = self.implied_vol * (variance_time - mean_time);
+        black.vega() * (variance_time / self.t).sqrt()
+            + 0.01 * black.delta() * black.f * forward_sensitivity
+    }
+
+    /// Sensitivity to the risk-free rate, through both discounting and the average's forward.
+    pub fn geometric_rho(&self) -> f64 {
+        let (mean_time, _) = self.geometric_times();
+        let black = self.geometric_black76();
+        black.rho() + 0.01 * black.delta() * black.f * mean_time
+    }
+
+    /// The first two moments of the arithmetic average, per unit and squared unit of spot.
+    pub fn arithmetic_moments(&self) -> (f64, f64) {
+        let vol = self.implied_vol;
+        let b = self.r - self.q;
+        match self.averaging {
+            Averaging::Continuous => {
+                let (start, end) = (self.averaging_start, self.t);
+                let length = end - start;
+                // E[S_u S_v] for v <= u grows like e^(b u + (b + vol^2) v); integrate v in
+                // closed form and u by quadrature
+                let inner = |u: f64| (b * u).exp() * exp_integral(b + vol * vol, start, u);
+                let second = 2.0 * quad::integrate_legendre(inner, start, end, 32);
+                (
+                    exp_integral(b, start, end) / length,
+                    second / (length * length),
+                )
+            }
+            Averaging::Discrete(_) => {
+                let times = self.fixing_times();
+                let n = times.len() as f64;
+                let first = times.iter().map(|&t| (b * t).exp()).sum::<f64>() / n;
+                let second: f64 = times
+                    .iter()
+                    .map(|&ti| {
+                        times
+                            .iter()
+                            .map(|&tj| (b * (ti + tj) + vol * vol * ti.min(tj)).exp())
+                            .sum::<f64>()
+                    })
+                    .sum();
+                (first, second / (n * n))
+            }
+        }
+    }
+
+    /// Black-76 inputs on the lognormal distribution matching the arithmetic average's first two
+    /// moments: the forward is the average's and the implied vol is the effective vol.
+    pub fn arithmetic_black76(&self) -> Black76Inputs {
+        let (first, second) = self.arithmetic_moments();
+        let vol = ((second / (first * first)).ln() / self.t).sqrt();
+        Black76Inputs::new(self.is_call, self.s * first, self.k, self.r, self.t)
+            .with_implied_vol(vol)
+    }
+
+    /// Turnbull-Wakeman price on the arithmetic average.
+    pub fn arithmetic_price(&self) -> f64 {
+        self.arithmetic_black76().price()
+    }
+
+    /// The option with its average over the fixings a simulation with `steps` observes.
+    fn simulated(&self, steps: usize) -> Self {
+        match self.averaging {
+            Averaging::Continuous => Self {
+                averaging: Averaging::Discrete(steps.max(1)),
+                ..self.clone()
+            },
+            Averaging::Discrete(_) => self.clone(),
+        }
+    }
+}
+
+impl ControlVariate for AsianOption {
+    fn underlying(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_implied_vol(self.implied_vol)
+    }
+
+    fn observation_times(&self, steps: usize) -> Vec<f64> {
+        self.simulated(steps).fixing_times()
+    }
+
+    fn payoffs(&self, _times: &[f64], path: &[f64]) -> (f64, f64) {
+        let fixings = &path[1..];
+        let n = fixings.len() as f64;
+        let arithmetic = fixings.iter().sum::<f64>() / n;
+        let geometric = (fixings.iter().map(|s| s.ln()).sum::<f64>() / n).exp();
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        let discount = (-self.r * self.t).exp();
+        (
+            discount * (sign * (arithmetic - self.k)).max(0.0),
+            discount * (sign * (geometric - self.k)).max(0.0),
+        )
+    }
+
+    fn control_price(&self, steps: usize) -> f64 {
+        self.simulated(steps).geometric_price()
+    }
+}
@@ -0,0 +1,177 @@
+//! Worst-of autocallable notes, priced by correlated Monte Carlo.
+//!
+//! On each observation date the note looks at the worst performer of its basket, the lowest
+//! ratio of an asset's spot to its initial level. It pays a coupon if the worst performer is at
+//! or above the coupon barrier, with any coupons missed before under a memory feature, and it
+//! redeems early at par if the worst performer is at or above the autocall barrier. A note that
+//! survives to maturity repays par less a down-and-in put on the worst performer: if it ever fell
+//! below the knock-in barrier, the holder loses the worst performer's shortfall below the put
+//! strike.
+//!
+//! The note is a [`Payoff`] on the paths of [`MonteCarlo::correlated_paths`], whose steps must
+//! be a multiple of the number of observations so that every observation falls on a step. Cash
+//! paid on an earlier date is carried to maturity at the risk-free rate, so the simulation's
+//! discounting from maturity values it on the date it is paid.
+
+use crate::mc::{CorrelatedAssets, McPrice, MonteCarlo, NormalSource, Payoff};
+
+/// When the down-and-in put looks at the worst performer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KnockIn {
+    /// Only at maturity, a European barrier
+    #[default]
+    Maturity,
+
+    /// At every step of the simulation, approximating continuous monitoring with daily steps
+    Steps,
+}
+
+/// A worst-of autocallable note on correlated assets. Barriers and the put strike are fractions
+/// of the assets' initial levels, and the coupon is a fraction of the notional per observation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Autocallable {
+    /// The underlying assets, whose maturity is the note's
+    pub assets: CorrelatedAssets,
+
+    /// Equally spaced observation dates, the last at maturity
+    pub observations: usize,
+
+    pub notional: f64,
+    pub coupon: f64,
+    pub coupon_barrier: f64,
+
+    /// Autocall barrier, tested on every observation but the last
+    pub autocall_barrier: f64,
+
+    /// Whether a coupon paid also pays those missed on earlier observations
+    pub memory: bool,
+
+    pub knock_in_barrier: f64,
+    pub knock_in: KnockIn,
+
+    /// Strike of the down-and-in put. A knock-in loses the worst performer's shortfall below it
+    /// as a fraction of it, and nothing unless it is positive
+    pub put_strike: f64,
+}
+
+impl Autocallable {
+    /// A note on one unit of notional that autocalls and pays its coupon at the initial levels,
+    /// with an at-the-money put knocked in at maturity.
+    pub fn new(
+        assets: CorrelatedAssets,
+        observations: usize,
+        coupon: f64,
+        knock_in_barrier: f64,
+    ) -> Self {
+        Self {
+            assets,
+            observations: observations.max(1),
+            notional: 1.0,
+            coupon,
+            coupon_barrier: 1.0,
+            autocall_barrier: 1.0,
+            memory: false,
+            knock_in_barrier,
+            knock_in: KnockIn::default(),
+            put_strike: 1.0,
+        }
+    }
+
+    pub fn with_notional(mut self, notional: f64) -> Self {
+        self.notional = notional;
+        self
+    }
+
+    pub fn with_coupon_barrier(mut self, coupon_barrier: f64) -> Self {
+        self.coupon_barrier = coupon_barrier;
+        self
+    }
+
+    pub fn with_autocall_barrier(mut self, autocall_barrier: f64) -> Self {
+        self.autocall_barrier = autocall_barrier;
+        self
+    }
+
+    pub fn with_memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn with_knock_in(mut self, knock_in: KnockIn) -> Self {
+        self.knock_in = knock_in;
+        self
+    }
+
+    pub fn with_put_strike(mut self, put_strike: f64) -> Self {
+        self.put_strike = put_strike;
+        self
+    }
+
+    /// Price the note, `None` unless the simulation's steps are a multiple of the observations
+    /// and the correlation matrix is positive semi-definite.
+    pub fn price<R: NormalSource + ?Sized>(
+        &self,
+        simulation: &MonteCarlo,
+        rng: &mut R,
+    ) -> Option<McPrice> {
+        if !simulation.steps.is_multiple_of(self.observations) {
+            return None;
+        }
+        simulation.price_correlated(&self.assets, self, rng)
+    }
+
+    /// The worst performer at step `step` of `paths`.
+    fn worst(&self, paths: &[Vec<f64>], step: usize) -> f64 {
+        paths
+            .iter()
+            .zip(&self.assets.spots)
+            .map(|(path, s)| path[step] / s)
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+impl Payoff for Autocallable {
+    /// The note's cash flows carried to maturity, NaN unless the paths' steps are a multiple
+    /// of the observations.
+    fn payoff(&self, paths: &[Vec<f64>]) -> f64 {
+        let steps = paths[0].len() - 1;
+        if !steps.is_multiple_of(self.observations) {
+            return f64::NAN;
+        }
+        let stride = steps / self.observations;
+        let (r, t) = (self.assets.r, self.assets.t);
+        let carried = |amount: f64, i: usize| {
+            let paid = t * i as f64 / self.observations as f64;
+            amount * (r * (t - paid)).exp()
+        };
+
+        let mut value = 0.0;
+        let mut unpaid = 0;
+        for i in 1..=self.observations {
+            let worst = self.worst(paths, i * stride);
+            unpaid += 1;
+            if worst >= self.coupon_barrier {
+                let coupons = if self.memory { unpaid } else { 1 };
+                value += carried(self.notional * self.coupon * coupons as f64, i);
+                unpaid = 0;
+            }
+            if i < self.observations && worst >= self.autocall_barrier {
+                return value + carried(self.notional, i);
+            }
+        }
+
+        let worst = self.worst(paths, steps);
+        let knocked_in = match self.knock_in {
+            KnockIn::Maturity => worst < self.knock_in_barrier,
+            KnockIn::Steps => {
+                (0..=steps).any(|step| self.worst(paths, step) < self.knock_in_barrier)
+            }
+        };
+        let loss = if knocked_in && self.put_strike > 0.0 {
+            (self.put_strike - worst).max(0.0) / self.put_strike
+        } else {
+            0.0
+        };
+        value + self.notional * (1.0 - loss)
+    }
+}
@@ -0,0 +1,233 @@
+//! Bachelier (normal) model for European options on forwards.
+//!
+//! The forward follows an arithmetic Brownian motion, so the volatility is quoted in price units
+//! and negative forwards and strikes are allowed.
+//!
+//! Normal vols are solved from prices with [`BachelierInputs::with_price`], and
+//! [`normal_to_lognormal_vol`] and [`lognormal_to_normal_vol`] convert between the two quoting
+//! conventions by matching option prices.
+
+use crate::{calculate_ncdf, calculate_npdf, lets_be_rational, solve, DAYS_PER_YEAR};
+
+/// The inputs to the Bachelier model.
+#[derive(Debug, Clone)]
+pub struct BachelierInputs {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Forward price
+    pub f: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate used to discount the payoff
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// Normal (absolute) vol
+    pub normal_vol: f64,
+
+    /// Option price
+    pub price: f64,
+
+    /// Cache intermediate results to speed up subsequent calculations.
+    d: f64,
+    nd: f64,
+    nprimed: f64,
+}
+
+/// Methods for calculating the price and greeks of an option under the normal model.
+impl BachelierInputs {
+    pub fn new(is_call: bool, f: f64, k: f64, r: f64, t: f64) -> Self {
+        Self {
+            is_call,
+            f,
+            k,
+            r,
+            t,
+            normal_vol: f64::NAN,
+            price: f64::NAN,
+            d: f64::NAN,
+            nd: f64::NAN,
+            nprimed: f64::NAN,
+        }
+    }
+
+    pub fn with_normal_vol(mut self, normal_vol: f64) -> Self {
+        self.normal_vol = normal_vol;
+
+        let stddev = normal_vol * self.t.sqrt();
+        self.d = (self.f - self.k) / stddev;
+        self.nd = calculate_ncdf(self.sign() * self.d);
+        self.nprimed = calculate_npdf(self.d);
+
+        if !self.price.is_finite() {
+            self.price = self.rate_discount()
+                * (self.sign() * (self.f - self.k) * self.nd + stddev * self.nprimed);
+        }
+
+        self
+    }
+
+    /// Solve the normal vol from an option price. If the price admits no vol, the price is kept
+    /// and the normal vol and Greeks are left as NaN.
+    pub fn with_price(mut self, price: f64) -> Self {
+        self.price = price;
+        let undiscounted = price / self.rate_discount();
+        let normal_vol = implied_normal_vol(undiscounted, self.f, self.k, self.t, self.is_call);
+        if normal_vol > 0.0 {
+            self.with_normal_vol(normal_vol)
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    pub fn sign(&self) -> f64 {
+        if self.is_call {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    #[inline(always)]
+    pub fn rate_discount(&self) -> f64 {
+        (-self.r * self.t).exp()
+    }
+
+    pub fn normal_vol(&self) -> f64 {
+        self.normal_vol
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Sensitivity to the forward price.
+    pub fn delta(&self) -> f64 {
+        self.sign() * self.rate_discount() * self.nd
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.rate_discount() * self.nprimed / (self.normal_vol * self.t.sqrt())
+    }
+
+    /// Time decay per calendar day with the forward held fixed.
+    pub fn theta(&self) -> f64 {
+        (-self.rate_discount() * self.normal_vol * self.nprimed / (2.0 * self.t.sqrt())
+            + self.r * self.price)
+            / DAYS_PER_YEAR
+    }
+
+    /// Sensitivity to a 0.01 change in the normal vol.
+    pub fn vega(&self) -> f64 {
+        0.01 * self.rate_discount() * self.t.sqrt() * self.nprimed
+    }
+
+    /// Sensitivity to the discount rate with the forward held fixed.
+    pub fn rho(&self) -> f64 {
+        -0.01 * self.t * self.price
+    }
+
+    pub fn vanna(&self) -> f64 {
+        -0.01 * self.rate_discount() * self.nprimed * self.d / self.normal_vol
+    }
+
+    pub fn charm(&self) -> f64 {
+        let rate_discount = self.rate_discount();
+
+        self.sign() * self.r * rate_discount * self.nd
+            + rate_discount * self.nprimed * self.d / (2.0 * self.t)
+    }
+
+    pub fn vomma(&self) -> f64 {
+        self.vega() * self.d * self.d / self.normal_vol
+    }
+
+    pub fn speed(&self) -> f64 {
+        -self.gamma() * self.d / (self.normal_vol * self.t.sqrt())
+    }
+
+    pub fn zomma(&self) -> f64 {
+        self.gamma() * (self.d * self.d - 1.0) / self.normal_vol
+    }
+
+    pub fn dual_delta(&self) -> f64 {
+        -self.delta()
+    }
+
+    pub fn dual_gamma(&self) -> f64 {
+        self.gamma()
+    }
+}
+
+/// Undiscounted Bachelier price.
+fn undiscounted_price(is_call: bool, f: f64, k: f64, t: f64, normal_vol: f64) -> f64 {
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let stddev = normal_vol * t.sqrt();
+    let d = (f - k) / stddev;
+    sign * (f - k) * calculate_ncdf(sign * d) + stddev * calculate_npdf(d)
+}
+
+/// Normal vol at which the undiscounted Bachelier price is `price`, NaN if the price is not above
+/// intrinsic value.
+pub(crate) fn implied_normal_vol(price: f64, f: f64, k: f64, t: f64, is_call: bool) -> f64 {
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let intrinsic = (sign * (f - k)).max(0.0);
+    if price <= intrinsic || !price.is_finite() || t <= 0.0 {
+        return f64::NAN;
+    }
+    // solve on the out-of-the-money side by parity, where the price is all time value
+    let (price, is_call) = if intrinsic > 0.0 {
+        (price - intrinsic, !is_call)
+    } else {
+        (price, is_call)
+    };
+
+    // the at-the-money price is stddev / sqrt(2 pi), and widening the bracket covers the rest
+    let mut hi = (price + (f - k).abs()) * crate::SQRT_2PI / t.sqrt();
+    while undiscounted_price(is_call, f, k, t, hi) < price {
+        hi *= 2.0;
+    }
+    let value_and_vega = |vol: f64| {
+        let d = (f - k) / (vol * t.sqrt());
+        (
+            undiscounted_price(is_call, f, k, t, vol) - price,
+            t.sqrt() * calculate_npdf(d),
+        )
+    };
+    solve::newton(value_and_vega, 0.5 * hi, 1e-12 * hi, hi, 1e-15 * hi).unwrap_or(f64::NAN)
+}
+
+/// Lognormal vol giving the same price as `normal_vol` for forward `f`, strike `k`, and expiry
+/// `t`, priced on the out-of-the-money side. Needs positive `f` and `k`; NaN if no lognormal vol
+/// matches.
+pub fn normal_to_lognormal_vol(f: f64, k: f64, t: f64, normal_vol: f64) -> f64 {
+    if f <= 0.0 || k <= 0.0 {
+        return f64::NAN;
+    }
+    let is_call = k >= f;
+    let price = undiscounted_price(is_call, f, k, t, normal_vol);
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let vol = lets_be_rational::implied_volatility_from_a_transformed_rational_guess(
+        price, f, k, t, sign,
+    );
+    if vol > 0.0 && vol.is_finite() {
+        vol
+    } else {
+        f64::NAN
+    }
+}
+
+/// Normal vol giving the same price as the lognormal `vol` for forward `f`, strike `k`, and
+/// expiry `t`, priced on the out-of-the-money side.
+pub fn lognormal_to_normal_vol(f: f64, k: f64, t: f64, vol: f64) -> f64 {
+    let is_call = k >= f;
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let price = lets_be_rational::black(f, k, vol, t, sign);
+    implied_normal_vol(price, f, k, t, is_call)
+}
@@ -0,0 +1,282 @@
+//! Historical backtests of systematic option strategies.
+//!
+//! A backtest walks a series of daily spot and vol observations, opening, marking, and rolling
+//! option positions with the BSM pricer, and reports the daily P&L, position Greeks, and trades.
+
+use crate::calendar::Date;
+use crate::OptionInputs;
+
+/// Market state on a single day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+    pub date: Date,
+
+    /// Underlying price
+    pub spot: f64,
+
+    /// Implied vol used to price the option on this day
+    pub vol: f64,
+}
+
+impl Observation {
+    pub fn new(date: Date, spot: f64, vol: f64) -> Self {
+        Self { date, spot, vol }
+    }
+}
+
+/// Repeatedly sell or buy an option at a target delta and roll it on a fixed schedule, e.g.
+/// writing 30-delta calls against stock every month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollStrategy {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Absolute delta at which to choose the strike, e.g. 0.3
+    pub delta: f64,
+
+    /// Calendar days to expiry of each new option
+    pub tenor_days: i32,
+
+    /// Calendar days to hold each option before rolling, at most `tenor_days`
+    pub roll_days: i32,
+
+    /// Contracts held, negative for short options
+    pub quantity: f64,
+
+    /// Units of the underlying held throughout
+    pub underlying: f64,
+
+    /// Listed strike spacing to round chosen strikes to
+    pub strike_increment: Option<f64>,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+}
+
+impl RollStrategy {
+    pub fn new(is_call: bool, delta: f64, tenor_days: i32, quantity: f64) -> Self {
+        Self {
+            is_call,
+            delta,
+            tenor_days,
+            roll_days: tenor_days,
+            quantity,
+            underlying: 0.0,
+            strike_increment: None,
+            r: 0.0,
+            q: 0.0,
+        }
+    }
+
+    pub fn with_roll_days(mut self, roll_days: i32) -> Self {
+        self.roll_days = roll_days.min(self.tenor_days);
+        self
+    }
+
+    pub fn with_underlying(mut self, underlying: f64) -> Self {
+        self.underlying = underlying;
+        self
+    }
+
+    pub fn with_strike_increment(mut self, strike_increment: f64) -> Self {
+        self.strike_increment = Some(strike_increment);
+        self
+    }
+
+    pub fn with_rates(mut self, r: f64, q: f64) -> Self {
+        self.r = r;
+        self.q = q;
+        self
+    }
+
+    fn open(&self, obs: &Observation) -> OpenOption {
+        let t = self.tenor_days as f64 / crate::DAYS_PER_YEAR;
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        let k = OptionInputs::strike_from_delta(
+            self.is_call,
+            obs.spot,
+            self.r,
+            self.q,
+            t,
+            obs.vol,
+            sign * self.delta,
+        );
+        let k = match self.strike_increment {
+            Some(increment) => (k / increment).round() * increment,
+            None => k,
+        };
+
+        let mut option = OpenOption {
+            entry: obs.date,
+            expiry: obs.date.add_days(self.tenor_days),
+            k,
+            entry_price: 0.0,
+            inputs: None,
+        };
+        option.entry_price = option.mark(self, obs);
+        option
+    }
+
+    /// Run the strategy over the observations, which must be in date order.
+    pub fn backtest(&self, observations: &[Observation]) -> BacktestReport {
+        let mut report = BacktestReport::default();
+        let mut open: Option<OpenOption> = None;
+        let mut prev_spot = f64::NAN;
+        let mut prev_price = f64::NAN;
+        let mut value = 0.0;
+
+        for obs in observations {
+            let mut pnl = 0.0;
+            if prev_spot.is_finite() {
+                pnl += self.underlying * (obs.spot - prev_spot);
+            }
+
+            if let Some(option) = open.as_mut() {
+                let price = option.mark(self, obs);
+                pnl += self.quantity * (price - prev_price);
+                prev_price = price;
+
+                let held = obs.date.days_since(option.entry);
+                if held >= self.roll_days || obs.date >= option.expiry {
+                    report.trades.push(Trade {
+                        entry: option.entry,
+                        exit: obs.date,
+                        strike: option.k,
+                        entry_price: option.entry_price,
+                        exit_price: price,
+                        pnl: self.quantity * (price - option.entry_price),
+                    });
+                    open = None;
+                }
+            }
+
+            let option = open.get_or_insert_with(|| {
+                let option = self.open(obs);
+                prev_price = option.entry_price;
+                option
+            });
+
+            value += pnl;
+            prev_spot = obs.spot;
+
+            let (delta, gamma, vega, theta) = match &option.inputs {
+                Some(inputs) => (
+                    self.quantity * inputs.delta() + self.underlying,
+                    self.quantity * inputs.gamma(),
+                    self.quantity * inputs.vega(),
+                    self.quantity * inputs.theta(),
+                ),
+                None => (self.underlying, 0.0, 0.0, 0.0),
+            };
+
+            report.steps.push(BacktestStep {
+                date: obs.date,
+                spot: obs.spot,
+                vol: obs.vol,
+                strike: option.k,
+                option_price: prev_price,
+                pnl,
+                value,
+                delta,
+                gamma,
+                vega,
+                theta,
+            });
+        }
+
+        report
+    }
+}
+
+struct OpenOption {
+    entry: Date,
+    expiry: Date,
+    k: f64,
+    entry_price: f64,
+    /// Inputs at the latest mark, `None` once expired
+    inputs: Option<OptionInputs>,
+}
+
+impl OpenOption {
+    fn mark(&mut self, strategy: &RollStrategy, obs: &Observation) -> f64 {
+        let t = self.expiry.years_since(obs.date);
+        if t <= 0.0 {
+            self.inputs = None;
+            let sign = if strategy.is_call { 1.0 } else { -1.0 };
+            return (sign * (obs.spot - self.k)).max(0.0);
+        }
+
+        let inputs = OptionInputs::new(
+            strategy.is_call,
+            obs.spot,
+            self.k,
+            strategy.r,
+            strategy.q,
+            t,
+        )
+        .with_implied_vol(obs.vol);
+        let price = inputs.price();
+        self.inputs = Some(inputs);
+        price
+    }
+}
+
+/// The state of the strategy at the end of one observation date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestStep {
+    pub date: Date,
+    pub spot: f64,
+    pub vol: f64,
+
+    /// Strike of the option held at the end of the day
+    pub strike: f64,
+    pub option_price: f64,
+
+    /// P&L since the previous observation
+    pub pnl: f64,
+
+    /// Cumulative P&L since the start
+    pub value: f64,
+
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+}
+
+/// A completed option holding from entry to roll or expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub entry: Date,
+    pub exit: Date,
+    pub strike: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub pnl: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BacktestReport {
+    pub steps: Vec<BacktestStep>,
+    pub trades: Vec<Trade>,
+}
+
+impl BacktestReport {
+    pub fn total_pnl(&self) -> f64 {
+        self.steps.last().map_or(0.0, |s| s.value)
+    }
+
+    /// Largest peak-to-trough fall in cumulative P&L.
+    pub fn max_drawdown(&self) -> f64 {
+        let mut peak = 0.0_f64;
+        let mut drawdown = 0.0_f64;
+        for step in &self.steps {
+            peak = peak.max(step.value);
+            drawdown = drawdown.max(peak - step.value);
+        }
+        drawdown
+    }
+}
@@ -0,0 +1,603 @@
+//! Single barrier options with rebates, priced analytically.
+//!
+//! A knock-out option dies the first time the underlying touches the barrier and a knock-in
+//! option only comes alive then, with the barrier monitored continuously. The prices are the
+//! Reiner and Rubinstein (1991) formulas as arranged by Haug: each case is a sum of the terms
+//! `A` to `F`, the vanilla-like pieces and their reflections across the barrier. Knock-in rebates
+//! are paid at expiry if the barrier was never touched and knock-out rebates when it is.
+//!
+//! Each term is a sum of pieces of the form `w S^p N(u)` with `u` linear in `ln S`, so delta and
+//! gamma are differentiated in closed form, and vega follows by carrying each piece's derivative
+//! in the vol alongside its value. Bumping the spot instead is noisy next to the barrier, where
+//! the price bends sharply.
+//!
+//! Simulated single barrier options use the vanilla as their control variate, see
+//! [`crate::mc::ControlVariate`]. Between the simulated times each path is weighted by the
+//! probability that its Brownian bridge stayed clear of the barrier, which keeps the monitoring
+//! continuous; rebates paid on touching are discounted from the end of the step.
+//!
+//! [`DoubleBarrierOption`] has a barrier on each side of the spot and knocks on touching either.
+//! Its knock-out price is the Ikeda and Kunitomo (1992) series over repeated reflections in both
+//! barriers, summed until the terms vanish, which takes only a few terms unless the corridor is
+//! narrow next to the vol; the knock-in is the vanilla less the knock-out.
+
+use crate::mc::ControlVariate;
+use crate::reflection::BrownianMotion;
+use crate::{calculate_ncdf, calculate_npdf, OptionInputs};
+
+/// Which side of the spot the barrier is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// Whether touching the barrier activates or extinguishes the option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Knock {
+    In,
+    Out,
+}
+
+/// The inputs to a single barrier option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarrierOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub direction: Direction,
+    pub knock: Knock,
+    pub barrier: f64,
+
+    /// Cash paid if a knock-in option is never activated or a knock-out option is
+    pub rebate: f64,
+
+    /// Implied vol
+    pub implied_vol: f64,
+}
+
+/// The terms `A` to `F` of Haug's arrangement, for option sign `phi` and barrier sign `eta`.
+struct Terms {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+/// A number with its derivative in the vol.
+#[derive(Debug, Clone, Copy)]
+struct Dual {
+    value: f64,
+    vol: f64,
+}
+
+impl Dual {
+    fn constant(value: f64) -> Self {
+        Self { value, vol: 0.0 }
+    }
+
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        Self {
+            value,
+            vol: value * self.vol,
+        }
+    }
+}
+
+impl std::ops::Add for Dual {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: self.value + other.value,
+            vol: self.vol + other.vol,
+        }
+    }
+}
+
+impl std::ops::Sub for Dual {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            value: self.value - other.value,
+            vol: self.vol - other.vol,
+        }
+    }
+}
+
+impl std::ops::Mul for Dual {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self {
+            value: self.value * other.value,
+            vol: self.vol * other.value + self.value * other.vol,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for Dual {
+    type Output = Self;
+    fn mul(self, scale: f64) -> Self {
+        Self {
+            value: self.value * scale,
+            vol: self.vol * scale,
+        }
+    }
+}
+
+impl std::ops::Div for Dual {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Self {
+            value: self.value / other.value,
+            vol: (self.vol - self.value * other.vol / other.value) / other.value,
+        }
+    }
+}
+
+/// One piece `weight * S^power * N(arg)` of a term, where `arg` moves with `slope * ln S`, so
+/// that its derivatives in the spot are closed-form.
+struct Piece {
+    weight: Dual,
+    power: Dual,
+    arg: Dual,
+    slope: f64,
+}
+
+impl Piece {
+    fn price(&self, s: f64) -> f64 {
+        self.weight.value * s.powf(self.power.value) * calculate_ncdf(self.arg.value)
+    }
+
+    fn delta(&self, s: f64) -> f64 {
+        let (p, u) = (self.power.value, self.arg.value);
+        self.weight.value
+            * s.powf(p - 1.0)
+            * (p * calculate_ncdf(u) + self.slope * calculate_npdf(u))
+    }
+
+    fn gamma(&self, s: f64) -> f64 {
+        let (p, u, a) = (self.power.value, self.arg.value, self.slope);
+        self.weight.value
+            * s.powf(p - 2.0)
+            * (p * (p - 1.0) * calculate_ncdf(u)
+                + ((2.0 * p - 1.0) * a - a * a * u) * calculate_npdf(u))
+    }
+
+    fn vega(&self, s: f64) -> f64 {
+        let growth = (self.power * Dual::constant(s.ln())).exp();
+        let (u, n) = (self.arg.value, calculate_ncdf(self.arg.value));
+        let weighted = self.weight * growth;
+        0.01 * (weighted.vol * n + weighted.value * calculate_npdf(u) * self.arg.vol)
+    }
+}
+
+impl BarrierOption {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_call: bool,
+        s: f64,
+        k: f64,
+        r: f64,
+        q: f64,
+        t: f64,
+        direction: Direction,
+        knock: Knock,
+        barrier: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            direction,
+            knock,
+            barrier,
+            rebate: 0.0,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    pub fn with_rebate(mut self, rebate: f64) -> Self {
+        self.rebate = rebate;
+        self
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    /// Whether the spot is already at or through the barrier.
+    pub fn is_breached(&self) -> bool {
+        match self.direction {
+            Direction::Up => self.s >= self.barrier,
+            Direction::Down => self.s <= self.barrier,
+        }
+    }
+
+    /// The vanilla option the barrier option knocks into or out of.
+    pub fn vanilla(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_implied_vol(self.implied_vol)
+    }
+
+    /// The pieces of each of the terms `A` to `F`.
+    fn pieces(&self) -> [Vec<Piece>; 6] {
+        let phi = if self.is_call { 1.0 } else { -1.0 };
+        let eta = match self.direction {
+            Direction::Down => 1.0,
+            Direction::Up => -1.0,
+        };
+        let (k, h, r, t) = (self.k, self.barrier, self.r, self.t);
+        let vol = self.implied_vol;
+        let b = r - self.q;
+        let ln_s = Dual::constant(self.s.ln());
+        let one = Dual::constant(1.0);
+        let stddev = Dual {
+            value: vol * t.sqrt(),
+            vol: t.sqrt(),
+        };
+        let mu = Dual {
+            value: (b - 0.5 * vol * vol) / (vol * vol),
+            vol: -2.0 * b / vol.powi(3),
+        };
+        let lambda_value = (mu.value * mu.value + 2.0 * r / (vol * vol)).sqrt();
+        let lambda = Dual {
+            value: lambda_value,
+            vol: (mu.value * mu.vol - 2.0 * r / vol.powi(3)) / lambda_value,
+        };
+        let m = one + mu;
+        let slope = 1.0 / stddev.value;
+
+        // `(ln S - ln level) / stddev + (1 + mu) stddev` and its reflection across the barrier
+        let x = |level: f64| (ln_s - Dual::constant(level.ln())) / stddev + m * stddev;
+        let y = |level: f64| (Dual::constant(level.ln()) - ln_s) / stddev + m * stddev;
+        let z = (Dual::constant(h.ln()) - ln_s) / stddev + lambda * stddev;
+
+        let share = Dual::constant(phi * (-self.q * t).exp());
+        let cash = Dual::constant(-phi * k * (-r * t).exp());
+        let ln_h = Dual::constant(h.ln());
+        let h_to = |power: Dual| (power * ln_h).exp();
+        let zero = Dual::constant(0.0);
+
+        let vanilla_like = |x: Dual| {
+            vec![
+                Piece {
+                    weight: share,
+                    power: one,
+                    arg: x * phi,
+                    slope: phi * slope,
+                },
+                Piece {
+                    weight: cash,
+                    power: zero,
+                    arg: (x - stddev) * phi,
+                    slope: phi * slope,
+                },
+            ]
+        };
+        let reflected = |y: Dual| {
+            vec![
+                Piece {
+                    weight: share * h_to(m * 2.0),
+                    power: one - m * 2.0,
+                    arg: y * eta,
+                    slope: -eta * slope,
+                },
+                Piece {
+                    weight: cash * h_to(mu * 2.0),
+                    power: mu * -2.0,
+                    arg: (y - stddev) * eta,
+                    slope: -eta * slope,
+                },
+            ]
+        };
+        let rebate = Dual::constant(self.rebate);
+        let rebate_cash = rebate * Dual::constant((-r * t).exp());
+
+        [
+            vanilla_like(x(k)),
+            vanilla_like(x(h)),
+            reflected(y(h * h / k)),
+            reflected(y(h)),
+            vec![
+                Piece {
+                    weight: rebate_cash,
+                    power: zero,
+                    arg: (x(h) - stddev) * eta,
+                    slope: eta * slope,
+                },
+                Piece {
+                    weight: rebate_cash * h_to(mu * 2.0) * -1.0,
+                    power: mu * -2.0,
+                    arg: (y(h) - stddev) * eta,
+                    slope: -eta * slope,
+                },
+            ],
+            vec![
+                Piece {
+                    weight: rebate * h_to(mu + lambda),
+                    power: (mu + lambda) * -1.0,
+                    arg: z * eta,
+                    slope: -eta * slope,
+                },
+                Piece {
+                    weight: rebate * h_to(mu - lambda),
+                    power: (lambda - mu),
+                    arg: (z - lambda * stddev * 2.0) * eta,
+                    slope: -eta * slope,
+                },
+            ],
+        ]
+    }
+
+    /// The terms `A` to `F` measured by `measure` at the spot, which is linear in the pieces.
+    fn terms(&self, measure: impl Fn(&Piece, f64) -> f64) -> Terms {
+        let [a, b, c, d, e, f] = self
+            .pieces()
+            .map(|pieces| pieces.iter().map(|piece| measure(piece, self.s)).sum());
+        Terms { a, b, c, d, e, f }
+    }
+
+    /// The combination of the terms that makes up the option, measured by `measure`.
+    fn combine(&self, measure: impl Fn(&Piece, f64) -> f64) -> f64 {
+        let Terms { a, b, c, d, e, f } = self.terms(measure);
+        let above = self.k >= self.barrier;
+        match (self.knock, self.direction, self.is_call, above) {
+            (Knock::In, Direction::Down, true, true) => c + e,
+            (Knock::In, Direction::Down, true, false) => a - b + d + e,
+            (Knock::In, Direction::Up, true, true) => a + e,
+            (Knock::In, Direction::Up, true, false) => b - c + d + e,
+            (Knock::In, Direction::Down, false, true) => b - c + d + e,
+            (Knock::In, Direction::Down, false, false) => a + e,
+            (Knock::In, Direction::Up, false, true) => a - b + d + e,
+            (Knock::In, Direction::Up, false, false) => c + e,
+            (Knock::Out, Direction::Down, true, true) => a - c + f,
+            (Knock::Out, Direction::Down, true, false) => b - d + f,
+            (Knock::Out, Direction::Up, true, true) => f,
+            (Knock::Out, Direction::Up, true, false) => a - b + c - d + f,
+            (Knock::Out, Direction::Down, false, true) => a - b + c - d + f,
+            (Knock::Out, Direction::Down, false, false) => f,
+            (Knock::Out, Direction::Up, false, true) => b - d + f,
+            (Knock::Out, Direction::Up, false, false) => a - c + f,
+        }
+    }
+
+    pub fn price(&self) -> f64 {
+        if self.is_breached() {
+            return match self.knock {
+                Knock::In => self.vanilla().price(),
+                Knock::Out => self.rebate,
+            };
+        }
+        self.combine(Piece::price)
+    }
+
+    /// Sensitivity to the spot, differentiated in closed form. Once breached the option is the
+    /// vanilla or a fixed rebate.
+    pub fn delta(&self) -> f64 {
+        match (self.is_breached(), self.knock) {
+            (true, Knock::In) => self.vanilla().delta(),
+            (true, Knock::Out) => 0.0,
+            (false, _) => self.combine(Piece::delta),
+        }
+    }
+
+    pub fn gamma(&self) -> f64 {
+        match (self.is_breached(), self.knock) {
+            (true, Knock::In) => self.vanilla().gamma(),
+            (true, Knock::Out) => 0.0,
+            (false, _) => self.combine(Piece::gamma),
+        }
+    }
+
+    /// Sensitivity to the vol, per 0.01.
+    pub fn vega(&self) -> f64 {
+        match (self.is_breached(), self.knock) {
+            (true, Knock::In) => self.vanilla().vega(),
+            (true, Knock::Out) => 0.0,
+            (false, _) => self.combine(Piece::vega),
+        }
+    }
+}
+
+impl ControlVariate for BarrierOption {
+    fn underlying(&self) -> OptionInputs {
+        self.vanilla()
+    }
+
+    fn observation_times(&self, steps: usize) -> Vec<f64> {
+        let dt = self.t / steps.max(1) as f64;
+        (1..=steps.max(1)).map(|i| i as f64 * dt).collect()
+    }
+
+    fn payoffs(&self, times: &[f64], path: &[f64]) -> (f64, f64) {
+        let motion = BrownianMotion::log_spot(self.r, self.q, self.implied_vol);
+        let level = self.barrier.ln();
+        let (mut survival, mut touch_rebate, mut previous) = (1.0, 0.0, 0.0);
+        for (&t, ends) in times.iter().zip(path.windows(2)) {
+            let hit =
+                motion.bridge_hit_probability(ends[0].ln(), ends[1].ln(), level, t - previous);
+            touch_rebate += survival * hit * self.rebate * (-self.r * t).exp();
+            survival *= 1.0 - hit;
+            previous = t;
+        }
+
+        let discount = (-self.r * self.t).exp();
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        let vanilla = discount * (sign * (path[path.len() - 1] - self.k)).max(0.0);
+        let value = match self.knock {
+            Knock::Out => vanilla * survival + touch_rebate,
+            Knock::In => vanilla * (1.0 - survival) + discount * self.rebate * survival,
+        };
+        (value, vanilla)
+    }
+
+    fn control_price(&self, _steps: usize) -> f64 {
+        self.vanilla().price()
+    }
+}
+
+/// The inputs to a double barrier option, with flat barriers either side of the spot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoubleBarrierOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub knock: Knock,
+    pub lower: f64,
+    pub upper: f64,
+
+    /// Implied vol
+    pub implied_vol: f64,
+}
+
+/// More reflections than any corridor wider than a thousandth of the vol needs.
+const MAX_REFLECTIONS: i32 = 1000;
+
+impl DoubleBarrierOption {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_call: bool,
+        s: f64,
+        k: f64,
+        r: f64,
+        q: f64,
+        t: f64,
+        knock: Knock,
+        lower: f64,
+        upper: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            knock,
+            lower,
+            upper,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    /// Whether the spot is already at or outside either barrier.
+    pub fn is_breached(&self) -> bool {
+        self.s <= self.lower || self.s >= self.upper
+    }
+
+    /// The vanilla option the barrier option knocks into or out of.
+    pub fn vanilla(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_implied_vol(self.implied_vol)
+    }
+
+    /// The knock-out's share and cash legs: the values, per unit of forward share and of
+    /// discounted cash, of finishing between `lo` and `hi` without touching either barrier.
+    fn surviving_legs(&self, lo: f64, hi: f64) -> (f64, f64) {
+        let vol = self.implied_vol;
+        let stddev = vol * self.t.sqrt();
+        let drift = (self.r - self.q + 0.5 * vol * vol) * self.t;
+        let m = 2.0 * (self.r - self.q) / (vol * vol) + 1.0;
+        let (ln_s, ln_l) = (self.s.ln(), self.lower.ln());
+        let width = (self.upper / self.lower).ln();
+
+        // reflection n contributes the paths shifted by 2n corridor widths, less their images
+        // in the lower barrier
+        let leg = |n: f64, power: f64, shift: f64| {
+            let band = |ln_z: f64| {
+                let e = |bound: f64| calculate_ncdf((ln_z - bound.ln() + drift) / stddev - shift);
+                e(lo) - e(hi)
+            };
+            let direct = (power * n * width).exp() * band(ln_s + 2.0 * n * width);
+            let image_log = 2.0 * ln_l - ln_s - 2.0 * n * width;
+            let image = (power * (ln_l - ln_s - n * width)).exp() * band(image_log);
+            direct - image
+        };
+        let term = |n: f64| (leg(n, m, 0.0), leg(n, m - 2.0, stddev));
+
+        let (mut share, mut cash) = term(0.0);
+        for n in 1..=MAX_REFLECTIONS {
+            let (up, down) = (term(n as f64), term(-n as f64));
+            let (ds, dc) = (up.0 + down.0, up.1 + down.1);
+            share += ds;
+            cash += dc;
+            if ds.abs() < 1e-16 && dc.abs() < 1e-16 {
+                break;
+            }
+        }
+        (share, cash)
+    }
+
+    pub fn price(&self) -> f64 {
+        if self.is_breached() {
+            return match self.knock {
+                Knock::In => self.vanilla().price(),
+                Knock::Out => 0.0,
+            };
+        }
+
+        let (lo, hi) = if self.is_call {
+            (self.k.max(self.lower), self.upper)
+        } else {
+            (self.lower, self.k.min(self.upper))
+        };
+        let knock_out = if lo >= hi {
+            0.0
+        } else {
+            let (share, cash) = self.surviving_legs(lo, hi);
+            let share = self.s * (-self.q * self.t).exp() * share;
+            let cash = self.k * (-self.r * self.t).exp() * cash;
+            if self.is_call {
+                share - cash
+            } else {
+                cash - share
+            }
+        };
+        match self.knock {
+            Knock::Out => knock_out,
+            Knock::In => self.vanilla().price() - knock_out,
+        }
+    }
+}
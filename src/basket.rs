@@ -0,0 +1,259 @@
+//! Basket options on a weighted sum of lognormal assets, priced by moment matching.
+//!
+//! A basket call pays `max(w1 S1 + ... + wn Sn - K, 0)` at expiry. The sum of lognormals has no
+//! closed-form distribution, but its moments do: each is a sum over products of the assets'
+//! forwards growing by their covariances. The lognormal approximation of Levy (1992) treats the
+//! basket as lognormal with its first two moments, a Black-76 option on the basket's forward at
+//! an effective vol, which is also the vol at which an index on the basket should trade. The
+//! shifted-lognormal approximation matches the third moment as well through a displacement,
+//! which captures the basket's skew and handles negative weights, whose skew may even be
+//! negative, by reflecting the basket.
+
+use crate::correlation::{shifted, CorrelationRisk};
+use crate::mc::{CorrelatedAssets, Payoff};
+use crate::Black76Inputs;
+
+/// How the basket's distribution is approximated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasketApproximation {
+    /// Lognormal with the basket's mean and variance
+    Lognormal,
+
+    /// Shifted lognormal with the basket's mean, variance, and skewness
+    ShiftedLognormal,
+}
+
+/// The inputs to an option on a basket of assets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasketOption {
+    /// The type of the option (call or put on the basket)
+    pub is_call: bool,
+
+    /// Prices of the assets
+    pub spots: Vec<f64>,
+
+    /// Units of each asset in the basket, negative for short positions
+    pub weights: Vec<f64>,
+
+    /// Dividend yields of the assets
+    pub dividends: Vec<f64>,
+
+    /// Vols of the assets
+    pub vols: Vec<f64>,
+
+    /// Correlations of the assets' returns, a symmetric matrix with a unit diagonal
+    pub correlation: Vec<Vec<f64>>,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub approximation: BasketApproximation,
+}
+
+impl BasketOption {
+    /// A basket of assets without dividends, priced with the lognormal approximation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_call: bool,
+        spots: Vec<f64>,
+        weights: Vec<f64>,
+        vols: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+        k: f64,
+        r: f64,
+        t: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            dividends: vec![0.0; spots.len()],
+            spots,
+            weights,
+            vols,
+            correlation,
+            k,
+            r,
+            t,
+            approximation: BasketApproximation::Lognormal,
+        }
+    }
+
+    pub fn with_dividends(mut self, dividends: Vec<f64>) -> Self {
+        self.dividends = dividends;
+        self
+    }
+
+    pub fn with_approximation(mut self, approximation: BasketApproximation) -> Self {
+        self.approximation = approximation;
+        self
+    }
+
+    /// The assets to simulate the basket on, see [`crate::mc::MonteCarlo::price_correlated`].
+    pub fn assets(&self) -> CorrelatedAssets {
+        CorrelatedAssets::new(
+            self.spots.clone(),
+            self.vols.clone(),
+            self.correlation.clone(),
+            self.r,
+            self.t,
+        )
+        .with_dividends(self.dividends.clone())
+    }
+
+    /// Forward of each asset's position in the basket, its weight times its forward price.
+    pub fn weighted_forwards(&self) -> Vec<f64> {
+        self.spots
+            .iter()
+            .zip(&self.weights)
+            .zip(&self.dividends)
+            .map(|((s, w), q)| w * s * ((self.r - q) * self.t).exp())
+            .collect()
+    }
+
+    /// Forward of the basket.
+    pub fn forward(&self) -> f64 {
+        self.weighted_forwards().iter().sum()
+    }
+
+    /// `E[S_i S_j] / (F_i F_j)`, the growth of each pair of assets by their covariance.
+    fn covariance_growth(&self) -> Vec<Vec<f64>> {
+        let n = self.spots.len();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (self.correlation[i][j] * self.vols[i] * self.vols[j] * self.t).exp())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The first three raw moments of the basket's value at expiry.
+    pub fn moments(&self) -> [f64; 3] {
+        let forwards = self.weighted_forwards();
+        let growth = self.covariance_growth();
+        let n = forwards.len();
+        let mut moments = [forwards.iter().sum(), 0.0, 0.0];
+        for i in 0..n {
+            for j in 0..n {
+                let pair = forwards[i] * forwards[j] * growth[i][j];
+                moments[1] += pair;
+                moments[2] += (0..n)
+                    .map(|l| pair * forwards[l] * growth[i][l] * growth[j][l])
+                    .sum::<f64>();
+            }
+        }
+        moments
+    }
+
+    /// Vol of the lognormal with the basket's mean and variance, NaN if the basket's forward is
+    /// not positive.
+    pub fn effective_vol(&self) -> f64 {
+        let [first, second, _] = self.moments();
+        if first <= 0.0 {
+            return f64::NAN;
+        }
+        ((second / (first * first)).ln() / self.t).sqrt()
+    }
+
+    /// Black-76 inputs whose price is the basket option's under the approximation, or `None`
+    /// when the matched distribution leaves the option certain to finish in or out of the money,
+    /// or the basket's forward is not positive under the lognormal approximation.
+    ///
+    /// With negative skewness the shifted lognormal is matched to the negated basket, so the
+    /// inputs are then for the opposite option on it at the negated forward and strike.
+    pub fn black76(&self) -> Option<Black76Inputs> {
+        let [first, second, third] = self.moments();
+        let variance = second - first * first;
+        let (reflection, shift, vol) = match self.approximation {
+            BasketApproximation::Lognormal => (1.0, 0.0, self.effective_vol()),
+            BasketApproximation::ShiftedLognormal => {
+                let skewness =
+                    (third - 3.0 * first * variance - first.powi(3)) / variance.powf(1.5);
+                // a lognormal with `w = exp(vol^2 t)` has skewness `(w + 2) sqrt(w - 1)`, a
+                // cubic in `u = sqrt(w - 1)` solved by Cardano's formula
+                let half = 0.5 * skewness.abs();
+                let root = (half * half + 1.0).sqrt();
+                let u = (half + root).cbrt() + (half - root).cbrt();
+                let reflection = if skewness < 0.0 { -1.0 } else { 1.0 };
+                let mean = variance.sqrt() / u;
+                (
+                    reflection,
+                    mean - reflection * first,
+                    ((1.0 + u * u).ln() / self.t).sqrt(),
+                )
+            }
+        };
+        let is_call = self.is_call == (reflection > 0.0);
+        let (f, k) = (reflection * first, reflection * self.k);
+        if !vol.is_finite() || k + shift <= 0.0 {
+            return None;
+        }
+        Some(
+            Black76Inputs::new(is_call, f, k, self.r, self.t)
+                .with_shift(shift)
+                .with_implied_vol(vol),
+        )
+    }
+
+    pub fn price(&self) -> f64 {
+        match self.black76() {
+            Some(black) => black.price(),
+            None if self.effective_vol().is_nan()
+                && self.approximation == BasketApproximation::Lognormal =>
+            {
+                f64::NAN
+            }
+            // the option is a forward on the basket or worthless
+            None => {
+                let sign = if self.is_call { 1.0 } else { -1.0 };
+                ((sign * (self.forward() - self.k)).max(0.0)) * (-self.r * self.t).exp()
+            }
+        }
+    }
+}
+
+impl Payoff for BasketOption {
+    fn payoff(&self, paths: &[Vec<f64>]) -> f64 {
+        let basket: f64 = paths
+            .iter()
+            .zip(&self.weights)
+            .map(|(path, w)| w * path[path.len() - 1])
+            .sum();
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        (sign * (basket - self.k)).max(0.0)
+    }
+
+    /// The approximation's price.
+    fn analytic(&self) -> f64 {
+        self.price()
+    }
+}
+
+impl CorrelationRisk for BasketOption {
+    fn value(&self) -> f64 {
+        self.price()
+    }
+
+    fn shift_correlation(&self, shift: f64) -> Self {
+        let correlation = self
+            .correlation
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &rho)| if i == j { rho } else { shifted(rho, shift) })
+                    .collect()
+            })
+            .collect();
+        Self {
+            correlation,
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Cox-Ross-Rubinstein binomial trees.
+//!
+//! The spot moves up by `u = exp(vol * sqrt(dt))` or down by `1 / u` each step, with the
+//! risk-neutral probability matching the forward. European prices converge to the BSM price as
+//! the number of steps grows, with an error oscillating in the step count; American prices take
+//! the better of holding and exercising at every node.
+//!
+//! Discrete cash dividends, which listed single-stock options need, are handled as Vellekoop
+//! and Nieuwenhuis (2006) do: the tree stays recombining, and on the step nearest each ex-date
+//! the value at each node becomes the value after the ex-date at spot less the dividend,
+//! interpolated between the neighbouring nodes. Exercise is then checked at the cum-dividend
+//! spot, just before the dividend goes ex.
+//!
+//! Smoothing the last step with the BSM formula makes the error fall steadily in the step
+//! count, so Richardson extrapolation from half the steps, Broadie and Detemple's (1996) BBSR
+//! method, cancels its leading term: [`BinomialTree::price_extrapolated`] is typically as
+//! accurate as a plain tree with several times the steps.
+
+use crate::american::Dividend;
+use crate::extrapolation;
+use crate::OptionInputs;
+
+/// When an option may be exercised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exercise {
+    /// At expiry only
+    European,
+
+    /// At any time up to expiry
+    American,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinomialTree {
+    pub steps: usize,
+    pub exercise: Exercise,
+
+    /// Value the last step with the BSM formula rather than the tree, which removes the
+    /// oscillation of the error in the step count
+    pub smoothing: bool,
+}
+
+impl BinomialTree {
+    pub fn new(steps: usize, exercise: Exercise) -> Self {
+        Self {
+            steps: steps.max(1),
+            exercise,
+            smoothing: false,
+        }
+    }
+
+    pub fn with_smoothing(mut self, smoothing: bool) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Price of `option` at its implied vol.
+    pub fn price(&self, option: &OptionInputs) -> f64 {
+        self.price_with_dividends(option, &[])
+    }
+
+    /// Price of `option` at its implied vol, extrapolated from smoothed trees of half and all of
+    /// the steps.
+    pub fn price_extrapolated(&self, option: &OptionInputs) -> f64 {
+        self.price_with_dividends_extrapolated(option, &[])
+    }
+
+    /// [`BinomialTree::price_with_dividends`] extrapolated from smoothed trees of half and all
+    /// of the steps.
+    pub fn price_with_dividends_extrapolated(
+        &self,
+        option: &OptionInputs,
+        dividends: &[Dividend],
+    ) -> f64 {
+        let price = |steps| {
+            BinomialTree::new(steps, self.exercise)
+                .with_smoothing(true)
+                .price_with_dividends(option, dividends)
+        };
+        extrapolation::richardson_in_steps(price, self.steps, 1.0)
+    }
+
+    /// Price of `option` at its implied vol with the spot dropping by each of `dividends` on its
+    /// ex-date, on top of any dividend yield. Dividends outside the option's life are ignored.
+    pub fn price_with_dividends(&self, option: &OptionInputs, dividends: &[Dividend]) -> f64 {
+        let n = self.steps;
+        let dt = option.t / n as f64;
+        let u = (option.implied_vol * dt.sqrt()).exp();
+        let d = 1.0 / u;
+        let p = ((option.carry() * dt).exp() - d) / (u - d);
+        let discount = (-option.r * dt).exp();
+        let (up, down) = (discount * p, discount * (1.0 - p));
+
+        // total dividend going ex on each step
+        let mut step_dividends = vec![0.0; n];
+        for dividend in dividends {
+            if n > 1 && dividend.t > 0.0 && dividend.t < option.t {
+                let step = ((dividend.t / dt).round() as usize).clamp(1, n - 1);
+                step_dividends[step] += dividend.amount;
+            }
+        }
+
+        let sign = option.sign();
+        let payoff = |s: f64| (sign * (s - option.k)).max(0.0);
+        let exercise = |value: f64, s: f64| match self.exercise {
+            Exercise::European => value,
+            Exercise::American => value.max(payoff(s)),
+        };
+        // node j of step i has spot s * u^(2j - i); walk spots up from the lowest by u^2
+        let u2 = u * u;
+        let mut spot = option.s * d.powi(n as i32);
+        let mut values: Vec<f64> = (0..=n)
+            .map(|_| {
+                let value = payoff(spot);
+                spot *= u2;
+                value
+            })
+            .collect();
+
+        for i in (0..n).rev() {
+            let lowest = option.s * d.powi(i as i32);
+            let mut spot = lowest;
+            for j in 0..=i {
+                let hold = if self.smoothing && i == n - 1 {
+                    OptionInputs::new(option.is_call, spot, option.k, option.r, option.q, dt)
+                        .with_implied_vol(option.implied_vol)
+                        .price()
+                } else {
+                    up * values[j + 1] + down * values[j]
+                };
+                values[j] = exercise(hold, spot);
+                spot *= u2;
+            }
+            if step_dividends[i] > 0.0 {
+                let after = values[..=i].to_vec();
+                let mut spot = lowest;
+                for value in &mut values[..=i] {
+                    let ex = interpolate_nodes(&after, lowest, u2, spot - step_dividends[i]);
+                    *value = exercise(ex, spot);
+                    spot *= u2;
+                }
+            }
+        }
+        values[0]
+    }
+}
+
+/// Linear interpolation in spot of `values` on the nodes `lowest * growth^j`, extrapolating
+/// beyond the ends and never below a spot of zero.
+fn interpolate_nodes(values: &[f64], lowest: f64, growth: f64, s: f64) -> f64 {
+    let s = s.max(0.0);
+    let last = values.len() - 1;
+    let position = if s > 0.0 {
+        (s / lowest).ln() / growth.ln()
+    } else {
+        f64::NEG_INFINITY
+    };
+    let j = position.floor().clamp(0.0, (last - 1) as f64) as usize;
+    let (lo, hi) = (
+        lowest * growth.powi(j as i32),
+        lowest * growth.powi(j as i32 + 1),
+    );
+    values[j] + (values[j + 1] - values[j]) * (s - lo) / (hi - lo)
+}
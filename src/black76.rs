@@ -0,0 +1,265 @@
+//! Black-76 model for European options on futures and forwards.
+//!
+//! With a shift, the model becomes the displaced diffusion used for low or negative rates and
+//! spreads: `F + shift` is lognormal, and the price, implied vol, and Greeks are all computed on
+//! the shifted forward and strike while still being quoted against the unshifted ones.
+//! [`OptionInputs::shifted`] gives the same model for options on a spot, on their forward.
+
+use crate::{calculate_ncdf, calculate_npdf, lets_be_rational, OptionInputs, DAYS_PER_YEAR};
+
+/// The inputs to the Black-76 model.
+#[derive(Debug, Clone)]
+pub struct Black76Inputs {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Forward (or futures) price
+    pub f: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate used to discount the payoff
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// Displacement added to the forward and strike, zero for the lognormal model
+    pub shift: f64,
+
+    /// Implied vol of the shifted forward
+    pub implied_vol: f64,
+
+    /// Option price
+    pub price: f64,
+
+    /// Cache intermediate results to speed up subsequent calculations.
+    d1: f64,
+    d2: f64,
+    nd1: f64,
+    nd2: f64,
+    nprimed1: f64,
+    nprimed2: f64,
+}
+
+/// Methods for calculating the price and greeks of an option on a forward.
+impl Black76Inputs {
+    pub fn new(is_call: bool, f: f64, k: f64, r: f64, t: f64) -> Self {
+        Self {
+            is_call,
+            f,
+            k,
+            r,
+            t,
+            shift: 0.0,
+            implied_vol: f64::NAN,
+            price: f64::NAN,
+            d1: f64::NAN,
+            d2: f64::NAN,
+            nd1: f64::NAN,
+            nd2: f64::NAN,
+            nprimed1: f64::NAN,
+            nprimed2: f64::NAN,
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+
+        // Calculate d1, d2, there is no drift term under the forward measure
+        let denominator = implied_vol * self.t.sqrt();
+        self.d1 = ((self.shifted_forward() / self.shifted_strike()).ln()
+            + 0.5 * implied_vol.powi(2) * self.t)
+            / denominator;
+        self.d2 = self.d1 - denominator;
+
+        self.nd1 = calculate_ncdf(self.sign() * self.d1);
+        self.nd2 = calculate_ncdf(self.sign() * self.d2);
+
+        self.nprimed1 = calculate_npdf(self.d1);
+        self.nprimed2 = calculate_npdf(self.d2);
+
+        if !self.price.is_finite() {
+            let undiscounted_price = lets_be_rational::black(
+                self.shifted_forward(),
+                self.shifted_strike(),
+                implied_vol,
+                self.t,
+                self.sign(),
+            );
+
+            self.price = undiscounted_price * self.rate_discount();
+        }
+
+        self
+    }
+
+    /// Displace the forward and strike by `shift`, so that `F + shift` is lognormal. An implied
+    /// vol already set is kept and the option repriced at it on the shifted forward.
+    pub fn with_shift(mut self, shift: f64) -> Self {
+        self.shift = shift;
+        if self.implied_vol.is_finite() {
+            self.price = f64::NAN;
+            let vol = self.implied_vol;
+            return self.with_implied_vol(vol);
+        }
+        self
+    }
+
+    #[inline(always)]
+    pub fn shifted_forward(&self) -> f64 {
+        self.f + self.shift
+    }
+
+    #[inline(always)]
+    pub fn shifted_strike(&self) -> f64 {
+        self.k + self.shift
+    }
+
+    /// Solve the implied vol from an option price. If the price admits no vol, the price is kept
+    /// and the implied vol and Greeks are left as NaN.
+    pub fn with_price(mut self, price: f64) -> Self {
+        self.price = price;
+
+        // "let's be rational" works with the undiscounted price
+        let undiscounted = price / self.rate_discount();
+        let implied_vol = lets_be_rational::implied_volatility_from_a_transformed_rational_guess(
+            undiscounted,
+            self.shifted_forward(),
+            self.shifted_strike(),
+            self.t,
+            self.sign(),
+        );
+
+        if implied_vol > 0.0 {
+            self.with_implied_vol(implied_vol)
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    pub fn sign(&self) -> f64 {
+        if self.is_call {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    #[inline(always)]
+    pub fn rate_discount(&self) -> f64 {
+        (-self.r * self.t).exp()
+    }
+
+    pub fn implied_vol(&self) -> f64 {
+        self.implied_vol
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Sensitivity to the forward price.
+    pub fn delta(&self) -> f64 {
+        self.sign() * self.nd1 * self.rate_discount()
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.rate_discount() * self.nprimed1
+            / (self.shifted_forward() * self.implied_vol * self.t.sqrt())
+    }
+
+    /// Time decay per calendar day with the forward held fixed.
+    pub fn theta(&self) -> f64 {
+        (-(self.shifted_forward() * self.implied_vol * self.rate_discount() * self.nprimed1)
+            / (2.0 * self.t.sqrt())
+            + self.r * self.price)
+            / DAYS_PER_YEAR
+    }
+
+    pub fn vega(&self) -> f64 {
+        0.01 * self.shifted_forward() * self.rate_discount() * self.t.sqrt() * self.nprimed1
+    }
+
+    /// Sensitivity to the discount rate with the forward held fixed.
+    pub fn rho(&self) -> f64 {
+        -0.01 * self.t * self.price
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.delta() * self.f / self.price
+    }
+
+    pub fn vanna(&self) -> f64 {
+        self.d2 * self.rate_discount() * self.nprimed1 * -0.01 / self.implied_vol
+    }
+
+    pub fn charm(&self) -> f64 {
+        let rate_discount = self.rate_discount();
+
+        self.sign() * self.r * rate_discount * self.nd1
+            + rate_discount * self.nprimed1 * self.d2 / (2.0 * self.t)
+    }
+
+    pub fn veta(&self) -> f64 {
+        -self.shifted_forward()
+            * self.rate_discount()
+            * self.nprimed1
+            * self.t.sqrt()
+            * (self.r - (1.0 + self.d1 * self.d2) / (2.0 * self.t))
+    }
+
+    pub fn vomma(&self) -> f64 {
+        self.vega() * self.d1 * self.d2 / self.implied_vol
+    }
+
+    pub fn speed(&self) -> f64 {
+        -self.gamma() / self.shifted_forward()
+            * (self.d1 / (self.implied_vol * self.t.sqrt()) + 1.0)
+    }
+
+    pub fn zomma(&self) -> f64 {
+        self.gamma() * ((self.d1 * self.d2 - 1.0) / self.implied_vol)
+    }
+
+    pub fn color(&self) -> f64 {
+        -self.rate_discount()
+            * (self.nprimed1
+                / (2.0 * self.shifted_forward() * self.t * self.implied_vol * self.t.sqrt()))
+            * (2.0 * self.r * self.t + 1.0 - self.d1 * self.d2)
+    }
+
+    pub fn ultima(&self) -> f64 {
+        -self.vega() / (self.implied_vol * self.implied_vol)
+            * (self.d1 * self.d2 * (1.0 - self.d1 * self.d2)
+                + self.d1.powf(2.0)
+                + self.d2.powf(2.0))
+    }
+
+    pub fn dual_delta(&self) -> f64 {
+        -self.sign() * self.rate_discount() * self.nd2
+    }
+
+    pub fn dual_gamma(&self) -> f64 {
+        self.rate_discount()
+            * (self.nprimed2 / (self.shifted_strike() * self.implied_vol * self.t.sqrt()))
+    }
+}
+
+impl OptionInputs {
+    /// This option as a displaced diffusion on its forward `s e^{(r - q) t}`, with `F + shift`
+    /// lognormal at this option's vol if one is set. Price it, or solve its vol from a price, on
+    /// the returned inputs, whose delta and gamma are with respect to the forward.
+    pub fn shifted(&self, shift: f64) -> Black76Inputs {
+        let forward = self.s * (self.carry() * self.t).exp();
+        let option =
+            Black76Inputs::new(self.is_call, forward, self.k, self.r, self.t).with_shift(shift);
+        if self.implied_vol.is_finite() {
+            option.with_implied_vol(self.implied_vol)
+        } else {
+            option
+        }
+    }
+}
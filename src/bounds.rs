@@ -0,0 +1,115 @@
+//! Model-free bounds on option quotes.
+//!
+//! Whatever the model, European prices must lie between intrinsic value on the forward and the
+//! discounted spot or strike, must fall (calls) or rise (puts) with strike no faster than the
+//! discounted strike difference, must be convex in strike, and must satisfy put-call parity.
+//! Quotes that cannot be reconciled with these bounds are stale or bad ticks and are best removed
+//! before solving implied vols.
+
+use crate::chain::{OptionChain, OptionQuote};
+
+/// The bounds a quote breaks. A violation between two or three quotes is flagged on each of
+/// them except for convexity, which is flagged on the middle strike.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoundViolations {
+    /// The ask is below the lower bound, or the bid above the upper bound
+    pub price_bounds: bool,
+
+    /// Out of order with, or sloping too steeply against, an adjacent strike
+    pub monotonicity: bool,
+
+    /// Above the interpolation of its neighbouring strikes
+    pub convexity: bool,
+
+    /// Irreconcilable with the option of the other type at the same strike
+    pub parity: bool,
+}
+
+impl BoundViolations {
+    pub fn any(&self) -> bool {
+        self.price_bounds || self.monotonicity || self.convexity || self.parity
+    }
+}
+
+impl OptionChain {
+    /// Check every quote against the model-free bounds, allowing `tolerance` in price. The
+    /// result is in quote order.
+    pub fn check_bounds(&self, tolerance: f64) -> Vec<BoundViolations> {
+        let mut flags = vec![BoundViolations::default(); self.quotes.len()];
+
+        for (flag, q) in flags.iter_mut().zip(&self.quotes) {
+            let spot = self.s * (-self.q * q.t).exp();
+            let strike = q.k * (-self.r * q.t).exp();
+            let (lower, upper) = if q.is_call {
+                ((spot - strike).max(0.0), spot)
+            } else {
+                ((strike - spot).max(0.0), strike)
+            };
+            flag.price_bounds = q.ask < lower - tolerance || q.bid > upper + tolerance;
+        }
+
+        // adjacent strikes of one type and expiry, by index into the quotes
+        let mut order: Vec<usize> = (0..self.quotes.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.quotes[a], &self.quotes[b]);
+            a.is_call
+                .cmp(&b.is_call)
+                .then(a.t.total_cmp(&b.t))
+                .then(a.k.total_cmp(&b.k))
+        });
+        let same_slice = |a: &OptionQuote, b: &OptionQuote| a.is_call == b.is_call && a.t == b.t;
+
+        for w in order.windows(2) {
+            let (lo, hi) = (&self.quotes[w[0]], &self.quotes[w[1]]);
+            if !same_slice(lo, hi) || lo.k == hi.k {
+                continue;
+            }
+            let max_slope = (hi.k - lo.k) * (-self.r * lo.t).exp();
+            // calls fall with strike and puts rise, each by at most the discounted difference
+            let (cheap, dear) = if lo.is_call { (hi, lo) } else { (lo, hi) };
+            if cheap.bid > dear.ask + tolerance || dear.bid - cheap.ask > max_slope + tolerance {
+                flags[w[0]].monotonicity = true;
+                flags[w[1]].monotonicity = true;
+            }
+        }
+
+        for w in order.windows(3) {
+            let (a, b, c) = (&self.quotes[w[0]], &self.quotes[w[1]], &self.quotes[w[2]]);
+            if !same_slice(a, b) || !same_slice(b, c) || a.k == b.k || b.k == c.k {
+                continue;
+            }
+            let weight = (c.k - b.k) / (c.k - a.k);
+            if b.bid > weight * a.ask + (1.0 - weight) * c.ask + tolerance {
+                flags[w[1]].convexity = true;
+            }
+        }
+
+        for (i, call) in self.quotes.iter().enumerate().filter(|(_, q)| q.is_call) {
+            let put = self
+                .quotes
+                .iter()
+                .position(|p| !p.is_call && p.k == call.k && p.t == call.t);
+            if let Some(j) = put {
+                if self.violates_parity(call, &self.quotes[j], tolerance) {
+                    flags[i].parity = true;
+                    flags[j].parity = true;
+                }
+            }
+        }
+
+        flags
+    }
+
+    /// A copy of the chain without the quotes that break any bound.
+    pub fn within_bounds(&self, tolerance: f64) -> OptionChain {
+        let flags = self.check_bounds(tolerance);
+        let quotes = self
+            .quotes
+            .iter()
+            .zip(flags)
+            .filter(|(_, f)| !f.any())
+            .map(|(q, _)| q.clone())
+            .collect();
+        OptionChain::new(self.s, self.r, self.q, quotes)
+    }
+}
@@ -0,0 +1,229 @@
+//! Bulk pricing of memory-mapped binary files.
+//!
+//! For research datasets with billions of historical quotes, the input file is memory-mapped and
+//! priced record by record on the batch vector path into a memory-mapped output file, so no
+//! per-row allocation or parsing takes place and the OS pages data in and out as needed.
+//!
+//! # File layout
+//!
+//! All values are native-endian, which is little-endian on every target this module builds for.
+//!
+//! Input file:
+//!
+//! | offset | size      | contents                                   |
+//! |--------|-----------|--------------------------------------------|
+//! | 0      | 8         | magic bytes `BSIN0001`                     |
+//! | 8      | 8         | record count `n` as `u64`                  |
+//! | 16     | `56 * n`  | `n` [`BulkRecord`]s                        |
+//!
+//! Each record is seven 8-byte fields: `s`, `k`, `r`, `q`, `t`, `implied_vol` as `f64`, then
+//! `is_call` as a `u64` that is 1 for calls and 0 for puts.
+//!
+//! Output file:
+//!
+//! | offset | size     | contents                                   |
+//! |--------|----------|--------------------------------------------|
+//! | 0      | 8        | magic bytes `BSOUT001`                     |
+//! | 8      | 8        | record count `n` as `u64`                  |
+//! | 16     | `8 * n`  | `n` prices as `f64`, in input order        |
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use crate::batch::{lanes, BatchInputs, LANES};
+
+pub const INPUT_MAGIC: [u8; 8] = *b"BSIN0001";
+pub const OUTPUT_MAGIC: [u8; 8] = *b"BSOUT001";
+const HEADER_LEN: usize = 16;
+
+/// One option in a bulk input file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct BulkRecord {
+    pub s: f64,
+    pub k: f64,
+    pub r: f64,
+    pub q: f64,
+    pub t: f64,
+    pub implied_vol: f64,
+
+    /// 1 for calls, 0 for puts
+    pub is_call: u64,
+}
+
+impl BulkRecord {
+    pub fn new(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, implied_vol: f64) -> Self {
+        Self {
+            s,
+            k,
+            r,
+            q,
+            t,
+            implied_vol,
+            is_call: u64::from(is_call),
+        }
+    }
+}
+
+/// A memory mapping that is unmapped on drop.
+struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(file: &File, len: usize, writable: bool) -> io::Result<Self> {
+        let prot = if writable {
+            libc::PROT_READ | libc::PROT_WRITE
+        } else {
+            libc::PROT_READ
+        };
+        // SAFETY: mapping a file descriptor we own for `len` bytes; failure is reported by
+        // MAP_FAILED and checked below.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                prot,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { ptr, len })
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        // SAFETY: ptr and len come from a successful mmap call.
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Write records to a new input file in the documented layout.
+pub fn write_input(path: &Path, records: &[BulkRecord]) -> io::Result<()> {
+    let mut file = io::BufWriter::new(File::create(path)?);
+    file.write_all(&INPUT_MAGIC)?;
+    file.write_all(&(records.len() as u64).to_ne_bytes())?;
+    for r in records {
+        for x in [r.s, r.k, r.r, r.q, r.t, r.implied_vol] {
+            file.write_all(&x.to_ne_bytes())?;
+        }
+        file.write_all(&r.is_call.to_ne_bytes())?;
+    }
+    file.flush()
+}
+
+/// Read the prices from an output file.
+pub fn read_output(path: &Path) -> io::Result<Vec<f64>> {
+    let bytes = std::fs::read(path)?;
+    if bytes.len() < HEADER_LEN || bytes[..8] != OUTPUT_MAGIC {
+        return Err(invalid("not a bulk output file"));
+    }
+    Ok(bytes[HEADER_LEN..]
+        .chunks_exact(8)
+        .map(|b| f64::from_ne_bytes(b.try_into().unwrap()))
+        .collect())
+}
+
+/// Price lanes gathered from records into stack arrays.
+fn price_records(records: &[BulkRecord], out: &mut [f64]) {
+    let mut is_call = [false; LANES];
+    let mut fields = [[0.0; LANES]; 6];
+
+    for (chunk, out) in records.chunks(LANES).zip(out.chunks_mut(LANES)) {
+        for j in 0..LANES {
+            // pad a short final chunk with its last record
+            let r = &chunk[j.min(chunk.len() - 1)];
+            is_call[j] = r.is_call != 0;
+            for (f, x) in fields
+                .iter_mut()
+                .zip([r.s, r.k, r.r, r.q, r.t, r.implied_vol])
+            {
+                f[j] = x;
+            }
+        }
+        let [s, k, r, q, t, vol] = &fields;
+        let inputs = BatchInputs::new(&is_call, s, k, r, q, t, vol);
+        out.copy_from_slice(&lanes(&inputs, 0).price[..out.len()]);
+    }
+}
+
+/// Price every record of `input` into a new file at `output`, returning the number of records.
+/// The two must be different files, since the output is truncated while the input is mapped.
+pub fn price_file(input: &Path, output: &Path) -> io::Result<usize> {
+    let input = File::open(input)?;
+    let metadata = input.metadata()?;
+    if let Ok(existing) = std::fs::metadata(output) {
+        if (existing.dev(), existing.ino()) == (metadata.dev(), metadata.ino()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "bulk output would overwrite its input",
+            ));
+        }
+    }
+    let len =
+        usize::try_from(metadata.len()).map_err(|_| invalid("bulk input file is too large"))?;
+    if len < HEADER_LEN {
+        return Err(invalid("bulk input file is too short"));
+    }
+
+    let source = Mmap::new(&input, len, false)?;
+    // SAFETY: the mapping is at least HEADER_LEN bytes and page aligned.
+    let header = unsafe { std::slice::from_raw_parts(source.ptr as *const u8, HEADER_LEN) };
+    if header[..8] != INPUT_MAGIC {
+        return Err(invalid("not a bulk input file"));
+    }
+    let n = usize::try_from(u64::from_ne_bytes(header[8..].try_into().unwrap()))
+        .map_err(|_| invalid("bulk input record count is too large"))?;
+    let needed = n
+        .checked_mul(std::mem::size_of::<BulkRecord>())
+        .and_then(|bytes| bytes.checked_add(HEADER_LEN))
+        .ok_or_else(|| invalid("bulk input record count is too large"))?;
+    if len < needed {
+        return Err(invalid("bulk input file is truncated"));
+    }
+
+    let out_len = HEADER_LEN + 8 * n;
+    let output = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(output)?;
+    output.set_len(out_len as u64)?;
+    let target = Mmap::new(&output, out_len, true)?;
+
+    // SAFETY: both mappings are page aligned and large enough for the header plus `n` 8-byte
+    // aligned values, BulkRecord is repr(C) with only 8-byte fields, and the mappings do not
+    // overlap.
+    let (records, header, prices) = unsafe {
+        (
+            std::slice::from_raw_parts(
+                (source.ptr as *const u8).add(HEADER_LEN) as *const BulkRecord,
+                n,
+            ),
+            std::slice::from_raw_parts_mut(target.ptr as *mut u8, HEADER_LEN),
+            std::slice::from_raw_parts_mut((target.ptr as *mut u8).add(HEADER_LEN) as *mut f64, n),
+        )
+    };
+
+    header[..8].copy_from_slice(&OUTPUT_MAGIC);
+    header[8..].copy_from_slice(&(n as u64).to_ne_bytes());
+    price_records(records, prices);
+
+    Ok(n)
+}
@@ -0,0 +1,224 @@
+//! Calendar dates for expiries and valuation dates.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::DAYS_PER_YEAR;
+
+/// A proleptic Gregorian calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Days since 1970-01-01
+    days: i32,
+}
+
+impl Date {
+    /// Returns `None` if the components do not form a valid date.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        // Howard Hinnant's days_from_civil
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = month as i32;
+        let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + day as i32 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        Some(Self {
+            days: era * 146097 + doe - 719468,
+        })
+    }
+
+    pub fn from_days_since_epoch(days: i32) -> Self {
+        Self { days }
+    }
+
+    pub fn days_since_epoch(&self) -> i32 {
+        self.days
+    }
+
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        // Howard Hinnant's civil_from_days
+        let z = self.days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i32::from(month <= 2);
+        (year, month, day)
+    }
+
+    pub fn add_days(&self, days: i32) -> Self {
+        Self {
+            days: self.days + days,
+        }
+    }
+
+    /// Signed number of calendar days from `other` to `self`.
+    pub fn days_since(&self, other: Date) -> i32 {
+        self.days - other.days
+    }
+
+    /// Time from `other` to `self` in years, using the same day count as the rest of the crate.
+    pub fn years_since(&self, other: Date) -> f64 {
+        self.days_since(other) as f64 / DAYS_PER_YEAR
+    }
+
+    /// Day of the week, 0 for Monday through 6 for Sunday.
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days + 3).rem_euclid(7) as u32
+    }
+
+    pub fn is_weekend(&self) -> bool {
+        self.weekday() >= 5
+    }
+
+    /// Parse `YYYY-MM-DD`, `YYYYMMDD`, or US-style `MM/DD/YYYY`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let num = |x: &str| x.parse::<i64>().ok();
+
+        if let Some((y, rest)) = s.split_once('-') {
+            let (m, d) = rest.split_once('-')?;
+            return Self::from_ymd(num(y)? as i32, num(m)? as u32, num(d)? as u32);
+        }
+        if let Some((m, rest)) = s.split_once('/') {
+            let (d, y) = rest.split_once('/')?;
+            return Self::from_ymd(num(y)? as i32, num(m)? as u32, num(d)? as u32);
+        }
+        if s.len() == 8 && s.bytes().all(|b| b.is_ascii_digit()) {
+            return Self::from_ymd(
+                num(&s[..4])? as i32,
+                num(&s[4..6])? as u32,
+                num(&s[6..])? as u32,
+            );
+        }
+        None
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (y, m, d) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", y, m, d)
+    }
+}
+
+/// Error returned when a string is not a recognised date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateError;
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid date")
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or(ParseDateError)
+    }
+}
+
+/// A business day calendar: weekends plus a list of holidays.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Calendar {
+    /// Holidays, kept sorted
+    holidays: Vec<Date>,
+}
+
+impl Calendar {
+    /// A calendar with weekends only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = Date>) -> Self {
+        self.holidays.extend(holidays);
+        self.holidays.sort_unstable();
+        self.holidays.dedup();
+        self
+    }
+
+    pub fn holidays(&self) -> &[Date] {
+        &self.holidays
+    }
+
+    pub fn is_business_day(&self, date: Date) -> bool {
+        !date.is_weekend() && self.holidays.binary_search(&date).is_err()
+    }
+
+    /// The date itself if it is a business day, otherwise the previous business day. This is how
+    /// exchanges move expiries that fall on holidays.
+    pub fn preceding(&self, date: Date) -> Date {
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date = date.add_days(-1);
+        }
+        date
+    }
+
+    /// The date itself if it is a business day, otherwise the next business day.
+    pub fn following(&self, date: Date) -> Date {
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date = date.add_days(1);
+        }
+        date
+    }
+
+    /// Number of business days after `start` up to and including `end`.
+    pub fn business_days_between(&self, start: Date, end: Date) -> i32 {
+        let (lo, hi, sign) = if end >= start {
+            (start, end, 1)
+        } else {
+            (end, start, -1)
+        };
+        let mut count = 0;
+        let mut date = lo.add_days(1);
+        while date <= hi {
+            if self.is_business_day(date) {
+                count += 1;
+            }
+            date = date.add_days(1);
+        }
+        sign * count
+    }
+
+    pub fn add_business_days(&self, date: Date, days: i32) -> Date {
+        let step = if days >= 0 { 1 } else { -1 };
+        let mut date = date;
+        let mut remaining = days.abs();
+        while remaining > 0 {
+            date = date.add_days(step);
+            if self.is_business_day(date) {
+                remaining -= 1;
+            }
+        }
+        date
+    }
+}
@@ -0,0 +1,196 @@
+//! Cost of carry for the generalized Black-Scholes model.
+//!
+//! The generalized model differs between underlyings only in the drift `b` of the underlying
+//! under the pricing measure, with the dividend yield `q = r - b`. [`Carry`] names the common
+//! cases so that one constructor covers stocks, futures, currencies, and commodities.
+//!
+//! Crypto options are priced off forwards that trade at a basis set by perpetual swap funding: a
+//! long spot, short perpetual position collects the funding rate, so the funding rate is the cost
+//! of carry. A [`FundingCurve`] built from exchange funding prints supplies the average funding
+//! to each expiry, in place of a dividend yield.
+
+use crate::OptionInputs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Carry {
+    /// A non-dividend paying stock, `b = r`
+    Stock,
+
+    /// A stock or index with a continuous dividend yield, `b = r - q`
+    Dividend(f64),
+
+    /// A futures contract, which costs nothing to hold, `b = 0` (Black-76)
+    Futures,
+
+    /// A currency earning the foreign risk-free rate, `b = r - r_f` (Garman-Kohlhagen)
+    Fx { foreign_rate: f64 },
+
+    /// A commodity with continuous storage cost and convenience yield,
+    /// `b = r + storage_cost - convenience_yield`
+    Commodity {
+        storage_cost: f64,
+        convenience_yield: f64,
+    },
+
+    /// A crypto asset hedged with a perpetual swap, whose annualized funding rate is the carry,
+    /// `b = funding_rate`
+    Funding { funding_rate: f64 },
+
+    /// An explicit cost of carry
+    Custom(f64),
+}
+
+impl Carry {
+    /// The cost of carry `b` for risk-free rate `r`.
+    pub fn b(&self, r: f64) -> f64 {
+        match *self {
+            Self::Stock => r,
+            Self::Dividend(q) => r - q,
+            Self::Futures => 0.0,
+            Self::Fx { foreign_rate } => r - foreign_rate,
+            Self::Commodity {
+                storage_cost,
+                convenience_yield,
+            } => r + storage_cost - convenience_yield,
+            Self::Funding { funding_rate } => funding_rate,
+            Self::Custom(b) => b,
+        }
+    }
+
+    /// The equivalent continuous dividend yield `q = r - b`.
+    pub fn yield_for(&self, r: f64) -> f64 {
+        r - self.b(r)
+    }
+}
+
+impl OptionInputs {
+    /// Inputs to the generalized Black-Scholes model for the given kind of carry.
+    pub fn generalized(is_call: bool, s: f64, k: f64, r: f64, carry: Carry, t: f64) -> Self {
+        Self::cost_of_carry(is_call, s, k, r, carry.b(r), t)
+    }
+}
+
+/// Hours in a year of 365.25 days, for annualizing funding intervals.
+const HOURS_PER_YEAR: f64 = 24.0 * crate::DAYS_PER_YEAR;
+
+/// One funding payment of a perpetual swap as published by an exchange.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingPrint {
+    /// Time of the print in years, measured back from now so the latest print is smallest
+    pub age: f64,
+
+    /// Funding paid by longs to shorts over the interval, e.g. 0.0001 for one basis point
+    pub rate: f64,
+
+    /// Length of the funding interval in hours, commonly 8
+    pub interval_hours: f64,
+}
+
+impl FundingPrint {
+    pub fn new(age: f64, rate: f64, interval_hours: f64) -> Self {
+        Self {
+            age,
+            rate,
+            interval_hours,
+        }
+    }
+
+    /// The print as a simple annual rate.
+    pub fn annualized(&self) -> f64 {
+        self.rate * HOURS_PER_YEAR / self.interval_hours
+    }
+}
+
+/// Annualized perpetual funding rates as a piecewise flat curve: `rates[i]` applies up to
+/// `times[i]` and the last rate beyond it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingCurve {
+    /// Ends of each period in years, ascending
+    pub times: Vec<f64>,
+
+    /// Annualized funding over each period
+    pub rates: Vec<f64>,
+}
+
+impl FundingCurve {
+    pub fn new(times: Vec<f64>, rates: Vec<f64>) -> Self {
+        assert_eq!(times.len(), rates.len(), "one rate per period");
+        assert!(!rates.is_empty(), "a funding curve needs a rate");
+        Self { times, rates }
+    }
+
+    /// The same funding rate at every horizon.
+    pub fn flat(rate: f64) -> Self {
+        Self::new(vec![f64::INFINITY], vec![rate])
+    }
+
+    /// A flat curve at the exponentially weighted mean of annualized `prints`, each weighted by
+    /// `0.5^(age / half_life)` so recent funding counts most. Funding is persistent but noisy, so
+    /// a half life of a few days smooths single prints without lagging regime changes. Returns
+    /// `None` without prints.
+    pub fn from_prints(prints: &[FundingPrint], half_life: f64) -> Option<Self> {
+        let (sum, weight) = prints.iter().fold((0.0, 0.0), |(sum, weight), p| {
+            let w = 0.5_f64.powf(p.age / half_life);
+            (sum + w * p.annualized(), weight + w)
+        });
+        (weight > 0.0).then(|| Self::flat(sum / weight))
+    }
+
+    /// A curve that starts at `current` and decays towards `long_run` with mean reversion speed
+    /// `speed`, sampled as flat periods ending at each of `times`. Each period takes the average
+    /// of the decaying rate over it.
+    pub fn mean_reverting(current: f64, long_run: f64, speed: f64, times: Vec<f64>) -> Self {
+        // integral of long_run + (current - long_run) e^(-speed s) from 0 to t
+        let integral = |t: f64| {
+            let decay = if speed > 0.0 {
+                (1.0 - (-speed * t).exp()) / speed
+            } else {
+                t
+            };
+            long_run * t + (current - long_run) * decay
+        };
+        let mut start = 0.0;
+        let rates = times
+            .iter()
+            .map(|&end| {
+                let rate = (integral(end) - integral(start)) / (end - start);
+                start = end;
+                rate
+            })
+            .collect();
+        Self::new(times, rates)
+    }
+
+    /// Average annualized funding from now to `t`.
+    pub fn average(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return self.rates[0];
+        }
+        let (mut start, mut total) = (0.0, 0.0);
+        for (&end, &rate) in self.times.iter().zip(&self.rates) {
+            if t <= end {
+                return (total + rate * (t - start)) / t;
+            }
+            total += rate * (end - start);
+            start = end;
+        }
+        (total + self.rates[self.rates.len() - 1] * (t - start)) / t
+    }
+
+    /// The carry to expiry `t`.
+    pub fn carry(&self, t: f64) -> Carry {
+        Carry::Funding {
+            funding_rate: self.average(t),
+        }
+    }
+
+    /// Forward price of spot `s` at `t` implied by the funding basis.
+    pub fn forward(&self, s: f64, t: f64) -> f64 {
+        s * (self.average(t) * t).exp()
+    }
+
+    /// Inputs for an option on spot `s` priced off the funding-implied forward.
+    pub fn option(&self, is_call: bool, s: f64, k: f64, r: f64, t: f64) -> OptionInputs {
+        OptionInputs::generalized(is_call, s, k, r, self.carry(t), t)
+    }
+}
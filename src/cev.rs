@@ -0,0 +1,129 @@
+//! The constant elasticity of variance (CEV) model.
+//!
+//! The underlying follows `dS = (r - q) S dt + sigma S^beta dW`, so its lognormal vol
+//! `sigma S^(beta - 1)` rises as the price falls for `beta < 1`, a simple model of equity skew
+//! and the backbone of SABR without stochastic vol. Zero is absorbing. Prices follow Schroder
+//! (1989) in terms of the noncentral chi-square distribution, in the form given by Hull.
+
+use crate::{ncx2, OptionInputs};
+
+/// Parameters of the CEV model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cev {
+    /// Scale of the vol, so that the lognormal vol at price `S` is `sigma S^(beta - 1)`
+    pub sigma: f64,
+
+    /// Elasticity in [0, 1], 1 for BSM and 0 for normal dynamics
+    pub beta: f64,
+}
+
+impl Cev {
+    pub fn new(sigma: f64, beta: f64) -> Self {
+        Self { sigma, beta }
+    }
+
+    /// The model whose local vol at `s` is the lognormal vol `vol`.
+    pub fn with_local_vol(vol: f64, s: f64, beta: f64) -> Self {
+        Self::new(vol * s.powf(1.0 - beta), beta)
+    }
+
+    /// Lognormal local vol at price `s`.
+    pub fn local_vol(&self, s: f64) -> f64 {
+        self.sigma * s.powf(self.beta - 1.0)
+    }
+
+    /// Hagan and Woodward's (1999) approximation of the BSM implied vol at strike `k` for
+    /// forward `f` and expiry `t`, which shows the skew directly: the vol is roughly the local
+    /// vol at the midpoint of forward and strike.
+    pub fn approximate_implied_vol(&self, f: f64, k: f64, t: f64) -> f64 {
+        let omb = 1.0 - self.beta;
+        let mid = 0.5 * (f + k);
+        let base = self.sigma / mid.powf(omb);
+        base * (1.0
+            + omb * (2.0 + self.beta) / 24.0 * ((f - k) / mid).powi(2)
+            + omb * omb / 24.0 * base * base * t)
+    }
+}
+
+/// A European option under CEV.
+#[derive(Debug, Clone)]
+pub struct CevOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub model: Cev,
+}
+
+impl CevOption {
+    pub fn new(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, model: Cev) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            model,
+        }
+    }
+
+    /// Price for `0 <= beta <= 1`, NaN for larger elasticities.
+    pub fn price(&self) -> f64 {
+        let Cev { sigma, beta } = self.model;
+        if beta > 1.0 {
+            return f64::NAN;
+        }
+        if beta == 1.0 {
+            return OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+                .with_implied_vol(sigma)
+                .price();
+        }
+
+        let omb = 1.0 - beta;
+        let carry = self.r - self.q;
+        let v = if carry.abs() < 1e-12 {
+            sigma * sigma * self.t
+        } else {
+            sigma * sigma / (2.0 * carry * (beta - 1.0))
+                * ((2.0 * carry * (beta - 1.0) * self.t).exp() - 1.0)
+        };
+        let scale = omb * omb * v;
+        let a = (self.k * (-carry * self.t).exp()).powf(2.0 * omb) / scale;
+        let c = self.s.powf(2.0 * omb) / scale;
+        let b = 1.0 / omb;
+
+        let share = self.s * (-self.q * self.t).exp();
+        let cash = self.k * (-self.r * self.t).exp();
+        if self.is_call {
+            share * ncx2::sf(a, b + 2.0, c) - cash * ncx2::cdf(c, b, a)
+        } else {
+            cash * ncx2::sf(c, b, a) - share * ncx2::cdf(a, b + 2.0, c)
+        }
+    }
+
+    /// BSM inputs carrying the CEV price, with the implied vol solved from it.
+    pub fn to_bsm(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_price(self.price())
+    }
+
+    /// BSM implied vol of the CEV price, NaN if the price admits none.
+    pub fn implied_vol(&self) -> f64 {
+        self.to_bsm().implied_vol()
+    }
+}
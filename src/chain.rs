@@ -0,0 +1,319 @@
+//! Option chains: market quotes for one underlying and their implied vols.
+
+use crate::OptionInputs;
+
+/// A two-sided market quote for one option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionQuote {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub bid: f64,
+    pub ask: f64,
+
+    /// Quoted size, used to weight quotes when aggregating
+    pub size: f64,
+
+    /// Where the quote came from
+    pub venue: String,
+}
+
+impl OptionQuote {
+    pub fn new(is_call: bool, k: f64, t: f64, bid: f64, ask: f64) -> Self {
+        Self {
+            is_call,
+            k,
+            t,
+            bid,
+            ask,
+            size: 1.0,
+            venue: String::new(),
+        }
+    }
+
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_venue(mut self, venue: impl Into<String>) -> Self {
+        self.venue = venue.into();
+        self
+    }
+
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+}
+
+/// A quote with the implied vols solved from its bid, mid, and ask.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvedQuote {
+    pub quote: OptionQuote,
+
+    /// Implied vols, NaN where the price admits none
+    pub bid_vol: f64,
+    pub mid_vol: f64,
+    pub ask_vol: f64,
+
+    /// Vega at the mid vol
+    pub vega: f64,
+}
+
+/// Quotes for one underlying together with the carry used to solve them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChain {
+    /// Spot price of the underlying
+    pub s: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    pub quotes: Vec<OptionQuote>,
+}
+
+impl OptionChain {
+    pub fn new(s: f64, r: f64, q: f64, quotes: Vec<OptionQuote>) -> Self {
+        Self { s, r, q, quotes }
+    }
+
+    /// Inputs for a quote without a vol applied.
+    pub fn option(&self, quote: &OptionQuote) -> OptionInputs {
+        OptionInputs::new(quote.is_call, self.s, quote.k, self.r, self.q, quote.t)
+    }
+
+    fn implied_vol(&self, quote: &OptionQuote, price: f64) -> f64 {
+        if price.is_nan() || price <= 0.0 {
+            return f64::NAN;
+        }
+        self.option(quote).with_price(price).implied_vol()
+    }
+
+    pub fn solve_quote(&self, quote: &OptionQuote) -> SolvedQuote {
+        let mid = self.option(quote).with_price(quote.mid());
+        SolvedQuote {
+            quote: quote.clone(),
+            bid_vol: self.implied_vol(quote, quote.bid),
+            mid_vol: mid.implied_vol(),
+            ask_vol: self.implied_vol(quote, quote.ask),
+            vega: mid.vega(),
+        }
+    }
+
+    /// Solve implied vols for every quote in the chain.
+    pub fn solve(&self) -> Vec<SolvedQuote> {
+        self.quotes.iter().map(|q| self.solve_quote(q)).collect()
+    }
+
+    /// Whether a call and put at the same strike and expiry miss the parity value by more than
+    /// `tolerance` even when one is bought at its ask and the other sold at its bid.
+    pub(crate) fn violates_parity(
+        &self,
+        call: &OptionQuote,
+        put: &OptionQuote,
+        tolerance: f64,
+    ) -> bool {
+        let parity = self.s * (-self.q * call.t).exp() - call.k * (-self.r * call.t).exp();
+        call.bid - put.ask > parity + tolerance || call.ask - put.bid < parity - tolerance
+    }
+
+    /// Diagnose the chain. A call and put pair violates parity when even buying one at its ask
+    /// and selling the other at its bid misses the parity value by more than `parity_tolerance`.
+    pub fn diagnostics(&self, parity_tolerance: f64) -> ChainDiagnostics {
+        let solved = self.solve();
+        let failed_solves = solved.iter().filter(|q| !q.mid_vol.is_finite()).count();
+        let spreads: Vec<f64> = solved
+            .iter()
+            .filter(|q| q.bid_vol.is_finite() && q.ask_vol.is_finite())
+            .map(|q| q.ask_vol - q.bid_vol)
+            .collect();
+
+        let parity_violations = self
+            .quotes
+            .iter()
+            .filter(|c| c.is_call)
+            .filter(|c| {
+                self.quotes
+                    .iter()
+                    .find(|p| !p.is_call && p.k == c.k && p.t == c.t)
+                    .is_some_and(|p| self.violates_parity(c, p, parity_tolerance))
+            })
+            .count();
+
+        let mut sorted: Vec<&OptionQuote> = self.quotes.iter().collect();
+        sorted.sort_by(|a, b| {
+            a.is_call
+                .cmp(&b.is_call)
+                .then(a.t.total_cmp(&b.t))
+                .then(a.k.total_cmp(&b.k))
+        });
+        let monotonicity_breaks = sorted
+            .windows(2)
+            .filter(|w| w[0].is_call == w[1].is_call && w[0].t == w[1].t && w[0].k < w[1].k)
+            .filter(|w| {
+                let rise = w[1].mid() - w[0].mid();
+                if w[0].is_call {
+                    rise > 0.0
+                } else {
+                    rise < 0.0
+                }
+            })
+            .count();
+
+        ChainDiagnostics {
+            quotes: self.quotes.len(),
+            failed_solves,
+            parity_violations,
+            monotonicity_breaks,
+            mean_vol_spread: (!spreads.is_empty())
+                .then(|| spreads.iter().sum::<f64>() / spreads.len() as f64),
+        }
+    }
+
+    /// Combine quotes for the same contract from different venues into one implied vol per
+    /// contract, ordered by expiry, strike, and calls before puts. See [`aggregate`].
+    pub fn aggregate(&self, rejection: &OutlierRejection) -> Vec<AggregatedVol> {
+        let mut solved = self.solve();
+        solved.sort_by(|a, b| {
+            a.quote
+                .t
+                .total_cmp(&b.quote.t)
+                .then(a.quote.k.total_cmp(&b.quote.k))
+                .then(b.quote.is_call.cmp(&a.quote.is_call))
+        });
+
+        solved
+            .chunk_by(|a, b| {
+                a.quote.t == b.quote.t
+                    && a.quote.k == b.quote.k
+                    && a.quote.is_call == b.quote.is_call
+            })
+            .filter_map(|group| aggregate(group, rejection))
+            .collect()
+    }
+}
+
+/// Data quality summary of a chain, to check before fitting anything to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainDiagnostics {
+    pub quotes: usize,
+
+    /// Quotes whose mid admits no implied vol
+    pub failed_solves: usize,
+
+    /// Call and put pairs at the same strike and expiry whose quotes cannot be reconciled with
+    /// put-call parity at the chain's carry
+    pub parity_violations: usize,
+
+    /// Adjacent strikes of one expiry and type whose mids are in the wrong order, calls falling
+    /// and puts rising with strike
+    pub monotonicity_breaks: usize,
+
+    /// Mean of ask vol less bid vol over quotes where both solve, `None` if none do
+    pub mean_vol_spread: Option<f64>,
+}
+
+/// How far a quote's implied vol may stray from the others before it is ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierRejection {
+    /// Quotes further than this many scaled median absolute deviations from the median vol are
+    /// rejected
+    pub threshold: f64,
+
+    /// Floor on the deviation scale in vol, so near-identical quotes do not reject a slightly
+    /// different one
+    pub min_deviation: f64,
+}
+
+impl Default for OutlierRejection {
+    fn default() -> Self {
+        Self {
+            threshold: 3.0,
+            min_deviation: 0.005,
+        }
+    }
+}
+
+/// Implied vol of one contract combined across venues.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatedVol {
+    pub is_call: bool,
+    pub k: f64,
+    pub t: f64,
+
+    /// Vega- and size-weighted mean of the accepted mid vols
+    pub implied_vol: f64,
+
+    /// Total weight of the accepted quotes
+    pub weight: f64,
+
+    /// Number of quotes used
+    pub accepted: usize,
+
+    /// Number of quotes ignored as outliers or failed solves
+    pub rejected: usize,
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        0.5 * (sorted[n / 2 - 1] + sorted[n / 2])
+    }
+}
+
+/// Combine solved quotes for the same contract into a single implied vol, weighting each mid vol
+/// by vega times quoted size after rejecting outliers around the median. Returns `None` if no
+/// quote has a usable vol.
+pub fn aggregate(quotes: &[SolvedQuote], rejection: &OutlierRejection) -> Option<AggregatedVol> {
+    let first = quotes.first()?;
+    let mut vols: Vec<f64> = quotes
+        .iter()
+        .map(|q| q.mid_vol)
+        .filter(|v| v.is_finite())
+        .collect();
+    if vols.is_empty() {
+        return None;
+    }
+    vols.sort_by(f64::total_cmp);
+    let centre = median(&vols);
+
+    let mut deviations: Vec<f64> = vols.iter().map(|v| (v - centre).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    // 1.4826 scales the MAD to a standard deviation for normal data
+    let scale = (1.4826 * median(&deviations)).max(rejection.min_deviation);
+
+    let (mut sum, mut weight, mut accepted) = (0.0, 0.0, 0);
+    for q in quotes {
+        if q.mid_vol.is_finite() && (q.mid_vol - centre).abs() <= rejection.threshold * scale {
+            let w = q.vega * q.quote.size;
+            sum += w * q.mid_vol;
+            weight += w;
+            accepted += 1;
+        }
+    }
+
+    Some(AggregatedVol {
+        is_call: first.quote.is_call,
+        k: first.quote.k,
+        t: first.quote.t,
+        implied_vol: if weight > 0.0 { sum / weight } else { centre },
+        weight,
+        accepted,
+        rejected: quotes.len() - accepted,
+    })
+}
@@ -0,0 +1,146 @@
+//! Chooser options, whose holder picks on a future date whether they are a call or a put.
+//!
+//! A simple chooser's call and put share a strike and expiry. By put-call parity on the choice
+//! date the choice is worth a put struck at the strike discounted to that date, so the price of
+//! Rubinstein (1991) is a vanilla call and a shorter put, both plain [`OptionInputs`]. A complex
+//! chooser's call and put have their own strikes and expiries. It chooses the call above the
+//! critical spot at which the two are worth the same on the choice date, which leaves bivariate
+//! normals in the spot at the choice date and at each expiry.
+
+use crate::solve;
+use crate::{calculate_bivariate_ncdf, OptionInputs};
+
+/// The inputs to a chooser option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChooserOption {
+    /// Stock price
+    pub s: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time in years to the date the holder chooses a call or a put
+    pub choice_time: f64,
+
+    /// Strike and time to maturity in years of the call
+    pub call_k: f64,
+    pub call_t: f64,
+
+    /// Strike and time to maturity in years of the put
+    pub put_k: f64,
+    pub put_t: f64,
+
+    /// Implied vol
+    pub implied_vol: f64,
+}
+
+impl ChooserOption {
+    /// A simple chooser between a call and a put with strike `k` and maturity `t`.
+    pub fn simple(s: f64, k: f64, r: f64, q: f64, choice_time: f64, t: f64) -> Self {
+        Self::complex(s, r, q, choice_time, k, t, k, t)
+    }
+
+    /// A complex chooser between a call and a put with their own strikes and maturities.
+    #[allow(clippy::too_many_arguments)]
+    pub fn complex(
+        s: f64,
+        r: f64,
+        q: f64,
+        choice_time: f64,
+        call_k: f64,
+        call_t: f64,
+        put_k: f64,
+        put_t: f64,
+    ) -> Self {
+        Self {
+            s,
+            r,
+            q,
+            choice_time,
+            call_k,
+            call_t,
+            put_k,
+            put_t,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    /// Whether the call and put share a strike and expiry.
+    pub fn is_simple(&self) -> bool {
+        self.call_k == self.put_k && self.call_t == self.put_t
+    }
+
+    /// The call the holder may choose, valued today.
+    pub fn call(&self) -> OptionInputs {
+        OptionInputs::new(true, self.s, self.call_k, self.r, self.q, self.call_t)
+            .with_implied_vol(self.implied_vol)
+    }
+
+    /// The put the holder may choose, valued today.
+    pub fn put(&self) -> OptionInputs {
+        OptionInputs::new(false, self.s, self.put_k, self.r, self.q, self.put_t)
+            .with_implied_vol(self.implied_vol)
+    }
+
+    /// The spot at which the call and put are worth the same on the choice date.
+    pub fn critical_spot(&self) -> f64 {
+        let at_choice = |is_call: bool, s: f64, k: f64, t: f64| {
+            OptionInputs::new(is_call, s, k, self.r, self.q, t - self.choice_time)
+                .with_implied_vol(self.implied_vol)
+                .price()
+        };
+        let difference = |s: f64| {
+            at_choice(true, s, self.call_k, self.call_t)
+                - at_choice(false, s, self.put_k, self.put_t)
+        };
+        // the difference rises from below zero at zero spot without bound
+        let (mut lo, mut hi) = (1e-8 * self.call_k, self.call_k);
+        while difference(hi) < 0.0 {
+            lo = hi;
+            hi *= 2.0;
+        }
+        solve::brent(difference, lo, hi, 1e-12 * self.call_k).unwrap_or(f64::NAN)
+    }
+
+    pub fn price(&self) -> f64 {
+        if self.is_simple() {
+            return self.simple_price();
+        }
+        let vol = self.implied_vol;
+        let drift = self.r - self.q + 0.5 * vol * vol;
+        let choice = vol * self.choice_time.sqrt();
+        let d1 = ((self.s / self.critical_spot()).ln() + drift * self.choice_time) / choice;
+        let d2 = d1 - choice;
+
+        let (call, put) = (self.call(), self.put());
+        let leg = |option: &OptionInputs, sign: f64| {
+            let stddev = vol * option.t.sqrt();
+            let y = ((self.s / option.k).ln() + drift * option.t) / stddev;
+            let rho = (self.choice_time / option.t).sqrt();
+            sign * (self.s
+                * option.dividend_discount()
+                * calculate_bivariate_ncdf(sign * d1, sign * y, rho)
+                - option.k
+                    * option.rate_discount()
+                    * calculate_bivariate_ncdf(sign * d2, sign * (y - stddev), rho))
+        };
+        leg(&call, 1.0) + leg(&put, -1.0)
+    }
+
+    /// The call and, by parity on the choice date, the put on the strike discounted to it.
+    fn simple_price(&self) -> f64 {
+        let remaining = self.call_t - self.choice_time;
+        let strike = self.call_k * (-(self.r - self.q) * remaining).exp();
+        let put = OptionInputs::new(false, self.s, strike, self.r, self.q, self.choice_time)
+            .with_implied_vol(self.implied_vol);
+        self.call().price() + (-self.q * remaining).exp() * put.price()
+    }
+}
@@ -0,0 +1,159 @@
+//! Searching a chain for collars and protective puts.
+//!
+//! A protective put sets a floor under a stock holding; a collar pays for the put, in part or in
+//! full, by selling a call above it and giving up the upside beyond the call strike. Candidates
+//! are priced at the touch: the put is bought at the ask and the call sold at the bid. Each
+//! candidate carries its [`Strategy`], with the legs at their mid vols, for its Greeks.
+
+use crate::chain::{OptionChain, OptionQuote};
+use crate::strategy::Strategy;
+use crate::OptionInputs;
+
+/// Default tolerance on the net premium as a fraction of the spot, enough to absorb rounding in
+/// quoted prices when searching for zero-cost structures.
+pub const DEFAULT_COST_TOLERANCE: f64 = 1e-4;
+
+/// Requirements a protective structure has to meet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HedgeConstraints {
+    /// Lowest acceptable put strike
+    pub floor: f64,
+
+    /// Largest acceptable delta of the short call, the share of upside sold away
+    pub max_delta_give_up: f64,
+
+    /// Net premium paid per share the structure should cost, zero for a zero-cost collar
+    pub target_cost: f64,
+
+    /// How far the net premium may differ from the target, `None` for
+    /// [`DEFAULT_COST_TOLERANCE`] times the spot
+    pub cost_tolerance: Option<f64>,
+}
+
+impl HedgeConstraints {
+    /// Zero-cost structures with a put struck at or above `floor`.
+    pub fn new(floor: f64) -> Self {
+        Self {
+            floor,
+            max_delta_give_up: 1.0,
+            target_cost: 0.0,
+            cost_tolerance: None,
+        }
+    }
+
+    pub fn with_max_delta_give_up(mut self, max_delta_give_up: f64) -> Self {
+        self.max_delta_give_up = max_delta_give_up;
+        self
+    }
+
+    pub fn with_target_cost(mut self, target_cost: f64, cost_tolerance: f64) -> Self {
+        self.target_cost = target_cost;
+        self.cost_tolerance = Some(cost_tolerance);
+        self
+    }
+
+    fn cost_ok(&self, cost: f64, s: f64) -> bool {
+        let tolerance = self.cost_tolerance.unwrap_or(DEFAULT_COST_TOLERANCE * s);
+        (cost - self.target_cost).abs() <= tolerance
+    }
+}
+
+/// A protective put on one share, with the call sold against it for a collar.
+#[derive(Debug, Clone)]
+pub struct HedgeCandidate {
+    pub put: OptionQuote,
+    pub call: Option<OptionQuote>,
+
+    /// The share and its option legs at their mid vols
+    pub strategy: Strategy,
+
+    /// Put ask less call bid
+    pub net_cost: f64,
+
+    /// Delta of the short call, zero without one
+    pub delta_give_up: f64,
+
+    /// Delta of the share, put, and call together
+    pub delta: f64,
+}
+
+fn at_mid(chain: &OptionChain, quote: &OptionQuote) -> OptionInputs {
+    let vol = chain.solve_quote(quote).mid_vol;
+    chain.option(quote).with_implied_vol(vol)
+}
+
+fn puts(chain: &OptionChain, t: f64, floor: f64) -> impl Iterator<Item = &OptionQuote> {
+    chain
+        .quotes
+        .iter()
+        .filter(move |q| !q.is_call && q.t == t && q.k >= floor)
+}
+
+/// Order by highest floor, then highest cap, then cheapest.
+fn rank(candidates: &mut [HedgeCandidate]) {
+    let cap = |c: &HedgeCandidate| c.call.as_ref().map_or(f64::INFINITY, |q| q.k);
+    candidates.sort_by(|a, b| {
+        b.put
+            .k
+            .total_cmp(&a.put.k)
+            .then(cap(b).total_cmp(&cap(a)))
+            .then(a.net_cost.total_cmp(&b.net_cost))
+    });
+}
+
+/// Every put and call pair expiring at `t` whose net cost is within tolerance of the target and
+/// whose call gives up no more delta than allowed, best first.
+pub fn collars(chain: &OptionChain, t: f64, constraints: &HedgeConstraints) -> Vec<HedgeCandidate> {
+    let calls: Vec<(&OptionQuote, OptionInputs)> = chain
+        .quotes
+        .iter()
+        .filter(|q| q.is_call && q.t == t)
+        .map(|q| (q, at_mid(chain, q)))
+        .filter(|(_, option)| option.delta() <= constraints.max_delta_give_up)
+        .collect();
+
+    let mut candidates = Vec::new();
+    for put in puts(chain, t, constraints.floor) {
+        let put_option = at_mid(chain, put);
+        for (call, call_option) in calls.iter().filter(|(c, _)| c.k > put.k) {
+            let net_cost = put.ask - call.bid;
+            if constraints.cost_ok(net_cost, chain.s) {
+                let strategy = Strategy::collar(put_option.clone(), call_option.clone());
+                candidates.push(HedgeCandidate {
+                    put: put.clone(),
+                    call: Some((*call).clone()),
+                    net_cost,
+                    delta_give_up: call_option.delta(),
+                    delta: strategy.delta(),
+                    strategy,
+                });
+            }
+        }
+    }
+    rank(&mut candidates);
+    candidates
+}
+
+/// Puts expiring at `t` whose ask is within tolerance of the target cost, best first.
+pub fn protective_puts(
+    chain: &OptionChain,
+    t: f64,
+    constraints: &HedgeConstraints,
+) -> Vec<HedgeCandidate> {
+    let mut candidates: Vec<HedgeCandidate> = puts(chain, t, constraints.floor)
+        .filter(|put| constraints.cost_ok(put.ask, chain.s))
+        .map(|put| {
+            let strategy = Strategy::protective_put(at_mid(chain, put));
+            HedgeCandidate {
+                put: put.clone(),
+                call: None,
+                net_cost: put.ask,
+                delta_give_up: 0.0,
+                delta: strategy.delta(),
+                strategy,
+            }
+        })
+        .collect();
+    rank(&mut candidates);
+    candidates
+}
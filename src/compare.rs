@@ -0,0 +1,211 @@
+//! Pricing one contract under several models to measure model risk.
+//!
+//! Each model is reduced to a price function of the contract, and the Greeks are taken by
+//! bumping spot and time in the same way for all of them, so differences in the report come from
+//! the models alone and not from how each one computes its risk. The models' vol parameters have
+//! no common meaning, so vega is the BSM vega at each model's implied vol: the change in price
+//! for a vol point move of its implied vol.
+
+use crate::cev::{Cev, CevOption};
+use crate::fourier::{CosPricer, Gbm};
+use crate::heston::{Bates, Heston, HestonOption};
+use crate::merton::{Merton, MertonOption};
+use crate::sabr::Sabr;
+use crate::{OptionInputs, DAYS_PER_YEAR};
+
+/// A model that can price a European option. The contract is given as BSM inputs whose implied
+/// vol is ignored.
+pub trait PricingModel {
+    /// Name used in reports
+    fn name(&self) -> String;
+
+    fn price(&self, contract: &OptionInputs) -> f64;
+}
+
+impl PricingModel for Gbm {
+    fn name(&self) -> String {
+        "BSM".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        OptionInputs::new(c.is_call, c.s, c.k, c.r, c.q, c.t)
+            .with_implied_vol(self.vol)
+            .price()
+    }
+}
+
+impl PricingModel for Heston {
+    fn name(&self) -> String {
+        "Heston".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        HestonOption::new(c.is_call, c.s, c.k, c.r, c.q, c.t, *self).price()
+    }
+}
+
+impl PricingModel for Bates {
+    fn name(&self) -> String {
+        "Bates".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        CosPricer::new(512, 12.0).price(self, c.is_call, c.s, c.k, c.r, c.q, c.t)
+    }
+}
+
+impl PricingModel for Merton {
+    fn name(&self) -> String {
+        "Merton".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        MertonOption::new(c.is_call, c.s, c.k, c.r, c.q, c.t, *self).price()
+    }
+}
+
+impl PricingModel for Sabr {
+    fn name(&self) -> String {
+        "SABR".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        self.option(c.is_call, c.s, c.k, c.r, c.q, c.t).price()
+    }
+}
+
+impl PricingModel for Cev {
+    fn name(&self) -> String {
+        "CEV".to_string()
+    }
+
+    fn price(&self, c: &OptionInputs) -> f64 {
+        CevOption::new(c.is_call, c.s, c.k, c.r, c.q, c.t, *self).price()
+    }
+}
+
+/// One model's view of the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelResult {
+    pub name: String,
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+
+    /// Price change per vol point of implied vol, NaN without an implied vol
+    pub vega: f64,
+
+    /// Price change over one calendar day, NaN for a contract expiring within the day
+    pub theta: f64,
+
+    /// BSM implied vol of the model price, NaN if the price admits none
+    pub implied_vol: f64,
+}
+
+/// Spread of a quantity across models.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dispersion {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+
+    /// Population standard deviation
+    pub std_dev: f64,
+}
+
+impl Dispersion {
+    /// The spread of the finite `values`, `None` if there are none.
+    fn of(values: impl Iterator<Item = f64>) -> Option<Self> {
+        let values: Vec<f64> = values.filter(|v| v.is_finite()).collect();
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        Some(Self {
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            std_dev: (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt(),
+        })
+    }
+
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+/// The models' results and the spread of each quantity across them. A spread is `None` when
+/// no model gives a finite value, as for implied vols when no model price admits one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    pub results: Vec<ModelResult>,
+    pub price: Option<Dispersion>,
+    pub delta: Option<Dispersion>,
+    pub gamma: Option<Dispersion>,
+    pub vega: Option<Dispersion>,
+    pub theta: Option<Dispersion>,
+    pub implied_vol: Option<Dispersion>,
+}
+
+/// Price `contract` under every model in `models` and summarise how far they disagree, `None`
+/// without any models.
+pub fn compare(contract: &OptionInputs, models: &[&dyn PricingModel]) -> Option<ComparisonReport> {
+    if models.is_empty() {
+        return None;
+    }
+    let h = 1e-3 * contract.s;
+    let day = 1.0 / DAYS_PER_YEAR;
+    let bumped = |ds: f64, dt: f64| OptionInputs {
+        s: contract.s + ds,
+        t: contract.t - dt,
+        ..contract.clone()
+    };
+    let (up, down, later) = (bumped(h, 0.0), bumped(-h, 0.0), bumped(0.0, day));
+
+    let results: Vec<ModelResult> = models
+        .iter()
+        .map(|model| {
+            let price = model.price(contract);
+            let (p_up, p_down) = (model.price(&up), model.price(&down));
+            let implied = OptionInputs::new(
+                contract.is_call,
+                contract.s,
+                contract.k,
+                contract.r,
+                contract.q,
+                contract.t,
+            )
+            .with_price(price);
+            let implied_vol = implied.implied_vol();
+            ModelResult {
+                name: model.name(),
+                price,
+                delta: (p_up - p_down) / (2.0 * h),
+                gamma: (p_up - 2.0 * price + p_down) / (h * h),
+                vega: if implied_vol.is_finite() {
+                    implied.vega()
+                } else {
+                    f64::NAN
+                },
+                theta: if contract.t > day {
+                    model.price(&later) - price
+                } else {
+                    f64::NAN
+                },
+                implied_vol,
+            }
+        })
+        .collect();
+
+    let spread = |value: fn(&ModelResult) -> f64| Dispersion::of(results.iter().map(value));
+    Some(ComparisonReport {
+        price: spread(|r| r.price),
+        delta: spread(|r| r.delta),
+        gamma: spread(|r| r.gamma),
+        vega: spread(|r| r.vega),
+        theta: spread(|r| r.theta),
+        implied_vol: spread(|r| r.implied_vol),
+        results,
+    })
+}
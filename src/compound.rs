@@ -0,0 +1,110 @@
+//! Compound options, options on options, priced in closed form after Geske (1979).
+//!
+//! A compound option gives the right to buy or sell a vanilla option at a fixed price on an
+//! earlier date, which makes it a model for staged investments and financing contingencies:
+//! paying the compound strike keeps the project alive until the next stage. The compound is
+//! exercised when the underlying option is worth more than its strike, above or below the
+//! critical spot at which they are equal, so its price is a bivariate normal in the spot at the
+//! two expiries, whose log returns have correlation `sqrt(t / T)`. In Haug's arrangement the four
+//! cases differ only in signs.
+
+use crate::solve;
+use crate::{calculate_bivariate_ncdf, calculate_ncdf, OptionInputs};
+
+/// The inputs to an option on a vanilla option.
+#[derive(Debug, Clone)]
+pub struct CompoundOption {
+    /// The type of the compound option (call or put on the underlying option)
+    pub is_call: bool,
+
+    /// Price paid or received for the underlying option on exercise
+    pub k: f64,
+
+    /// Time to the compound option's expiry in years, before the underlying option's
+    pub t: f64,
+
+    /// The underlying option, with its own strike and expiry from today
+    pub underlying: OptionInputs,
+}
+
+impl CompoundOption {
+    pub fn new(is_call: bool, k: f64, t: f64, underlying: OptionInputs) -> Self {
+        Self {
+            is_call,
+            k,
+            t,
+            underlying,
+        }
+    }
+
+    /// Value of the underlying option at the compound's expiry when the spot is `s`.
+    fn underlying_at_expiry(&self, s: f64) -> f64 {
+        let u = &self.underlying;
+        OptionInputs::new(u.is_call, s, u.k, u.r, u.q, u.t - self.t)
+            .with_implied_vol(u.implied_vol)
+            .price()
+    }
+
+    /// The spot at which the underlying option is worth the compound strike at the compound's
+    /// expiry, `None` if it is worth less at every spot, as a put can be.
+    pub fn critical_spot(&self) -> Option<f64> {
+        let excess = |s: f64| self.underlying_at_expiry(s) - self.k;
+        let k2 = self.underlying.k;
+        let (mut lo, mut hi) = (1e-8 * k2, k2);
+        // the call's value rises with the spot and the put's falls
+        let rising = self.underlying.is_call;
+        while (excess(hi) < 0.0) == rising {
+            if hi > 1e8 * k2 {
+                return None;
+            }
+            lo = hi;
+            hi *= 2.0;
+        }
+        if !rising && excess(lo) < 0.0 {
+            return None;
+        }
+        solve::brent(excess, lo, hi, 1e-12 * k2).ok()
+    }
+
+    /// Geske's bivariate normal arguments `(y1, y2, z1, z2, rho)`, or `None` without a critical
+    /// spot.
+    fn arguments(&self) -> Option<(f64, f64, f64, f64, f64)> {
+        let critical = self.critical_spot()?;
+        let u = &self.underlying;
+        let vol = u.implied_vol;
+        let drift = u.r - u.q + 0.5 * vol * vol;
+        let (short, long) = (vol * self.t.sqrt(), vol * u.t.sqrt());
+        let y1 = ((u.s / critical).ln() + drift * self.t) / short;
+        let z1 = ((u.s / u.k).ln() + drift * u.t) / long;
+        Some((y1, y1 - short, z1, z1 - long, (self.t / u.t).sqrt()))
+    }
+
+    pub fn price(&self) -> f64 {
+        let u = &self.underlying;
+        let cash = self.k * (-u.r * self.t).exp();
+        let Some((y1, y2, z1, z2, rho)) = self.arguments() else {
+            // the underlying is never worth its strike, so a put is always exercised
+            return if self.is_call { 0.0 } else { cash - u.price() };
+        };
+        let phi = if self.is_call { 1.0 } else { -1.0 };
+        let omega = u.sign();
+        let share = u.s * u.dividend_discount();
+        let strike = u.k * u.rate_discount();
+        phi * (omega * share * calculate_bivariate_ncdf(omega * z1, phi * omega * y1, phi * rho)
+            - omega * strike * calculate_bivariate_ncdf(omega * z2, phi * omega * y2, phi * rho)
+            - cash * calculate_ncdf(phi * omega * y2))
+    }
+
+    /// Sensitivity to the spot of the underlying option's underlying.
+    pub fn delta(&self) -> f64 {
+        let u = &self.underlying;
+        let Some((y1, _, z1, _, rho)) = self.arguments() else {
+            return if self.is_call { 0.0 } else { -u.delta() };
+        };
+        let phi = if self.is_call { 1.0 } else { -1.0 };
+        let omega = u.sign();
+        phi * omega
+            * u.dividend_discount()
+            * calculate_bivariate_ncdf(omega * z1, phi * omega * y1, phi * rho)
+    }
+}
@@ -0,0 +1,197 @@
+//! Versioned model configuration.
+//!
+//! A [`ModelConfig`] records the reporting conventions behind the numbers this crate produces:
+//! the day count used to turn days into year fractions, how Greeks are scaled, and which delta is
+//! reported. Two systems can compare [`ModelConfig::fingerprint`]s, or exchange the configuration
+//! itself with the `serde` feature enabled, to show they report on the same conventions. Solver
+//! tolerances are fixed by the crate rather than configured.
+//! The default is the crate's own convention.
+
+use std::fmt;
+
+use crate::{OptionInputs, DAYS_PER_YEAR};
+
+/// Version of the configuration layout, bumped whenever a setting is added or its meaning
+/// changes.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// How days are turned into year fractions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayCount {
+    /// Calendar days over 365.25, the crate's convention
+    Actual365_25,
+    Actual365Fixed,
+    Actual360,
+
+    /// Business days over 252
+    Business252,
+}
+
+impl DayCount {
+    pub fn days_per_year(&self) -> f64 {
+        match self {
+            Self::Actual365_25 => DAYS_PER_YEAR,
+            Self::Actual365Fixed => 365.0,
+            Self::Actual360 => 360.0,
+            Self::Business252 => 252.0,
+        }
+    }
+}
+
+/// Time unit theta is reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThetaUnit {
+    PerYear,
+
+    /// Per day of the configured day count, the crate's convention
+    PerDay,
+}
+
+/// Which delta is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeltaConvention {
+    /// Sensitivity to spot, the crate's convention
+    Spot,
+
+    /// Sensitivity to the forward, in forward value
+    Forward,
+}
+
+/// Units Greeks are reported in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreekScaling {
+    /// Vol move vega is quoted for, 0.01 for per vol point
+    pub vega: f64,
+
+    /// Rate move rho is quoted for, 0.01 for per percentage point
+    pub rho: f64,
+
+    pub theta: ThetaUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelConfig {
+    /// Layout version the configuration was written with, see [`CONFIG_VERSION`]
+    pub version: u32,
+    pub day_count: DayCount,
+    pub greeks: GreekScaling,
+    pub delta: DeltaConvention,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            day_count: DayCount::Actual365_25,
+            greeks: GreekScaling {
+                vega: 0.01,
+                rho: 0.01,
+                theta: ThetaUnit::PerDay,
+            },
+            delta: DeltaConvention::Spot,
+        }
+    }
+}
+
+/// A configuration written with a layout version this build does not understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedVersion(pub u32);
+
+impl fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "model configuration version {} is not supported, expected {}",
+            self.0, CONFIG_VERSION
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+impl ModelConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_day_count(mut self, day_count: DayCount) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    pub fn with_greeks(mut self, greeks: GreekScaling) -> Self {
+        self.greeks = greeks;
+        self
+    }
+
+    pub fn with_delta(mut self, delta: DeltaConvention) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Check that a configuration received from elsewhere uses this build's layout.
+    pub fn check_version(&self) -> Result<(), UnsupportedVersion> {
+        if self.version == CONFIG_VERSION {
+            Ok(())
+        } else {
+            Err(UnsupportedVersion(self.version))
+        }
+    }
+
+    /// A 64-bit FNV-1a hash of the version and every setting. Equal settings give equal
+    /// fingerprints, with signed zeros hashed alike, so differing fingerprints mean differing
+    /// settings; distinct settings may still collide.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut write = |bytes: &[u8]| {
+            for &b in bytes {
+                hash = (hash ^ u64::from(b)).wrapping_mul(0x100000001b3);
+            }
+        };
+        write(&self.version.to_le_bytes());
+        write(&[
+            self.day_count as u8,
+            self.greeks.theta as u8,
+            self.delta as u8,
+        ]);
+        for x in [self.greeks.vega, self.greeks.rho] {
+            // adding zero turns -0.0 into 0.0, which compares equal to it
+            write(&(x + 0.0).to_bits().to_le_bytes());
+        }
+        hash
+    }
+
+    /// Year fraction of `days` under the day count.
+    pub fn year_fraction(&self, days: f64) -> f64 {
+        days / self.day_count.days_per_year()
+    }
+
+    pub fn delta(&self, option: &OptionInputs) -> f64 {
+        match self.delta {
+            DeltaConvention::Spot => option.delta(),
+            // the spot delta is the forward delta discounted to spot at the dividend yield
+            DeltaConvention::Forward => option.delta() / option.dividend_discount(),
+        }
+    }
+
+    pub fn vega(&self, option: &OptionInputs) -> f64 {
+        option.vega() / 0.01 * self.greeks.vega
+    }
+
+    pub fn rho(&self, option: &OptionInputs) -> f64 {
+        option.rho() / 0.01 * self.greeks.rho
+    }
+
+    pub fn theta(&self, option: &OptionInputs) -> f64 {
+        let per_year = option.theta() * DAYS_PER_YEAR;
+        match self.greeks.theta {
+            ThetaUnit::PerYear => per_year,
+            ThetaUnit::PerDay => per_year / self.day_count.days_per_year(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! A pricing path evaluable at compile time.
+//!
+//! Everything here is a `const fn`, so embedded users can compute reference prices and lookup
+//! tables into constants. `exp`, `ln`, and `sqrt` are implemented by hand since the standard
+//! library's are not `const`, and the normal CDF is the same Hart rational approximation as the
+//! batch path. Results agree with [`OptionInputs::price`](crate::OptionInputs::price) to around
+//! 1e-13 relative.
+//!
+//! ```
+//! use blackscholes::const_eval;
+//!
+//! const PRICE: f64 = const_eval::price(true, 100.0, 100.0, 0.05, 0.0, 1.0, 0.2);
+//! assert!((PRICE - 10.4506).abs() < 1e-4);
+//! ```
+
+const LN_2_HI: f64 = 6.931471803691238e-1;
+const LN_2_LO: f64 = 1.9082149292705877e-10;
+
+/// `2^k` for `k` in the normal exponent range.
+const fn pow2(k: i64) -> f64 {
+    f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+pub const fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.78 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.0;
+    }
+
+    // x = k ln 2 + r with |r| <= ln 2 / 2
+    let k = (x * std::f64::consts::LOG2_E + if x < 0.0 { -0.5 } else { 0.5 }) as i64;
+    let r = (x - k as f64 * LN_2_HI) - k as f64 * LN_2_LO;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut n = 1;
+    while n <= 14 {
+        term *= r / n as f64;
+        sum += term;
+        n += 1;
+    }
+    // split the scale so subnormal results do not underflow the exponent
+    let half = k / 2;
+    sum * pow2(half) * pow2(k - half)
+}
+
+pub const fn ln(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if x == f64::INFINITY {
+        return x;
+    }
+
+    let (mut x, mut e) = (x, 0);
+    if x < f64::MIN_POSITIVE {
+        x *= pow2(54);
+        e -= 54;
+    }
+    let bits = x.to_bits();
+    e += ((bits >> 52) & 0x7ff) as i64 - 1023;
+    // mantissa in [1, 2), then folded into [sqrt(1/2), sqrt(2))
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if m > std::f64::consts::SQRT_2 {
+        m /= 2.0;
+        e += 1;
+    }
+
+    // ln m = 2 atanh(f)
+    let f = (m - 1.0) / (m + 1.0);
+    let f2 = f * f;
+    let mut power = f;
+    let mut sum = 0.0;
+    let mut n = 1;
+    while n <= 29 {
+        sum += power / n as f64;
+        power *= f2;
+        n += 2;
+    }
+    2.0 * sum + e as f64 * LN_2_HI + e as f64 * LN_2_LO
+}
+
+pub const fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == f64::INFINITY {
+        return x;
+    }
+    // halving the exponent gives a guess within a factor of two, Newton does the rest
+    let mut y = f64::from_bits((x.to_bits() >> 1) + 0x1ff8_0000_0000_0000);
+    let mut i = 0;
+    while i < 8 {
+        y = 0.5 * (y + x / y);
+        i += 1;
+    }
+    y
+}
+
+/// Standard normal CDF (Hart 1968, as given by West 2005).
+pub const fn ncdf(x: f64) -> f64 {
+    let a = x.abs();
+    let e = exp(-0.5 * a * a);
+
+    let lower = if a < 7.07106781186547 {
+        let mut num = 3.52624965998911e-2 * a + 0.700383064443688;
+        num = num * a + 6.37396220353165;
+        num = num * a + 33.912866078383;
+        num = num * a + 112.079291497871;
+        num = num * a + 221.213596169931;
+        num = num * a + 220.206867912376;
+        let mut den = 8.83883476483184e-2 * a + 1.75566716318264;
+        den = den * a + 16.064177579207;
+        den = den * a + 86.7807322029461;
+        den = den * a + 296.564248779674;
+        den = den * a + 637.333633378831;
+        den = den * a + 793.826512519948;
+        den = den * a + 440.413735824752;
+        e * num / den
+    } else {
+        let mut cf = a + 0.65;
+        cf = a + 4.0 / cf;
+        cf = a + 3.0 / cf;
+        cf = a + 2.0 / cf;
+        cf = a + 1.0 / cf;
+        e / cf / 2.506628274631
+    };
+    if x > 0.0 {
+        1.0 - lower
+    } else {
+        lower
+    }
+}
+
+/// BSM price of a European option.
+pub const fn price(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, vol: f64) -> f64 {
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let stddev = vol * sqrt(t);
+    let d1 = (ln(s / k) + (r - q) * t) / stddev + 0.5 * stddev;
+    let d2 = d1 - stddev;
+    sign * (s * exp(-q * t) * ncdf(sign * d1) - k * exp(-r * t) * ncdf(sign * d2))
+}
@@ -0,0 +1,50 @@
+//! Correlation risk for multi-asset pricers.
+//!
+//! Pricers for options on several underlyings implement [`CorrelationRisk`] to get correlation
+//! sensitivity (cega) and correlation scenarios from a single way of shifting their correlations.
+
+/// A multi-asset option whose value depends on the correlations between its underlyings.
+pub trait CorrelationRisk: Sized {
+    /// Present value
+    fn value(&self) -> f64;
+
+    /// A copy with every pairwise correlation shifted by `shift`, clamped to [-1, 1].
+    fn shift_correlation(&self, shift: f64) -> Self;
+
+    /// Value change per 0.01 shift of every correlation, by central difference.
+    fn cega(&self) -> f64 {
+        let h = 1e-4;
+        (self.shift_correlation(h).value() - self.shift_correlation(-h).value()) / (2.0 * h) * 0.01
+    }
+
+    /// Value change for each correlation shift in `shifts`.
+    fn correlation_scenarios(&self, shifts: &[f64]) -> Vec<CorrelationScenario> {
+        let base = self.value();
+        shifts
+            .iter()
+            .map(|&shift| {
+                let value = self.shift_correlation(shift).value();
+                CorrelationScenario {
+                    shift,
+                    value,
+                    pnl: value - base,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The result of shifting correlations by one amount.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorrelationScenario {
+    pub shift: f64,
+    pub value: f64,
+
+    /// Value change against the unshifted correlations
+    pub pnl: f64,
+}
+
+/// `rho + shift` kept a valid correlation.
+pub(crate) fn shifted(rho: f64, shift: f64) -> f64 {
+    (rho + shift).clamp(-1.0, 1.0)
+}
@@ -0,0 +1,147 @@
+//! Term structures of continuously compounded rates, and the discount factor, zero rate, forward
+//! rate, and annuity conversions built on them.
+
+use crate::surface::interpolate;
+
+/// How a quoted rate compounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compounding {
+    Continuous,
+
+    /// Simple interest, as money-market and FRA rates are quoted
+    Simple,
+
+    /// Compounded the given number of times a year, e.g. 2 for semi-annual bond yields
+    Periodic(u32),
+}
+
+/// Discount factor to `t` of a zero rate quoted with `compounding`.
+pub fn discount_factor(rate: f64, t: f64, compounding: Compounding) -> f64 {
+    match compounding {
+        Compounding::Continuous => (-rate * t).exp(),
+        Compounding::Simple => 1.0 / (1.0 + rate * t),
+        Compounding::Periodic(n) => {
+            let n = f64::from(n);
+            (1.0 + rate / n).powf(-n * t)
+        }
+    }
+}
+
+/// Zero rate to `t` quoted with `compounding` that gives the discount factor `df`.
+pub fn zero_rate(df: f64, t: f64, compounding: Compounding) -> f64 {
+    match compounding {
+        Compounding::Continuous => -df.ln() / t,
+        Compounding::Simple => (1.0 / df - 1.0) / t,
+        Compounding::Periodic(n) => {
+            let n = f64::from(n);
+            n * (df.powf(-1.0 / (n * t)) - 1.0)
+        }
+    }
+}
+
+/// Forward rate from `t1` to `t2` quoted with `compounding`, implied by the discount factors to
+/// each.
+pub fn forward_rate(df1: f64, t1: f64, df2: f64, t2: f64, compounding: Compounding) -> f64 {
+    zero_rate(df2 / df1, t2 - t1, compounding)
+}
+
+/// Payment times every `1 / frequency` years from `start` up to and including `end`, with a
+/// short first period if the tenor is not a whole number of periods.
+pub fn schedule(start: f64, end: f64, frequency: u32) -> Vec<f64> {
+    let period = 1.0 / f64::from(frequency.max(1));
+    let mut times = Vec::new();
+    let mut t = end;
+    while t > start + 1e-9 {
+        times.push(t);
+        t -= period;
+    }
+    times.reverse();
+    times
+}
+
+/// Zero rates by maturity, linearly interpolated and held flat outside the nodes. Used both for
+/// risk-free discounting and for dividend or borrow yield curves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateCurve {
+    /// Maturities in years, ascending
+    pub times: Vec<f64>,
+
+    /// Continuously compounded zero rates
+    pub zero_rates: Vec<f64>,
+}
+
+impl RateCurve {
+    pub fn new(times: Vec<f64>, zero_rates: Vec<f64>) -> Self {
+        assert_eq!(times.len(), zero_rates.len(), "one zero rate per maturity");
+        assert!(!times.is_empty(), "a curve needs at least one node");
+        Self { times, zero_rates }
+    }
+
+    pub fn flat(rate: f64) -> Self {
+        Self::new(vec![1.0], vec![rate])
+    }
+
+    /// A curve through the given discount factors.
+    pub fn from_discount_factors(times: Vec<f64>, discount_factors: &[f64]) -> Self {
+        let zero_rates = times
+            .iter()
+            .zip(discount_factors)
+            .map(|(&t, &df)| zero_rate(df, t, Compounding::Continuous))
+            .collect();
+        Self::new(times, zero_rates)
+    }
+
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        interpolate(&self.times, &self.zero_rates, t)
+    }
+
+    pub fn discount_factor(&self, t: f64) -> f64 {
+        (-self.zero_rate(t) * t).exp()
+    }
+
+    /// Forward rate from `t1` to `t2` quoted with `compounding`.
+    pub fn forward_rate(&self, t1: f64, t2: f64, compounding: Compounding) -> f64 {
+        forward_rate(
+            self.discount_factor(t1),
+            t1,
+            self.discount_factor(t2),
+            t2,
+            compounding,
+        )
+    }
+
+    /// Present value of 1 a year paid on each of `payment_times`, accruing from `start` and
+    /// then from each payment to the next.
+    pub fn annuity(&self, start: f64, payment_times: &[f64]) -> f64 {
+        let mut previous = start;
+        payment_times
+            .iter()
+            .map(|&t| {
+                let accrual = t - previous;
+                previous = t;
+                accrual * self.discount_factor(t)
+            })
+            .sum()
+    }
+
+    /// Par swap rate from `start` to `end` with fixed payments `frequency` times a year: the
+    /// fixed rate at which the fixed leg is worth the floating leg.
+    pub fn par_rate(&self, start: f64, end: f64, frequency: u32) -> f64 {
+        let times = schedule(start, end, frequency);
+        (self.discount_factor(start) - self.discount_factor(end)) / self.annuity(start, &times)
+    }
+
+    /// Value of a forward rate agreement paying `notional * (L - rate) * (end - start)` at
+    /// `end`, where `L` is the simple rate fixed at `start`.
+    pub fn fra_value(&self, start: f64, end: f64, rate: f64, notional: f64) -> f64 {
+        let forward = self.forward_rate(start, end, Compounding::Simple);
+        notional * (forward - rate) * (end - start) * self.discount_factor(end)
+    }
+
+    /// Price per unit face of a bullet bond paying `coupon` a year in `frequency` instalments
+    /// until `maturity`.
+    pub fn bond_price(&self, coupon: f64, maturity: f64, frequency: u32) -> f64 {
+        let times = schedule(0.0, maturity, frequency);
+        coupon * self.annuity(0.0, &times) + self.discount_factor(maturity)
+    }
+}
@@ -0,0 +1,91 @@
+//! Digital (binary) options.
+//!
+//! A cash-or-nothing call pays a fixed amount if the underlying finishes above the strike, and an
+//! asset-or-nothing call pays the underlying itself; puts pay below the strike. Both have closed
+//! forms in the `N(d1)` and `N(d2)` the BSM inputs already cache. Their Greeks grow without bound
+//! at the strike as expiry nears, so dealers price and hedge them as tight vanilla spreads
+//! instead, which [`OptionInputs::digital_spread`] provides.
+
+use crate::OptionInputs;
+
+/// What a digital option pays when it finishes in the money.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Payout {
+    /// A fixed amount of cash
+    Cash(f64),
+
+    /// One unit of the underlying
+    Asset,
+}
+
+/// Value and Greeks of a digital option, in the units of the [`OptionInputs`] methods of the
+/// same names.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DigitalValue {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+}
+
+impl OptionInputs {
+    /// Closed-form value and Greeks of a digital with this option's type, strike, and vol.
+    pub fn digital(&self, payout: Payout) -> DigitalValue {
+        let sign = self.sign();
+        let stddev = self.implied_vol * self.t.sqrt();
+        match payout {
+            Payout::Cash(amount) => {
+                let discounted = amount * self.rate_discount();
+                let density = discounted * self.nprimed2;
+                DigitalValue {
+                    price: discounted * self.nd2,
+                    delta: sign * density / (self.s * stddev),
+                    gamma: -sign * density * self.d1 / (self.s * self.s * stddev * stddev),
+                    vega: -0.01 * sign * density * self.d1 / self.implied_vol,
+                }
+            }
+            Payout::Asset => {
+                let forward_value = self.s * self.dividend_discount();
+                let density = self.dividend_discount() * self.nprimed1;
+                DigitalValue {
+                    price: forward_value * self.nd1,
+                    delta: self.dividend_discount() * self.nd1 + sign * density / stddev,
+                    gamma: -sign * density * self.d2 / (self.s * stddev * stddev),
+                    vega: -0.01 * sign * self.s * density * self.d2 / self.implied_vol,
+                }
+            }
+        }
+    }
+
+    /// A digital replicated by vanillas struck `width` apart around the strike at this option's
+    /// vol, whose Greeks stay bounded near expiry. The cash digital is the vanilla spread scaled
+    /// by `amount / width`, and the asset digital is `K` cash digitals plus the vanilla at the
+    /// strike for calls, or less it for puts. The exact digital is the limit as the width goes
+    /// to zero.
+    pub fn digital_spread(&self, payout: Payout, width: f64) -> DigitalValue {
+        let vanilla = |k: f64| {
+            let o = OptionInputs::new(self.is_call, self.s, k, self.r, self.q, self.t)
+                .with_implied_vol(self.implied_vol);
+            [o.price(), o.delta(), o.gamma(), o.vega()]
+        };
+        let (low, high) = (vanilla(self.k - 0.5 * width), vanilla(self.k + 0.5 * width));
+        // calls lose and puts gain value as the strike rises
+        let cash = |amount: f64| {
+            let scale = self.sign() * amount / width;
+            [0, 1, 2, 3].map(|i| scale * (low[i] - high[i]))
+        };
+        let [price, delta, gamma, vega] = match payout {
+            Payout::Cash(amount) => cash(amount),
+            Payout::Asset => {
+                let (digitals, at) = (cash(self.k), vanilla(self.k));
+                [0, 1, 2, 3].map(|i| digitals[i] + self.sign() * at[i])
+            }
+        };
+        DigitalValue {
+            price,
+            delta,
+            gamma,
+            vega,
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Dispersion trades: index options against options on the index's components.
+//!
+//! The variance of an index of weights `w_i` on components with vols `sigma_i` and a single
+//! pairwise correlation `rho` is `sum (w_i sigma_i)^2 + rho sum_{i != j} w_i w_j sigma_i sigma_j`,
+//! so index and component implied vols together imply a correlation. A dispersion trade, usually
+//! short index vol and long component vol, is a position in that implied correlation.
+//!
+//! [`DispersionTrade::explain`] walks the index leg from one market to the next in two steps:
+//! first to the index vol the new component vols imply at the old correlation, then to the index
+//! vol actually quoted. Both legs' first steps are attributed by [`pnl::explain`], and the second
+//! step, repriced in full, is the P&L from the change in implied correlation.
+//!
+//! The variance formula is the short-horizon limit of the index as a basket of its components;
+//! [`DispersionMarket::basket`] builds that basket at a given correlation, whose effective vol
+//! and option prices hold to any expiry.
+
+use crate::basket::BasketOption;
+use crate::pnl::{self, PathPoint, PnlExplain};
+use crate::portfolio::Portfolio;
+use crate::OptionInputs;
+
+/// The index variance split into `own + rho * cross`.
+fn variance_terms(weights: &[f64], vols: &[f64]) -> (f64, f64) {
+    let own: f64 = weights.iter().zip(vols).map(|(w, v)| (w * v).powi(2)).sum();
+    let total: f64 = weights.iter().zip(vols).map(|(w, v)| w * v).sum();
+    (own, total * total - own)
+}
+
+/// Index vol from component vols and a single pairwise correlation, with `weights` the
+/// components' fractions of the index value.
+pub fn index_vol(weights: &[f64], vols: &[f64], correlation: f64) -> f64 {
+    let (own, cross) = variance_terms(weights, vols);
+    (own + correlation * cross).sqrt()
+}
+
+/// The single pairwise correlation that reconciles the index vol with the component vols,
+/// `None` unless at least two components with a vol have weight in the index.
+pub fn implied_correlation(index_vol: f64, weights: &[f64], vols: &[f64]) -> Option<f64> {
+    let (own, cross) = variance_terms(weights, vols);
+    (cross != 0.0).then(|| (index_vol * index_vol - own) / cross)
+}
+
+/// Correlation matrix of `n` assets with a single pairwise `correlation`.
+pub fn flat_correlation(n: usize, correlation: f64) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { 1.0 } else { correlation })
+                .collect()
+        })
+        .collect()
+}
+
+/// Spots and implied vols of an index and its components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispersionMarket {
+    pub index_spot: f64,
+    pub index_vol: f64,
+
+    /// Component spots and vols, in the order of the trade's components
+    pub spots: Vec<f64>,
+    pub vols: Vec<f64>,
+}
+
+impl DispersionMarket {
+    pub fn new(index_spot: f64, index_vol: f64, spots: Vec<f64>, vols: Vec<f64>) -> Self {
+        Self {
+            index_spot,
+            index_vol,
+            spots,
+            vols,
+        }
+    }
+
+    /// An option on the index as a basket of its components with a single pairwise
+    /// `correlation`, holding the units of each that make up its fraction `weights` of the index
+    /// value. The components pay no dividends.
+    pub fn basket(
+        &self,
+        weights: &[f64],
+        correlation: f64,
+        is_call: bool,
+        k: f64,
+        r: f64,
+        t: f64,
+    ) -> BasketOption {
+        let units = weights
+            .iter()
+            .zip(&self.spots)
+            .map(|(w, s)| w * self.index_spot / s)
+            .collect();
+        BasketOption::new(
+            is_call,
+            self.spots.clone(),
+            units,
+            self.vols.clone(),
+            flat_correlation(self.spots.len(), correlation),
+            k,
+            r,
+            t,
+        )
+    }
+}
+
+/// Attribution of a dispersion trade's P&L between two markets.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DispersionExplain {
+    /// The index leg, with its vol moved to the level the component vols imply at the starting
+    /// correlation
+    pub index: PnlExplain,
+
+    /// The component legs together
+    pub components: PnlExplain,
+
+    /// The index leg's repriced change from the move in implied correlation
+    pub correlation: f64,
+}
+
+impl DispersionExplain {
+    pub fn actual(&self) -> f64 {
+        self.index.actual + self.components.actual + self.correlation
+    }
+
+    /// Actual P&L not explained by the Greek terms of either leg.
+    pub fn residual(&self) -> f64 {
+        self.index.residual + self.components.residual
+    }
+}
+
+/// Options on an index and on its components. Each leg holds options on one underlying, which
+/// are valued at the spot and vol of a [`DispersionMarket`] rather than their own.
+#[derive(Debug, Clone)]
+pub struct DispersionTrade {
+    pub index: Portfolio,
+    pub components: Vec<Portfolio>,
+
+    /// The components' fractions of the index value, held fixed as prices move
+    pub weights: Vec<f64>,
+}
+
+impl DispersionTrade {
+    pub fn new(index: Portfolio, components: Vec<Portfolio>, weights: Vec<f64>) -> Self {
+        Self {
+            index,
+            components,
+            weights,
+        }
+    }
+
+    pub fn implied_correlation(&self, market: &DispersionMarket) -> Option<f64> {
+        implied_correlation(market.index_vol, &self.weights, &market.vols)
+    }
+
+    /// The trade with each leg's options at the market's spot and vol.
+    pub fn marked(&self, market: &DispersionMarket) -> Self {
+        let mark = |leg: &Portfolio, s: f64, vol: f64| {
+            let mut leg = leg.clone();
+            for position in &mut leg.positions {
+                let o = &position.option;
+                position.option =
+                    OptionInputs::new(o.is_call, s, o.k, o.r, o.q, o.t).with_implied_vol(vol);
+            }
+            leg
+        };
+        Self {
+            index: mark(&self.index, market.index_spot, market.index_vol),
+            components: self
+                .components
+                .iter()
+                .zip(market.spots.iter().zip(&market.vols))
+                .map(|(leg, (&s, &vol))| mark(leg, s, vol))
+                .collect(),
+            weights: self.weights.clone(),
+        }
+    }
+
+    pub fn value(&self, market: &DispersionMarket) -> f64 {
+        let marked = self.marked(market);
+        marked.index.value() + marked.components.iter().map(Portfolio::value).sum::<f64>()
+    }
+
+    /// Vega of the components per vol point, the sensitivity to a parallel move in their vols.
+    pub fn component_vega(&self, market: &DispersionMarket) -> f64 {
+        let marked = self.marked(market);
+        marked.components.iter().map(Portfolio::vega).sum()
+    }
+
+    /// Change in value for a rise of 0.01 in implied correlation with the component vols held,
+    /// which moves only the index leg.
+    pub fn correlation_exposure(&self, market: &DispersionMarket) -> f64 {
+        let (_, cross) = variance_terms(&self.weights, &market.vols);
+        // d index_vol / d rho = cross / (2 index_vol), and vega is per 0.01 of vol
+        self.marked(market).index.vega() * cross / (2.0 * market.index_vol)
+    }
+
+    /// Explain the P&L from `from` to `to` over `dt` years, which must end before any option
+    /// in the trade expires.
+    pub fn explain(
+        &self,
+        from: &DispersionMarket,
+        to: &DispersionMarket,
+        dt: f64,
+    ) -> DispersionExplain {
+        // with a single component the index vol does not depend on the correlation
+        let correlation = self.implied_correlation(from).unwrap_or(0.0);
+        let held = index_vol(&self.weights, &to.vols, correlation);
+        let index = explain_leg(
+            &self.index,
+            &[
+                (from.index_spot, from.index_vol, 0.0),
+                (to.index_spot, held, dt),
+                (to.index_spot, to.index_vol, dt),
+            ],
+        );
+
+        let mut components = PnlExplain::default();
+        for (i, leg) in self.components.iter().enumerate() {
+            components += explain_leg(
+                leg,
+                &[
+                    (from.spots[i], from.vols[i], 0.0),
+                    (to.spots[i], to.vols[i], dt),
+                ],
+            )[0];
+        }
+
+        DispersionExplain {
+            index: index[0],
+            components,
+            correlation: index[1].actual,
+        }
+    }
+}
+
+/// Explain each step of a leg's path of `(spot, vol, elapsed years)`, summed over its positions.
+fn explain_leg(leg: &Portfolio, path: &[(f64, f64, f64)]) -> Vec<PnlExplain> {
+    let mut steps = vec![PnlExplain::default(); path.len() - 1];
+    for position in &leg.positions {
+        let points: Vec<PathPoint> = path
+            .iter()
+            .map(|&(s, vol, elapsed)| PathPoint::new(s, vol, position.option.t - elapsed))
+            .collect();
+        let explained = pnl::explain(&position.option, position.units(), &points);
+        for (step, e) in steps.iter_mut().zip(explained) {
+            *step += e;
+        }
+    }
+    steps
+}
@@ -0,0 +1,133 @@
+//! Scheduled events in the implied vol term structure.
+//!
+//! Earnings, central bank meetings, and similar events each add a discrete jump in variance on
+//! their date on top of a diffusive base vol, so total implied variance to expiry `T` is
+//!
+//! ```text
+//! w(T) = base_vol^2 T + sum of move_e^2 over events e at or before T
+//! ```
+//!
+//! where `move_e` is the standard deviation of the log return over event `e`. Stripping the base
+//! variance from each step of a term structure recovers the moves implied for the events in that
+//! step, and dropping events that have happened gives the term structure after them.
+
+/// A scheduled event and the move priced for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Event {
+    /// Time of the event in years
+    pub time: f64,
+
+    /// Standard deviation of the log return over the event, NaN if the term structure implies
+    /// negative event variance
+    pub implied_move: f64,
+}
+
+impl Event {
+    pub fn new(time: f64, implied_move: f64) -> Self {
+        Self { time, implied_move }
+    }
+
+    /// Expected absolute log return over the event, `move * sqrt(2 / pi)`, which is how implied
+    /// moves are usually quoted from straddle prices.
+    pub fn expected_abs_move(&self) -> f64 {
+        self.implied_move * (2.0 / crate::PI).sqrt()
+    }
+
+    fn variance(&self) -> f64 {
+        self.implied_move * self.implied_move
+    }
+}
+
+/// A term structure of implied vol built from a base vol and a series of events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventTermStructure {
+    /// Diffusive vol between events
+    pub base_vol: f64,
+
+    /// Events in ascending time
+    pub events: Vec<Event>,
+}
+
+impl EventTermStructure {
+    pub fn new(base_vol: f64, mut events: Vec<Event>) -> Self {
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { base_vol, events }
+    }
+
+    /// Decompose implied `vols` at ascending `expiries` into moves for events at `event_times`,
+    /// given the base vol. The variance the term structure adds between consecutive expiries
+    /// beyond the base vol is assigned to the events in that interval, shared equally when there
+    /// are several since one expiry cannot tell them apart. Variance in intervals without events
+    /// is ignored, and events after the last expiry get no move.
+    pub fn from_term_structure(
+        base_vol: f64,
+        expiries: &[f64],
+        vols: &[f64],
+        event_times: &[f64],
+    ) -> Self {
+        let mut events: Vec<Event> = event_times.iter().map(|&t| Event::new(t, 0.0)).collect();
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        let (mut start, mut variance) = (0.0, 0.0);
+        for (&end, &vol) in expiries.iter().zip(vols) {
+            let total = vol * vol * end;
+            let excess = total - variance - base_vol * base_vol * (end - start);
+            let inside: Vec<&mut Event> = events
+                .iter_mut()
+                .filter(|e| e.time > start && e.time <= end)
+                .collect();
+            let share = excess / inside.len().max(1) as f64;
+            let implied_move = if share >= 0.0 { share.sqrt() } else { f64::NAN };
+            for e in inside {
+                e.implied_move = implied_move;
+            }
+            start = end;
+            variance = total;
+        }
+        Self { base_vol, events }
+    }
+
+    /// Total implied variance to `t`.
+    pub fn total_variance(&self, t: f64) -> f64 {
+        let events: f64 = self
+            .events
+            .iter()
+            .filter(|e| e.time > 0.0 && e.time <= t)
+            .map(Event::variance)
+            .sum();
+        self.base_vol * self.base_vol * t + events
+    }
+
+    /// Implied vol to expiry `t`.
+    pub fn vol(&self, t: f64) -> f64 {
+        (self.total_variance(t) / t).sqrt()
+    }
+
+    /// Implied vols at each of `expiries`.
+    pub fn term_structure(&self, expiries: &[f64]) -> Vec<f64> {
+        expiries.iter().map(|&t| self.vol(t)).collect()
+    }
+
+    /// The term structure once `elapsed` years have passed, with times measured from then and
+    /// the events up to and including that time dropped. Pricing off it after an event date
+    /// shows the vol crush the event leaves behind.
+    pub fn after(&self, elapsed: f64) -> Self {
+        let events = self
+            .events
+            .iter()
+            .filter(|e| e.time > elapsed)
+            .map(|e| Event::new(e.time - elapsed, e.implied_move))
+            .collect();
+        Self {
+            base_vol: self.base_vol,
+            events,
+        }
+    }
+
+    /// A copy with the move of event `index` replaced, for scenarios on a single event.
+    pub fn with_move(&self, index: usize, implied_move: f64) -> Self {
+        let mut structure = self.clone();
+        structure.events[index].implied_move = implied_move;
+        structure
+    }
+}
@@ -0,0 +1,95 @@
+//! Margrabe (1978) exchange options.
+//!
+//! An exchange option gives the right to hand over one asset and receive another at expiry,
+//! paying `max(S1 - S2, 0)`. Measured in units of the second asset it is a call struck at one,
+//! so it prices as Black-76 on the assets' prepaid forwards with the vol of their ratio and no
+//! discounting. Interest rates drop out. Stock-for-stock mergers and relative-value trades
+//! between two names are built from it.
+
+use crate::correlation::{shifted, CorrelationRisk};
+use crate::Black76Inputs;
+
+/// The inputs to an option to exchange the second asset for the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeOption {
+    /// Price of the asset received
+    pub s1: f64,
+
+    /// Price of the asset delivered
+    pub s2: f64,
+
+    /// Dividend yields of the two assets
+    pub q1: f64,
+    pub q2: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub vol1: f64,
+    pub vol2: f64,
+
+    /// Correlation of the two assets' returns
+    pub rho: f64,
+}
+
+impl ExchangeOption {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(s1: f64, s2: f64, q1: f64, q2: f64, t: f64, vol1: f64, vol2: f64, rho: f64) -> Self {
+        Self {
+            s1,
+            s2,
+            q1,
+            q2,
+            t,
+            vol1,
+            vol2,
+            rho,
+        }
+    }
+
+    /// Vol of the ratio `S1 / S2`.
+    pub fn effective_vol(&self) -> f64 {
+        (self.vol1.powi(2) - 2.0 * self.rho * self.vol1 * self.vol2 + self.vol2.powi(2)).sqrt()
+    }
+
+    /// Black-76 inputs on the first asset's prepaid forward struck at the second's, at zero
+    /// rate, whose price is the exchange option's.
+    pub fn black76(&self) -> Black76Inputs {
+        let forward1 = self.s1 * (-self.q1 * self.t).exp();
+        let forward2 = self.s2 * (-self.q2 * self.t).exp();
+        Black76Inputs::new(true, forward1, forward2, 0.0, self.t)
+            .with_implied_vol(self.effective_vol())
+    }
+
+    pub fn price(&self) -> f64 {
+        self.black76().price()
+    }
+
+    /// Sensitivity to the price of the asset received.
+    pub fn delta1(&self) -> f64 {
+        self.black76().delta() * (-self.q1 * self.t).exp()
+    }
+
+    /// Sensitivity to the price of the asset delivered, negative.
+    pub fn delta2(&self) -> f64 {
+        self.black76().dual_delta() * (-self.q2 * self.t).exp()
+    }
+
+    /// Sensitivity to the vol of the ratio, per 0.01.
+    pub fn vega(&self) -> f64 {
+        self.black76().vega()
+    }
+}
+
+impl CorrelationRisk for ExchangeOption {
+    fn value(&self) -> f64 {
+        self.price()
+    }
+
+    fn shift_correlation(&self, shift: f64) -> Self {
+        Self {
+            rho: shifted(self.rho, shift),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Exercise decisions and settlement at expiry.
+//!
+//! Given the settlement price of the underlying, every position in a portfolio is either
+//! exercised (long), assigned (short), or left to expire, following the same in-the-money
+//! threshold clearing houses use for automatic exercise. Physically settled options turn into
+//! share positions and strike cash flows; cash-settled options pay their intrinsic value.
+
+use crate::{Portfolio, Position};
+
+/// How exercised options settle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Settlement {
+    /// Delivery of the underlying against the strike
+    Physical,
+
+    /// Payment of the intrinsic value
+    Cash,
+}
+
+/// Automatic exercise rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExerciseRules {
+    /// Options at least this far in the money are exercised, e.g. 0.01 for the OCC rule
+    pub threshold: f64,
+
+    pub settlement: Settlement,
+
+    /// Labels of long positions the holder has instructed not to exercise
+    pub do_not_exercise: Vec<String>,
+}
+
+impl Default for ExerciseRules {
+    fn default() -> Self {
+        Self {
+            threshold: 0.01,
+            settlement: Settlement::Physical,
+            do_not_exercise: Vec::new(),
+        }
+    }
+}
+
+impl ExerciseRules {
+    pub fn new(threshold: f64, settlement: Settlement) -> Self {
+        Self {
+            threshold,
+            settlement,
+            do_not_exercise: Vec::new(),
+        }
+    }
+
+    pub fn with_do_not_exercise(mut self, label: impl Into<String>) -> Self {
+        self.do_not_exercise.push(label.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExerciseAction {
+    /// A long position was exercised
+    Exercised,
+
+    /// A short position was assigned
+    Assigned,
+
+    /// The option expired unexercised
+    Expired,
+}
+
+/// What happened to one position at expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiryOutcome {
+    pub label: String,
+    pub action: ExerciseAction,
+
+    /// In-the-money amount per unit of the option
+    pub intrinsic: f64,
+
+    /// Cash received, negative when paid
+    pub cash: f64,
+
+    /// Change in the share position
+    pub shares: f64,
+}
+
+impl ExpiryOutcome {
+    /// Value of the outcome at the settlement price.
+    pub fn value(&self, settlement_price: f64) -> f64 {
+        self.cash + self.shares * settlement_price
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiryReport {
+    pub settlement_price: f64,
+    pub outcomes: Vec<ExpiryOutcome>,
+}
+
+impl ExpiryReport {
+    pub fn cash(&self) -> f64 {
+        self.outcomes.iter().map(|o| o.cash).sum()
+    }
+
+    pub fn shares(&self) -> f64 {
+        self.outcomes.iter().map(|o| o.shares).sum()
+    }
+
+    /// Total value delivered by expiry, which equals the portfolio's intrinsic value for options
+    /// that were exercised or assigned.
+    pub fn value(&self) -> f64 {
+        self.cash() + self.shares() * self.settlement_price
+    }
+}
+
+impl ExerciseRules {
+    pub fn settle(&self, position: &Position, settlement_price: f64) -> ExpiryOutcome {
+        let option = &position.option;
+        let intrinsic = (option.sign() * (settlement_price - option.k)).max(0.0);
+        let units = position.units();
+
+        let in_the_money = intrinsic > 0.0 && intrinsic >= self.threshold;
+        let action = if units < 0.0 && in_the_money {
+            ExerciseAction::Assigned
+        } else if units > 0.0 && in_the_money && !self.do_not_exercise.contains(&position.label) {
+            ExerciseAction::Exercised
+        } else {
+            ExerciseAction::Expired
+        };
+
+        let (cash, shares) = match (action, self.settlement) {
+            (ExerciseAction::Expired, _) => (0.0, 0.0),
+            (_, Settlement::Cash) => (units * intrinsic, 0.0),
+            // long calls and short puts buy the underlying at the strike
+            (_, Settlement::Physical) => {
+                let shares = option.sign() * units;
+                (-shares * option.k, shares)
+            }
+        };
+
+        ExpiryOutcome {
+            label: position.label.clone(),
+            action,
+            intrinsic,
+            cash,
+            shares,
+        }
+    }
+
+    /// Settle every position of the portfolio, all of which are treated as expiring.
+    pub fn process(&self, portfolio: &Portfolio, settlement_price: f64) -> ExpiryReport {
+        ExpiryReport {
+            settlement_price,
+            outcomes: portfolio
+                .positions
+                .iter()
+                .map(|p| self.settle(p, settlement_price))
+                .collect(),
+        }
+    }
+}
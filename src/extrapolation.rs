@@ -0,0 +1,20 @@
+//! Richardson extrapolation of discretized prices.
+//!
+//! A lattice or grid price with error `c h^p` in the step size `h` can be rid of its leading
+//! error term by combining two step sizes. The error must be smooth in the step for this to
+//! help, so binomial trees need smoothing first, see [`crate::binomial`]: their raw error
+//! oscillates with the strike's position between nodes.
+
+/// Extrapolate to zero step from `coarse`, computed at a step `ratio` times larger than `fine`,
+/// for an error of order `order` in the step.
+pub fn richardson(coarse: f64, fine: f64, ratio: f64, order: f64) -> f64 {
+    let factor = ratio.powf(order);
+    (factor * fine - coarse) / (factor - 1.0)
+}
+
+/// Extrapolate `price`, a function of the number of steps with error of order `order` in the
+/// step size, from `steps / 2` and `steps` steps.
+pub fn richardson_in_steps(price: impl Fn(usize) -> f64, steps: usize, order: f64) -> f64 {
+    let half = (steps / 2).max(1);
+    richardson(price(half), price(2 * half), 2.0, order)
+}
@@ -0,0 +1,141 @@
+//! Filters for cleaning noisy implied vol series.
+//!
+//! Tick-level implied vols inherit bid/ask bounce and quote noise from the prices they are solved
+//! from. These filters produce a smoother estimate of the underlying vol level one observation at
+//! a time, so they can run on live data as well as over stored series. Non-finite observations,
+//! such as failed IV solves, are skipped and leave the estimate unchanged.
+
+/// Exponentially weighted moving average.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ewma {
+    /// Weight given to each new observation, in (0, 1]
+    pub alpha: f64,
+
+    value: f64,
+}
+
+impl Ewma {
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            alpha,
+            value: f64::NAN,
+        }
+    }
+
+    /// EWMA whose weights halve every `half_life` observations.
+    pub fn from_half_life(half_life: f64) -> Self {
+        Self::new(1.0 - 0.5_f64.powf(1.0 / half_life))
+    }
+
+    /// Current estimate, NaN before the first observation.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn update(&mut self, x: f64) -> f64 {
+        self.update_after(x, 1.0)
+    }
+
+    /// Update with an observation arriving `dt` observation periods after the previous one, for
+    /// irregularly spaced ticks.
+    pub fn update_after(&mut self, x: f64, dt: f64) -> f64 {
+        if x.is_finite() {
+            if self.value.is_finite() {
+                let alpha = 1.0 - (1.0 - self.alpha).powf(dt);
+                self.value += alpha * (x - self.value);
+            } else {
+                self.value = x;
+            }
+        }
+        self.value
+    }
+}
+
+/// Local-level Kalman filter: the true vol follows a random walk and each quote observes it with
+/// noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanFilter {
+    /// Variance of the true vol's change per observation period
+    pub process_variance: f64,
+
+    /// Variance of the observation noise
+    pub measurement_variance: f64,
+
+    /// Observations further than this many standard deviations from the prediction are rejected
+    pub outlier_threshold: f64,
+
+    estimate: f64,
+    error_variance: f64,
+}
+
+impl KalmanFilter {
+    pub fn new(process_variance: f64, measurement_variance: f64) -> Self {
+        Self {
+            process_variance,
+            measurement_variance,
+            outlier_threshold: f64::INFINITY,
+            estimate: f64::NAN,
+            error_variance: f64::NAN,
+        }
+    }
+
+    pub fn with_outlier_threshold(mut self, outlier_threshold: f64) -> Self {
+        self.outlier_threshold = outlier_threshold;
+        self
+    }
+
+    /// Current estimate, NaN before the first observation.
+    pub fn value(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Variance of the current estimate.
+    pub fn error_variance(&self) -> f64 {
+        self.error_variance
+    }
+
+    pub fn update(&mut self, x: f64) -> f64 {
+        self.update_with(x, 1.0, self.measurement_variance)
+    }
+
+    /// Update with an observation arriving `dt` observation periods after the previous one and
+    /// its own measurement variance, e.g. derived from the bid/ask IV spread of the quote.
+    pub fn update_with(&mut self, x: f64, dt: f64, measurement_variance: f64) -> f64 {
+        if !x.is_finite() {
+            return self.estimate;
+        }
+        if !self.estimate.is_finite() {
+            self.estimate = x;
+            self.error_variance = measurement_variance;
+            return self.estimate;
+        }
+
+        let predicted_variance = self.error_variance + self.process_variance * dt;
+        let innovation = x - self.estimate;
+        let innovation_variance = predicted_variance + measurement_variance;
+
+        if innovation.abs() > self.outlier_threshold * innovation_variance.sqrt() {
+            // Keep the prediction but let the uncertainty grow so a genuine jump is accepted
+            // once it persists.
+            self.error_variance = predicted_variance;
+            return self.estimate;
+        }
+
+        let gain = predicted_variance / innovation_variance;
+        self.estimate += gain * innovation;
+        self.error_variance = (1.0 - gain) * predicted_variance;
+        self.estimate
+    }
+}
+
+/// Apply an EWMA over a whole series.
+pub fn ewma(series: &[f64], alpha: f64) -> Vec<f64> {
+    let mut filter = Ewma::new(alpha);
+    series.iter().map(|&x| filter.update(x)).collect()
+}
+
+/// Apply a Kalman filter over a whole series.
+pub fn kalman(series: &[f64], process_variance: f64, measurement_variance: f64) -> Vec<f64> {
+    let mut filter = KalmanFilter::new(process_variance, measurement_variance);
+    series.iter().map(|&x| filter.update(x)).collect()
+}
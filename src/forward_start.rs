@@ -0,0 +1,156 @@
+//! Forward-start options and the ratchet (cliquet) legs built from them.
+//!
+//! A forward-start option comes alive at a future date with its strike set to a fraction
+//! `alpha` of the spot on that date, as employee options granted at the money are. Under BSM
+//! the option is then worth that spot times an option on one unit struck at `alpha`, so by
+//! Rubinstein (1990) it is worth today the spot times the dividend yield discount to the start
+//! times the unit option, and it is linear in the spot. A ratchet is a strip of them, each leg
+//! starting as the previous one expires with a strike reset from the spot at that date.
+
+use crate::OptionInputs;
+
+/// The forward vol between `t1` and `t2` implied by the vols to each, with `t1 < t2`.
+pub fn forward_vol(vol1: f64, t1: f64, vol2: f64, t2: f64) -> f64 {
+    ((vol2 * vol2 * t2 - vol1 * vol1 * t1) / (t2 - t1)).sqrt()
+}
+
+/// An option whose strike is set as a fraction of the spot at a future date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForwardStartOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike as a fraction of the spot at the start date, 1 for at the money
+    pub alpha: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time in years to the date the strike is set
+    pub start: f64,
+
+    /// Time to maturity in years from today
+    pub t: f64,
+
+    /// Implied vol from the start date to maturity
+    pub implied_vol: f64,
+}
+
+impl ForwardStartOption {
+    pub fn new(is_call: bool, s: f64, alpha: f64, r: f64, q: f64, start: f64, t: f64) -> Self {
+        Self {
+            is_call,
+            s,
+            alpha,
+            r,
+            q,
+            start,
+            t,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    /// The option on one unit of the underlying struck at `alpha`, over the life remaining
+    /// after the start date.
+    pub fn unit_option(&self) -> OptionInputs {
+        OptionInputs::new(
+            self.is_call,
+            1.0,
+            self.alpha,
+            self.r,
+            self.q,
+            self.t - self.start,
+        )
+        .with_implied_vol(self.implied_vol)
+    }
+
+    /// Units of the unit option the forward-start option is worth today.
+    fn scale(&self) -> f64 {
+        self.s * (-self.q * self.start).exp()
+    }
+
+    pub fn price(&self) -> f64 {
+        self.scale() * self.unit_option().price()
+    }
+
+    /// The price per unit of spot, since the strike scales with it. Gamma is zero.
+    pub fn delta(&self) -> f64 {
+        (-self.q * self.start).exp() * self.unit_option().price()
+    }
+
+    /// Sensitivity to the forward vol, per 0.01 change.
+    pub fn vega(&self) -> f64 {
+        self.scale() * self.unit_option().vega()
+    }
+
+    /// Sensitivity to the risk-free rate, per 0.01 change, which only matters after the start.
+    pub fn rho(&self) -> f64 {
+        self.scale() * self.unit_option().rho()
+    }
+}
+
+/// A strip of forward-start options, each starting as the previous one expires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ratchet {
+    pub legs: Vec<ForwardStartOption>,
+}
+
+impl Ratchet {
+    /// Legs between today and each of the increasing `resets`, the last of which is maturity.
+    /// The first leg starts today and is a vanilla option struck at `alpha` times the spot.
+    pub fn new(is_call: bool, s: f64, alpha: f64, r: f64, q: f64, resets: &[f64]) -> Self {
+        let mut start = 0.0;
+        let legs = resets
+            .iter()
+            .map(|&t| {
+                let leg = ForwardStartOption::new(is_call, s, alpha, r, q, start, t);
+                start = t;
+                leg
+            })
+            .collect();
+        Self { legs }
+    }
+
+    /// Price every leg at one vol.
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        for leg in &mut self.legs {
+            leg.implied_vol = implied_vol;
+        }
+        self
+    }
+
+    /// Price each leg at the forward vol between its start and maturity implied by the term
+    /// structure `vols` quoted to each reset date.
+    pub fn with_term_structure(mut self, vols: &[f64]) -> Self {
+        let mut previous = (0.0, 0.0);
+        for (leg, &vol) in self.legs.iter_mut().zip(vols) {
+            leg.implied_vol = forward_vol(previous.0, previous.1, vol, leg.t);
+            previous = (vol, leg.t);
+        }
+        self
+    }
+
+    pub fn price(&self) -> f64 {
+        self.legs.iter().map(ForwardStartOption::price).sum()
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.legs.iter().map(ForwardStartOption::delta).sum()
+    }
+
+    /// Sensitivity to a parallel move in the legs' forward vols, per 0.01 change.
+    pub fn vega(&self) -> f64 {
+        self.legs.iter().map(ForwardStartOption::vega).sum()
+    }
+}
@@ -0,0 +1,524 @@
+//! Garman-Kohlhagen FX options and FX market conventions.
+//!
+//! An option on a currency pair `FOR/DOM` is BSM with the foreign rate as the dividend yield, so
+//! the price comes out in domestic currency per unit of foreign notional, "domestic pips". FX
+//! markets also quote premiums as a percentage of either notional or in foreign pips, and when
+//! the premium is paid in the foreign currency the hedge delta must be adjusted for it. The
+//! conventions follow Reiswich and Wystup (2010).
+//!
+//! FX smiles are quoted at delta pillars rather than strikes. A [`DeltaSmile`] interpolates vol
+//! linearly in forward call delta between its pillars and converts to strikes for the market's
+//! delta convention, so a quoted smile prices options at any strike.
+//!
+//! A [`QuantoOption`] is an option on a foreign asset whose payoff, in the asset's currency, is
+//! paid in domestic currency at a rate fixed today. Hedging it in the foreign asset leaves an
+//! exposure to the exchange rate that costs `correlation * asset_vol * fx_vol` in the asset's
+//! drift, so it prices as BSM on the asset at the foreign carry less that adjustment, discounted
+//! at the domestic rate.
+
+use crate::carry::Carry;
+use crate::correlation::{shifted, CorrelationRisk};
+use crate::surface::{self, Smile};
+use crate::{calculate_inv_ncdf, calculate_ncdf, solve, OptionInputs};
+
+/// Units in which a premium, or a Greek measured in premium, is quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PremiumQuote {
+    /// Domestic currency per unit of foreign notional, the model's natural unit
+    DomesticPips,
+
+    /// Foreign currency per unit of domestic notional
+    ForeignPips,
+
+    /// Fraction of the domestic notional
+    DomesticPercent,
+
+    /// Fraction of the foreign notional
+    ForeignPercent,
+}
+
+/// How a delta is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaType {
+    /// Foreign notional to trade in spot to hedge, `phi e^(-r_f T) N(phi d1)`
+    Spot,
+
+    /// Foreign notional to trade in the forward to hedge, `phi N(phi d1)`
+    Forward,
+
+    /// Spot delta less the premium when it is paid in the foreign currency
+    PremiumAdjustedSpot,
+
+    /// Forward delta less the premium when it is paid in the foreign currency,
+    /// `phi K / F N(phi d2)`
+    PremiumAdjustedForward,
+}
+
+/// A European option on an exchange rate quoted as domestic per unit of foreign currency.
+#[derive(Debug, Clone)]
+pub struct FxOption {
+    pub option: OptionInputs,
+}
+
+impl FxOption {
+    pub fn new(
+        is_call: bool,
+        spot: f64,
+        k: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        t: f64,
+    ) -> Self {
+        Self {
+            option: OptionInputs::generalized(
+                is_call,
+                spot,
+                k,
+                domestic_rate,
+                Carry::Fx { foreign_rate },
+                t,
+            ),
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.option = self.option.with_implied_vol(implied_vol);
+        self
+    }
+
+    /// Solve the implied vol from a premium quoted in `quote`.
+    pub fn with_premium(mut self, premium: f64, quote: PremiumQuote) -> Self {
+        let pips = premium / self.scale(quote);
+        self.option = self.option.with_price(pips);
+        self
+    }
+
+    pub fn domestic_rate(&self) -> f64 {
+        self.option.r
+    }
+
+    pub fn foreign_rate(&self) -> f64 {
+        self.option.q
+    }
+
+    pub fn forward(&self) -> f64 {
+        self.option.s * ((self.option.r - self.option.q) * self.option.t).exp()
+    }
+
+    /// Factor converting a value in domestic pips to `quote`.
+    fn scale(&self, quote: PremiumQuote) -> f64 {
+        let (s, k) = (self.option.s, self.option.k);
+        match quote {
+            PremiumQuote::DomesticPips => 1.0,
+            PremiumQuote::ForeignPips => 1.0 / (s * k),
+            PremiumQuote::DomesticPercent => 1.0 / k,
+            PremiumQuote::ForeignPercent => 1.0 / s,
+        }
+    }
+
+    pub fn premium(&self, quote: PremiumQuote) -> f64 {
+        self.option.price() * self.scale(quote)
+    }
+
+    pub fn delta(&self, delta_type: DeltaType) -> f64 {
+        let o = &self.option;
+        let sign = o.sign();
+        match delta_type {
+            DeltaType::Spot => o.delta(),
+            DeltaType::Forward => o.delta() / o.dividend_discount(),
+            DeltaType::PremiumAdjustedSpot => o.delta() - o.price() / o.s,
+            DeltaType::PremiumAdjustedForward => {
+                sign * o.k / self.forward() * calculate_ncdf(sign * o.d2)
+            }
+        }
+    }
+
+    /// Change in premium, in `quote` units, per 0.01 change in vol.
+    pub fn vega(&self, quote: PremiumQuote) -> f64 {
+        self.option.vega() * self.scale(quote)
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.option.gamma()
+    }
+
+    /// Time decay of the premium, in `quote` units, per day.
+    pub fn theta(&self, quote: PremiumQuote) -> f64 {
+        self.option.theta() * self.scale(quote)
+    }
+
+    /// Sensitivity of the premium in domestic pips to a 0.01 change in the domestic rate.
+    pub fn domestic_rho(&self) -> f64 {
+        self.option.rho()
+    }
+
+    /// Sensitivity of the premium in domestic pips to a 0.01 change in the foreign rate.
+    pub fn foreign_rho(&self) -> f64 {
+        0.01 * self.option.epsilon()
+    }
+}
+
+/// An option on a foreign asset paid in domestic currency at a fixed exchange rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantoOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Price of the asset in foreign currency
+    pub s: f64,
+
+    /// Strike in foreign currency
+    pub k: f64,
+
+    pub domestic_rate: f64,
+    pub foreign_rate: f64,
+
+    /// Dividend yield of the asset
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// Implied vol of the asset
+    pub asset_vol: f64,
+
+    /// Implied vol of the exchange rate, quoted as domestic per unit of foreign currency
+    pub fx_vol: f64,
+
+    /// Correlation of the asset's returns with the exchange rate's
+    pub correlation: f64,
+
+    /// Domestic currency paid per unit of foreign currency in the payoff
+    pub quanto_rate: f64,
+}
+
+impl QuantoOption {
+    /// A quanto on an asset without dividends paying one unit of domestic currency per unit of
+    /// foreign, uncorrelated with the exchange rate until [`Self::with_fx`] is set.
+    pub fn new(
+        is_call: bool,
+        s: f64,
+        k: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        t: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            domestic_rate,
+            foreign_rate,
+            q: 0.0,
+            t,
+            asset_vol: f64::NAN,
+            fx_vol: 0.0,
+            correlation: 0.0,
+            quanto_rate: 1.0,
+        }
+    }
+
+    pub fn with_dividend(mut self, q: f64) -> Self {
+        self.q = q;
+        self
+    }
+
+    pub fn with_implied_vol(mut self, asset_vol: f64) -> Self {
+        self.asset_vol = asset_vol;
+        self
+    }
+
+    /// Set the exchange rate's vol and its correlation with the asset.
+    pub fn with_fx(mut self, fx_vol: f64, correlation: f64) -> Self {
+        self.fx_vol = fx_vol;
+        self.correlation = correlation;
+        self
+    }
+
+    pub fn with_quanto_rate(mut self, quanto_rate: f64) -> Self {
+        self.quanto_rate = quanto_rate;
+        self
+    }
+
+    /// The quanto drift adjustment, `-correlation * asset_vol * fx_vol`.
+    pub fn drift_adjustment(&self) -> f64 {
+        -self.correlation * self.asset_vol * self.fx_vol
+    }
+
+    /// BSM inputs on the asset at its quanto-adjusted carry, discounted at the domestic rate,
+    /// whose price per unit of quanto rate is the quanto's.
+    pub fn option(&self) -> OptionInputs {
+        let b = self.foreign_rate - self.q + self.drift_adjustment();
+        OptionInputs::cost_of_carry(self.is_call, self.s, self.k, self.domestic_rate, b, self.t)
+            .with_implied_vol(self.asset_vol)
+    }
+
+    /// Forward of the asset under the domestic measure.
+    pub fn forward(&self) -> f64 {
+        self.s * ((self.domestic_rate - self.option().q) * self.t).exp()
+    }
+
+    /// Price in domestic currency.
+    pub fn price(&self) -> f64 {
+        self.quanto_rate * self.option().price()
+    }
+
+    /// Domestic currency per unit change in the asset's foreign price.
+    pub fn delta(&self) -> f64 {
+        self.quanto_rate * self.option().delta()
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.quanto_rate * self.option().gamma()
+    }
+
+    /// Sensitivity to the asset's vol per 0.01, which moves the drift adjustment too.
+    pub fn vega(&self) -> f64 {
+        let option = self.option();
+        let through_drift = option.epsilon() * self.correlation * self.fx_vol;
+        self.quanto_rate * (option.vega() + 0.01 * through_drift)
+    }
+
+    /// Sensitivity to the exchange rate's vol per 0.01, which acts only through the drift.
+    pub fn fx_vega(&self) -> f64 {
+        let option = self.option();
+        self.quanto_rate * 0.01 * option.epsilon() * self.correlation * self.asset_vol
+    }
+}
+
+impl CorrelationRisk for QuantoOption {
+    fn value(&self) -> f64 {
+        self.price()
+    }
+
+    fn shift_correlation(&self, shift: f64) -> Self {
+        Self {
+            correlation: shifted(self.correlation, shift),
+            ..self.clone()
+        }
+    }
+}
+
+/// Strike at which an option with `vol` has `delta` in `delta_type`, a positive delta for a call
+/// and negative for a put. Premium-adjusted deltas have no closed form and are solved for; a
+/// premium-adjusted call delta is searched for among strikes above the forward less half the
+/// variance, where it falls monotonically in strike.
+#[allow(clippy::too_many_arguments)]
+pub fn strike_for_delta(
+    delta_type: DeltaType,
+    delta: f64,
+    spot: f64,
+    domestic_rate: f64,
+    foreign_rate: f64,
+    t: f64,
+    vol: f64,
+) -> f64 {
+    let is_call = delta > 0.0;
+    let sign = if is_call { 1.0 } else { -1.0 };
+    let forward = spot * ((domestic_rate - foreign_rate) * t).exp();
+    let stddev = vol * t.sqrt();
+    let foreign_discount = (-foreign_rate * t).exp();
+
+    // unadjusted forward delta solves to K = F exp(-phi N^-1(phi delta) stddev + stddev^2 / 2)
+    let unadjusted = |forward_delta: f64| {
+        forward
+            * (-sign * calculate_inv_ncdf(sign * forward_delta) * stddev + 0.5 * stddev * stddev)
+                .exp()
+    };
+    match delta_type {
+        DeltaType::Forward => unadjusted(delta),
+        DeltaType::Spot => unadjusted(delta / foreign_discount),
+        DeltaType::PremiumAdjustedForward | DeltaType::PremiumAdjustedSpot => {
+            let target = if delta_type == DeltaType::PremiumAdjustedSpot {
+                delta / foreign_discount
+            } else {
+                delta
+            };
+            let adjusted = |k: f64| {
+                FxOption::new(is_call, spot, k, domestic_rate, foreign_rate, t)
+                    .with_implied_vol(vol)
+                    .delta(DeltaType::PremiumAdjustedForward)
+                    - target
+            };
+            // the premium only lowers the delta, so the unadjusted strike bounds the search
+            let upper = unadjusted(target);
+            let lower = if is_call {
+                forward * (-0.5 * stddev * stddev).exp()
+            } else {
+                1e-3 * upper
+            };
+            solve::brent(adjusted, lower, upper, 1e-12 * upper).unwrap_or(f64::NAN)
+        }
+    }
+}
+
+/// A smile vol quoted at one delta.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaPillar {
+    /// Delta in the smile's convention, positive for calls and negative for puts, e.g. -0.25
+    /// for the 25 delta put
+    pub delta: f64,
+
+    pub vol: f64,
+}
+
+impl DeltaPillar {
+    pub fn new(delta: f64, vol: f64) -> Self {
+        Self { delta, vol }
+    }
+}
+
+/// An FX smile for one expiry given at delta pillars around a delta-neutral straddle at the
+/// money vol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaSmile {
+    pub spot: f64,
+    pub domestic_rate: f64,
+    pub foreign_rate: f64,
+    pub t: f64,
+
+    /// Convention of the pillar deltas
+    pub delta_type: DeltaType,
+
+    pub atm_vol: f64,
+    pub pillars: Vec<DeltaPillar>,
+
+    /// Strikes of the at the money point and pillars, ascending
+    pub strikes: Vec<f64>,
+
+    /// Unadjusted forward call deltas of the strikes, ascending, and their vols
+    deltas: Vec<f64>,
+    vols: Vec<f64>,
+}
+
+impl DeltaSmile {
+    pub fn new(
+        spot: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        t: f64,
+        delta_type: DeltaType,
+        atm_vol: f64,
+        pillars: Vec<DeltaPillar>,
+    ) -> Self {
+        let forward = spot * ((domestic_rate - foreign_rate) * t).exp();
+        let variance = atm_vol * atm_vol * t;
+        // the delta-neutral straddle strike, where call and put deltas cancel
+        let atm = match delta_type {
+            DeltaType::Spot | DeltaType::Forward => forward * (0.5 * variance).exp(),
+            _ => forward * (-0.5 * variance).exp(),
+        };
+
+        let mut points: Vec<(f64, f64)> = std::iter::once((atm, atm_vol))
+            .chain(pillars.iter().map(|p| {
+                let k = strike_for_delta(
+                    delta_type,
+                    p.delta,
+                    spot,
+                    domestic_rate,
+                    foreign_rate,
+                    t,
+                    p.vol,
+                );
+                (k, p.vol)
+            }))
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let stddev = |vol: f64| vol * t.sqrt();
+        let call_delta =
+            |k: f64, vol: f64| calculate_ncdf((forward / k).ln() / stddev(vol) + 0.5 * stddev(vol));
+        // deltas fall with strike, so reverse for an ascending axis
+        let (deltas, vols): (Vec<f64>, Vec<f64>) = points
+            .iter()
+            .rev()
+            .map(|&(k, vol)| (call_delta(k, vol), vol))
+            .unzip();
+
+        Self {
+            spot,
+            domestic_rate,
+            foreign_rate,
+            t,
+            delta_type,
+            atm_vol,
+            pillars,
+            strikes: points.iter().map(|p| p.0).collect(),
+            deltas,
+            vols,
+        }
+    }
+
+    /// The usual five point smile from the at the money vol and the 25 and 10 delta risk
+    /// reversals and butterflies, with the pillar vols `atm + bf +/- rr / 2`. This treats the
+    /// quoted butterflies as smile strangles rather than market strangles.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_quotes(
+        spot: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        t: f64,
+        delta_type: DeltaType,
+        atm_vol: f64,
+        rr25: f64,
+        bf25: f64,
+        rr10: f64,
+        bf10: f64,
+    ) -> Self {
+        let pillars = vec![
+            DeltaPillar::new(-0.10, atm_vol + bf10 - 0.5 * rr10),
+            DeltaPillar::new(-0.25, atm_vol + bf25 - 0.5 * rr25),
+            DeltaPillar::new(0.25, atm_vol + bf25 + 0.5 * rr25),
+            DeltaPillar::new(0.10, atm_vol + bf10 + 0.5 * rr10),
+        ];
+        Self::new(
+            spot,
+            domestic_rate,
+            foreign_rate,
+            t,
+            delta_type,
+            atm_vol,
+            pillars,
+        )
+    }
+
+    pub fn forward(&self) -> f64 {
+        self.spot * ((self.domestic_rate - self.foreign_rate) * self.t).exp()
+    }
+
+    /// Vol at unadjusted forward call delta `delta`, flat beyond the outer pillars.
+    pub fn vol_at_delta(&self, delta: f64) -> f64 {
+        surface::interpolate(&self.deltas, &self.vols, delta)
+    }
+
+    /// Vol at strike `k`, solving for the delta that is consistent with its own vol.
+    pub fn vol_at_strike(&self, k: f64) -> f64 {
+        let forward = self.forward();
+        let sqrt_t = self.t.sqrt();
+        let consistency = |delta: f64| {
+            let vol = self.vol_at_delta(delta);
+            delta - calculate_ncdf((forward / k).ln() / (vol * sqrt_t) + 0.5 * vol * sqrt_t)
+        };
+        solve::brent(consistency, 1e-12, 1.0 - 1e-12, 1e-14)
+            .map_or(f64::NAN, |delta| self.vol_at_delta(delta))
+    }
+
+    /// An option on the smile at strike `k`.
+    pub fn option(&self, is_call: bool, k: f64) -> FxOption {
+        FxOption::new(
+            is_call,
+            self.spot,
+            k,
+            self.domestic_rate,
+            self.foreign_rate,
+            self.t,
+        )
+        .with_implied_vol(self.vol_at_strike(k))
+    }
+}
+
+impl Smile for DeltaSmile {
+    fn vol(&self, k: f64) -> f64 {
+        self.vol_at_strike(k)
+    }
+}
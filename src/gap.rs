@@ -0,0 +1,60 @@
+//! Gap options, whose trigger strike differs from the strike that sets the payoff.
+//!
+//! A gap call pays `S - K1` whenever the underlying finishes above the trigger `K2`, and a gap
+//! put pays `K1 - S` below it, so the payoff jumps by `K2 - K1` at the trigger and can be
+//! negative. The option is an asset digital less `K1` cash digitals, both triggered at `K2`, and
+//! is a vanilla option when the strikes agree. Two gaps on one trigger differ by cash digitals,
+//! so a cash digital paying `A` is a gap struck `A` below the trigger for a call, or above it for
+//! a put, less the vanilla at the trigger; the narrower the jump, the closer the gap to the
+//! vanilla.
+
+use crate::digital::{DigitalValue, Payout};
+use crate::OptionInputs;
+
+/// A gap option, triggered at the strike of its [`OptionInputs`].
+#[derive(Debug, Clone)]
+pub struct GapOption {
+    /// The option whose type, strike, and vol set the trigger
+    pub option: OptionInputs,
+
+    /// The strike the payoff is measured from
+    pub payoff_strike: f64,
+}
+
+impl GapOption {
+    pub fn new(option: OptionInputs, payoff_strike: f64) -> Self {
+        Self {
+            option,
+            payoff_strike,
+        }
+    }
+
+    /// Value and Greeks, in the units of [`DigitalValue`].
+    pub fn value(&self) -> DigitalValue {
+        let asset = self.option.digital(Payout::Asset);
+        let cash = self.option.digital(Payout::Cash(self.payoff_strike));
+        let sign = self.option.sign();
+        DigitalValue {
+            price: sign * (asset.price - cash.price),
+            delta: sign * (asset.delta - cash.delta),
+            gamma: sign * (asset.gamma - cash.gamma),
+            vega: sign * (asset.vega - cash.vega),
+        }
+    }
+
+    pub fn price(&self) -> f64 {
+        self.value().price
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.value().delta
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.value().gamma
+    }
+
+    pub fn vega(&self) -> f64 {
+        self.value().vega
+    }
+}
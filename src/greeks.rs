@@ -0,0 +1,105 @@
+//! Computing a selection of Greeks at once.
+//!
+//! Third-order Greeks cost several times a delta and are rarely all needed, so
+//! [`OptionInputs::all_greeks`] takes a [`GreekSet`] of the sensitivities to compute and leaves
+//! the rest NaN. Every Greek is in the units of the [`OptionInputs`] method of the same name.
+
+use bitflags::bitflags;
+
+use crate::OptionInputs;
+
+bitflags! {
+    /// A set of Greeks to compute.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct GreekSet: u32 {
+        const DELTA = 1 << 0;
+        const VEGA = 1 << 1;
+        const THETA = 1 << 2;
+        const RHO = 1 << 3;
+        const EPSILON = 1 << 4;
+        const LAMBDA = 1 << 5;
+        const DUAL_DELTA = 1 << 6;
+        const GAMMA = 1 << 7;
+        const VANNA = 1 << 8;
+        const CHARM = 1 << 9;
+        const VETA = 1 << 10;
+        const VOMMA = 1 << 11;
+        const DUAL_GAMMA = 1 << 12;
+        const SPEED = 1 << 13;
+        const ZOMMA = 1 << 14;
+        const COLOR = 1 << 15;
+        const ULTIMA = 1 << 16;
+
+        const FIRST_ORDER = Self::DELTA.bits()
+            | Self::VEGA.bits()
+            | Self::THETA.bits()
+            | Self::RHO.bits()
+            | Self::EPSILON.bits()
+            | Self::LAMBDA.bits()
+            | Self::DUAL_DELTA.bits();
+        const SECOND_ORDER = Self::GAMMA.bits()
+            | Self::VANNA.bits()
+            | Self::CHARM.bits()
+            | Self::VETA.bits()
+            | Self::VOMMA.bits()
+            | Self::DUAL_GAMMA.bits();
+        const THIRD_ORDER = Self::SPEED.bits()
+            | Self::ZOMMA.bits()
+            | Self::COLOR.bits()
+            | Self::ULTIMA.bits();
+    }
+}
+
+/// Greeks of one option, NaN where not requested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    pub delta: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+    pub epsilon: f64,
+    pub lambda: f64,
+    pub dual_delta: f64,
+    pub gamma: f64,
+    pub vanna: f64,
+    pub charm: f64,
+    pub veta: f64,
+    pub vomma: f64,
+    pub dual_gamma: f64,
+    pub speed: f64,
+    pub zomma: f64,
+    pub color: f64,
+    pub ultima: f64,
+}
+
+impl OptionInputs {
+    /// The Greeks in `set`, leaving the others NaN.
+    pub fn all_greeks(&self, set: GreekSet) -> Greeks {
+        let get = |flag: GreekSet, greek: fn(&Self) -> f64| {
+            if set.contains(flag) {
+                greek(self)
+            } else {
+                f64::NAN
+            }
+        };
+        Greeks {
+            delta: get(GreekSet::DELTA, Self::delta),
+            vega: get(GreekSet::VEGA, Self::vega),
+            theta: get(GreekSet::THETA, Self::theta),
+            rho: get(GreekSet::RHO, Self::rho),
+            epsilon: get(GreekSet::EPSILON, Self::epsilon),
+            lambda: get(GreekSet::LAMBDA, Self::lambda),
+            dual_delta: get(GreekSet::DUAL_DELTA, Self::dual_delta),
+            gamma: get(GreekSet::GAMMA, Self::gamma),
+            vanna: get(GreekSet::VANNA, Self::vanna),
+            charm: get(GreekSet::CHARM, Self::charm),
+            veta: get(GreekSet::VETA, Self::veta),
+            vomma: get(GreekSet::VOMMA, Self::vomma),
+            dual_gamma: get(GreekSet::DUAL_GAMMA, Self::dual_gamma),
+            speed: get(GreekSet::SPEED, Self::speed),
+            zomma: get(GreekSet::ZOMMA, Self::zomma),
+            color: get(GreekSet::COLOR, Self::color),
+            ultima: get(GreekSet::ULTIMA, Self::ultima),
+        }
+    }
+}
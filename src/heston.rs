@@ -0,0 +1,382 @@
+//! The Heston (1993) stochastic volatility model and Bates (1996) model with jumps.
+//!
+//! Under Heston the variance follows a mean-reverting square-root process correlated with the
+//! spot. Bates adds lognormal jumps in the spot, which produce the steep short-dated skew that
+//! diffusive stochastic vol cannot. Both are priced through their characteristic functions, see
+//! [`crate::fourier`].
+
+use crate::chain::OptionChain;
+use crate::fourier::{CharacteristicFn, Complex64, CosPricer};
+use crate::optimize::{self, Calibration};
+use crate::OptionInputs;
+
+/// Parameters of the Heston model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Heston {
+    /// Initial variance
+    pub v0: f64,
+
+    /// Speed of mean reversion of the variance
+    pub kappa: f64,
+
+    /// Long-run variance
+    pub theta: f64,
+
+    /// Vol of the variance
+    pub sigma: f64,
+
+    /// Correlation of spot and variance
+    pub rho: f64,
+}
+
+impl Heston {
+    pub fn new(v0: f64, kappa: f64, theta: f64, sigma: f64, rho: f64) -> Self {
+        Self {
+            v0,
+            kappa,
+            theta,
+            sigma,
+            rho,
+        }
+    }
+
+    /// Whether `2 kappa theta > sigma^2`, which keeps the variance away from zero.
+    pub fn feller(&self) -> bool {
+        2.0 * self.kappa * self.theta > self.sigma * self.sigma
+    }
+
+    /// BSM implied vols of the model at each strike for expiry `t`, each implied from the
+    /// out-of-the-money option, which carries the least intrinsic value. Strikes whose price
+    /// admits no implied vol are NaN.
+    pub fn smile(&self, s: f64, strikes: &[f64], r: f64, q: f64, t: f64) -> Vec<f64> {
+        let forward = s * ((r - q) * t).exp();
+        strikes
+            .iter()
+            .map(|&k| HestonOption::new(k >= forward, s, k, r, q, t, *self).implied_vol())
+            .collect()
+    }
+}
+
+impl Heston {
+    /// The exponents `C` and `D` of the characteristic function `exp(C + D v0)`, in Albrecher et
+    /// al.'s (2007) form, which avoids the branch cut of the complex log.
+    fn exponents(&self, u: Complex64, t: f64) -> (Complex64, Complex64) {
+        let (c, dv) = self.exponents_per_theta(u, t);
+        (c * self.theta, dv)
+    }
+
+    /// The exponents with `C`, which is linear in `theta`, given per unit of `theta`.
+    fn exponents_per_theta(&self, u: Complex64, t: f64) -> (Complex64, Complex64) {
+        let i = Complex64::i();
+        let (kappa, sigma) = (self.kappa, self.sigma);
+        let beta = kappa - self.rho * sigma * i * u;
+        let d = (beta * beta + sigma * sigma * (i * u + u * u)).sqrt();
+        let g = (beta - d) / (beta + d);
+        let e = (-d * t).exp();
+
+        let c = kappa / (sigma * sigma) * ((beta - d) * t - 2.0 * ((1.0 - g * e) / (1.0 - g)).ln());
+        let dv = (beta - d) / (sigma * sigma) * (1.0 - e) / (1.0 - g * e);
+        (c, dv)
+    }
+}
+
+impl CharacteristicFn for Heston {
+    fn cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let (c, dv) = self.exponents(u, t);
+        (c + dv * self.v0).exp()
+    }
+}
+
+/// A European option under Heston, priced semi-analytically.
+///
+/// The price is `S e^(-qT) P1 - K e^(-rT) P2` for a call, where `P1` and `P2` are the exercise
+/// probabilities under the share and money market measures, each a Fourier integral of the
+/// characteristic function. The Greeks come from differentiating those integrals under the
+/// integral sign, so they are as accurate as the price.
+#[derive(Debug, Clone)]
+pub struct HestonOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub model: Heston,
+
+    /// Cache the integrals, from which the price and every Greek follow.
+    p1: f64,
+    p2: f64,
+    density1: f64,
+    dp1_dv0: f64,
+    dp2_dv0: f64,
+    dp1_dtheta: f64,
+    dp2_dtheta: f64,
+}
+
+/// Gauss-Legendre points per panel of the Fourier integrals.
+const PANEL_POINTS: usize = 32;
+
+impl HestonOption {
+    pub fn new(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, model: Heston) -> Self {
+        let x = (k / (s * ((r - q) * t).exp())).ln();
+        let i = Complex64::i();
+        let shift = Complex64::new(0.0, -1.0);
+
+        // integrands at u for [P1, P2, P1 density, dP1/dv0, dP2/dv0, dP1/dtheta, dP2/dtheta]
+        let integrands = |u: f64| -> [f64; 7] {
+            let u = Complex64::new(u, 0.0);
+            let kernel = (-i * u * x).exp();
+            let (c1, d1) = model.exponents_per_theta(u + shift, t);
+            let (c2, d2) = model.exponents_per_theta(u, t);
+            let phi1 = (c1 * model.theta + d1 * model.v0).exp();
+            let phi2 = (c2 * model.theta + d2 * model.v0).exp();
+            let f1 = kernel * phi1 / (i * u);
+            let f2 = kernel * phi2 / (i * u);
+            // C is linear in theta, so dC/dtheta is C per unit of theta, also at theta = 0
+            [
+                f1.re,
+                f2.re,
+                (kernel * phi1).re,
+                (f1 * d1).re,
+                (f2 * d2).re,
+                (f1 * c1).re,
+                (f2 * c2).re,
+            ]
+        };
+
+        // integrate panel by panel until the characteristic function has died away
+        let rule = crate::quad::gauss_legendre(PANEL_POINTS);
+        let width = 5.0 / (model.theta.max(model.v0) * t).sqrt().max(1e-3);
+        let mut sums = [0.0; 7];
+        let mut a = 0.0;
+        for _ in 0..200 {
+            let half = 0.5 * width;
+            let mut panel = [0.0; 7];
+            for (&node, &weight) in rule.nodes.iter().zip(&rule.weights) {
+                let values = integrands(a + half * (node + 1.0));
+                for (p, v) in panel.iter_mut().zip(values) {
+                    *p += weight * half * v;
+                }
+            }
+            for (s, p) in sums.iter_mut().zip(panel) {
+                *s += p;
+            }
+            a += width;
+            if panel.iter().all(|p| p.abs() < 1e-15) {
+                break;
+            }
+        }
+        let [i1, i2, g1, v1, v2, t1, t2] = sums.map(|v| v / crate::PI);
+
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            model,
+            p1: 0.5 + i1,
+            p2: 0.5 + i2,
+            density1: g1,
+            dp1_dv0: v1,
+            dp2_dv0: v2,
+            dp1_dtheta: t1,
+            dp2_dtheta: t2,
+        }
+    }
+
+    #[inline(always)]
+    pub fn dividend_discount(&self) -> f64 {
+        (-self.q * self.t).exp()
+    }
+
+    #[inline(always)]
+    pub fn rate_discount(&self) -> f64 {
+        (-self.r * self.t).exp()
+    }
+
+    pub fn price(&self) -> f64 {
+        let call =
+            self.s * self.dividend_discount() * self.p1 - self.k * self.rate_discount() * self.p2;
+        if self.is_call {
+            call
+        } else {
+            call - self.s * self.dividend_discount() + self.k * self.rate_discount()
+        }
+    }
+
+    pub fn delta(&self) -> f64 {
+        let p1 = if self.is_call { self.p1 } else { self.p1 - 1.0 };
+        self.dividend_discount() * p1
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.dividend_discount() * self.density1 / self.s
+    }
+
+    /// Sensitivity to the initial variance `v0`, per unit of variance.
+    pub fn vega_v0(&self) -> f64 {
+        self.s * self.dividend_discount() * self.dp1_dv0
+            - self.k * self.rate_discount() * self.dp2_dv0
+    }
+
+    /// Sensitivity to the long-run variance `theta`, per unit of variance.
+    pub fn vega_theta(&self) -> f64 {
+        self.s * self.dividend_discount() * self.dp1_dtheta
+            - self.k * self.rate_discount() * self.dp2_dtheta
+    }
+
+    pub fn rho(&self) -> f64 {
+        let p2 = if self.is_call { self.p2 } else { self.p2 - 1.0 };
+        0.01 * self.k * self.t * self.rate_discount() * p2
+    }
+
+    /// BSM inputs carrying the Heston price, with the implied vol solved from it.
+    pub fn to_bsm(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_price(self.price())
+    }
+
+    /// BSM implied vol of the Heston price, NaN if the price admits none.
+    pub fn implied_vol(&self) -> f64 {
+        self.to_bsm().implied_vol()
+    }
+}
+
+/// Largest correlation magnitude a calibration starts from.
+const MAX_START_CORRELATION: f64 = 0.999;
+
+/// Parameters of the Bates model: Heston with lognormal jumps in the spot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bates {
+    pub heston: Heston,
+
+    /// Jump intensity per year
+    pub lambda: f64,
+
+    /// Mean of the log jump size
+    pub mu_j: f64,
+
+    /// Standard deviation of the log jump size
+    pub sigma_j: f64,
+}
+
+impl Bates {
+    pub fn new(heston: Heston, lambda: f64, mu_j: f64, sigma_j: f64) -> Self {
+        Self {
+            heston,
+            lambda,
+            mu_j,
+            sigma_j,
+        }
+    }
+
+    /// Unconstrained coordinates for the optimizer: logs of the positive parameters and the
+    /// inverse hyperbolic tangent of the correlation, held just inside (-1, 1) so that a perfectly
+    /// correlated start stays finite.
+    fn unconstrained(&self) -> Vec<f64> {
+        let h = &self.heston;
+        vec![
+            h.v0.ln(),
+            h.kappa.ln(),
+            h.theta.ln(),
+            h.sigma.ln(),
+            h.rho
+                .clamp(-MAX_START_CORRELATION, MAX_START_CORRELATION)
+                .atanh(),
+            self.lambda.ln(),
+            self.mu_j,
+            self.sigma_j.ln(),
+        ]
+    }
+
+    fn from_unconstrained(x: &[f64]) -> Self {
+        Self::new(
+            Heston::new(x[0].exp(), x[1].exp(), x[2].exp(), x[3].exp(), x[4].tanh()),
+            x[5].exp(),
+            x[6],
+            x[7].exp(),
+        )
+    }
+
+    /// Fit the model to the quote mids of `chain`, starting from `initial`, by minimizing
+    /// vega-weighted squared pricing errors with the COS method. Quotes without a valid implied
+    /// vol are ignored, and `None` is returned if that leaves none.
+    pub fn calibrate(
+        chain: &OptionChain,
+        initial: &Bates,
+        pricer: &CosPricer,
+    ) -> Option<Calibration<Bates>> {
+        let mut quotes: Vec<_> = chain
+            .solve()
+            .into_iter()
+            .filter(|q| q.mid_vol.is_finite() && q.vega > 0.0)
+            .collect();
+        if quotes.is_empty() {
+            return None;
+        }
+        quotes.sort_by(|a, b| a.quote.t.total_cmp(&b.quote.t));
+
+        let objective = |model: &Bates| -> f64 {
+            let mut sum = 0.0;
+            for group in quotes.chunk_by(|a, b| a.quote.t == b.quote.t) {
+                for is_call in [true, false] {
+                    let (ks, legs): (Vec<f64>, Vec<_>) = group
+                        .iter()
+                        .filter(|q| q.quote.is_call == is_call)
+                        .map(|q| (q.quote.k, q))
+                        .unzip();
+                    if ks.is_empty() {
+                        continue;
+                    }
+                    let t = group[0].quote.t;
+                    let prices = pricer.prices(model, is_call, chain.s, &ks, chain.r, chain.q, t);
+                    for (price, q) in prices.iter().zip(legs) {
+                        // vega is per vol point
+                        sum += ((price - q.quote.mid()) / (100.0 * q.vega)).powi(2);
+                    }
+                }
+            }
+            sum
+        };
+
+        let minimum = optimize::nelder_mead(
+            |x| objective(&Bates::from_unconstrained(x)),
+            &initial.unconstrained(),
+            0.1,
+            1e-14,
+            5000,
+        );
+        Some(Calibration {
+            model: Bates::from_unconstrained(&minimum.x),
+            rmse: (minimum.value / quotes.len() as f64).sqrt(),
+            evaluations: minimum.evaluations,
+        })
+    }
+}
+
+impl CharacteristicFn for Bates {
+    fn cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let i = Complex64::i();
+        let s2 = self.sigma_j * self.sigma_j;
+        // compensated so that the jumps leave the forward unchanged
+        let mean_jump = (self.mu_j + 0.5 * s2).exp() - 1.0;
+        let jumps = self.lambda
+            * t
+            * ((i * u * self.mu_j - 0.5 * s2 * u * u).exp() - 1.0 - i * u * mean_jump);
+        self.heston.cf(u, t) * jumps.exp()
+    }
+}
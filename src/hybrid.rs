@@ -0,0 +1,77 @@
+//! Black-Scholes-Hull-White: equity or FX options under stochastic interest rates.
+//!
+//! When the short rate follows Hull-White, `dr = (theta(t) - a r) dt + sigma_r dW_r`, zero coupon
+//! bond prices are lognormal and so is the forward `S / P(t, T)` to the option's expiry. The
+//! option is then a Black-76 option on that forward with total variance
+//!
+//! ```text
+//! V = integral over [0, T] of sigma_s^2 + 2 rho sigma_s sigma_r B(t) + sigma_r^2 B(t)^2 dt
+//! ```
+//!
+//! where `B(t) = (1 - e^(-a (T - t))) / a` is the bond's rate sensitivity. With the discount
+//! factor matching the flat rate, the adjustment amounts to pricing BSM at the effective vol
+//! `sqrt(V / T)`. It grows with expiry, and is small below a couple of years but material for
+//! five year and longer options.
+
+use crate::OptionInputs;
+
+/// Hull-White short rate dynamics and their correlation with the underlying.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticRates {
+    /// Mean reversion speed `a` of the short rate
+    pub mean_reversion: f64,
+
+    /// Normal vol of the short rate
+    pub rate_vol: f64,
+
+    /// Correlation of the underlying's returns with the short rate
+    pub correlation: f64,
+}
+
+impl StochasticRates {
+    pub fn new(mean_reversion: f64, rate_vol: f64, correlation: f64) -> Self {
+        Self {
+            mean_reversion,
+            rate_vol,
+            correlation,
+        }
+    }
+
+    /// Integrals of `B` and `B^2` over [0, t].
+    fn bond_sensitivity_integrals(&self, t: f64) -> (f64, f64) {
+        let a = self.mean_reversion;
+        if a.abs() < 1e-8 {
+            return (0.5 * t * t, t * t * t / 3.0);
+        }
+        let b = (1.0 - (-a * t).exp()) / a;
+        let b2 = (1.0 - (-2.0 * a * t).exp()) / (2.0 * a);
+        ((t - b) / a, (t - 2.0 * b + b2) / (a * a))
+    }
+
+    /// Total variance of the forward to expiry `t` for an underlying with vol `vol`.
+    pub fn forward_variance(&self, vol: f64, t: f64) -> f64 {
+        let (int_b, int_b2) = self.bond_sensitivity_integrals(t);
+        vol * vol * t
+            + 2.0 * self.correlation * vol * self.rate_vol * int_b
+            + self.rate_vol * self.rate_vol * int_b2
+    }
+
+    /// Flat vol that prices under BSM like `vol` does under stochastic rates.
+    pub fn effective_vol(&self, vol: f64, t: f64) -> f64 {
+        (self.forward_variance(vol, t) / t).sqrt()
+    }
+
+    /// The option repriced with its implied vol taken as the underlying's vol under stochastic
+    /// rates.
+    pub fn adjust(&self, option: &OptionInputs) -> OptionInputs {
+        OptionInputs::new(
+            option.is_call,
+            option.s,
+            option.k,
+            option.r,
+            option.q,
+            option.t,
+        )
+        .with_implied_vol(self.effective_vol(option.implied_vol, option.t))
+    }
+}
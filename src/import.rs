@@ -0,0 +1,392 @@
+//! Building a [`Portfolio`] from broker or exchange position exports.
+//!
+//! Exports differ mostly in column names and in how the option type and expiry are written, so a
+//! [`CsvMapping`] describes where each field lives and the parser does the rest.
+//!
+//! ```
+//! use blackscholes::calendar::Date;
+//! use blackscholes::import::{CsvMapping, ExpiryFormat};
+//! use blackscholes::Portfolio;
+//!
+//! let export = "\
+//! Symbol,Qty,Put/Call,Underlying Price,Strike,Expiration,IV
+//! AAPL 240621C190,-2,CALL,185.5,190,2024-06-21,0.24
+//! AAPL 240621P180,3,PUT,185.5,180,2024-06-21,0.26
+//! ";
+//!
+//! let mut mapping = CsvMapping::default();
+//! mapping.label = "Symbol".into();
+//! mapping.quantity = "Qty".into();
+//! mapping.option_type = "Put/Call".into();
+//! mapping.spot = "Underlying Price".into();
+//! mapping.strike = "Strike".into();
+//! mapping.expiry = "Expiration".into();
+//! mapping.expiry_format = ExpiryFormat::Date(Date::from_ymd(2024, 5, 22).unwrap());
+//! mapping.vol = Some("IV".into());
+//! mapping.multiplier = None;
+//! mapping.default_multiplier = 100.0;
+//!
+//! let portfolio = Portfolio::from_csv(export.as_bytes(), &mapping).unwrap();
+//! assert_eq!(portfolio.len(), 2);
+//! assert_eq!(portfolio.positions[0].units(), -200.0);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+use crate::calendar::Date;
+use crate::{OptionInputs, Portfolio, Position, DAYS_PER_YEAR};
+
+/// How the expiry column is written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpiryFormat {
+    /// Time to maturity in years
+    Years,
+
+    /// Calendar days to maturity
+    Days,
+
+    /// An expiry date, measured from the given valuation date
+    Date(Date),
+}
+
+/// How bare numbers in the vol column are written. A cell ending in `%` is always a percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VolUnit {
+    /// As a decimal, 0.2 for 20%
+    #[default]
+    Decimal,
+
+    /// As a percentage, 20 for 20%
+    Percent,
+}
+
+/// Column names and conventions of a position export.
+///
+/// The default mapping matches the generic schema
+/// `symbol,quantity,type,spot,strike,expiry,iv,rate,yield,multiplier` with C/P option types,
+/// expiries in years, and decimal vols.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvMapping {
+    /// Field separator
+    pub delimiter: char,
+
+    /// Column holding the position label
+    pub label: String,
+
+    /// Column holding the signed number of contracts
+    pub quantity: String,
+
+    /// Column holding the option type
+    pub option_type: String,
+
+    /// Values of the option type column that denote a call, compared case-insensitively
+    pub call_values: Vec<String>,
+
+    /// Values of the option type column that denote a put, compared case-insensitively. Rows
+    /// whose type is neither a call nor a put value are rejected.
+    pub put_values: Vec<String>,
+
+    /// Column holding the underlying price
+    pub spot: String,
+
+    /// Column holding the strike price
+    pub strike: String,
+
+    /// Column holding the expiry
+    pub expiry: String,
+
+    pub expiry_format: ExpiryFormat,
+
+    /// Column holding the implied vol
+    pub vol: Option<String>,
+
+    pub vol_unit: VolUnit,
+
+    /// Column holding the option price, used to solve for implied vol when there is no vol
+    /// column or the vol cell is empty
+    pub price: Option<String>,
+
+    /// Column holding the risk-free rate, falling back to `default_rate`
+    pub rate: Option<String>,
+
+    /// Column holding the dividend yield, falling back to `default_dividend_yield`
+    pub dividend_yield: Option<String>,
+
+    /// Column holding the contract multiplier, falling back to `default_multiplier`
+    pub multiplier: Option<String>,
+
+    pub default_rate: f64,
+    pub default_dividend_yield: f64,
+    pub default_multiplier: f64,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            label: "symbol".into(),
+            quantity: "quantity".into(),
+            option_type: "type".into(),
+            call_values: vec!["c".into(), "call".into()],
+            put_values: vec!["p".into(), "put".into()],
+            spot: "spot".into(),
+            strike: "strike".into(),
+            expiry: "expiry".into(),
+            expiry_format: ExpiryFormat::Years,
+            vol: Some("iv".into()),
+            vol_unit: VolUnit::default(),
+            price: None,
+            rate: Some("rate".into()),
+            dividend_yield: Some("yield".into()),
+            multiplier: Some("multiplier".into()),
+            default_rate: 0.0,
+            default_dividend_yield: 0.0,
+            default_multiplier: 1.0,
+        }
+    }
+}
+
+/// The reason a row of an export could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportErrorKind {
+    Io(String),
+    EmptyInput,
+    MissingColumn(String),
+    MissingValue(String),
+    InvalidNumber(String),
+    InvalidDate(String),
+    /// The option type is neither a call nor a put value of the mapping
+    InvalidOptionType(String),
+    /// Neither a vol nor a price that implies one was available
+    NoVolatility,
+}
+
+/// Error returned when an export cannot be converted into a portfolio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    /// One-based line number in the input
+    pub line: usize,
+    pub kind: ImportErrorKind,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ImportErrorKind::Io(e) => write!(f, "{}", e),
+            ImportErrorKind::EmptyInput => f.write_str("no header row"),
+            ImportErrorKind::MissingColumn(c) => write!(f, "missing column `{}`", c),
+            ImportErrorKind::MissingValue(c) => write!(f, "empty value in column `{}`", c),
+            ImportErrorKind::InvalidNumber(v) => write!(f, "invalid number `{}`", v),
+            ImportErrorKind::InvalidDate(v) => write!(f, "invalid date `{}`", v),
+            ImportErrorKind::InvalidOptionType(v) => write!(f, "unknown option type `{}`", v),
+            ImportErrorKind::NoVolatility => {
+                f.write_str("no implied vol or price to solve for one")
+            }
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+/// Split a line into fields, honouring double-quoted fields and `""` escapes.
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Parse a number as brokers write them: thousands separators, currency signs, percent signs, and
+/// accounting-style parentheses for negatives.
+fn parse_number(value: &str) -> Option<f64> {
+    let negative = value.starts_with('(') && value.ends_with(')');
+    let percent = value.ends_with('%');
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !matches!(c, ',' | '$' | '%' | '(' | ')' | '+' | ' '))
+        .collect();
+    let x: f64 = cleaned.parse().ok()?;
+    let x = if percent { x / 100.0 } else { x };
+    Some(if negative { -x } else { x })
+}
+
+struct Row<'a> {
+    line: usize,
+    header: &'a [String],
+    fields: Vec<String>,
+}
+
+impl Row<'_> {
+    fn error(&self, kind: ImportErrorKind) -> ImportError {
+        ImportError {
+            line: self.line,
+            kind,
+        }
+    }
+
+    fn get(&self, column: &str) -> Result<Option<&str>, ImportError> {
+        let index = self
+            .header
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(column))
+            .ok_or_else(|| self.error(ImportErrorKind::MissingColumn(column.into())))?;
+        Ok(self
+            .fields
+            .get(index)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty()))
+    }
+
+    fn text(&self, column: &str) -> Result<&str, ImportError> {
+        self.get(column)?
+            .ok_or_else(|| self.error(ImportErrorKind::MissingValue(column.into())))
+    }
+
+    fn number(&self, column: &str) -> Result<f64, ImportError> {
+        let value = self.text(column)?;
+        parse_number(value).ok_or_else(|| self.error(ImportErrorKind::InvalidNumber(value.into())))
+    }
+
+    /// Optional columns may be absent from the export altogether.
+    fn optional_number(&self, column: &Option<String>) -> Result<Option<f64>, ImportError> {
+        let value = match column {
+            Some(c) if self.header.iter().any(|h| h.eq_ignore_ascii_case(c)) => self.get(c)?,
+            _ => None,
+        };
+        value
+            .map(|v| {
+                parse_number(v).ok_or_else(|| self.error(ImportErrorKind::InvalidNumber(v.into())))
+            })
+            .transpose()
+    }
+}
+
+impl CsvMapping {
+    fn position(&self, row: &Row) -> Result<Position, ImportError> {
+        let label = row.text(&self.label)?.to_string();
+        let quantity = row.number(&self.quantity)?;
+        let option_type = row.text(&self.option_type)?;
+        let is_one_of =
+            |values: &[String]| values.iter().any(|v| v.eq_ignore_ascii_case(option_type));
+        let is_call = if is_one_of(&self.call_values) {
+            true
+        } else if is_one_of(&self.put_values) {
+            false
+        } else {
+            return Err(row.error(ImportErrorKind::InvalidOptionType(option_type.into())));
+        };
+        let s = row.number(&self.spot)?;
+        let k = row.number(&self.strike)?;
+
+        let t = match self.expiry_format {
+            ExpiryFormat::Years => row.number(&self.expiry)?,
+            ExpiryFormat::Days => row.number(&self.expiry)? / DAYS_PER_YEAR,
+            ExpiryFormat::Date(valuation) => {
+                let value = row.text(&self.expiry)?;
+                Date::parse(value)
+                    .ok_or_else(|| row.error(ImportErrorKind::InvalidDate(value.into())))?
+                    .years_since(valuation)
+            }
+        };
+
+        let r = row
+            .optional_number(&self.rate)?
+            .unwrap_or(self.default_rate);
+        let q = row
+            .optional_number(&self.dividend_yield)?
+            .unwrap_or(self.default_dividend_yield);
+        let multiplier = row
+            .optional_number(&self.multiplier)?
+            .unwrap_or(self.default_multiplier);
+
+        let option = OptionInputs::new(is_call, s, k, r, q, t);
+        let option = match (self.vol(row)?, row.optional_number(&self.price)?) {
+            (Some(vol), _) => option.with_implied_vol(vol),
+            (None, Some(price)) => option.with_price(price),
+            (None, None) => return Err(row.error(ImportErrorKind::NoVolatility)),
+        };
+        if !option.implied_vol().is_finite() {
+            return Err(row.error(ImportErrorKind::NoVolatility));
+        }
+
+        Ok(Position::new(label, option, quantity).with_multiplier(multiplier))
+    }
+
+    /// The row's implied vol as a decimal, reading bare numbers in the mapping's unit.
+    fn vol(&self, row: &Row) -> Result<Option<f64>, ImportError> {
+        let Some(vol) = row.optional_number(&self.vol)? else {
+            return Ok(None);
+        };
+        let percent_sign = self
+            .vol
+            .as_deref()
+            .and_then(|column| row.get(column).ok().flatten())
+            .is_some_and(|v| v.ends_with('%'));
+        Ok(Some(match self.vol_unit {
+            VolUnit::Percent if !percent_sign => vol / 100.0,
+            _ => vol,
+        }))
+    }
+
+    /// Read an export whose first non-empty line is the header row.
+    pub fn parse<R: BufRead>(&self, reader: R) -> Result<Portfolio, ImportError> {
+        let mut header: Option<Vec<String>> = None;
+        let mut portfolio = Portfolio::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| ImportError {
+                line: i + 1,
+                kind: ImportErrorKind::Io(e.to_string()),
+            })?;
+            let line = line.trim_start_matches('\u{feff}');
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = split_fields(line, self.delimiter);
+            match &header {
+                None => header = Some(fields),
+                Some(header) => {
+                    let row = Row {
+                        line: i + 1,
+                        header,
+                        fields,
+                    };
+                    portfolio.push(self.position(&row)?);
+                }
+            }
+        }
+
+        if header.is_none() {
+            return Err(ImportError {
+                line: 0,
+                kind: ImportErrorKind::EmptyInput,
+            });
+        }
+        Ok(portfolio)
+    }
+}
+
+impl Portfolio {
+    /// Build a portfolio from a position export described by `mapping`.
+    pub fn from_csv<R: BufRead>(reader: R, mapping: &CsvMapping) -> Result<Self, ImportError> {
+        mapping.parse(reader)
+    }
+}
@@ -34,11 +34,6 @@ pub fn implied_volatility_from_a_transformed_rational_guess(
     t: f64,
     q: f64,
 ) -> f64 {
-    let price: c_double = price.into();
-    let f: c_double = f.into();
-    let k: c_double = k.into();
-    let t: c_double = t.into();
-    let q: c_double = q.into();
     unsafe { implied_volatility_from_a_transformed_rational_guess_ffi(price, f, k, t, q) }
 }
 
@@ -50,10 +45,5 @@ pub fn implied_volatility_from_a_transformed_rational_guess(
 /// f64 of the price of the option.
 #[inline(always)]
 pub fn black(f: f64, k: f64, sigma: f64, t: f64, q: f64) -> f64 {
-    let f: c_double = f.into();
-    let k: c_double = k.into();
-    let sigma: c_double = sigma.into();
-    let t: c_double = t.into();
-    let q: c_double = q.into();
     unsafe { black_ffi(f, k, sigma, t, q) }
 }
@@ -13,7 +13,87 @@
 //!
 //! See the [Github Repo](https://github.com/hayden4r4/blackscholes-rust/tree/master) for full source code.  Other implementations such as a [npm WASM package](https://www.npmjs.com/package/@haydenr4/blackscholes_wasm) and a [python module](https://pypi.org/project/blackscholes/) are also available.
 
+#[cfg(feature = "accuracy")]
+pub mod accuracy;
+pub mod american;
+pub mod asian;
+pub mod autocallable;
+pub mod bachelier;
+pub mod backtest;
+pub mod barrier;
+pub mod basket;
+pub mod batch;
+pub mod binomial;
+pub mod black76;
+pub mod bounds;
+pub mod broadie_kaya;
+#[cfg(all(unix, target_endian = "little"))]
+pub mod bulk;
+pub mod calendar;
+pub mod carry;
+pub mod cev;
+pub mod chain;
+pub mod chooser;
+pub mod collar;
+pub mod compare;
+pub mod compound;
+pub mod config;
+pub mod const_eval;
+pub mod correlation;
+pub mod curve;
+pub mod digital;
+pub mod dispersion;
+pub mod events;
+pub mod exchange;
+pub mod expiry;
+pub mod extrapolation;
+pub mod filter;
+pub mod forward_start;
+pub mod fourier;
+pub mod fx;
+pub mod gap;
+pub mod greeks;
+pub mod heston;
+pub mod hybrid;
+pub mod import;
 mod lets_be_rational;
+pub mod live;
+pub mod lookback;
+pub mod lookup;
+pub mod lsmc;
+pub mod margin;
+pub mod market;
+pub mod mc;
+pub mod merton;
+pub mod ncx2;
+pub mod optimize;
+pub mod parity;
+pub mod pde;
+pub mod pnl;
+pub mod portfolio;
+pub mod power;
+pub mod qmc;
+pub mod quad;
+pub mod quoting;
+pub mod reflection;
+pub mod replay;
+pub mod risk;
+pub mod roll;
+pub mod sabr;
+pub mod scenario;
+pub mod solve;
+pub mod spread;
+pub mod stir;
+pub mod strategy;
+pub mod surface;
+pub mod synthetic;
+pub mod tick;
+pub mod trinomial;
+
+pub use bachelier::BachelierInputs;
+pub use black76::Black76Inputs;
+pub use portfolio::{Portfolio, Position};
+pub use scenario::{PnlCube, Scenario, ScenarioGrid};
 
 use statrs::distribution::{ContinuousCDF, Normal};
 
@@ -28,10 +108,146 @@ pub const D: f64 = 7.53502261e-05;
 pub const _E: f64 = 1.42451646e-05;
 pub const F: f64 = -2.10237683e-05;
 
-fn calculate_npdf(x: f64) -> f64 {
+pub(crate) fn calculate_npdf(x: f64) -> f64 {
     (-0.5 * x * x).exp() / SQRT_2PI
 }
 
+pub(crate) fn calculate_ncdf(x: f64) -> f64 {
+    Normal::new(0.0, 1.0).unwrap().cdf(x)
+}
+
+pub(crate) fn calculate_inv_ncdf(p: f64) -> f64 {
+    Normal::new(0.0, 1.0).unwrap().inverse_cdf(p)
+}
+
+/// Gauss-Legendre abscissae and weights on [-1, 1] for 6, 12, and 20 points, one half of each
+/// symmetric rule.
+const GL_X: [&[f64]; 3] = [
+    &[-0.9324695142031522, -0.6612093864662647, -0.238619186083197],
+    &[
+        -0.9815606342467191,
+        -0.904117256370475,
+        -0.769902674194305,
+        -0.5873179542866171,
+        -0.3678314989981802,
+        -0.1252334085114692,
+    ],
+    &[
+        -0.9931285991850949,
+        -0.9639719272779138,
+        -0.912234428251326,
+        -0.8391169718222188,
+        -0.7463319064601508,
+        -0.636053680726515,
+        -0.5108670019508271,
+        -0.3737060887154196,
+        -0.2277858511416451,
+        -0.07652652113349733,
+    ],
+];
+const GL_W: [&[f64]; 3] = [
+    &[0.1713244923791705, 0.3607615730481384, 0.4679139345726904],
+    &[
+        0.04717533638651177,
+        0.1069393259953183,
+        0.1600783285433464,
+        0.2031674267230659,
+        0.2334925365383547,
+        0.2491470458134029,
+    ],
+    &[
+        0.01761400713915212,
+        0.04060142980038694,
+        0.06267204833410906,
+        0.08327674157670475,
+        0.1019301198172404,
+        0.1181945319615184,
+        0.1316886384491766,
+        0.1420961093183821,
+        0.1491729864726037,
+        0.1527533871307259,
+    ],
+];
+
+/// Standard bivariate normal CDF `P(X <= x, Y <= y)` with correlation `rho`, by Genz's (2004)
+/// refinement of the Drezner-Wesolowsky method, accurate to about 1e-15.
+pub(crate) fn calculate_bivariate_ncdf(x: f64, y: f64, rho: f64) -> f64 {
+    // Genz computes the upper tail P(X > h, Y > k)
+    let (h, mut k) = (-x, -y);
+    let rule = if rho.abs() < 0.3 {
+        0
+    } else if rho.abs() < 0.75 {
+        1
+    } else {
+        2
+    };
+    let (xs, ws) = (GL_X[rule], GL_W[rule]);
+    let mut hk = h * k;
+
+    if rho.abs() < 0.925 {
+        let hs = 0.5 * (h * h + k * k);
+        let asr = rho.asin();
+        let mut bvn = 0.0;
+        for (&xi, &wi) in xs.iter().zip(ws) {
+            for sn in [
+                (0.5 * asr * (xi + 1.0)).sin(),
+                (0.5 * asr * (1.0 - xi)).sin(),
+            ] {
+                bvn += wi * ((sn * hk - hs) / (1.0 - sn * sn)).exp();
+            }
+        }
+        return bvn * asr / (4.0 * PI) + calculate_ncdf(-h) * calculate_ncdf(-k);
+    }
+
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+    let mut bvn = 0.0;
+    if rho.abs() < 1.0 {
+        let as_ = (1.0 - rho) * (1.0 + rho);
+        let mut a = as_.sqrt();
+        let bs = (h - k).powi(2);
+        let c = (4.0 - hk) / 8.0;
+        let d = (12.0 - hk) / 16.0;
+        bvn = a
+            * (-0.5 * (bs / as_ + hk)).exp()
+            * (1.0 - c * (bs - as_) * (1.0 - d * bs / 5.0) / 3.0 + c * d * as_ * as_ / 5.0);
+        if hk > -160.0 {
+            let b = bs.sqrt();
+            bvn -= (-0.5 * hk).exp()
+                * (2.0 * PI).sqrt()
+                * calculate_ncdf(-b / a)
+                * b
+                * (1.0 - c * bs * (1.0 - d * bs / 5.0) / 3.0);
+        }
+        a *= 0.5;
+        for (&xi, &wi) in xs.iter().zip(ws) {
+            for sign in [-1.0, 1.0] {
+                let xs = (a * (sign * xi + 1.0)).powi(2);
+                let rs = (1.0 - xs).sqrt();
+                let asr = -0.5 * (bs / xs + hk);
+                if asr > -100.0 {
+                    bvn += a
+                        * wi
+                        * asr.exp()
+                        * ((-hk * (1.0 - rs) / (2.0 * (1.0 + rs))).exp() / rs
+                            - (1.0 + c * xs * (1.0 + d * xs)));
+                }
+            }
+        }
+        bvn = -bvn / (2.0 * PI);
+    }
+
+    if rho > 0.0 {
+        bvn + calculate_ncdf(-h.max(k))
+    } else if k > h {
+        calculate_ncdf(k) - calculate_ncdf(h) - bvn
+    } else {
+        -bvn
+    }
+}
+
 /// The inputs to the Black-Scholes-Merton model.
 #[derive(Debug, Clone)]
 pub struct OptionInputs {
@@ -89,6 +305,48 @@ impl OptionInputs {
         }
     }
 
+    /// Inputs in terms of Haug's generalized Black-Scholes cost of carry `b`, the drift of the
+    /// underlying under the pricing measure, so that `q = r - b`.
+    pub fn cost_of_carry(is_call: bool, s: f64, k: f64, r: f64, b: f64, t: f64) -> Self {
+        Self::new(is_call, s, k, r, r - b, t)
+    }
+
+    /// Inputs for a commodity with a continuous storage cost and convenience yield, which net to
+    /// a dividend yield of `convenience_yield - storage_cost`. Storage costs above the
+    /// convenience yield give a negative yield and a forward above the financed spot.
+    pub fn commodity(
+        is_call: bool,
+        s: f64,
+        k: f64,
+        r: f64,
+        storage_cost: f64,
+        convenience_yield: f64,
+        t: f64,
+    ) -> Self {
+        Self::new(is_call, s, k, r, convenience_yield - storage_cost, t)
+    }
+
+    /// The cost of carry `b = r - q`.
+    #[inline(always)]
+    pub fn carry(&self) -> f64 {
+        self.r - self.q
+    }
+
+    /// Strike at which an option with the given vol has the given delta. Put deltas are negative.
+    pub fn strike_from_delta(
+        is_call: bool,
+        s: f64,
+        r: f64,
+        q: f64,
+        t: f64,
+        implied_vol: f64,
+        delta: f64,
+    ) -> f64 {
+        let sign = if is_call { 1.0 } else { -1.0 };
+        let d1 = sign * calculate_inv_ncdf(sign * delta * (q * t).exp());
+        s * (-d1 * implied_vol * t.sqrt() + (r - q + 0.5 * implied_vol.powi(2)) * t).exp()
+    }
+
     pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
         self.implied_vol = implied_vol;
 
@@ -273,4 +531,26 @@ impl OptionInputs {
     pub fn dual_gamma(&self) -> f64 {
         self.dividend_discount() * (self.nprimed2 / (self.k * self.implied_vol * self.t.sqrt()))
     }
+
+    /// Delta including the vol change that accompanies a spot move, where `beta` is the change in
+    /// implied vol per unit relative spot move (e.g. -0.5 for +5 vol points on a 10% sell-off).
+    pub fn shadow_delta(&self, beta: f64) -> f64 {
+        // vega, vanna, and vomma are scaled per vol point
+        self.delta() + 100.0 * self.vega() * beta / self.s
+    }
+
+    /// Gamma including the vol change that accompanies a spot move, with `beta` as in
+    /// [`shadow_delta`](Self::shadow_delta).
+    pub fn shadow_gamma(&self, beta: f64) -> f64 {
+        let dvol_ds = beta / self.s;
+
+        self.gamma() + 200.0 * self.vanna() * dvol_ds + 100.0 * self.vomma() * dvol_ds * dvol_ds
+            - 100.0 * self.vega() * dvol_ds / self.s
+    }
+
+    /// Vega including the spot move that accompanies a vol change, where `spot_beta` is the
+    /// relative spot move per unit change in vol (e.g. -2.0 for a 2% sell-off per vol point).
+    pub fn shadow_vega(&self, spot_beta: f64) -> f64 {
+        self.vega() + 0.01 * self.delta() * self.s * spot_beta
+    }
 }
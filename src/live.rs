@@ -0,0 +1,120 @@
+//! Live vol surfaces updated quote by quote.
+//!
+//! A [`LiveSurface`] keeps the latest implied vol observed at each strike of each expiry slice.
+//! When a quote ticks only the nodes of its own slice that lie between the neighbouring
+//! observations are refit, by linear interpolation in moneyness across the observations, so an
+//! update costs a handful of node writes rather than a full recalibration. Every node carries the
+//! time it was last refit so stale parts of the surface can be found.
+
+use crate::chain::SolvedQuote;
+use crate::surface::VolSurface;
+
+/// An implied vol observed at one strike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Observation {
+    moneyness: f64,
+    vol: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveSurface {
+    pub surface: VolSurface,
+
+    /// Time each node was last refit, indexed like the surface vols, in whatever clock the
+    /// caller uses
+    pub updated: Vec<Vec<f64>>,
+
+    /// Latest observation per strike for each expiry, ascending in moneyness
+    observations: Vec<Vec<Observation>>,
+}
+
+impl LiveSurface {
+    /// Start from a fitted surface whose every node is as of `time`.
+    pub fn new(surface: VolSurface, time: f64) -> Self {
+        let updated = surface
+            .vols
+            .iter()
+            .map(|row| vec![time; row.len()])
+            .collect();
+        let observations = vec![Vec::new(); surface.expiries.len()];
+        Self {
+            surface,
+            updated,
+            observations,
+        }
+    }
+
+    /// Index of the expiry slice closest to `t` in log time.
+    fn slice(&self, t: f64) -> usize {
+        let distance = |e: f64| (e / t).ln().abs();
+        (0..self.surface.expiries.len())
+            .min_by(|&a, &b| {
+                distance(self.surface.expiries[a]).total_cmp(&distance(self.surface.expiries[b]))
+            })
+            .unwrap()
+    }
+
+    /// Record `vol` observed at strike `k` for the slice with the expiry closest to `t` at
+    /// `time`, refit the nodes of that slice between its neighbouring observations, and return
+    /// the slice's index.
+    pub fn update(&mut self, k: f64, t: f64, vol: f64, time: f64) -> usize {
+        let i = self.slice(t);
+        let moneyness = (k / self.surface.forward(self.surface.expiries[i])).ln();
+        let observations = &mut self.observations[i];
+
+        let at = observations.partition_point(|o| o.moneyness < moneyness - 1e-12);
+        let observation = Observation { moneyness, vol };
+        if observations
+            .get(at)
+            .is_some_and(|o| (o.moneyness - moneyness).abs() <= 1e-12)
+        {
+            observations[at] = observation;
+        } else {
+            observations.insert(at, observation);
+        }
+
+        let below = at
+            .checked_sub(1)
+            .map_or(f64::NEG_INFINITY, |j| observations[j].moneyness);
+        let above = observations
+            .get(at + 1)
+            .map_or(f64::INFINITY, |o| o.moneyness);
+        let ms: Vec<f64> = observations.iter().map(|o| o.moneyness).collect();
+        let vols: Vec<f64> = observations.iter().map(|o| o.vol).collect();
+
+        for (j, &m) in self.surface.moneyness.iter().enumerate() {
+            if m > below && m < above {
+                self.surface.vols[i][j] = crate::surface::interpolate(&ms, &vols, m);
+                self.updated[i][j] = time;
+            }
+        }
+        i
+    }
+
+    /// Record the mid vol of a solved quote, ignoring quotes whose mid did not solve.
+    pub fn update_quote(&mut self, quote: &SolvedQuote, time: f64) -> Option<usize> {
+        quote
+            .mid_vol
+            .is_finite()
+            .then(|| self.update(quote.quote.k, quote.quote.t, quote.mid_vol, time))
+    }
+
+    /// Time since node `(i, j)` was last refit.
+    pub fn age(&self, i: usize, j: usize, now: f64) -> f64 {
+        now - self.updated[i][j]
+    }
+
+    /// Nodes, as expiry and moneyness indices, not refit within `max_age` of `now`.
+    pub fn stale_nodes(&self, now: f64, max_age: f64) -> Vec<(usize, usize)> {
+        self.updated
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(move |(_, &time)| now - time > max_age)
+                    .map(move |(j, _)| (i, j))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,130 @@
+//! Lookback options, priced in closed form under BSM.
+//!
+//! A floating-strike lookback call pays the final spot less its minimum over the option's life,
+//! and the put the maximum less the final spot, so the holder buys at the low or sells at the
+//! high. A fixed-strike lookback call pays the maximum less the strike and the put the strike
+//! less the minimum. Monitoring is continuous. The floating-strike prices are those of Goldman,
+//! Sosin, and Gatto (1979) and the fixed-strike prices those of Conze and Viswanathan (1991), in
+//! Haug's arrangement with the extreme observed so far as an input for seasoned options.
+//!
+//! The formulas divide by the cost of carry `r - q` and lose precision as it nears zero, so
+//! within `1e-5` of zero the price is interpolated between the carries either side.
+
+use crate::calculate_ncdf;
+
+/// Carries closer to zero than this are interpolated across.
+const MIN_CARRY: f64 = 1e-5;
+
+/// Whether the strike is set by the path or fixed in advance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookbackStrike {
+    /// The minimum of the spot for calls and the maximum for puts
+    Floating,
+
+    /// A fixed strike
+    Fixed(f64),
+}
+
+/// The inputs to a lookback option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookbackOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    pub strike: LookbackStrike,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// The extreme of the spot observed so far that the payoff depends on: the minimum for
+    /// floating-strike calls and fixed-strike puts, the maximum otherwise
+    pub observed: f64,
+
+    /// Implied vol
+    pub implied_vol: f64,
+}
+
+impl LookbackOption {
+    /// A lookback starting today, so that the observed extreme is the spot.
+    pub fn new(is_call: bool, s: f64, strike: LookbackStrike, r: f64, q: f64, t: f64) -> Self {
+        Self {
+            is_call,
+            s,
+            strike,
+            r,
+            q,
+            t,
+            observed: s,
+            implied_vol: f64::NAN,
+        }
+    }
+
+    /// Set the extreme observed so far for a seasoned option.
+    pub fn with_observed(mut self, observed: f64) -> Self {
+        self.observed = observed;
+        self
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    pub fn price(&self) -> f64 {
+        let b = self.r - self.q;
+        if b.abs() >= MIN_CARRY {
+            return self.price_at_carry(b);
+        }
+        let (below, above) = (
+            self.price_at_carry(-MIN_CARRY),
+            self.price_at_carry(MIN_CARRY),
+        );
+        below + (above - below) * (b + MIN_CARRY) / (2.0 * MIN_CARRY)
+    }
+
+    fn price_at_carry(&self, b: f64) -> f64 {
+        let (s, r, t, vol) = (self.s, self.r, self.t, self.implied_vol);
+        let stddev = vol * t.sqrt();
+        let share = s * ((b - r) * t).exp();
+        let discount = (-r * t).exp();
+        let d = |level: f64| ((s / level).ln() + (b + 0.5 * vol * vol) * t) / stddev;
+        let skew = 2.0 * b * t.sqrt() / vol;
+
+        // the value of the extreme moving past `level`, the last term of every formula
+        let extension = |level: f64, sign: f64| {
+            let d1 = d(level);
+            sign * s * discount * vol * vol / (2.0 * b)
+                * ((s / level).powf(-2.0 * b / (vol * vol)) * calculate_ncdf(sign * (skew - d1))
+                    - (b * t).exp() * calculate_ncdf(-sign * d1))
+        };
+        // a vanilla-like payoff against `level`, for calls above and puts below it
+        let vanilla = |level: f64, sign: f64| {
+            let d1 = d(level);
+            sign * (share * calculate_ncdf(sign * d1)
+                - level * discount * calculate_ncdf(sign * (d1 - stddev)))
+        };
+
+        let m = self.observed;
+        match (self.strike, self.is_call) {
+            (LookbackStrike::Floating, true) => vanilla(m, 1.0) + extension(m, 1.0),
+            (LookbackStrike::Floating, false) => vanilla(m, -1.0) + extension(m, -1.0),
+            (LookbackStrike::Fixed(k), true) if k > m => vanilla(k, 1.0) + extension(k, -1.0),
+            (LookbackStrike::Fixed(k), true) => {
+                discount * (m - k) + vanilla(m, 1.0) + extension(m, -1.0)
+            }
+            (LookbackStrike::Fixed(k), false) if k < m => vanilla(k, -1.0) + extension(k, 1.0),
+            (LookbackStrike::Fixed(k), false) => {
+                discount * (k - m) + vanilla(m, -1.0) + extension(m, 1.0)
+            }
+        }
+    }
+}
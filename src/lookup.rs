@@ -0,0 +1,169 @@
+//! Lookup-table pricing for low-latency approximate prices.
+//!
+//! A [`PriceTable`] precomputes the normalized Black call price `E[(F_T / F - K / F)+]` on a
+//! uniform grid of log-moneyness `ln(K / F)` and total vol `vol * sqrt(t)`, then prices by cubic
+//! interpolation on the grid: sixteen loads and a few multiplies instead of two normal CDFs and
+//! a log. Puts follow from parity. The table records its own worst error, measured against the
+//! exact price at the centre of every cell, and inputs outside the table are priced exactly.
+
+use crate::lets_be_rational;
+
+/// Normalized undiscounted Black call price.
+fn normalized_call(x: f64, w: f64) -> f64 {
+    lets_be_rational::black(1.0, x.exp(), w, 1.0, 1.0)
+}
+
+/// Uniform axis of a table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Axis {
+    min: f64,
+    step: f64,
+    points: usize,
+}
+
+impl Axis {
+    fn new(min: f64, max: f64, points: usize) -> Self {
+        let points = points.max(4);
+        Self {
+            min,
+            step: (max - min) / (points - 1) as f64,
+            points,
+        }
+    }
+
+    fn node(&self, i: usize) -> f64 {
+        self.min + i as f64 * self.step
+    }
+
+    fn max(&self) -> f64 {
+        self.node(self.points - 1)
+    }
+
+    /// First of the four nodes to interpolate from and the cubic Lagrange weights, or `None`
+    /// outside the axis.
+    #[inline(always)]
+    fn weights(&self, x: f64) -> Option<(usize, [f64; 4])> {
+        let position = (x - self.min) / self.step;
+        if !(0.0..=(self.points - 1) as f64).contains(&position) {
+            return None;
+        }
+        let i = (position as usize).clamp(1, self.points - 3);
+        let u = position - i as f64;
+        Some((
+            i - 1,
+            [
+                -u * (u - 1.0) * (u - 2.0) / 6.0,
+                (u + 1.0) * (u - 1.0) * (u - 2.0) / 2.0,
+                -(u + 1.0) * u * (u - 2.0) / 2.0,
+                (u + 1.0) * u * (u - 1.0) / 6.0,
+            ],
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTable {
+    moneyness: Axis,
+    total_vol: Axis,
+
+    /// Normalized call prices indexed by moneyness then total vol
+    values: Vec<f64>,
+
+    /// Largest error in normalized price, as a fraction of the forward, found at the centres of
+    /// the cells
+    pub max_error: f64,
+}
+
+impl PriceTable {
+    /// A table over log-moneyness in `[-max_moneyness, max_moneyness]` and total vol in
+    /// `[min_total_vol, max_total_vol]` with the given number of points on each axis.
+    pub fn new(
+        max_moneyness: f64,
+        min_total_vol: f64,
+        max_total_vol: f64,
+        moneyness_points: usize,
+        vol_points: usize,
+    ) -> Self {
+        let moneyness = Axis::new(-max_moneyness, max_moneyness, moneyness_points);
+        let total_vol = Axis::new(min_total_vol, max_total_vol, vol_points);
+        let mut values = Vec::with_capacity(moneyness.points * total_vol.points);
+        for i in 0..moneyness.points {
+            for j in 0..total_vol.points {
+                values.push(normalized_call(moneyness.node(i), total_vol.node(j)));
+            }
+        }
+
+        let mut table = Self {
+            moneyness,
+            total_vol,
+            values,
+            max_error: 0.0,
+        };
+        for i in 0..moneyness.points - 1 {
+            for j in 0..total_vol.points - 1 {
+                let x = moneyness.node(i) + 0.5 * moneyness.step;
+                let w = total_vol.node(j) + 0.5 * total_vol.step;
+                let error = (table.normalized(x, w).unwrap() - normalized_call(x, w)).abs();
+                table.max_error = table.max_error.max(error);
+            }
+        }
+        table
+    }
+
+    /// The coarsest table over the same domain, doubling the resolution from 32 points per axis
+    /// up to 4096, whose measured error is within `tolerance` of the forward.
+    pub fn with_tolerance(
+        tolerance: f64,
+        max_moneyness: f64,
+        min_total_vol: f64,
+        max_total_vol: f64,
+    ) -> Self {
+        let mut points = 32;
+        loop {
+            let table = Self::new(max_moneyness, min_total_vol, max_total_vol, points, points);
+            if table.max_error <= tolerance || points >= 4096 {
+                return table;
+            }
+            points *= 2;
+        }
+    }
+
+    /// Interpolated normalized call price, or `None` outside the table.
+    #[inline(always)]
+    pub fn normalized(&self, x: f64, w: f64) -> Option<f64> {
+        let (i, wx) = self.moneyness.weights(x)?;
+        let (j, ww) = self.total_vol.weights(w)?;
+        let n = self.total_vol.points;
+        let mut sum = 0.0;
+        for (a, wa) in wx.iter().enumerate() {
+            let row = &self.values[(i + a) * n + j..(i + a) * n + j + 4];
+            sum += wa * (ww[0] * row[0] + ww[1] * row[1] + ww[2] * row[2] + ww[3] * row[3]);
+        }
+        Some(sum)
+    }
+
+    /// Whether the table covers log-moneyness `x` and total vol `w`.
+    pub fn covers(&self, x: f64, w: f64) -> bool {
+        (self.moneyness.min..=self.moneyness.max()).contains(&x)
+            && (self.total_vol.min..=self.total_vol.max()).contains(&w)
+    }
+
+    /// BSM price from the table, or exactly when the option lies outside it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn price(&self, is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, vol: f64) -> f64 {
+        let forward = s * ((r - q) * t).exp();
+        let x = (k / forward).ln();
+        let w = vol * t.sqrt();
+        let discount = (-r * t).exp();
+        let Some(call) = self.normalized(x, w) else {
+            let sign = if is_call { 1.0 } else { -1.0 };
+            return discount * lets_be_rational::black(forward, k, vol, t, sign);
+        };
+        let undiscounted = if is_call {
+            call
+        } else {
+            call - 1.0 + k / forward
+        };
+        discount * forward * undiscounted
+    }
+}
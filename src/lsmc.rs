@@ -0,0 +1,190 @@
+//! Longstaff-Schwartz least-squares Monte Carlo for early exercise.
+//!
+//! Paths are simulated by a [`MonteCarlo`] engine. Walking back from expiry, the value of
+//! continuing at each exercise date is estimated by regressing the discounted realised cash
+//! flows of the in-the-money paths on a set of basis functions of moneyness, and a path is
+//! exercised wherever its immediate payoff beats the estimate. The price is the mean discounted
+//! cash flow under that exercise policy, a slight underestimate of the true value since the
+//! policy is suboptimal.
+
+use crate::mc::{self, MonteCarlo, NormalSource};
+use crate::OptionInputs;
+
+/// Regression basis in moneyness `x = S / K`.
+#[derive(Debug, Clone, Copy)]
+pub enum Basis {
+    /// `1, x, ..., x^degree`
+    Monomial(usize),
+
+    /// A constant and the weighted Laguerre polynomials `exp(-x / 2) L_n(x)` for `n` up to the
+    /// degree, as in Longstaff and Schwartz (2001)
+    Laguerre(usize),
+
+    /// Any other set of functions of moneyness
+    Custom(fn(f64) -> Vec<f64>),
+}
+
+impl Basis {
+    fn evaluate(&self, x: f64) -> Vec<f64> {
+        match *self {
+            Self::Monomial(degree) => (0..=degree).map(|n| x.powi(n as i32)).collect(),
+            Self::Laguerre(degree) => {
+                let weight = (-0.5 * x).exp();
+                let mut values = vec![1.0];
+                let (mut previous, mut current) = (0.0, 1.0);
+                for n in 0..=degree {
+                    values.push(weight * current);
+                    let next = ((2 * n + 1) as f64 - x) * current - n as f64 * previous;
+                    (previous, current) = (current, next / (n + 1) as f64);
+                }
+                values
+            }
+            Self::Custom(f) => f(x),
+        }
+    }
+}
+
+/// Least-squares coefficients of `ys` on the rows of `xs`, by the normal equations.
+fn least_squares(xs: &[Vec<f64>], ys: &[f64]) -> Option<Vec<f64>> {
+    let m = xs.first()?.len();
+    // augmented normal equations [X'X | X'y]
+    let mut a = vec![vec![0.0; m + 1]; m];
+    for (x, &y) in xs.iter().zip(ys) {
+        for i in 0..m {
+            for j in 0..m {
+                a[i][j] += x[i] * x[j];
+            }
+            a[i][m] += x[i] * y;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting
+    for col in 0..m {
+        let pivot = (col..m).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        let pivot = a[col].clone();
+        for row in a.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot[col];
+            for (x, p) in row.iter_mut().zip(&pivot).skip(col) {
+                *x -= factor * p;
+            }
+        }
+    }
+    let mut beta = vec![0.0; m];
+    for i in (0..m).rev() {
+        let sum: f64 = (i + 1..m).map(|j| a[i][j] * beta[j]).sum();
+        beta[i] = (a[i][m] - sum) / a[i][i];
+    }
+    Some(beta)
+}
+
+/// A least-squares Monte Carlo price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LsmcPrice {
+    pub price: f64,
+
+    /// Standard error of the price
+    pub std_error: f64,
+
+    /// BSM price of the European option
+    pub european: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LongstaffSchwartz {
+    pub mc: MonteCarlo,
+    pub basis: Basis,
+
+    /// Times in years at which exercise is allowed, rounded to the nearest simulation step.
+    /// Empty for exercise at every step, approximating an American option
+    pub exercise_dates: Vec<f64>,
+}
+
+impl LongstaffSchwartz {
+    pub fn new(mc: MonteCarlo, basis: Basis) -> Self {
+        Self {
+            mc,
+            basis,
+            exercise_dates: Vec::new(),
+        }
+    }
+
+    /// Restrict exercise to the given dates, making the option Bermudan.
+    pub fn with_exercise_dates(mut self, exercise_dates: Vec<f64>) -> Self {
+        self.exercise_dates = exercise_dates;
+        self
+    }
+
+    /// Steps before expiry at which the option may be exercised, descending.
+    fn exercise_steps(&self, t: f64) -> Vec<usize> {
+        let steps = self.mc.steps;
+        let mut indices: Vec<usize> = if self.exercise_dates.is_empty() {
+            (1..steps).collect()
+        } else {
+            self.exercise_dates
+                .iter()
+                .map(|&d| (d / t * steps as f64).round() as usize)
+                .filter(|&i| i >= 1 && i < steps)
+                .collect()
+        };
+        indices.sort_unstable();
+        indices.dedup();
+        indices.reverse();
+        indices
+    }
+
+    pub fn price<R: NormalSource + ?Sized>(&self, option: &OptionInputs, rng: &mut R) -> LsmcPrice {
+        let paths = self.mc.paths(option, rng);
+        let steps = self.mc.steps;
+        let dt = option.t / steps as f64;
+        let sign = option.sign();
+        let payoff = |s: f64| (sign * (s - option.k)).max(0.0);
+
+        // realised cash flow of each path and the step it is paid at
+        let mut cash: Vec<(f64, usize)> = paths.iter().map(|p| (payoff(p[steps]), steps)).collect();
+        for i in self.exercise_steps(option.t) {
+            let in_the_money: Vec<usize> = (0..paths.len())
+                .filter(|&p| payoff(paths[p][i]) > 0.0)
+                .collect();
+            let xs: Vec<Vec<f64>> = in_the_money
+                .iter()
+                .map(|&p| self.basis.evaluate(paths[p][i] / option.k))
+                .collect();
+            let ys: Vec<f64> = in_the_money
+                .iter()
+                .map(|&p| {
+                    let (value, at) = cash[p];
+                    value * (-option.r * (at - i) as f64 * dt).exp()
+                })
+                .collect();
+            if in_the_money.len() <= xs.first().map_or(0, Vec::len) {
+                continue;
+            }
+            let Some(beta) = least_squares(&xs, &ys) else {
+                continue;
+            };
+            for (&p, x) in in_the_money.iter().zip(&xs) {
+                let continuation: f64 = beta.iter().zip(x).map(|(b, x)| b * x).sum();
+                let exercise = payoff(paths[p][i]);
+                if exercise > continuation {
+                    cash[p] = (exercise, i);
+                }
+            }
+        }
+
+        let values: Vec<f64> = cash
+            .iter()
+            .map(|&(value, at)| value * (-option.r * at as f64 * dt).exp())
+            .collect();
+        // antithetic pairs are averaged into one sample, as for European prices
+        let (price, std_error) = mc::mean_and_error(&self.mc.samples(values));
+        LsmcPrice {
+            price,
+            std_error,
+            european: option.price(),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! SPAN-style scenario margin for option portfolios.
+//!
+//! The portfolio is revalued under the standard sixteen risk scenarios: the underlying unchanged
+//! and moved by a third, two thirds, and the full price scan range in each direction, each paired
+//! with vol up and vol down, plus two extreme moves whose losses only count partially. The scan
+//! risk is the largest loss across the scenarios, floored by a per-contract short option minimum.
+
+use crate::{Portfolio, Scenario};
+
+/// Risk array parameters for a product group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanParameters {
+    /// Relative price scan range, e.g. 0.08 for an 8% move
+    pub price_scan_range: f64,
+
+    /// Absolute vol scan range, e.g. 0.04 for 4 vol points
+    pub vol_scan_range: f64,
+
+    /// Size of the extreme moves as a multiple of the price scan range
+    pub extreme_move_multiple: f64,
+
+    /// Fraction of the extreme move loss counted towards scan risk
+    pub extreme_move_coverage: f64,
+
+    /// Minimum charge per short option contract, in the same units as position value
+    pub short_option_minimum: f64,
+
+    /// Calendar days to roll forward when revaluing
+    pub time_shift: f64,
+}
+
+impl SpanParameters {
+    pub fn new(price_scan_range: f64, vol_scan_range: f64) -> Self {
+        Self {
+            price_scan_range,
+            vol_scan_range,
+            extreme_move_multiple: 2.0,
+            extreme_move_coverage: 0.35,
+            short_option_minimum: 0.0,
+            time_shift: 0.0,
+        }
+    }
+
+    pub fn with_extreme_move(mut self, multiple: f64, coverage: f64) -> Self {
+        self.extreme_move_multiple = multiple;
+        self.extreme_move_coverage = coverage;
+        self
+    }
+
+    pub fn with_short_option_minimum(mut self, short_option_minimum: f64) -> Self {
+        self.short_option_minimum = short_option_minimum;
+        self
+    }
+
+    pub fn with_time_shift(mut self, time_shift: f64) -> Self {
+        self.time_shift = time_shift;
+        self
+    }
+
+    /// The sixteen risk scenarios in their conventional order, with the weight applied to each
+    /// scenario's loss.
+    pub fn scenarios(&self) -> [(Scenario, f64); 16] {
+        let (p, v, t) = (self.price_scan_range, self.vol_scan_range, self.time_shift);
+        let mut scenarios = [(Scenario::default(), 1.0); 16];
+
+        for (i, fraction) in [0.0, 1.0, -1.0, 2.0, -2.0, 3.0, -3.0].iter().enumerate() {
+            let spot_shift = fraction / 3.0 * p;
+            scenarios[2 * i].0 = Scenario::new(spot_shift, v, t);
+            scenarios[2 * i + 1].0 = Scenario::new(spot_shift, -v, t);
+        }
+
+        let extreme = self.extreme_move_multiple * p;
+        scenarios[14] = (Scenario::new(extreme, 0.0, t), self.extreme_move_coverage);
+        scenarios[15] = (Scenario::new(-extreme, 0.0, t), self.extreme_move_coverage);
+
+        scenarios
+    }
+
+    pub fn margin(&self, portfolio: &Portfolio) -> MarginEstimate {
+        let mut losses = [0.0; 16];
+        for (loss, (scenario, weight)) in losses.iter_mut().zip(self.scenarios()) {
+            *loss = -weight * scenario.pnl(portfolio);
+        }
+
+        let (worst_scenario, scan_risk) = losses
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+
+        let short_contracts: f64 = portfolio
+            .positions
+            .iter()
+            .filter(|p| p.quantity < 0.0)
+            .map(|p| -p.quantity)
+            .sum();
+
+        MarginEstimate {
+            losses,
+            worst_scenario,
+            scan_risk: scan_risk.max(0.0),
+            short_option_minimum: short_contracts * self.short_option_minimum,
+        }
+    }
+}
+
+/// Result of a scenario margin calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginEstimate {
+    /// Weighted loss under each scenario, positive for losses
+    pub losses: [f64; 16],
+
+    /// Index of the scenario producing the scan risk
+    pub worst_scenario: usize,
+
+    /// Largest weighted loss, zero if no scenario loses money
+    pub scan_risk: f64,
+
+    /// Total short option minimum charge
+    pub short_option_minimum: f64,
+}
+
+impl MarginEstimate {
+    pub fn requirement(&self) -> f64 {
+        self.scan_risk.max(self.short_option_minimum)
+    }
+}
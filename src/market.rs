@@ -0,0 +1,121 @@
+//! Market data shared across pricing threads.
+//!
+//! A [`MarketContext`] bundles the curves, vol surface, and calendar for one underlying. It is
+//! immutable once built, so it is wrapped in an [`Arc`] and shared between threads; an
+//! [`OptionHandle`] holds a reference to it plus the contract terms and builds the
+//! [`OptionInputs`] on demand without copying any curve or surface data.
+
+use std::sync::Arc;
+
+use crate::calendar::{Calendar, Date};
+use crate::curve::RateCurve;
+use crate::surface::VolSurface;
+use crate::OptionInputs;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketContext {
+    pub valuation_date: Date,
+
+    /// Spot price of the underlying
+    pub s: f64,
+
+    /// Risk-free zero curve
+    pub rates: RateCurve,
+
+    /// Dividend or borrow yield curve
+    pub dividends: RateCurve,
+
+    /// Implied vols, looked up by log-forward-moneyness against this context's forwards
+    pub surface: VolSurface,
+
+    pub calendar: Calendar,
+}
+
+impl MarketContext {
+    pub fn new(
+        valuation_date: Date,
+        s: f64,
+        rates: RateCurve,
+        dividends: RateCurve,
+        surface: VolSurface,
+    ) -> Self {
+        Self {
+            valuation_date,
+            s,
+            rates,
+            dividends,
+            surface,
+            calendar: Calendar::new(),
+        }
+    }
+
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Wrap the context for sharing between threads.
+    pub fn shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    pub fn forward(&self, t: f64) -> f64 {
+        self.s * ((self.rates.zero_rate(t) - self.dividends.zero_rate(t)) * t).exp()
+    }
+
+    /// Time to an expiry date in years, with expiries on holidays moved to the preceding
+    /// business day.
+    pub fn time_to(&self, expiry: Date) -> f64 {
+        self.calendar
+            .preceding(expiry)
+            .years_since(self.valuation_date)
+    }
+
+    pub fn vol(&self, k: f64, t: f64) -> f64 {
+        self.surface.vol_at_moneyness((k / self.forward(t)).ln(), t)
+    }
+
+    /// BSM inputs for an option expiring `t` years from the valuation date.
+    pub fn option(&self, is_call: bool, k: f64, t: f64) -> OptionInputs {
+        OptionInputs::new(
+            is_call,
+            self.s,
+            k,
+            self.rates.zero_rate(t),
+            self.dividends.zero_rate(t),
+            t,
+        )
+        .with_implied_vol(self.vol(k, t))
+    }
+
+    /// A lightweight handle for repeatedly pricing one contract against this context.
+    pub fn handle(self: &Arc<Self>, is_call: bool, k: f64, expiry: Date) -> OptionHandle {
+        OptionHandle {
+            context: Arc::clone(self),
+            is_call,
+            k,
+            t: self.time_to(expiry),
+        }
+    }
+}
+
+/// A contract bound to a shared market context.
+#[derive(Debug, Clone)]
+pub struct OptionHandle {
+    pub context: Arc<MarketContext>,
+    pub is_call: bool,
+    pub k: f64,
+
+    /// Time to expiry in years
+    pub t: f64,
+}
+
+impl OptionHandle {
+    pub fn inputs(&self) -> OptionInputs {
+        self.context.option(self.is_call, self.k, self.t)
+    }
+
+    pub fn price(&self) -> f64 {
+        self.inputs().price()
+    }
+}
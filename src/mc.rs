@@ -0,0 +1,442 @@
+//! Monte Carlo pricing under geometric Brownian motion.
+//!
+//! [`MonteCarlo`] simulates the spot at the option's carry and implied vol, either straight to
+//! expiry or along a path of equal time steps, and prices European options by the discounted
+//! mean payoff. Each price is reported with its standard error and the analytic BSM price, so
+//! convergence can be checked directly.
+//!
+//! Draws come from any [`NormalSource`]: every [`Rng`] is one, for pseudo-random sampling, and
+//! so is a [`Sobol`](crate::qmc::Sobol) sequence for quasi-random sampling.
+//!
+//! Path-dependent options implementing [`ControlVariate`] are priced with
+//! [`MonteCarlo::price_with_control`], which simulates each one alongside its closest relative
+//! with a closed form, such as the geometric Asian for the arithmetic one, and corrects the
+//! price by the relative's simulation error. The closer the two payoffs, the larger the
+//! reduction in variance.
+//!
+//! [`MonteCarlo::price_correlated`] simulates several assets whose normal draws are correlated
+//! through the Cholesky factor of their correlation matrix, and prices any [`Payoff`] on their
+//! paths: baskets, rainbows such as best-of and worst-of options, and dispersion trades.
+
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::OptionInputs;
+
+/// A source of standard normal draws, one path of them at a time.
+pub trait NormalSource {
+    /// Fill `draws` with the standard normals for the next path.
+    fn fill(&mut self, draws: &mut [f64]);
+}
+
+impl<R: Rng + ?Sized> NormalSource for R {
+    fn fill(&mut self, draws: &mut [f64]) {
+        for z in draws {
+            *z = self.sample(StandardNormal);
+        }
+    }
+}
+
+/// A Monte Carlo price next to the closed form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct McPrice {
+    pub price: f64,
+
+    /// Standard error of the price
+    pub std_error: f64,
+
+    /// Closed-form price of the same option, BSM for a vanilla and NaN when there is none
+    pub analytic: f64,
+}
+
+impl McPrice {
+    /// Difference from the analytic price in standard errors.
+    pub fn z_score(&self) -> f64 {
+        (self.price - self.analytic) / self.std_error
+    }
+}
+
+/// A path-dependent option simulated alongside a closely related payoff with a closed-form
+/// price, whose known simulation error is subtracted from the option's.
+pub trait ControlVariate {
+    /// The underlying to simulate, with the option's spot, carry, vol, and expiry.
+    fn underlying(&self) -> OptionInputs;
+
+    /// Times after today at which the paths are observed, given a number of steps for
+    /// continuously monitored options.
+    fn observation_times(&self, steps: usize) -> Vec<f64>;
+
+    /// Discounted payoffs of the option and its control along `path`, the spots today and at
+    /// each of `times`.
+    fn payoffs(&self, times: &[f64], path: &[f64]) -> (f64, f64);
+
+    /// Closed-form price of the control observed at the same times.
+    fn control_price(&self, steps: usize) -> f64;
+}
+
+/// A Monte Carlo price with a control variate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ControlledPrice {
+    pub price: f64,
+
+    /// Standard error of the price
+    pub std_error: f64,
+
+    /// Standard error the same paths would give without the control
+    pub plain_std_error: f64,
+
+    /// Units of the control's simulation error subtracted per unit of the option
+    pub beta: f64,
+}
+
+/// Mean of independent `samples` and its standard error.
+pub(crate) fn mean_and_error(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, (variance / n).sqrt())
+}
+
+/// Correlated assets following geometric Brownian motion, for multi-asset simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelatedAssets {
+    /// Prices of the assets
+    pub spots: Vec<f64>,
+
+    /// Dividend yields of the assets
+    pub dividends: Vec<f64>,
+
+    /// Vols of the assets
+    pub vols: Vec<f64>,
+
+    /// Correlations of the assets' returns, a symmetric matrix with a unit diagonal
+    pub correlation: Vec<Vec<f64>>,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+}
+
+impl CorrelatedAssets {
+    /// Assets without dividends.
+    pub fn new(
+        spots: Vec<f64>,
+        vols: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+        r: f64,
+        t: f64,
+    ) -> Self {
+        Self {
+            dividends: vec![0.0; spots.len()],
+            spots,
+            vols,
+            correlation,
+            r,
+            t,
+        }
+    }
+
+    pub fn with_dividends(mut self, dividends: Vec<f64>) -> Self {
+        self.dividends = dividends;
+        self
+    }
+}
+
+/// A payoff on the paths of several assets.
+pub trait Payoff {
+    /// Undiscounted payoff at expiry given each asset's path, its spots today and at each step.
+    fn payoff(&self, paths: &[Vec<f64>]) -> f64;
+
+    /// Closed-form or approximate price to report next to the simulated one, NaN by default.
+    fn analytic(&self) -> f64 {
+        f64::NAN
+    }
+}
+
+impl<F: Fn(&[Vec<f64>]) -> f64> Payoff for F {
+    fn payoff(&self, paths: &[Vec<f64>]) -> f64 {
+        self(paths)
+    }
+}
+
+/// Lower triangular `L` with `L L^T = matrix`, `None` unless the matrix is positive
+/// semi-definite. Zero pivots, as from perfectly correlated assets, leave zero columns.
+pub fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        let pivot = matrix[j][j] - (0..j).map(|k| lower[j][k] * lower[j][k]).sum::<f64>();
+        if pivot < -1e-12 {
+            return None;
+        }
+        let diagonal = pivot.max(0.0).sqrt();
+        lower[j][j] = diagonal;
+        for i in j + 1..n {
+            let off = matrix[i][j] - (0..j).map(|k| lower[i][k] * lower[j][k]).sum::<f64>();
+            lower[i][j] = if diagonal > 1e-12 {
+                off / diagonal
+            } else if off.abs() > 1e-9 {
+                return None;
+            } else {
+                0.0
+            };
+        }
+    }
+    Some(lower)
+}
+
+/// Fewest independent samples a simulation draws.
+const MIN_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonteCarlo {
+    /// Paths to simulate, raised if need be to give at least three independent samples after
+    /// antithetic pairs are averaged, the fewest the control variate estimate needs
+    pub paths: usize,
+
+    /// Time steps per path, 1 to simulate the terminal spot directly
+    pub steps: usize,
+
+    /// Pair every draw with its negation to reduce variance
+    pub antithetic: bool,
+}
+
+impl MonteCarlo {
+    pub fn new(paths: usize) -> Self {
+        Self {
+            paths,
+            steps: 1,
+            antithetic: false,
+        }
+    }
+
+    /// Paths actually simulated, see [`MonteCarlo::paths`](Self#structfield.paths).
+    fn path_count(&self) -> usize {
+        let per_sample = if self.antithetic { 2 } else { 1 };
+        self.paths.max(MIN_SAMPLES * per_sample)
+    }
+
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps.max(1);
+        self
+    }
+
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Simulated spots at each of the `steps + 1` times from today to expiry, one path per
+    /// entry. With antithetic draws, paths come in pairs of mirror images.
+    pub fn paths<R: NormalSource + ?Sized>(
+        &self,
+        option: &OptionInputs,
+        rng: &mut R,
+    ) -> Vec<Vec<f64>> {
+        let dt = option.t / self.steps as f64;
+        let times: Vec<f64> = (1..=self.steps).map(|i| i as f64 * dt).collect();
+        self.paths_at(option, &times, rng)
+    }
+
+    /// Simulated spots today and at each of the ascending `times`, one path per entry. With
+    /// antithetic draws, paths come in pairs of mirror images.
+    pub fn paths_at<R: NormalSource + ?Sized>(
+        &self,
+        option: &OptionInputs,
+        times: &[f64],
+        rng: &mut R,
+    ) -> Vec<Vec<f64>> {
+        let vol = option.implied_vol;
+        let mut previous = 0.0;
+        let increments: Vec<(f64, f64)> = times
+            .iter()
+            .map(|&t| {
+                let dt = t - previous;
+                previous = t;
+                ((option.carry() - 0.5 * vol * vol) * dt, vol * dt.sqrt())
+            })
+            .collect();
+
+        let count = self.path_count();
+        let mut paths = Vec::with_capacity(count);
+        while paths.len() < count {
+            let mut draws = vec![0.0; times.len()];
+            rng.fill(&mut draws);
+            let signs: &[f64] = if self.antithetic {
+                &[1.0, -1.0]
+            } else {
+                &[1.0]
+            };
+            for &sign in signs {
+                let mut s = option.s;
+                let mut path = Vec::with_capacity(times.len() + 1);
+                path.push(s);
+                for (z, (drift, diffusion)) in draws.iter().zip(&increments) {
+                    s *= (drift + diffusion * sign * z).exp();
+                    path.push(s);
+                }
+                paths.push(path);
+            }
+        }
+        paths.truncate(count);
+        paths
+    }
+
+    /// Simulated paths of correlated assets at `steps` equal time steps, one entry per path
+    /// holding each asset's spots from today to expiry. `None` unless the correlation matrix is
+    /// positive semi-definite.
+    pub fn correlated_paths<R: NormalSource + ?Sized>(
+        &self,
+        assets: &CorrelatedAssets,
+        rng: &mut R,
+    ) -> Option<Vec<Vec<Vec<f64>>>> {
+        let lower = cholesky(&assets.correlation)?;
+        let n = assets.spots.len();
+        let dt = assets.t / self.steps as f64;
+        let increments: Vec<(f64, f64)> = assets
+            .vols
+            .iter()
+            .zip(&assets.dividends)
+            .map(|(vol, q)| ((assets.r - q - 0.5 * vol * vol) * dt, vol * dt.sqrt()))
+            .collect();
+
+        let count = self.path_count();
+        let mut paths = Vec::with_capacity(count);
+        let mut correlated = vec![0.0; n];
+        while paths.len() < count {
+            let mut draws = vec![0.0; n * self.steps];
+            rng.fill(&mut draws);
+            let signs: &[f64] = if self.antithetic {
+                &[1.0, -1.0]
+            } else {
+                &[1.0]
+            };
+            for &sign in signs {
+                let mut spots = assets.spots.clone();
+                let mut path: Vec<Vec<f64>> = spots.iter().map(|&s| vec![s]).collect();
+                for step in draws.chunks(n) {
+                    for (i, z) in correlated.iter_mut().enumerate() {
+                        *z = (0..=i).map(|k| lower[i][k] * step[k]).sum();
+                    }
+                    for (i, s) in spots.iter_mut().enumerate() {
+                        let (drift, diffusion) = increments[i];
+                        *s *= (drift + diffusion * sign * correlated[i]).exp();
+                        path[i].push(*s);
+                    }
+                }
+                paths.push(path);
+            }
+        }
+        paths.truncate(count);
+        Some(paths)
+    }
+
+    /// Price `payoff` on correlated assets, `None` unless the correlation matrix is positive
+    /// semi-definite.
+    pub fn price_correlated<P, R>(
+        &self,
+        assets: &CorrelatedAssets,
+        payoff: &P,
+        rng: &mut R,
+    ) -> Option<McPrice>
+    where
+        P: Payoff + ?Sized,
+        R: NormalSource + ?Sized,
+    {
+        let discount = (-assets.r * assets.t).exp();
+        let payoffs: Vec<f64> = self
+            .correlated_paths(assets, rng)?
+            .iter()
+            .map(|paths| discount * payoff.payoff(paths))
+            .collect();
+        let (price, std_error) = mean_and_error(&self.samples(payoffs));
+        Some(McPrice {
+            price,
+            std_error,
+            analytic: payoff.analytic(),
+        })
+    }
+
+    /// Simulated spots at expiry.
+    pub fn terminal_spots<R: NormalSource + ?Sized>(
+        &self,
+        option: &OptionInputs,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        self.paths(option, rng)
+            .into_iter()
+            .map(|path| *path.last().unwrap())
+            .collect()
+    }
+
+    /// Independent samples of `values` simulated along the paths, with antithetic pairs averaged.
+    pub(crate) fn samples(&self, values: Vec<f64>) -> Vec<f64> {
+        if self.antithetic {
+            values
+                .chunks(2)
+                .map(|p| p.iter().sum::<f64>() / p.len() as f64)
+                .collect()
+        } else {
+            values
+        }
+    }
+
+    /// Price a path-dependent `option` with its closed-form control, observing the paths at the
+    /// option's times for this many steps. The control's coefficient is estimated by regression
+    /// on the same paths, which biases the price by an amount of order `1 / paths` only.
+    pub fn price_with_control<O, R>(&self, option: &O, rng: &mut R) -> ControlledPrice
+    where
+        O: ControlVariate + ?Sized,
+        R: NormalSource + ?Sized,
+    {
+        let times = option.observation_times(self.steps);
+        let (payoffs, controls): (Vec<f64>, Vec<f64>) = self
+            .paths_at(&option.underlying(), &times, rng)
+            .iter()
+            .map(|path| option.payoffs(&times, path))
+            .unzip();
+        let (payoffs, controls) = (self.samples(payoffs), self.samples(controls));
+
+        let n = payoffs.len() as f64;
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / n;
+        let (payoff_mean, control_mean) = (mean(&payoffs), mean(&controls));
+        let (mut covariance, mut control_variance, mut payoff_variance) = (0.0, 0.0, 0.0);
+        for (y, x) in payoffs.iter().zip(&controls) {
+            let (dy, dx) = (y - payoff_mean, x - control_mean);
+            covariance += dx * dy;
+            control_variance += dx * dx;
+            payoff_variance += dy * dy;
+        }
+        let beta = if control_variance > 0.0 {
+            covariance / control_variance
+        } else {
+            0.0
+        };
+        let residual_variance = (payoff_variance - beta * covariance).max(0.0) / (n - 2.0);
+        ControlledPrice {
+            price: payoff_mean - beta * (control_mean - option.control_price(self.steps)),
+            std_error: (residual_variance / n).sqrt(),
+            plain_std_error: (payoff_variance / (n - 1.0) / n).sqrt(),
+            beta,
+        }
+    }
+
+    /// Price a European option, with antithetic pairs averaged into one sample for the error.
+    pub fn price<R: NormalSource + ?Sized>(&self, option: &OptionInputs, rng: &mut R) -> McPrice {
+        let discount = option.rate_discount();
+        let sign = option.sign();
+        let payoffs: Vec<f64> = self
+            .terminal_spots(option, rng)
+            .iter()
+            .map(|&s| discount * (sign * (s - option.k)).max(0.0))
+            .collect();
+        let (price, std_error) = mean_and_error(&self.samples(payoffs));
+        McPrice {
+            price,
+            std_error,
+            analytic: option.price(),
+        }
+    }
+}
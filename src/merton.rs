@@ -0,0 +1,161 @@
+//! The Merton (1976) jump-diffusion model.
+//!
+//! The spot diffuses with a constant vol and jumps at the times of a Poisson process, each jump
+//! multiplying it by a lognormal factor. Conditional on `n` jumps the terminal price is lognormal,
+//! so the option price is a Poisson-weighted sum of Black-76 prices, each on a forward shifted by
+//! the jumps and with the jump variance added to the diffusive variance. Jumps fatten the tails
+//! most at short expiries, where a single earnings or event move dominates the diffusion.
+
+use crate::black76::Black76Inputs;
+use crate::fourier::{CharacteristicFn, Complex64};
+use crate::OptionInputs;
+
+/// Largest number of jumps summed, far beyond any weight that matters for sensible intensities.
+const MAX_JUMPS: usize = 500;
+
+/// Parameters of the Merton jump-diffusion model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Merton {
+    /// Vol of the diffusion
+    pub vol: f64,
+
+    /// Jump intensity per year
+    pub lambda: f64,
+
+    /// Mean of the log jump size
+    pub mu_j: f64,
+
+    /// Standard deviation of the log jump size
+    pub sigma_j: f64,
+}
+
+impl Merton {
+    pub fn new(vol: f64, lambda: f64, mu_j: f64, sigma_j: f64) -> Self {
+        Self {
+            vol,
+            lambda,
+            mu_j,
+            sigma_j,
+        }
+    }
+
+    /// Expected relative jump size `E[J] - 1`.
+    pub fn mean_jump(&self) -> f64 {
+        (self.mu_j + 0.5 * self.sigma_j * self.sigma_j).exp() - 1.0
+    }
+}
+
+impl CharacteristicFn for Merton {
+    fn cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let i = Complex64::i();
+        let v = self.vol * self.vol * t;
+        let s2 = self.sigma_j * self.sigma_j;
+        // compensated so that the jumps leave the forward unchanged
+        let jumps = self.lambda
+            * t
+            * ((i * u * self.mu_j - 0.5 * s2 * u * u).exp() - 1.0 - i * u * self.mean_jump());
+        (-0.5 * v * (u * u + i * u) + jumps).exp()
+    }
+}
+
+/// A European option under Merton's model, priced as a Poisson mixture of Black-76 options.
+#[derive(Debug, Clone)]
+pub struct MertonOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub model: Merton,
+
+    /// Poisson weight of each jump count and the option conditional on it.
+    terms: Vec<(f64, Black76Inputs)>,
+}
+
+impl MertonOption {
+    /// Sum jump counts until the remaining Poisson weight is below `1e-15`.
+    pub fn new(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, model: Merton) -> Self {
+        let forward = s * ((r - q - model.lambda * model.mean_jump()) * t).exp();
+        let intensity = model.lambda * t;
+
+        let mut terms = Vec::new();
+        let mut weight = (-intensity).exp();
+        let mut total = 0.0;
+        for n in 0..MAX_JUMPS {
+            let jumps = n as f64;
+            let f = forward * (jumps * (model.mu_j + 0.5 * model.sigma_j.powi(2))).exp();
+            let vol = (model.vol.powi(2) + jumps * model.sigma_j.powi(2) / t).sqrt();
+            terms.push((
+                weight,
+                Black76Inputs::new(is_call, f, k, r, t).with_implied_vol(vol),
+            ));
+            total += weight;
+            if 1.0 - total < 1e-15 || intensity == 0.0 {
+                break;
+            }
+            weight *= intensity / (jumps + 1.0);
+        }
+
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            model,
+            terms,
+        }
+    }
+
+    fn sum(&self, f: impl Fn(&Black76Inputs) -> f64) -> f64 {
+        self.terms.iter().map(|(w, option)| w * f(option)).sum()
+    }
+
+    /// Number of jump counts summed.
+    pub fn terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn price(&self) -> f64 {
+        self.sum(Black76Inputs::price)
+    }
+
+    /// Each conditional forward is proportional to spot.
+    pub fn delta(&self) -> f64 {
+        self.sum(|o| o.delta() * o.f) / self.s
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.sum(|o| o.gamma() * o.f * o.f) / (self.s * self.s)
+    }
+
+    /// Sensitivity to a 0.01 change in the diffusive vol.
+    pub fn vega(&self) -> f64 {
+        self.sum(|o| o.vega() * self.model.vol / o.implied_vol())
+    }
+
+    /// BSM inputs carrying the Merton price, with the implied vol solved from it.
+    pub fn to_bsm(&self) -> OptionInputs {
+        OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t)
+            .with_price(self.price())
+    }
+
+    /// BSM implied vol of the Merton price, NaN if the price admits none.
+    pub fn implied_vol(&self) -> f64 {
+        self.to_bsm().implied_vol()
+    }
+}
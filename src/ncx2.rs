@@ -0,0 +1,107 @@
+//! The noncentral chi-square distribution.
+//!
+//! A noncentral chi-square variable with `k` degrees of freedom and noncentrality `lambda` is a
+//! Poisson mixture of central chi-squares with `k + 2j` degrees of freedom, the Poisson weights
+//! having mean `lambda / 2`. The sums start at the Poisson mode and run outwards until the
+//! weights are negligible, which keeps large noncentralities accurate.
+//!
+//! The CEV model prices in terms of the distribution function, and the terminal variance of the
+//! Heston model is a scaled noncentral chi-square, see [`crate::broadie_kaya`].
+
+use std::cmp::Ordering;
+
+use statrs::function::gamma::{gamma_lr, gamma_ur, ln_gamma};
+
+use crate::solve;
+
+/// Poisson weights below this are dropped from the mixture.
+const WEIGHT_CUTOFF: f64 = 1e-17;
+
+/// Sum `weight(j) * term(k + 2j)` over the Poisson mixture.
+fn mixture(k: f64, lambda: f64, term: impl Fn(f64) -> f64) -> f64 {
+    if lambda <= 0.0 {
+        return term(k);
+    }
+    let half = 0.5 * lambda;
+    let weight = |j: f64| (-half + j * half.ln() - ln_gamma(j + 1.0)).exp();
+    let mode = half.floor();
+
+    let mut sum = 0.0;
+    let mut j = mode;
+    loop {
+        let w = weight(j);
+        sum += w * term(k + 2.0 * j);
+        if j == 0.0 || w < WEIGHT_CUTOFF {
+            break;
+        }
+        j -= 1.0;
+    }
+    let mut j = mode + 1.0;
+    loop {
+        let w = weight(j);
+        sum += w * term(k + 2.0 * j);
+        if w < WEIGHT_CUTOFF {
+            break;
+        }
+        j += 1.0;
+    }
+    sum
+}
+
+/// `P(X <= x)` for `k` degrees of freedom and noncentrality `lambda`.
+pub fn cdf(x: f64, k: f64, lambda: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    mixture(k, lambda, |dof| gamma_lr(0.5 * dof, 0.5 * x))
+}
+
+/// `P(X > x)`, computed directly so that small upper tails keep their precision.
+pub fn sf(x: f64, k: f64, lambda: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    mixture(k, lambda, |dof| gamma_ur(0.5 * dof, 0.5 * x))
+}
+
+/// Density at `x`. At zero only central terms of two degrees of freedom or fewer contribute,
+/// so for `k = 2` the density there is `exp(-lambda / 2) / 2` and for `k < 2` it is infinite.
+pub fn pdf(x: f64, k: f64, lambda: f64) -> f64 {
+    if x < 0.0 {
+        return 0.0;
+    }
+    mixture(k, lambda, |dof| {
+        let half = 0.5 * dof;
+        if x == 0.0 {
+            return match half.total_cmp(&1.0) {
+                Ordering::Less => f64::INFINITY,
+                Ordering::Equal => 0.5,
+                Ordering::Greater => 0.0,
+            };
+        }
+        ((half - 1.0) * x.ln() - 0.5 * x - half * std::f64::consts::LN_2 - ln_gamma(half)).exp()
+    })
+}
+
+/// The `x` with `P(X <= x) = p`, solved on whichever tail is smaller for precision. NaN for `p`
+/// outside [0, 1).
+pub fn quantile(p: f64, k: f64, lambda: f64) -> f64 {
+    if !(0.0..1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return 0.0;
+    }
+    let (mean, sd) = (k + lambda, (2.0 * (k + 2.0 * lambda)).sqrt());
+    let mut hi = mean + 10.0 * sd;
+    while cdf(hi, k, lambda) < p {
+        hi *= 2.0;
+    }
+    let tail = 1.0 - p;
+    let root = if p <= 0.5 {
+        solve::brent(|x| cdf(x, k, lambda) - p, 0.0, hi, 1e-14 * hi)
+    } else {
+        solve::brent(|x| tail - sf(x, k, lambda), 0.0, hi, 1e-14 * hi)
+    };
+    root.unwrap_or(f64::NAN)
+}
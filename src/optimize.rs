@@ -0,0 +1,119 @@
+//! Derivative-free minimization for model calibration.
+
+/// The best point found by a minimizer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimum {
+    pub x: Vec<f64>,
+    pub value: f64,
+
+    /// Number of objective function evaluations
+    pub evaluations: usize,
+}
+
+/// Minimize `f` by the Nelder-Mead simplex method, starting from a simplex around `x0` with
+/// edges of length `step`. Stops when the objective values across the simplex differ by less
+/// than `tol` or after `max_evaluations` evaluations.
+pub fn nelder_mead(
+    f: impl Fn(&[f64]) -> f64,
+    x0: &[f64],
+    step: f64,
+    tol: f64,
+    max_evaluations: usize,
+) -> Minimum {
+    let n = x0.len();
+    let evaluations = std::cell::Cell::new(0);
+    let eval = |x: &[f64]| {
+        evaluations.set(evaluations.get() + 1);
+        let v = f(x);
+        if v.is_nan() {
+            f64::INFINITY
+        } else {
+            v
+        }
+    };
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+        .map(|i| {
+            let mut x = x0.to_vec();
+            if i > 0 {
+                x[i - 1] += step;
+            }
+            let v = eval(&x);
+            (x, v)
+        })
+        .collect();
+
+    // affine combination centroid + t * (point - centroid)
+    let towards = |centroid: &[f64], point: &[f64], t: f64| -> Vec<f64> {
+        centroid
+            .iter()
+            .zip(point)
+            .map(|(c, p)| c + t * (p - c))
+            .collect()
+    };
+
+    loop {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[n].1);
+        if (worst - best).abs() <= tol || evaluations.get() >= max_evaluations {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+
+        let reflected = towards(&centroid, &simplex[n].0, -1.0);
+        let fr = eval(&reflected);
+        if fr < best {
+            let expanded = towards(&centroid, &simplex[n].0, -2.0);
+            let fe = eval(&expanded);
+            simplex[n] = if fe < fr {
+                (expanded, fe)
+            } else {
+                (reflected, fr)
+            };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (reflected, fr);
+        } else {
+            // contract towards the better of the worst point and its reflection
+            let (from, f_from) = if fr < worst {
+                (&reflected, fr)
+            } else {
+                (&simplex[n].0, worst)
+            };
+            let contracted = towards(&centroid, from, 0.5);
+            let fc = eval(&contracted);
+            if fc < f_from {
+                simplex[n] = (contracted, fc);
+            } else {
+                let best_x = simplex[0].0.clone();
+                for point in simplex.iter_mut().skip(1) {
+                    let x = towards(&best_x, &point.0, 0.5);
+                    let v = eval(&x);
+                    *point = (x, v);
+                }
+            }
+        }
+    }
+
+    let (x, value) = simplex.swap_remove(0);
+    Minimum {
+        x,
+        value,
+        evaluations: evaluations.get(),
+    }
+}
+
+/// A model fitted to market prices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration<M> {
+    pub model: M,
+
+    /// Root mean square pricing error of the fit, each error divided by the quote's Black-Scholes
+    /// vega so that it is expressed in vol
+    pub rmse: f64,
+
+    /// Number of times the model priced the quotes
+    pub evaluations: usize,
+}
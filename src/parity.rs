@@ -0,0 +1,144 @@
+//! Carry implied by put-call parity.
+//!
+//! For European options `C - P = S e^(-qT) - K e^(-rT)`, so across the strikes of one expiry the
+//! call less put mid is linear in strike. Its slope gives the discount factor and so the
+//! financing rate, and the intercept over the slope gives the forward, from which the borrow
+//! cost (dividends plus stock loan fee) follows. A robust line is fitted per expiry, pairs whose
+//! residual from it is an outlier are dropped, and the rest are fitted by least squares.
+
+use crate::chain::{OptionChain, OptionQuote};
+use crate::OptionInputs;
+
+/// Carry implied by one expiry of a chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpliedCarry {
+    /// Spot price of the underlying
+    pub s: f64,
+
+    /// Time to expiry in years
+    pub t: f64,
+
+    pub forward: f64,
+    pub discount_factor: f64,
+
+    /// Continuously compounded financing rate
+    pub r: f64,
+
+    /// Continuously compounded borrow cost, in the role of the dividend yield
+    pub q: f64,
+
+    /// Call and put pairs used in the final fit
+    pub accepted: usize,
+
+    /// Pairs dropped as outliers
+    pub rejected: usize,
+}
+
+impl ImpliedCarry {
+    /// Inputs for another strike of this expiry priced with the implied carry.
+    pub fn option(&self, is_call: bool, k: f64) -> OptionInputs {
+        OptionInputs::new(is_call, self.s, k, self.r, self.q, self.t)
+    }
+}
+
+/// Least squares fit of `y = a + b x`, returning `(a, b)`.
+fn fit(points: &[(f64, f64)]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mx = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let my = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxy: f64 = points.iter().map(|p| (p.0 - mx) * (p.1 - my)).sum();
+    let sxx: f64 = points.iter().map(|p| (p.0 - mx).powi(2)).sum();
+    let b = sxy / sxx;
+    (my - b * mx, b)
+}
+
+/// Theil-Sen fit of `y = a + b x`: the median of the pairwise slopes, then the median
+/// intercept, which ignores up to about a quarter of the points being outliers.
+fn robust_fit(points: &[(f64, f64)]) -> (f64, f64) {
+    let mut slopes = Vec::new();
+    for (i, p) in points.iter().enumerate() {
+        for q in &points[i + 1..] {
+            slopes.push((q.1 - p.1) / (q.0 - p.0));
+        }
+    }
+    let b = median(slopes);
+    (median(points.iter().map(|p| p.1 - b * p.0).collect()), b)
+}
+
+fn median(mut xs: Vec<f64>) -> f64 {
+    xs.sort_by(f64::total_cmp);
+    let n = xs.len();
+    if n % 2 == 1 {
+        xs[n / 2]
+    } else {
+        0.5 * (xs[n / 2 - 1] + xs[n / 2])
+    }
+}
+
+impl OptionChain {
+    /// Implied carry for every expiry with at least three strikes quoted on both sides, in
+    /// expiry order. Pairs whose residual from the robust fit exceeds `threshold` scaled median
+    /// absolute deviations are dropped before the final fit.
+    pub fn implied_carry(&self, threshold: f64) -> Vec<ImpliedCarry> {
+        let mut expiries: Vec<f64> = self.quotes.iter().map(|q| q.t).collect();
+        expiries.sort_by(f64::total_cmp);
+        expiries.dedup();
+
+        expiries
+            .into_iter()
+            .filter_map(|t| self.implied_carry_at(t, threshold))
+            .collect()
+    }
+
+    fn implied_carry_at(&self, t: f64, threshold: f64) -> Option<ImpliedCarry> {
+        let at = |is_call: bool, k: f64| -> Option<&OptionQuote> {
+            self.quotes
+                .iter()
+                .find(|q| q.t == t && q.k == k && q.is_call == is_call)
+        };
+
+        let mut strikes: Vec<f64> = self
+            .quotes
+            .iter()
+            .filter(|q| q.t == t && q.is_call)
+            .map(|q| q.k)
+            .collect();
+        strikes.sort_by(f64::total_cmp);
+        strikes.dedup();
+
+        let points: Vec<(f64, f64)> = strikes
+            .iter()
+            .filter_map(|&k| Some((k, at(true, k)?.mid() - at(false, k)?.mid())))
+            .collect();
+        if points.len() < 3 {
+            return None;
+        }
+
+        let (a, b) = robust_fit(&points);
+        let residuals: Vec<f64> = points.iter().map(|p| (p.1 - a - b * p.0).abs()).collect();
+        // 1.4826 scales the MAD to a standard deviation for normal data; the floor keeps an
+        // exact fit from rejecting rounding noise
+        let scale = (1.4826 * median(residuals.clone())).max(1e-9 * self.s);
+        let kept: Vec<(f64, f64)> = points
+            .iter()
+            .zip(&residuals)
+            .filter(|(_, &e)| e <= threshold * scale)
+            .map(|(&p, _)| p)
+            .collect();
+        let (a, b) = if kept.len() >= 2 { fit(&kept) } else { (a, b) };
+
+        let discount_factor = -b;
+        let forward = a / discount_factor;
+        let r = -discount_factor.ln() / t;
+        Some(ImpliedCarry {
+            s: self.s,
+            t,
+            forward,
+            discount_factor,
+            r,
+            q: r - (forward / self.s).ln() / t,
+            accepted: kept.len(),
+            rejected: points.len() - kept.len(),
+        })
+    }
+}
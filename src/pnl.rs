@@ -0,0 +1,131 @@
+//! P&L explain along a path of market states.
+//!
+//! Each step of the path is repriced in full and the change in value is attributed to the Greeks
+//! at the start of the step: delta and gamma for the spot move, vega and volga for the vol move,
+//! vanna for their cross term, and theta for the passage of time. Anything the Taylor expansion
+//! misses, including rate carry and higher order terms, is left in the residual.
+
+use std::ops::AddAssign;
+
+use crate::{OptionInputs, DAYS_PER_YEAR};
+
+/// Market state at one point of a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathPoint {
+    /// Spot price of the underlying
+    pub s: f64,
+
+    /// Implied vol of the option
+    pub vol: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+}
+
+impl PathPoint {
+    pub fn new(s: f64, vol: f64, t: f64) -> Self {
+        Self { s, vol, t }
+    }
+}
+
+/// Attribution of the P&L of one step, in position units.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PnlExplain {
+    /// Change in value from full repricing
+    pub actual: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub volga: f64,
+    pub vanna: f64,
+    pub theta: f64,
+
+    /// Actual P&L not explained by the Greek terms
+    pub residual: f64,
+}
+
+impl PnlExplain {
+    pub fn explained(&self) -> f64 {
+        self.delta + self.gamma + self.vega + self.volga + self.vanna + self.theta
+    }
+}
+
+impl AddAssign for PnlExplain {
+    fn add_assign(&mut self, other: Self) {
+        self.actual += other.actual;
+        self.delta += other.delta;
+        self.gamma += other.gamma;
+        self.vega += other.vega;
+        self.volga += other.volga;
+        self.vanna += other.vanna;
+        self.theta += other.theta;
+        self.residual += other.residual;
+    }
+}
+
+fn value(option: &OptionInputs, point: &PathPoint) -> f64 {
+    if point.t <= 0.0 {
+        return (option.sign() * (point.s - option.k)).max(0.0);
+    }
+    OptionInputs::new(
+        option.is_call,
+        point.s,
+        option.k,
+        option.r,
+        option.q,
+        point.t,
+    )
+    .with_implied_vol(point.vol)
+    .price()
+}
+
+/// Explain the P&L of `quantity` units of the option, whose strike, type, and rates are taken
+/// from `option`, over each step of `path`. Returns one entry per step.
+pub fn explain(option: &OptionInputs, quantity: f64, path: &[PathPoint]) -> Vec<PnlExplain> {
+    path.windows(2)
+        .map(|step| {
+            let (p0, p1) = (&step[0], &step[1]);
+            let start = OptionInputs::new(option.is_call, p0.s, option.k, option.r, option.q, p0.t)
+                .with_implied_vol(p0.vol);
+
+            let ds = p1.s - p0.s;
+            let dvol = p1.vol - p0.vol;
+            let days = (p0.t - p1.t) * DAYS_PER_YEAR;
+
+            // vega, vanna, and vomma are scaled per vol point
+            let mut e = PnlExplain {
+                actual: value(option, p1) - start.price(),
+                delta: start.delta() * ds,
+                gamma: 0.5 * start.gamma() * ds * ds,
+                vega: 100.0 * start.vega() * dvol,
+                volga: 50.0 * start.vomma() * dvol * dvol,
+                vanna: 100.0 * start.vanna() * ds * dvol,
+                theta: start.theta() * days,
+                residual: 0.0,
+            };
+
+            for x in [
+                &mut e.actual,
+                &mut e.delta,
+                &mut e.gamma,
+                &mut e.vega,
+                &mut e.volga,
+                &mut e.vanna,
+                &mut e.theta,
+            ] {
+                *x *= quantity;
+            }
+            e.residual = e.actual - e.explained();
+            e
+        })
+        .collect()
+}
+
+/// Sum an explain series into a single attribution.
+pub fn total(steps: &[PnlExplain]) -> PnlExplain {
+    let mut sum = PnlExplain::default();
+    for &step in steps {
+        sum += step;
+    }
+    sum
+}
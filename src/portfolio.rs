@@ -0,0 +1,110 @@
+//! Collections of option positions that can be valued and risked together.
+
+use crate::OptionInputs;
+
+/// A signed holding of a single option contract.
+#[derive(Debug, Clone)]
+pub struct Position {
+    /// Identifier used when reporting, e.g. the contract symbol
+    pub label: String,
+
+    /// The option, which must have an implied vol set before it can be valued
+    pub option: OptionInputs,
+
+    /// Number of contracts, negative for short positions
+    pub quantity: f64,
+
+    /// Units of the underlying per contract
+    pub multiplier: f64,
+}
+
+impl Position {
+    pub fn new(label: impl Into<String>, option: OptionInputs, quantity: f64) -> Self {
+        Self {
+            label: label.into(),
+            option,
+            quantity,
+            multiplier: 1.0,
+        }
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Units of the option held, i.e. quantity times multiplier.
+    #[inline(always)]
+    pub fn units(&self) -> f64 {
+        self.quantity * self.multiplier
+    }
+
+    pub fn value(&self) -> f64 {
+        self.units() * self.option.price()
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.units() * self.option.delta()
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.units() * self.option.gamma()
+    }
+
+    pub fn vega(&self) -> f64 {
+        self.units() * self.option.vega()
+    }
+
+    pub fn theta(&self) -> f64 {
+        self.units() * self.option.theta()
+    }
+}
+
+/// A set of positions.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    pub positions: Vec<Position>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.positions.push(position);
+        self
+    }
+
+    pub fn push(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn value(&self) -> f64 {
+        self.positions.iter().map(Position::value).sum()
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.positions.iter().map(Position::delta).sum()
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.positions.iter().map(Position::gamma).sum()
+    }
+
+    pub fn vega(&self) -> f64 {
+        self.positions.iter().map(Position::vega).sum()
+    }
+
+    pub fn theta(&self) -> f64 {
+        self.positions.iter().map(Position::theta).sum()
+    }
+}
@@ -0,0 +1,129 @@
+//! Power options, which pay on a power of the underlying at expiry.
+//!
+//! A power call pays `max(S^n - K, 0)` and a power put `max(K - S^n, 0)`. Under BSM `S^n` is
+//! itself lognormal, with vol `|n| vol` and a drift that acts as a dividend yield of
+//! `r - n (r - q) - n (n - 1) vol^2 / 2`, so a power option is a vanilla option on `S^n` and its
+//! price and Greeks come from the same `d1` and `d2`. A capped power option, as sold in retail
+//! notes to bound the leverage, pays at most the cap and is a spread of two power options.
+
+use crate::OptionInputs;
+
+/// The inputs to a power option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerOption {
+    /// The type of the option (call or put)
+    pub is_call: bool,
+
+    /// Stock price
+    pub s: f64,
+
+    /// Strike price, on the scale of `s^power`
+    pub k: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    /// The power the underlying is raised to
+    pub power: f64,
+
+    /// Implied vol of the underlying
+    pub implied_vol: f64,
+
+    /// The most the option pays, infinite when uncapped
+    pub cap: f64,
+}
+
+impl PowerOption {
+    pub fn new(is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64, power: f64) -> Self {
+        Self {
+            is_call,
+            s,
+            k,
+            r,
+            q,
+            t,
+            power,
+            implied_vol: f64::NAN,
+            cap: f64::INFINITY,
+        }
+    }
+
+    pub fn with_implied_vol(mut self, implied_vol: f64) -> Self {
+        self.implied_vol = implied_vol;
+        self
+    }
+
+    pub fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// The dividend yield that gives `s^power` its drift.
+    fn yield_of_power(&self) -> f64 {
+        let (n, vol) = (self.power, self.implied_vol);
+        self.r - n * (self.r - self.q) - 0.5 * n * (n - 1.0) * vol * vol
+    }
+
+    /// The vanilla option on `s^power` struck at `k`.
+    fn on_power(&self, k: f64) -> OptionInputs {
+        OptionInputs::new(
+            self.is_call,
+            self.s.powf(self.power),
+            k,
+            self.r,
+            self.yield_of_power(),
+            self.t,
+        )
+        .with_implied_vol(self.power.abs() * self.implied_vol)
+    }
+
+    /// The uncapped option and, with a cap, the option it is sold against: struck the cap
+    /// further out of the money, or for a put nothing if that strike is not positive.
+    fn legs(&self) -> (OptionInputs, Option<OptionInputs>) {
+        let bought = self.on_power(self.k);
+        if self.cap.is_infinite() {
+            return (bought, None);
+        }
+        let strike = self.k + bought.sign() * self.cap;
+        (bought, (strike > 0.0).then(|| self.on_power(strike)))
+    }
+
+    /// Combine a Greek of the two legs.
+    fn spread(&self, greek: impl Fn(&OptionInputs) -> f64) -> f64 {
+        let (bought, sold) = self.legs();
+        greek(&bought) - sold.map_or(0.0, |sold| greek(&sold))
+    }
+
+    pub fn price(&self) -> f64 {
+        self.spread(OptionInputs::price)
+    }
+
+    pub fn delta(&self) -> f64 {
+        let n = self.power;
+        n * self.s.powf(n - 1.0) * self.spread(OptionInputs::delta)
+    }
+
+    pub fn gamma(&self) -> f64 {
+        let n = self.power;
+        let (delta, gamma) = (
+            self.spread(OptionInputs::delta),
+            self.spread(OptionInputs::gamma),
+        );
+        n * (n - 1.0) * self.s.powf(n - 2.0) * delta + (n * self.s.powf(n - 1.0)).powi(2) * gamma
+    }
+
+    /// Sensitivity to the underlying's vol per 0.01 change, through the vol of `s^power` and
+    /// the convexity in its drift.
+    pub fn vega(&self) -> f64 {
+        let n = self.power;
+        let drift = -n * (n - 1.0) * self.implied_vol;
+        n.abs() * self.spread(OptionInputs::vega)
+            + 0.01 * drift * self.spread(OptionInputs::epsilon)
+    }
+}
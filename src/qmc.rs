@@ -0,0 +1,125 @@
+//! Quasi-random sampling by Sobol sequences.
+//!
+//! A low-discrepancy sequence fills the unit cube far more evenly than pseudo-random points, so
+//! Monte Carlo estimates of smooth payoffs and their Greeks converge at close to `1 / n` rather
+//! than `1 / sqrt(n)`. [`Sobol`] generates points in up to [`MAX_DIMENSION`] dimensions from the
+//! Joe and Kuo (2008) direction numbers, in Gray code order, and maps them to standard normals
+//! through the inverse normal CDF as a [`NormalSource`] for the MC engine.
+
+use crate::calculate_inv_ncdf;
+use crate::mc::NormalSource;
+
+/// Degree, coefficients, and initial direction numbers of the primitive polynomial for each
+/// dimension after the first, from Joe and Kuo's `new-joe-kuo-6.21201`.
+const DIRECTIONS: [(u32, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// Largest dimension [`Sobol`] supports.
+pub const MAX_DIMENSION: usize = DIRECTIONS.len() + 1;
+
+const BITS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sobol {
+    /// Direction numbers indexed by dimension then bit
+    directions: Vec<[u32; BITS]>,
+
+    /// Current point as fixed-point fractions
+    point: Vec<u32>,
+
+    /// Points generated so far
+    index: u32,
+}
+
+impl Sobol {
+    /// A sequence in `dimension` dimensions.
+    ///
+    /// # Panics
+    ///
+    /// If `dimension` is zero or above [`MAX_DIMENSION`].
+    pub fn new(dimension: usize) -> Self {
+        assert!(
+            (1..=MAX_DIMENSION).contains(&dimension),
+            "Sobol sequences are available in 1 to {MAX_DIMENSION} dimensions"
+        );
+        let mut directions = Vec::with_capacity(dimension);
+        // the first dimension is the van der Corput sequence in base 2
+        directions.push(std::array::from_fn(|i| 1 << (BITS - 1 - i)));
+        for &(degree, coefficients, initial) in &DIRECTIONS[..dimension - 1] {
+            let s = degree as usize;
+            let mut v = [0u32; BITS];
+            for i in 0..BITS {
+                v[i] = if i < s {
+                    initial[i] << (BITS - 1 - i)
+                } else {
+                    let mut x = v[i - s] ^ (v[i - s] >> s);
+                    for k in 1..s {
+                        if (coefficients >> (s - 1 - k)) & 1 == 1 {
+                            x ^= v[i - k];
+                        }
+                    }
+                    x
+                };
+            }
+            directions.push(v);
+        }
+        Self {
+            directions,
+            point: vec![0; dimension],
+            index: 0,
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.point.len()
+    }
+
+    /// The next point in the open unit cube, skipping the origin.
+    pub fn next_point(&mut self) -> Vec<f64> {
+        // Gray code order changes one bit per point: the lowest zero bit of the index
+        let bit = self.index.trailing_ones() as usize;
+        for (x, v) in self.point.iter_mut().zip(&self.directions) {
+            *x ^= v[bit];
+        }
+        self.index += 1;
+        self.point
+            .iter()
+            .map(|&x| x as f64 / (1u64 << BITS) as f64)
+            .collect()
+    }
+}
+
+impl NormalSource for Sobol {
+    /// # Panics
+    ///
+    /// If more draws are asked for than the sequence has dimensions.
+    fn fill(&mut self, draws: &mut [f64]) {
+        assert!(
+            draws.len() <= self.dimension(),
+            "a path needs one Sobol dimension per draw"
+        );
+        for (z, u) in draws.iter_mut().zip(self.next_point()) {
+            *z = calculate_inv_ncdf(u);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Numerical integration.
+//!
+//! Gaussian rules for integrals against the standard weights, and adaptive Simpson for
+//! everything else. The semi-analytic Heston integrals and the Turnbull-Wakeman moments of
+//! continuous Asian averages use the Gauss-Legendre rule.
+
+use crate::PI;
+
+/// Nodes and weights of a Gaussian quadrature rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub nodes: Vec<f64>,
+    pub weights: Vec<f64>,
+}
+
+impl Rule {
+    /// Weighted sum of `f` at the nodes, approximating the integral of `f` against the rule's
+    /// weight function.
+    pub fn integrate(&self, f: impl Fn(f64) -> f64) -> f64 {
+        self.nodes
+            .iter()
+            .zip(&self.weights)
+            .map(|(&x, &w)| w * f(x))
+            .sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+const EPS: f64 = 1e-14;
+const MAX_ITER: usize = 100;
+
+/// `n` point Gauss-Legendre rule on [-1, 1].
+pub fn gauss_legendre(n: usize) -> Rule {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    for i in 0..n.div_ceil(2) {
+        let mut z = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut pp = 0.0;
+        for _ in 0..MAX_ITER {
+            let (mut p1, mut p2) = (1.0, 0.0);
+            for j in 1..=n {
+                let p3 = p2;
+                p2 = p1;
+                p1 = ((2.0 * j as f64 - 1.0) * z * p2 - (j as f64 - 1.0) * p3) / j as f64;
+            }
+            pp = n as f64 * (z * p1 - p2) / (z * z - 1.0);
+            let z1 = z;
+            z = z1 - p1 / pp;
+            if (z - z1).abs() <= EPS {
+                break;
+            }
+        }
+        nodes[i] = -z;
+        nodes[n - 1 - i] = z;
+        weights[i] = 2.0 / ((1.0 - z * z) * pp * pp);
+        weights[n - 1 - i] = weights[i];
+    }
+    Rule { nodes, weights }
+}
+
+/// Integral of `f` over [`a`, `b`] by an `n` point Gauss-Legendre rule.
+pub fn integrate_legendre(f: impl Fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    let (mid, half) = (0.5 * (a + b), 0.5 * (b - a));
+    half * gauss_legendre(n).integrate(|x| f(mid + half * x))
+}
+
+/// `n` point Gauss-Hermite rule for integrals against `e^(-x^2)` over the real line.
+pub fn gauss_hermite(n: usize) -> Rule {
+    // pi^(-1/4)
+    const PIM4: f64 = 0.751_125_544_464_942_5;
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    let nf = n as f64;
+    let mut z = 0.0;
+    for i in 0..n.div_ceil(2) {
+        // initial guesses for the largest roots, then extrapolation from the previous two
+        z = match i {
+            0 => (2.0 * nf + 1.0).sqrt() - 1.85575 * (2.0 * nf + 1.0).powf(-0.16667),
+            1 => z - 1.14 * nf.powf(0.426) / z,
+            2 => 1.86 * z - 0.86 * nodes[0],
+            3 => 1.91 * z - 0.91 * nodes[1],
+            _ => 2.0 * z - nodes[i - 2],
+        };
+        let mut pp = 0.0;
+        for _ in 0..MAX_ITER {
+            let (mut p1, mut p2) = (PIM4, 0.0);
+            for j in 1..=n {
+                let p3 = p2;
+                p2 = p1;
+                let jf = j as f64;
+                p1 = z * (2.0 / jf).sqrt() * p2 - ((jf - 1.0) / jf).sqrt() * p3;
+            }
+            pp = (2.0 * nf).sqrt() * p2;
+            let z1 = z;
+            z = z1 - p1 / pp;
+            if (z - z1).abs() <= EPS {
+                break;
+            }
+        }
+        nodes[i] = z;
+        nodes[n - 1 - i] = -z;
+        weights[i] = 2.0 / (pp * pp);
+        weights[n - 1 - i] = weights[i];
+    }
+    nodes.reverse();
+    weights.reverse();
+    Rule { nodes, weights }
+}
+
+/// Expectation of `f(Z)` for a standard normal `Z` by an `n` point Gauss-Hermite rule.
+pub fn normal_expectation(f: impl Fn(f64) -> f64, n: usize) -> f64 {
+    let sqrt2 = std::f64::consts::SQRT_2;
+    gauss_hermite(n).integrate(|x| f(sqrt2 * x)) / PI.sqrt()
+}
+
+/// `n` point Gauss-Laguerre rule for integrals against `e^(-x)` over [0, infinity).
+pub fn gauss_laguerre(n: usize) -> Rule {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    let nf = n as f64;
+    let mut z = 0.0;
+    for i in 0..n {
+        z = match i {
+            0 => 3.0 / (1.0 + 2.4 * nf),
+            1 => z + 15.0 / (1.0 + 2.5 * nf),
+            _ => {
+                let ai = i as f64 - 1.0;
+                z + (1.0 + 2.55 * ai) / (1.9 * ai) * (z - nodes[i - 2])
+            }
+        };
+        let (mut pp, mut p2) = (0.0, 0.0);
+        for _ in 0..MAX_ITER {
+            let mut p1 = 1.0;
+            p2 = 0.0;
+            for j in 1..=n {
+                let p3 = p2;
+                p2 = p1;
+                let jf = j as f64;
+                p1 = ((2.0 * jf - 1.0 - z) * p2 - (jf - 1.0) * p3) / jf;
+            }
+            pp = (nf * p1 - nf * p2) / z;
+            let z1 = z;
+            z = z1 - p1 / pp;
+            if (z - z1).abs() <= EPS * z.abs().max(1.0) {
+                break;
+            }
+        }
+        nodes[i] = z;
+        weights[i] = -1.0 / (pp * nf * p2);
+    }
+    Rule { nodes, weights }
+}
+
+fn simpson(f: &impl Fn(f64) -> f64, a: f64, fa: f64, b: f64, fb: f64) -> (f64, f64, f64) {
+    let m = 0.5 * (a + b);
+    let fm = f(m);
+    (m, fm, (b - a) / 6.0 * (fa + 4.0 * fm + fb))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn simpson_step(
+    f: &impl Fn(f64) -> f64,
+    a: f64,
+    fa: f64,
+    b: f64,
+    fb: f64,
+    m: f64,
+    fm: f64,
+    whole: f64,
+    tol: f64,
+    depth: usize,
+) -> f64 {
+    let (lm, flm, left) = simpson(f, a, fa, m, fm);
+    let (rm, frm, right) = simpson(f, m, fm, b, fb);
+    let delta = left + right - whole;
+    if depth == 0 || delta.abs() <= 15.0 * tol {
+        // Richardson extrapolation of the two estimates
+        return left + right + delta / 15.0;
+    }
+    simpson_step(f, a, fa, m, fm, lm, flm, left, 0.5 * tol, depth - 1)
+        + simpson_step(f, m, fm, b, fb, rm, frm, right, 0.5 * tol, depth - 1)
+}
+
+/// Integral of `f` over [`a`, `b`] by adaptive Simpson's rule to an absolute tolerance `tol`,
+/// subdividing at most 50 times.
+pub fn adaptive_simpson(f: impl Fn(f64) -> f64, a: f64, b: f64, tol: f64) -> f64 {
+    let (fa, fb) = (f(a), f(b));
+    let (m, fm, whole) = simpson(&f, a, fa, b, fb);
+    simpson_step(&f, a, fa, b, fb, m, fm, whole, tol, 50)
+}
@@ -0,0 +1,106 @@
+//! Turning theoretical values into quotable markets.
+//!
+//! A [`Quoter`] widens a theoretical value by a target edge, expressed either in price or in vol,
+//! and rounds the result outwards to the exchange tick grid, so the edge actually quoted is never
+//! less than the target.
+
+use crate::tick::TickTable;
+use crate::OptionInputs;
+
+/// How far from the theoretical value to quote.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edge {
+    /// A fixed amount of price either side
+    Price(f64),
+
+    /// Vol either side, e.g. 0.01 to quote one vol point below and above the theoretical vol
+    Vol(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quoter {
+    pub edge: Edge,
+    pub ticks: TickTable,
+}
+
+/// A two-sided market with the implied vols it represents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Market {
+    pub theo: f64,
+    pub bid: f64,
+    pub ask: f64,
+
+    /// Implied vols of the bid and ask, NaN where the price admits none
+    pub bid_vol: f64,
+    pub ask_vol: f64,
+}
+
+impl Market {
+    /// Theoretical value less the bid.
+    pub fn bid_edge(&self) -> f64 {
+        self.theo - self.bid
+    }
+
+    /// Ask less the theoretical value.
+    pub fn ask_edge(&self) -> f64 {
+        self.ask - self.theo
+    }
+}
+
+fn implied_vol(option: &OptionInputs, price: f64) -> f64 {
+    if price.is_nan() || price <= 0.0 {
+        return f64::NAN;
+    }
+    OptionInputs::new(
+        option.is_call,
+        option.s,
+        option.k,
+        option.r,
+        option.q,
+        option.t,
+    )
+    .with_price(price)
+    .implied_vol()
+}
+
+impl Quoter {
+    pub fn new(edge: Edge, ticks: TickTable) -> Self {
+        Self { edge, ticks }
+    }
+
+    /// Quote `option`, which must have its theoretical vol set. The bid never goes below zero.
+    pub fn quote(&self, option: &OptionInputs) -> Market {
+        let theo = option.price();
+        let (raw_bid, raw_ask) = match self.edge {
+            Edge::Price(edge) => (theo - edge, theo + edge),
+            Edge::Vol(edge) => {
+                let at = |vol: f64| {
+                    OptionInputs::new(
+                        option.is_call,
+                        option.s,
+                        option.k,
+                        option.r,
+                        option.q,
+                        option.t,
+                    )
+                    .with_implied_vol(vol.max(1e-6))
+                    .price()
+                };
+                (
+                    at(option.implied_vol() - edge),
+                    at(option.implied_vol() + edge),
+                )
+            }
+        };
+
+        let bid = self.ticks.bid(raw_bid.max(0.0));
+        let ask = self.ticks.ask(raw_ask);
+        Market {
+            theo,
+            bid,
+            ask,
+            bid_vol: implied_vol(option, bid),
+            ask_vol: implied_vol(option, ask),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Path probabilities of drifted Brownian motion by the reflection principle.
+//!
+//! For `X_t = drift t + vol W_t` started at zero, reflecting the paths after they first touch a
+//! level gives the joint distribution of `X_t` and its running maximum or minimum in closed form,
+//! with the drift entering through the Girsanov factor `exp(2 drift level / vol^2)`. These are
+//! the building blocks of continuously monitored barrier, lookback, and touch products: the log
+//! spot under BSM is such a motion, see [`BrownianMotion::log_spot`], so the probability of the
+//! spot touching a barrier `H` from `S` is the motion's probability of touching `ln(H / S)`.
+//!
+//! [`BrownianMotion::bridge_hit_probability`] is the same principle for a path pinned at both
+//! ends, the correction Monte Carlo simulations apply between their time steps so that barriers
+//! are monitored continuously rather than only at the steps.
+
+use crate::{calculate_ncdf, calculate_npdf};
+
+/// A Brownian motion with constant drift and vol, started at zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrownianMotion {
+    /// Drift per year
+    pub drift: f64,
+
+    /// Vol per square root of a year
+    pub vol: f64,
+}
+
+impl BrownianMotion {
+    pub fn new(drift: f64, vol: f64) -> Self {
+        Self { drift, vol }
+    }
+
+    /// The log of the spot relative to today under BSM with rate `r`, dividend yield `q`, and
+    /// vol `vol`.
+    pub fn log_spot(r: f64, q: f64, vol: f64) -> Self {
+        Self::new(r - q - 0.5 * vol * vol, vol)
+    }
+
+    /// `X_t` standardized: `(x - drift t) / (vol sqrt(t))`.
+    fn standardize(&self, x: f64, t: f64) -> f64 {
+        (x - self.drift * t) / (self.vol * t.sqrt())
+    }
+
+    /// The Girsanov factor `exp(2 drift level / vol^2)` weighting paths reflected in `level`.
+    fn reflection_weight(&self, level: f64) -> f64 {
+        (2.0 * self.drift * level / (self.vol * self.vol)).exp()
+    }
+
+    /// `P(X_t <= x, max X <= m)` over [0, `t`], for a level `m >= 0`. Zero for negative `m`.
+    pub fn joint_max_cdf(&self, x: f64, m: f64, t: f64) -> f64 {
+        if m < 0.0 {
+            return 0.0;
+        }
+        let x = x.min(m);
+        calculate_ncdf(self.standardize(x, t))
+            - self.reflection_weight(m) * calculate_ncdf(self.standardize(x - 2.0 * m, t))
+    }
+
+    /// `P(X_t >= x, min X >= m)` over [0, `t`], for a level `m <= 0`. Zero for positive `m`.
+    pub fn joint_min_sf(&self, x: f64, m: f64, t: f64) -> f64 {
+        if m > 0.0 {
+            return 0.0;
+        }
+        let x = x.max(m);
+        calculate_ncdf(-self.standardize(x, t))
+            - self.reflection_weight(m) * calculate_ncdf(-self.standardize(x - 2.0 * m, t))
+    }
+
+    /// Joint density of `X_t` at `x` and its maximum over [0, `t`] at `m`, zero unless
+    /// `x <= m` and `m >= 0`.
+    pub fn joint_max_pdf(&self, x: f64, m: f64, t: f64) -> f64 {
+        if m < 0.0 || x > m {
+            return 0.0;
+        }
+        let variance = self.vol * self.vol * t;
+        let y = 2.0 * m - x;
+        let density = calculate_npdf(y / variance.sqrt());
+        2.0 * y / (variance * variance.sqrt())
+            * density
+            * (self.drift * x / (self.vol * self.vol)
+                - 0.5 * self.drift * self.drift * t / (self.vol * self.vol))
+                .exp()
+    }
+
+    /// `P(max X <= m)` over [0, `t`].
+    pub fn max_cdf(&self, m: f64, t: f64) -> f64 {
+        self.joint_max_cdf(m, m, t)
+    }
+
+    /// `P(min X <= m)` over [0, `t`].
+    pub fn min_cdf(&self, m: f64, t: f64) -> f64 {
+        1.0 - self.joint_min_sf(m, m, t)
+    }
+
+    /// Probability of touching `level`, above or below the start, by `t`.
+    pub fn hit_probability(&self, level: f64, t: f64) -> f64 {
+        if level >= 0.0 {
+            1.0 - self.max_cdf(level, t)
+        } else {
+            self.min_cdf(level, t)
+        }
+    }
+
+    /// Density of the first time the motion touches `level` at `t`, the inverse Gaussian.
+    pub fn first_passage_pdf(&self, level: f64, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        let sd = self.vol * t.sqrt();
+        level.abs() / (t * sd) * calculate_npdf((level - self.drift * t) / sd)
+    }
+
+    /// Probability that the motion, known to be at `from` and then at `to` a time `dt` later,
+    /// touched `level` in between. The drift does not matter once both ends are known.
+    pub fn bridge_hit_probability(&self, from: f64, to: f64, level: f64, dt: f64) -> f64 {
+        let (a, b) = (level - from, level - to);
+        if a * b <= 0.0 {
+            return 1.0;
+        }
+        (-2.0 * a * b / (self.vol * self.vol * dt)).exp()
+    }
+}
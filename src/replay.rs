@@ -0,0 +1,256 @@
+//! Recording and replay of pricing calls.
+//!
+//! A [`Recorder`] evaluates calls on [`OptionInputs`] and keeps each call's inputs and output.
+//! The log can be written in a compact binary form, read back elsewhere, and [`replay`]ed against
+//! the current code to find calls whose results have changed, whether to chase a discrepancy
+//! seen in production or to regression-test a model change.
+//!
+//! # Log layout
+//!
+//! All values are little-endian so logs can move between machines.
+//!
+//! | offset | size      | contents                                   |
+//! |--------|-----------|--------------------------------------------|
+//! | 0      | 8         | magic bytes `BSREC001`                     |
+//! | 8      | 8         | record count `n` as `u64`                  |
+//! | 16     | `57 * n`  | `n` [`Record`]s                            |
+//!
+//! Each record is one byte holding the [`Call`] code in its upper bits and 1 for calls or 0 for
+//! puts in its lowest bit, followed by `s`, `k`, `r`, `q`, `t`, the input, and the output as
+//! `f64`.
+
+use std::io::{self, Read, Write};
+
+use crate::OptionInputs;
+
+pub const LOG_MAGIC: [u8; 8] = *b"BSREC001";
+const RECORD_LEN: usize = 1 + 7 * 8;
+
+/// A recorded pricing call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    Price,
+
+    /// Implied vol from a price
+    ImpliedVol,
+    Delta,
+    Gamma,
+    Theta,
+    Vega,
+    Rho,
+    Vanna,
+    Vomma,
+    Charm,
+}
+
+impl Call {
+    const ALL: [Self; 10] = [
+        Self::Price,
+        Self::ImpliedVol,
+        Self::Delta,
+        Self::Gamma,
+        Self::Theta,
+        Self::Vega,
+        Self::Rho,
+        Self::Vanna,
+        Self::Vomma,
+        Self::Charm,
+    ];
+
+    fn code(self) -> u8 {
+        Self::ALL.iter().position(|&c| c == self).unwrap() as u8
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.get(code as usize).copied()
+    }
+}
+
+/// Inputs and output of one call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Record {
+    pub call: Call,
+    pub is_call: bool,
+    pub s: f64,
+    pub k: f64,
+    pub r: f64,
+    pub q: f64,
+    pub t: f64,
+
+    /// The price for [`Call::ImpliedVol`], otherwise the implied vol
+    pub input: f64,
+
+    pub output: f64,
+}
+
+impl Record {
+    /// Evaluate the call on the current code.
+    pub fn evaluate(&self) -> f64 {
+        let option = OptionInputs::new(self.is_call, self.s, self.k, self.r, self.q, self.t);
+        let greek: fn(&OptionInputs) -> f64 = match self.call {
+            Call::ImpliedVol => return option.with_price(self.input).implied_vol(),
+            Call::Price => OptionInputs::price,
+            Call::Delta => OptionInputs::delta,
+            Call::Gamma => OptionInputs::gamma,
+            Call::Theta => OptionInputs::theta,
+            Call::Vega => OptionInputs::vega,
+            Call::Rho => OptionInputs::rho,
+            Call::Vanna => OptionInputs::vanna,
+            Call::Vomma => OptionInputs::vomma,
+            Call::Charm => OptionInputs::charm,
+        };
+        greek(&option.with_implied_vol(self.input))
+    }
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&[self.call.code() << 1 | u8::from(self.is_call)])?;
+        for x in [
+            self.s,
+            self.k,
+            self.r,
+            self.q,
+            self.t,
+            self.input,
+            self.output,
+        ] {
+            w.write_all(&x.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let call = Call::from_code(bytes[0] >> 1).ok_or_else(|| invalid("unknown call code"))?;
+        let mut fields = bytes[1..]
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()));
+        let mut next = || fields.next().unwrap();
+        Ok(Self {
+            call,
+            is_call: bytes[0] & 1 == 1,
+            s: next(),
+            k: next(),
+            r: next(),
+            q: next(),
+            t: next(),
+            input: next(),
+            output: next(),
+        })
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Evaluates pricing calls and records them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorder {
+    pub records: Vec<Record>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate `call` on `option` and record it. `option` must have its implied vol set, or
+    /// for [`Call::ImpliedVol`] its price.
+    pub fn evaluate(&mut self, option: &OptionInputs, call: Call) -> f64 {
+        let input = if call == Call::ImpliedVol {
+            option.price()
+        } else {
+            option.implied_vol()
+        };
+        let mut record = Record {
+            call,
+            is_call: option.is_call,
+            s: option.s,
+            k: option.k,
+            r: option.r,
+            q: option.q,
+            t: option.t,
+            input,
+            output: f64::NAN,
+        };
+        record.output = record.evaluate();
+        self.records.push(record);
+        record.output
+    }
+
+    pub fn price(&mut self, option: &OptionInputs) -> f64 {
+        self.evaluate(option, Call::Price)
+    }
+
+    /// Implied vol of `option` at `price`.
+    pub fn implied_vol(&mut self, option: &OptionInputs, price: f64) -> f64 {
+        self.evaluate(&option.clone().with_price(price), Call::ImpliedVol)
+    }
+
+    /// Write the log in the documented layout.
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&LOG_MAGIC)?;
+        w.write_all(&(self.records.len() as u64).to_le_bytes())?;
+        for record in &self.records {
+            record.write(w)?;
+        }
+        w.flush()
+    }
+
+    /// Read a log written by [`Recorder::write`].
+    pub fn read(r: &mut impl Read) -> io::Result<Self> {
+        let mut header = [0; 16];
+        r.read_exact(&mut header)?;
+        if header[..8] != LOG_MAGIC {
+            return Err(invalid("not a pricing log"));
+        }
+        let len = usize::try_from(u64::from_le_bytes(header[8..].try_into().unwrap()))
+            .ok()
+            .and_then(|n| n.checked_mul(RECORD_LEN))
+            .ok_or_else(|| invalid("pricing log record count is too large"))?;
+
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)?;
+        if bytes.len() < len {
+            return Err(invalid("pricing log is truncated"));
+        }
+        let records = bytes
+            .chunks_exact(RECORD_LEN)
+            .take(len / RECORD_LEN)
+            .map(Record::parse)
+            .collect::<io::Result<_>>()?;
+        Ok(Self { records })
+    }
+}
+
+/// A recorded call whose result has changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mismatch {
+    /// Position of the record in the log
+    pub index: usize,
+    pub record: Record,
+
+    /// Output on the current code
+    pub replayed: f64,
+}
+
+/// Re-execute every record and return those whose output now differs from the recorded one by
+/// more than `tolerance`, or that have become NaN or stopped being NaN.
+pub fn replay(records: &[Record], tolerance: f64) -> Vec<Mismatch> {
+    records
+        .iter()
+        .enumerate()
+        .filter_map(|(index, record)| {
+            let replayed = record.evaluate();
+            let same = if record.output.is_nan() || replayed.is_nan() {
+                record.output.is_nan() && replayed.is_nan()
+            } else {
+                (replayed - record.output).abs() <= tolerance
+            };
+            (!same).then_some(Mismatch {
+                index,
+                record: *record,
+                replayed,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,159 @@
+//! Vol risk measured by bumping a whole surface.
+//!
+//! A single vega number assumes every option's vol moves by the same amount. Repricing a
+//! portfolio on surfaces bumped in parallel, twisted in skew, and tilted in term structure
+//! separates the exposure to each kind of move, and a [`VegaLadder`] shows where on the surface the
+//! vega sits.
+
+use crate::portfolio::Portfolio;
+use crate::surface::{SurfaceBump, VolSurface};
+
+/// Value of `portfolio` with every option priced at the surface's spot, carry, and vol for its
+/// strike and expiry. Expired options are worth their intrinsic value.
+pub fn value_on(portfolio: &Portfolio, surface: &VolSurface) -> f64 {
+    portfolio
+        .positions
+        .iter()
+        .map(|p| {
+            let o = &p.option;
+            let price = if o.t <= 0.0 {
+                (o.sign() * (surface.s - o.k)).max(0.0)
+            } else {
+                surface.option(o.is_call, o.k, o.t).price()
+            };
+            p.units() * price
+        })
+        .sum()
+}
+
+/// Change in value for `bump`, by central difference of the bump and its reverse.
+pub fn bump_risk(portfolio: &Portfolio, surface: &VolSurface, bump: &SurfaceBump) -> f64 {
+    let up = value_on(portfolio, &surface.bumped(bump));
+    let down = value_on(portfolio, &surface.bumped(&bump.reversed()));
+    0.5 * (up - down)
+}
+
+/// Sizes of the standard bumps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskBumps {
+    pub parallel: f64,
+
+    /// Vol added per unit log-forward-moneyness
+    pub skew_slope: f64,
+
+    /// Vol added per year past the pivot
+    pub term_slope: f64,
+
+    /// Expiry in years left unchanged by the term tilt
+    pub term_pivot: f64,
+}
+
+impl Default for RiskBumps {
+    /// One vol point in parallel, one vol point per 10% of moneyness, and one vol point per year
+    /// about one year.
+    fn default() -> Self {
+        Self {
+            parallel: 0.01,
+            skew_slope: 0.1,
+            term_slope: 0.01,
+            term_pivot: 1.0,
+        }
+    }
+}
+
+/// Value changes of a portfolio for each standard surface bump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceRisk {
+    pub vega: f64,
+    pub skew: f64,
+    pub term: f64,
+}
+
+impl SurfaceRisk {
+    pub fn new(portfolio: &Portfolio, surface: &VolSurface, bumps: &RiskBumps) -> Self {
+        Self {
+            vega: bump_risk(portfolio, surface, &SurfaceBump::Parallel(bumps.parallel)),
+            skew: bump_risk(
+                portfolio,
+                surface,
+                &SurfaceBump::SkewTwist(bumps.skew_slope),
+            ),
+            term: bump_risk(
+                portfolio,
+                surface,
+                &SurfaceBump::TermTilt {
+                    slope: bumps.term_slope,
+                    pivot: bumps.term_pivot,
+                },
+            ),
+        }
+    }
+}
+
+/// Vega bucketed by expiry and delta, the way vol risk limits are usually set.
+///
+/// Delta buckets use the call-equivalent delta, so a 25 delta put sits with the 75 delta calls of
+/// the same strike and each bucket covers one region of the smile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VegaLadder {
+    /// Upper edges of the expiry buckets in years, ascending; later expiries fall in the last one
+    pub expiry_edges: Vec<f64>,
+
+    /// Upper edges of the call-equivalent delta buckets, ascending; higher deltas fall in the
+    /// last one
+    pub delta_edges: Vec<f64>,
+
+    /// Vega for a parallel bump of the surface, indexed by expiry bucket then delta bucket
+    pub vega: Vec<Vec<f64>>,
+}
+
+fn bucket(edges: &[f64], x: f64) -> usize {
+    edges.partition_point(|&e| e < x).min(edges.len() - 1)
+}
+
+impl VegaLadder {
+    /// Bucket the vega of each position in `portfolio`, measured as its value change for a
+    /// parallel bump of `parallel` to `surface`.
+    pub fn new(
+        portfolio: &Portfolio,
+        surface: &VolSurface,
+        parallel: f64,
+        expiry_edges: Vec<f64>,
+        delta_edges: Vec<f64>,
+    ) -> Self {
+        assert!(!expiry_edges.is_empty() && !delta_edges.is_empty());
+        let mut vega = vec![vec![0.0; delta_edges.len()]; expiry_edges.len()];
+        let bump = SurfaceBump::Parallel(parallel);
+
+        for position in portfolio.positions.iter().filter(|p| p.option.t > 0.0) {
+            let o = &position.option;
+            let single = Portfolio::new().with_position(position.clone());
+            let call = surface.option(true, o.k, o.t);
+            let i = bucket(&expiry_edges, o.t);
+            let j = bucket(&delta_edges, call.delta());
+            vega[i][j] += bump_risk(&single, surface, &bump);
+        }
+
+        Self {
+            expiry_edges,
+            delta_edges,
+            vega,
+        }
+    }
+
+    /// Vega summed over delta buckets for each expiry bucket.
+    pub fn by_expiry(&self) -> Vec<f64> {
+        self.vega.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Vega summed over expiry buckets for each delta bucket.
+    pub fn by_delta(&self) -> Vec<f64> {
+        (0..self.delta_edges.len())
+            .map(|j| self.vega.iter().map(|row| row[j]).sum())
+            .collect()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.vega.iter().flatten().sum()
+    }
+}
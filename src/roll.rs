@@ -0,0 +1,79 @@
+//! Roll analysis for covered calls and short puts.
+//!
+//! Rolling a short option means buying it back and selling another one further out in time or at
+//! a different strike. Each candidate in the chain is scored by the net credit of the roll, the
+//! change in position delta, and the annualized yield of the new option on the capital it ties
+//! up: the spot price for a covered call and the strike for a cash-secured put. The roll itself
+//! is a [`Strategy`] long the current option and short the new one, both at their mid vols.
+
+use crate::chain::{OptionChain, OptionQuote};
+use crate::strategy::Strategy;
+
+/// One possible roll of the current option.
+#[derive(Debug, Clone)]
+pub struct RollCandidate {
+    pub quote: OptionQuote,
+
+    /// The closing and opening legs, per unit of the position
+    pub roll: Strategy,
+
+    /// Bid of the new option less the ask of the current one, per unit
+    pub net_credit: f64,
+
+    /// Position delta per unit after the roll
+    pub delta: f64,
+
+    /// Change in position delta per unit
+    pub delta_change: f64,
+
+    /// Bid of the new option over the capital, annualized over its life
+    pub annualized_yield: f64,
+
+    /// Net credit over the capital, annualized over the extra time gained by the roll
+    pub extension_yield: f64,
+}
+
+/// Score every later-dated or same-dated option of the same type in `chain` as a roll target
+/// for a short position in `current`, best annualized yield first.
+pub fn analyze(chain: &OptionChain, current: &OptionQuote) -> Vec<RollCandidate> {
+    let current_option = chain
+        .option(current)
+        .with_implied_vol(chain.solve_quote(current).mid_vol);
+
+    let mut candidates: Vec<RollCandidate> = chain
+        .quotes
+        .iter()
+        .filter(|q| q.is_call == current.is_call && q.t >= current.t)
+        .filter(|q| !(q.t == current.t && q.k == current.k))
+        .filter_map(|q| {
+            let solved = chain.solve_quote(q);
+            if !solved.mid_vol.is_finite() {
+                return None;
+            }
+            let new_option = chain.option(q).with_implied_vol(solved.mid_vol);
+            let roll = Strategy::new()
+                .with_leg(current_option.clone(), 1.0)
+                .with_leg(new_option.clone(), -1.0);
+            let capital = if q.is_call { chain.s } else { q.k };
+            let net_credit = q.bid - current.ask;
+            let extension = q.t - current.t;
+
+            Some(RollCandidate {
+                quote: q.clone(),
+                net_credit,
+                delta: -new_option.delta(),
+                delta_change: roll.delta(),
+                roll,
+                annualized_yield: q.bid / capital / q.t,
+                extension_yield: if extension > 0.0 {
+                    net_credit / capital / extension
+                } else {
+                    f64::NAN
+                },
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.annualized_yield.total_cmp(&a.annualized_yield));
+    candidates
+}
@@ -0,0 +1,98 @@
+//! The SABR stochastic vol model and Hagan et al.'s (2002) implied vol expansion.
+//!
+//! The forward follows `dF = alpha F^beta dW1` with `d alpha = nu alpha dW2` and correlation
+//! `rho` between the two. Hagan's expansion gives the lognormal implied vol of every strike in
+//! closed form, so a SABR smile plugs straight into [`OptionInputs::with_implied_vol`]. The
+//! expansion is accurate for moderate `nu^2 T` and loses accuracy, eventually implying negative
+//! densities, for very low strikes.
+
+use crate::surface::Smile;
+use crate::OptionInputs;
+
+/// Parameters of the SABR model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sabr {
+    /// Initial vol level
+    pub alpha: f64,
+
+    /// CEV exponent of the forward in [0, 1], 1 for lognormal and 0 for normal dynamics
+    pub beta: f64,
+
+    /// Correlation of the forward and its vol
+    pub rho: f64,
+
+    /// Vol of vol
+    pub nu: f64,
+}
+
+impl Sabr {
+    pub fn new(alpha: f64, beta: f64, rho: f64, nu: f64) -> Self {
+        Self {
+            alpha,
+            beta,
+            rho,
+            nu,
+        }
+    }
+
+    /// Lognormal implied vol at strike `k` for forward `f` and expiry `t`.
+    pub fn implied_vol(&self, f: f64, k: f64, t: f64) -> f64 {
+        let Self {
+            alpha,
+            beta,
+            rho,
+            nu,
+        } = *self;
+        let omb = 1.0 - beta;
+        let log_fk = (f / k).ln();
+        let fk = (f * k).powf(0.5 * omb);
+
+        let z = nu / alpha * fk * log_fk;
+        // z / x(z) tends to one at the money, where x(z) is computed with cancellation
+        let z_over_x = if z.abs() < 1e-8 {
+            1.0 - 0.5 * rho * z
+        } else {
+            let x = (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln();
+            z / x
+        };
+
+        let l2 = log_fk * log_fk;
+        let denominator = fk * (1.0 + omb * omb / 24.0 * l2 + omb.powi(4) / 1920.0 * l2 * l2);
+        let correction = 1.0
+            + (omb * omb / 24.0 * alpha * alpha / (fk * fk)
+                + 0.25 * rho * beta * nu * alpha / fk
+                + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu)
+                * t;
+        alpha / denominator * z_over_x * correction
+    }
+
+    /// Lognormal implied vol at the money.
+    pub fn atm_vol(&self, f: f64, t: f64) -> f64 {
+        self.implied_vol(f, f, t)
+    }
+
+    /// The smile for forward `f` at expiry `t`.
+    pub fn smile(&self, f: f64, t: f64) -> SabrSmile {
+        SabrSmile { model: *self, f, t }
+    }
+
+    /// BSM inputs for an option on a spot with the SABR vol of its forward applied.
+    pub fn option(&self, is_call: bool, s: f64, k: f64, r: f64, q: f64, t: f64) -> OptionInputs {
+        let f = s * ((r - q) * t).exp();
+        OptionInputs::new(is_call, s, k, r, q, t).with_implied_vol(self.implied_vol(f, k, t))
+    }
+}
+
+/// A SABR smile for one forward and expiry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SabrSmile {
+    pub model: Sabr,
+    pub f: f64,
+    pub t: f64,
+}
+
+impl Smile for SabrSmile {
+    fn vol(&self, k: f64) -> f64 {
+        self.model.implied_vol(self.f, k, self.t)
+    }
+}
@@ -0,0 +1,183 @@
+//! Spot, vol, and time scenario grids for portfolio risk.
+//!
+//! Running a [`ScenarioGrid`] against a [`Portfolio`] reprices every position under every
+//! combination of shifts and collects the P&L into a [`PnlCube`], which can be written out as CSV
+//! with labeled axes for consumption by spreadsheets and BI tools.
+//!
+//! Arrow IPC output is not provided, since the crate takes no Arrow dependency. The long CSV
+//! layout of [`PnlCube::write_csv`] is a flat table that Arrow's CSV readers load directly.
+
+use std::io::{self, Write};
+
+use crate::{OptionInputs, Portfolio, DAYS_PER_YEAR};
+
+/// The lowest vol a shift can take an option to, so that large negative shifts still price.
+pub const MIN_VOL: f64 = 1e-6;
+
+/// A single market move applied to an option.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Scenario {
+    /// Relative spot move, e.g. -0.1 for a 10% drop
+    pub spot_shift: f64,
+
+    /// Absolute vol move, e.g. 0.05 for +5 vol points
+    pub vol_shift: f64,
+
+    /// Calendar days elapsed
+    pub time_shift: f64,
+}
+
+impl Scenario {
+    pub fn new(spot_shift: f64, vol_shift: f64, time_shift: f64) -> Self {
+        Self {
+            spot_shift,
+            vol_shift,
+            time_shift,
+        }
+    }
+
+    /// Value of the option after the move. Options that expire within the scenario are worth
+    /// their intrinsic value, and vols are floored at [`MIN_VOL`].
+    pub fn price(&self, option: &OptionInputs) -> f64 {
+        let s = option.s * (1.0 + self.spot_shift);
+        let t = option.t - self.time_shift / DAYS_PER_YEAR;
+
+        if t <= 0.0 {
+            return (option.sign() * (s - option.k)).max(0.0);
+        }
+
+        let vol = (option.implied_vol + self.vol_shift).max(MIN_VOL);
+        OptionInputs::new(option.is_call, s, option.k, option.r, option.q, t)
+            .with_implied_vol(vol)
+            .price()
+    }
+
+    /// Change in portfolio value under the move.
+    pub fn pnl(&self, portfolio: &Portfolio) -> f64 {
+        portfolio
+            .positions
+            .iter()
+            .map(|p| p.units() * (self.price(&p.option) - p.option.price()))
+            .sum()
+    }
+}
+
+/// The axes of a scenario grid, every combination of which is evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioGrid {
+    pub spot_shifts: Vec<f64>,
+    pub vol_shifts: Vec<f64>,
+    pub time_shifts: Vec<f64>,
+}
+
+impl ScenarioGrid {
+    pub fn new(spot_shifts: Vec<f64>, vol_shifts: Vec<f64>) -> Self {
+        Self {
+            spot_shifts,
+            vol_shifts,
+            time_shifts: vec![0.0],
+        }
+    }
+
+    pub fn with_time_shifts(mut self, time_shifts: Vec<f64>) -> Self {
+        self.time_shifts = time_shifts;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.spot_shifts.len() * self.vol_shifts.len() * self.time_shifts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over every scenario in the same order as [`PnlCube::pnl`].
+    pub fn scenarios(&self) -> impl Iterator<Item = Scenario> + '_ {
+        self.time_shifts.iter().flat_map(move |&time_shift| {
+            self.vol_shifts.iter().flat_map(move |&vol_shift| {
+                self.spot_shifts
+                    .iter()
+                    .map(move |&spot_shift| Scenario::new(spot_shift, vol_shift, time_shift))
+            })
+        })
+    }
+
+    pub fn run(&self, portfolio: &Portfolio) -> PnlCube {
+        PnlCube {
+            grid: self.clone(),
+            pnl: self.scenarios().map(|s| s.pnl(portfolio)).collect(),
+        }
+    }
+}
+
+/// Portfolio P&L over a scenario grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PnlCube {
+    pub grid: ScenarioGrid,
+
+    /// P&L values laid out time-major, then vol, then spot
+    pub pnl: Vec<f64>,
+}
+
+impl PnlCube {
+    pub fn get(&self, spot: usize, vol: usize, time: usize) -> f64 {
+        let n_spot = self.grid.spot_shifts.len();
+        let n_vol = self.grid.vol_shifts.len();
+        self.pnl[(time * n_vol + vol) * n_spot + spot]
+    }
+
+    /// The worst P&L across the grid and the scenario producing it.
+    pub fn worst(&self) -> Option<(Scenario, f64)> {
+        self.grid
+            .scenarios()
+            .zip(self.pnl.iter().copied())
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Write the cube in long format with one row per scenario:
+    /// `time_shift,vol_shift,spot_shift,pnl`.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "time_shift,vol_shift,spot_shift,pnl")?;
+        for (scenario, pnl) in self.grid.scenarios().zip(&self.pnl) {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                scenario.time_shift, scenario.vol_shift, scenario.spot_shift, pnl
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write a single time slice as a matrix with vol shifts down the rows and spot shifts across
+    /// the columns, the layout usually used for risk slides. Fails without writing anything if
+    /// `time` is not an index of the grid's time shifts or the cube does not fill the grid.
+    pub fn write_csv_slice<W: Write>(&self, mut writer: W, time: usize) -> io::Result<()> {
+        if time >= self.grid.time_shifts.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no time slice {time} in the scenario grid"),
+            ));
+        }
+        if self.pnl.len() != self.grid.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "P&L cube does not match its scenario grid",
+            ));
+        }
+        write!(writer, "vol_shift\\spot_shift")?;
+        for spot_shift in &self.grid.spot_shifts {
+            write!(writer, ",{}", spot_shift)?;
+        }
+        writeln!(writer)?;
+
+        for (j, vol_shift) in self.grid.vol_shifts.iter().enumerate() {
+            write!(writer, "{}", vol_shift)?;
+            for i in 0..self.grid.spot_shifts.len() {
+                write!(writer, ",{}", self.get(i, j, time))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Scalar root finding.
+//!
+//! The solvers behind the crate's implied quantities, exposed for custom calibrations. Brent's
+//! method needs only a bracket and is the default choice; Newton and Halley converge faster when
+//! derivatives are cheap, for example from analytic Greeks.
+
+/// Why a solver gave up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The function has the same sign at both ends of the interval
+    NotBracketed,
+
+    /// The iteration limit was reached before the tolerance was met
+    MaxIterations,
+
+    /// The function or a derivative was NaN, infinite, or a derivative was zero
+    NonFinite,
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NotBracketed => "root is not bracketed",
+            Self::MaxIterations => "iteration limit reached",
+            Self::NonFinite => "function or derivative is not finite",
+        })
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+const MAX_ITER: usize = 200;
+
+/// Root of `f` in [`a`, `b`] to within `tol` by Brent's method. `f(a)` and `f(b)` must differ in
+/// sign.
+pub fn brent(f: impl Fn(f64) -> f64, a: f64, b: f64, tol: f64) -> Result<f64, SolveError> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolveError::NonFinite);
+    }
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fa * fb > 0.0 {
+        return Err(SolveError::NotBracketed);
+    }
+
+    let (mut c, mut fc) = (b, fb);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..MAX_ITER {
+        if fb * fc > 0.0 {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, b, c) = (b, c, b);
+            (fa, fb, fc) = (fb, fc, fb);
+        }
+
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // inverse quadratic interpolation, or secant when only two points are distinct
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            if 2.0 * p < (3.0 * xm * q - (tol1 * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+        if !fb.is_finite() {
+            return Err(SolveError::NonFinite);
+        }
+    }
+    Err(SolveError::MaxIterations)
+}
+
+/// Root of `f` in [`a`, `b`] to within `tol` by Newton's method from `x0`, where `f` returns the
+/// value and first derivative. Steps that leave the bracket or shrink it too slowly are replaced
+/// by bisection, so the iteration converges whenever the root is bracketed.
+pub fn newton(
+    f: impl Fn(f64) -> (f64, f64),
+    x0: f64,
+    a: f64,
+    b: f64,
+    tol: f64,
+) -> Result<f64, SolveError> {
+    let (fa, fb) = (f(a).0, f(b).0);
+    if !fa.is_finite() || !fb.is_finite() {
+        return Err(SolveError::NonFinite);
+    }
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    if fa * fb > 0.0 {
+        return Err(SolveError::NotBracketed);
+    }
+
+    // orient so that f(lo) < 0 < f(hi)
+    let (mut lo, mut hi) = if fa < 0.0 { (a, b) } else { (b, a) };
+    let mut x = x0.clamp(a.min(b), a.max(b));
+    let mut dx_old = (b - a).abs();
+    let mut dx = dx_old;
+    let (mut fx, mut dfx) = f(x);
+
+    for _ in 0..MAX_ITER {
+        if !fx.is_finite() || !dfx.is_finite() {
+            return Err(SolveError::NonFinite);
+        }
+        let newton_x = x - fx / dfx;
+        let outside = dfx == 0.0 || (newton_x - lo) * (newton_x - hi) > 0.0;
+        if outside || (2.0 * fx).abs() > (dx_old * dfx).abs() {
+            dx_old = dx;
+            dx = 0.5 * (hi - lo);
+            x = lo + dx;
+        } else {
+            dx_old = dx;
+            dx = fx / dfx;
+            x = newton_x;
+        }
+        if dx.abs() < tol {
+            return Ok(x);
+        }
+
+        (fx, dfx) = f(x);
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+    }
+    Err(SolveError::MaxIterations)
+}
+
+/// Root of `f` to within `tol` by Halley's method from `x0`, where `f` returns the value, first,
+/// and second derivatives. Cubic convergence near the root, but no bracket to fall back on.
+pub fn halley(f: impl Fn(f64) -> (f64, f64, f64), x0: f64, tol: f64) -> Result<f64, SolveError> {
+    let mut x = x0;
+    for _ in 0..MAX_ITER {
+        let (fx, d1, d2) = f(x);
+        if fx == 0.0 {
+            return Ok(x);
+        }
+        let denominator = 2.0 * d1 * d1 - fx * d2;
+        let step = 2.0 * fx * d1 / denominator;
+        if !step.is_finite() {
+            return Err(SolveError::NonFinite);
+        }
+        x -= step;
+        if step.abs() < tol {
+            return Ok(x);
+        }
+    }
+    Err(SolveError::MaxIterations)
+}
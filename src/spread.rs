@@ -0,0 +1,264 @@
+//! Spread options on two forwards.
+//!
+//! A spread call pays `max(F1 - F2 - K, 0)` at expiry. Kirk's approximation treats `F2 + K` as
+//! lognormal, which makes the option an exchange option and gives a Black-76 price on `F1`
+//! against `F2 + K` with an effective vol. It is exact for `K = 0` and accurate for strikes that
+//! are small against `F2`. Given the legs' vols, quoted spread prices imply a correlation per
+//! strike in the same way option prices imply a vol.
+//!
+//! A calendar spread option is the same payoff on two futures expirations of one commodity,
+//! usually the nearby less the deferred contract. The legs are highly but not perfectly
+//! correlated, strikes are often negative in contango, and each leg's vol to the option expiry
+//! depends on its own contract expiry through the Samuelson effect.
+
+use crate::correlation::{shifted, CorrelationRisk};
+use crate::solve;
+use crate::{calculate_ncdf, calculate_npdf, Black76Inputs};
+
+/// The inputs to Kirk's spread option approximation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadOption {
+    /// The type of the option (call or put on the spread)
+    pub is_call: bool,
+
+    /// Forward price of the long leg
+    pub f1: f64,
+
+    /// Forward price of the short leg
+    pub f2: f64,
+
+    /// Strike of the spread
+    pub k: f64,
+
+    /// Risk-free rate used to discount the payoff
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+
+    pub vol1: f64,
+    pub vol2: f64,
+
+    /// Correlation of the two forwards' returns
+    pub rho: f64,
+}
+
+impl SpreadOption {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_call: bool,
+        f1: f64,
+        f2: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        vol1: f64,
+        vol2: f64,
+        rho: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            f1,
+            f2,
+            k,
+            r,
+            t,
+            vol1,
+            vol2,
+            rho,
+        }
+    }
+
+    /// Vol of `F1 / (F2 + K)` under Kirk's approximation.
+    pub fn effective_vol(&self) -> f64 {
+        let b = self.f2 / (self.f2 + self.k);
+        (self.vol1.powi(2) - 2.0 * self.rho * self.vol1 * self.vol2 * b + (self.vol2 * b).powi(2))
+            .sqrt()
+    }
+
+    /// Black-76 inputs on `F1` struck at `F2 + K`, whose price is the spread option's.
+    pub fn black76(&self) -> Black76Inputs {
+        Black76Inputs::new(self.is_call, self.f1, self.f2 + self.k, self.r, self.t)
+            .with_implied_vol(self.effective_vol())
+    }
+
+    pub fn price(&self) -> f64 {
+        self.black76().price()
+    }
+
+    /// The correlation at which the option is worth `price`, NaN if no correlation in [-1, 1]
+    /// reproduces it. The price falls as the correlation rises, so the root is unique.
+    pub fn implied_correlation(&self, price: f64) -> f64 {
+        let at = |rho: f64| {
+            Self {
+                rho,
+                ..self.clone()
+            }
+            .price()
+                - price
+        };
+        solve::brent(at, -1.0, 1.0, 1e-12).unwrap_or(f64::NAN)
+    }
+
+    /// Implied correlations of quoted `prices` at each of `strikes`, with the legs, type, and
+    /// expiry of this option. Strikes whose price admits no correlation are NaN.
+    pub fn implied_correlations(&self, strikes: &[f64], prices: &[f64]) -> Vec<f64> {
+        strikes
+            .iter()
+            .zip(prices)
+            .map(|(&k, &price)| Self { k, ..self.clone() }.implied_correlation(price))
+            .collect()
+    }
+}
+
+impl CorrelationRisk for SpreadOption {
+    fn value(&self) -> f64 {
+        self.price()
+    }
+
+    fn shift_correlation(&self, shift: f64) -> Self {
+        Self {
+            rho: shifted(self.rho, shift),
+            ..self.clone()
+        }
+    }
+}
+
+/// Average vol to option expiry `t` of a futures contract expiring at `contract_expiry` whose
+/// instantaneous vol rises towards its expiry as `vol * exp(-decay * time remaining)`, the
+/// Samuelson effect seen in commodity futures. A zero decay gives `vol`.
+pub fn samuelson_vol(vol: f64, decay: f64, contract_expiry: f64, t: f64) -> f64 {
+    if decay.abs() < 1e-12 {
+        return vol;
+    }
+    let variance = vol
+        * vol
+        * ((-2.0 * decay * (contract_expiry - t)).exp() - (-2.0 * decay * contract_expiry).exp())
+        / (2.0 * decay * t);
+    variance.sqrt()
+}
+
+/// An option on the spread between two futures expirations of the same commodity, paying
+/// `max(F_near - F_far - K, 0)` for a call. Priced with Kirk's approximation, which needs
+/// `F_far + K > 0` but allows the negative strikes common in contango markets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarSpreadOption {
+    /// The type of the option (call or put on the spread)
+    pub is_call: bool,
+
+    /// Price of the nearby futures contract
+    pub near: f64,
+
+    /// Price of the deferred futures contract
+    pub far: f64,
+
+    /// Strike of the spread, which may be negative
+    pub k: f64,
+
+    /// Risk-free rate used to discount the payoff
+    pub r: f64,
+
+    /// Time to the option's expiry in years
+    pub t: f64,
+
+    /// Vols of each contract to the option expiry
+    pub near_vol: f64,
+    pub far_vol: f64,
+
+    /// Correlation of the two contracts' returns
+    pub rho: f64,
+}
+
+impl CalendarSpreadOption {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_call: bool,
+        near: f64,
+        far: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        near_vol: f64,
+        far_vol: f64,
+        rho: f64,
+    ) -> Self {
+        Self {
+            is_call,
+            near,
+            far,
+            k,
+            r,
+            t,
+            near_vol,
+            far_vol,
+            rho,
+        }
+    }
+
+    /// The equivalent two-asset spread option, long the nearby contract.
+    pub fn spread(&self) -> SpreadOption {
+        SpreadOption::new(
+            self.is_call,
+            self.near,
+            self.far,
+            self.k,
+            self.r,
+            self.t,
+            self.near_vol,
+            self.far_vol,
+            self.rho,
+        )
+    }
+
+    pub fn price(&self) -> f64 {
+        self.spread().price()
+    }
+
+    /// The correlation between the contracts at which the option is worth `price`, see
+    /// [`SpreadOption::implied_correlation`].
+    pub fn implied_correlation(&self, price: f64) -> f64 {
+        self.spread().implied_correlation(price)
+    }
+
+    /// Sensitivity to the nearby futures price. Kirk's effective vol does not depend on it.
+    pub fn near_delta(&self) -> f64 {
+        self.spread().black76().delta()
+    }
+
+    /// Sensitivity to the deferred futures price, including the change in Kirk's effective vol
+    /// as the weight `F_far / (F_far + K)` moves.
+    pub fn far_delta(&self) -> f64 {
+        let spread = self.spread();
+        let black = spread.black76();
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        let vol = spread.effective_vol();
+        let (d1, d2) = self.d(vol);
+        let strike_delta = -sign * black.rate_discount() * calculate_ncdf(sign * d2);
+
+        let x = self.far + self.k;
+        let b = self.far / x;
+        let dvol_db = (b * self.far_vol.powi(2) - self.rho * self.near_vol * self.far_vol) / vol;
+        let db_dfar = self.k / (x * x);
+        let vega = black.rate_discount() * self.near * self.t.sqrt() * calculate_npdf(d1);
+        strike_delta + vega * dvol_db * db_dfar
+    }
+
+    fn d(&self, vol: f64) -> (f64, f64) {
+        let stddev = vol * self.t.sqrt();
+        let d1 = ((self.near / (self.far + self.k)).ln() + 0.5 * stddev * stddev) / stddev;
+        (d1, d1 - stddev)
+    }
+}
+
+impl CorrelationRisk for CalendarSpreadOption {
+    fn value(&self) -> f64 {
+        self.price()
+    }
+
+    fn shift_correlation(&self, shift: f64) -> Self {
+        Self {
+            rho: shifted(self.rho, shift),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Options on short-term interest rate (STIR) futures.
+//!
+//! STIR futures such as SOFR or Euribor futures are quoted in price, `100 - rate` with the rate
+//! in percent, and so are the strikes of their options. Since the futures price falls as the rate
+//! rises, a call on the futures price pays `(R_K - R)+` in rate terms and is a put on the rate,
+//! and a price put is a rate call. [`StirOption`] holds a contract in price space and prices it
+//! either with a model on the futures price or with one on the implied rate, flipping the option
+//! type where needed.
+
+use crate::bachelier::BachelierInputs;
+use crate::black76::Black76Inputs;
+
+/// The futures price at a rate of zero.
+pub const PAR: f64 = 100.0;
+
+/// The rate in percent implied by a futures price.
+#[inline(always)]
+pub fn price_to_rate(price: f64) -> f64 {
+    PAR - price
+}
+
+/// The futures price for a rate in percent.
+#[inline(always)]
+pub fn rate_to_price(rate: f64) -> f64 {
+    PAR - rate
+}
+
+/// How the underlying of a STIR futures option is modelled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StirModel {
+    /// Black-76 on the futures price with a lognormal price vol
+    BlackPrice(f64),
+
+    /// Black-76 on the implied rate with a lognormal rate vol
+    BlackRate(f64),
+
+    /// Bachelier with a normal vol in price points, which equal rate points, so 0.01 is one
+    /// basis point per year
+    Normal(f64),
+}
+
+/// An option on a STIR future, with the type and strike quoted in price space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StirOption {
+    /// Whether the option is a call on the futures price
+    pub is_call: bool,
+
+    /// Futures price, `100 - rate`
+    pub futures_price: f64,
+
+    /// Strike in price terms
+    pub strike: f64,
+
+    /// Risk-free rate used to discount the premium, as a decimal
+    pub r: f64,
+
+    /// Time to maturity in years
+    pub t: f64,
+}
+
+impl StirOption {
+    pub fn new(is_call: bool, futures_price: f64, strike: f64, r: f64, t: f64) -> Self {
+        Self {
+            is_call,
+            futures_price,
+            strike,
+            r,
+            t,
+        }
+    }
+
+    /// The same contract specified in rate space: a call or put on the rate with its strike in
+    /// percent.
+    pub fn from_rate(is_rate_call: bool, rate: f64, strike_rate: f64, r: f64, t: f64) -> Self {
+        Self::new(
+            !is_rate_call,
+            rate_to_price(rate),
+            rate_to_price(strike_rate),
+            r,
+            t,
+        )
+    }
+
+    /// Whether the option is a call on the rate, i.e. a put on the futures price.
+    pub fn is_rate_call(&self) -> bool {
+        !self.is_call
+    }
+
+    /// Rate implied by the futures price, in percent.
+    pub fn rate(&self) -> f64 {
+        price_to_rate(self.futures_price)
+    }
+
+    /// Strike in rate terms, in percent.
+    pub fn strike_rate(&self) -> f64 {
+        price_to_rate(self.strike)
+    }
+
+    /// Black-76 inputs on the futures price.
+    pub fn black_price(&self, vol: f64) -> Black76Inputs {
+        Black76Inputs::new(
+            self.is_call,
+            self.futures_price,
+            self.strike,
+            self.r,
+            self.t,
+        )
+        .with_implied_vol(vol)
+    }
+
+    /// Black-76 inputs on the rate, with the option type flipped.
+    pub fn black_rate(&self, vol: f64) -> Black76Inputs {
+        Black76Inputs::new(
+            self.is_rate_call(),
+            self.rate(),
+            self.strike_rate(),
+            self.r,
+            self.t,
+        )
+        .with_implied_vol(vol)
+    }
+
+    /// Bachelier inputs on the rate, with the option type flipped.
+    pub fn normal_rate(&self, normal_vol: f64) -> BachelierInputs {
+        BachelierInputs::new(
+            self.is_rate_call(),
+            self.rate(),
+            self.strike_rate(),
+            self.r,
+            self.t,
+        )
+        .with_normal_vol(normal_vol)
+    }
+
+    /// Bachelier inputs on the futures price. Prices match [`normal_rate`](Self::normal_rate)
+    /// since a normal vol is the same in price and rate points.
+    pub fn normal_price(&self, normal_vol: f64) -> BachelierInputs {
+        BachelierInputs::new(
+            self.is_call,
+            self.futures_price,
+            self.strike,
+            self.r,
+            self.t,
+        )
+        .with_normal_vol(normal_vol)
+    }
+
+    /// Premium in price points.
+    pub fn price(&self, model: StirModel) -> f64 {
+        match model {
+            StirModel::BlackPrice(vol) => self.black_price(vol).price(),
+            StirModel::BlackRate(vol) => self.black_rate(vol).price(),
+            StirModel::Normal(vol) => self.normal_rate(vol).price(),
+        }
+    }
+
+    /// Sensitivity of the premium to the futures price. Rate-space deltas change sign, since a
+    /// one point rise in the rate is a one point fall in the price.
+    pub fn delta(&self, model: StirModel) -> f64 {
+        match model {
+            StirModel::BlackPrice(vol) => self.black_price(vol).delta(),
+            StirModel::BlackRate(vol) => -self.black_rate(vol).delta(),
+            StirModel::Normal(vol) => -self.normal_rate(vol).delta(),
+        }
+    }
+
+    /// Sensitivity of the premium to the implied rate in percent.
+    pub fn rate_delta(&self, model: StirModel) -> f64 {
+        -self.delta(model)
+    }
+
+    /// Second derivative of the premium in the futures price, the same in either space.
+    pub fn gamma(&self, model: StirModel) -> f64 {
+        match model {
+            StirModel::BlackPrice(vol) => self.black_price(vol).gamma(),
+            StirModel::BlackRate(vol) => self.black_rate(vol).gamma(),
+            StirModel::Normal(vol) => self.normal_rate(vol).gamma(),
+        }
+    }
+
+    /// Intrinsic value in price points.
+    pub fn intrinsic(&self) -> f64 {
+        let sign = if self.is_call { 1.0 } else { -1.0 };
+        (sign * (self.futures_price - self.strike)).max(0.0)
+    }
+}
@@ -0,0 +1,196 @@
+//! Multi-leg option strategies.
+
+use crate::solve;
+use crate::OptionInputs;
+
+/// One option leg of a strategy.
+#[derive(Debug, Clone)]
+pub struct Leg {
+    /// The option, which must have an implied vol set before it can be valued
+    pub option: OptionInputs,
+
+    /// Units held, negative for short legs
+    pub quantity: f64,
+}
+
+impl Leg {
+    pub fn new(option: OptionInputs, quantity: f64) -> Self {
+        Self { option, quantity }
+    }
+
+    /// Payoff per leg at expiry for an underlying price of `s`.
+    pub fn payoff(&self, s: f64) -> f64 {
+        self.quantity * (self.option.sign() * (s - self.option.k)).max(0.0)
+    }
+}
+
+/// A combination of option legs and an optional holding of the underlying.
+#[derive(Debug, Clone, Default)]
+pub struct Strategy {
+    pub legs: Vec<Leg>,
+
+    /// Units of the underlying held, negative for short stock
+    pub underlying: f64,
+}
+
+impl Strategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_leg(mut self, option: OptionInputs, quantity: f64) -> Self {
+        self.legs.push(Leg::new(option, quantity));
+        self
+    }
+
+    pub fn with_underlying(mut self, underlying: f64) -> Self {
+        self.underlying = underlying;
+        self
+    }
+
+    /// Stock plus a short call.
+    pub fn covered_call(call: OptionInputs) -> Self {
+        Self::new().with_underlying(1.0).with_leg(call, -1.0)
+    }
+
+    /// A short put backed by cash.
+    pub fn cash_secured_put(put: OptionInputs) -> Self {
+        Self::new().with_leg(put, -1.0)
+    }
+
+    /// Stock plus a long put.
+    pub fn protective_put(put: OptionInputs) -> Self {
+        Self::new().with_underlying(1.0).with_leg(put, 1.0)
+    }
+
+    /// Stock plus a long put and a short call.
+    pub fn collar(put: OptionInputs, call: OptionInputs) -> Self {
+        Self::protective_put(put).with_leg(call, -1.0)
+    }
+
+    /// Net value of the option legs, positive when the strategy costs premium.
+    pub fn premium(&self) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| l.quantity * l.option.price())
+            .sum()
+    }
+
+    fn sum(&self, greek: impl Fn(&OptionInputs) -> f64) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| l.quantity * greek(&l.option))
+            .sum()
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.underlying + self.sum(OptionInputs::delta)
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.sum(OptionInputs::gamma)
+    }
+
+    pub fn vega(&self) -> f64 {
+        self.sum(OptionInputs::vega)
+    }
+
+    pub fn theta(&self) -> f64 {
+        self.sum(OptionInputs::theta)
+    }
+
+    pub fn rho(&self) -> f64 {
+        self.sum(OptionInputs::rho)
+    }
+
+    /// A copy with every leg repriced at `vol`.
+    pub fn with_vol(&self, vol: f64) -> Self {
+        let legs = self
+            .legs
+            .iter()
+            .map(|l| {
+                let o = &l.option;
+                let option =
+                    OptionInputs::new(o.is_call, o.s, o.k, o.r, o.q, o.t).with_implied_vol(vol);
+                Leg::new(option, l.quantity)
+            })
+            .collect();
+        Self {
+            legs,
+            underlying: self.underlying,
+        }
+    }
+
+    /// The single vol which, applied to every leg, reproduces the package premium `price` as
+    /// given by [`premium`](Self::premium). Packages such as call spreads can be worth the same
+    /// at two vols, in which case the lower one is returned. NaN if no vol between 0.1% and 500%
+    /// reproduces the price.
+    pub fn implied_vol(&self, price: f64) -> f64 {
+        let error = |vol: f64| self.with_vol(vol).premium() - price;
+
+        // scan a geometric grid for the first sign change, then refine it
+        let vols: Vec<f64> = (0..=60)
+            .map(|i| 0.001 * 5000_f64.powf(i as f64 / 60.0))
+            .collect();
+        let errors: Vec<f64> = vols.iter().map(|&v| error(v)).collect();
+        (0..vols.len() - 1)
+            .find(|&i| errors[i] == 0.0 || errors[i] * errors[i + 1] < 0.0)
+            .and_then(|i| solve::brent(error, vols[i], vols[i + 1], 1e-12).ok())
+            .unwrap_or(f64::NAN)
+    }
+
+    /// Value at expiry of the legs and the underlying for an underlying price of `s`.
+    pub fn payoff(&self, s: f64) -> f64 {
+        self.underlying * s + self.legs.iter().map(|l| l.payoff(s)).sum::<f64>()
+    }
+}
+
+/// Return metrics for writing an option against stock or cash, assuming the premium is kept and
+/// nothing is reinvested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IncomeYield {
+    /// Premium over the capital, with the underlying unchanged at expiry
+    pub static_yield: f64,
+
+    /// Return on capital if the option finishes in the money and is exercised
+    pub if_called_yield: f64,
+
+    /// `static_yield` annualized over the option's life
+    pub annualized_static_yield: f64,
+
+    /// `if_called_yield` annualized over the option's life
+    pub annualized_if_called_yield: f64,
+
+    /// Underlying price at expiry below which the position loses money
+    pub breakeven: f64,
+}
+
+impl IncomeYield {
+    /// Metrics for a covered call on shares bought at `cost_basis`, which is the capital at risk.
+    pub fn covered_call(call: &OptionInputs, cost_basis: f64) -> Self {
+        let premium = call.price();
+        let static_yield = premium / cost_basis;
+        let if_called_yield = (premium + call.k - cost_basis) / cost_basis;
+        Self {
+            static_yield,
+            if_called_yield,
+            annualized_static_yield: static_yield / call.t,
+            annualized_if_called_yield: if_called_yield / call.t,
+            breakeven: cost_basis - premium,
+        }
+    }
+
+    /// Metrics for a put secured by cash equal to its strike. Assignment buys the shares at the
+    /// strike, so the return if assigned is the premium alone.
+    pub fn cash_secured_put(put: &OptionInputs) -> Self {
+        let premium = put.price();
+        let static_yield = premium / put.k;
+        Self {
+            static_yield,
+            if_called_yield: static_yield,
+            annualized_static_yield: static_yield / put.t,
+            annualized_if_called_yield: static_yield / put.t,
+            breakeven: put.k - premium,
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Implied volatility surfaces.
+//!
+//! A [`VolSurface`] stores implied vols on a grid of expiries and log-forward-moneyness
+//! `ln(K / F)`. Within an expiry the smile is interpolated linearly in moneyness; across expiries
+//! total implied variance is interpolated linearly in time at constant moneyness. Both are held
+//! flat outside the grid.
+
+use crate::compare::PricingModel;
+use crate::OptionInputs;
+
+/// An implied vol smile for a single expiry.
+pub trait Smile {
+    /// Implied vol at strike `k`
+    fn vol(&self, k: f64) -> f64;
+
+    /// Slope of the smile in strike, by central difference unless overridden.
+    fn dvol_dk(&self, k: f64) -> f64 {
+        let h = 1e-4 * k.abs().max(1e-4);
+        (self.vol(k + h) - self.vol(k - h)) / (2.0 * h)
+    }
+}
+
+impl<F: Fn(f64) -> f64> Smile for F {
+    fn vol(&self, k: f64) -> f64 {
+        self(k)
+    }
+}
+
+/// One expiry of a [`VolSurface`] viewed as a smile in strike.
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceSmile<'a> {
+    pub surface: &'a VolSurface,
+    pub t: f64,
+}
+
+impl Smile for SurfaceSmile<'_> {
+    fn vol(&self, k: f64) -> f64 {
+        self.surface.vol(k, self.t)
+    }
+}
+
+/// A change in shape applied to every node of a [`VolSurface`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurfaceBump {
+    /// Add the same vol everywhere
+    Parallel(f64),
+
+    /// Add `slope * ln(K / F)`, steepening the skew for negative slopes
+    SkewTwist(f64),
+
+    /// Add `slope * (t - pivot)`, raising long expiries against short ones for positive slopes
+    TermTilt { slope: f64, pivot: f64 },
+}
+
+impl SurfaceBump {
+    /// Vol added at log-forward-moneyness `m` and expiry `t`.
+    pub fn shift(&self, m: f64, t: f64) -> f64 {
+        match *self {
+            Self::Parallel(shift) => shift,
+            Self::SkewTwist(slope) => slope * m,
+            Self::TermTilt { slope, pivot } => slope * (t - pivot),
+        }
+    }
+
+    /// The same bump in the opposite direction.
+    pub fn reversed(&self) -> Self {
+        match *self {
+            Self::Parallel(shift) => Self::Parallel(-shift),
+            Self::SkewTwist(slope) => Self::SkewTwist(-slope),
+            Self::TermTilt { slope, pivot } => Self::TermTilt {
+                slope: -slope,
+                pivot,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolSurface {
+    /// Spot price of the underlying
+    pub s: f64,
+
+    /// Risk-free rate
+    pub r: f64,
+
+    /// Dividend yield
+    pub q: f64,
+
+    /// Expiries in years, ascending
+    pub expiries: Vec<f64>,
+
+    /// Log-forward-moneyness nodes `ln(K / F)`, ascending
+    pub moneyness: Vec<f64>,
+
+    /// Implied vols indexed by expiry then moneyness
+    pub vols: Vec<Vec<f64>>,
+}
+
+/// Index of the interval of `nodes` containing `x` and the interpolation weight of its upper
+/// end, clamped to the ends of the grid.
+pub(crate) fn bracket(nodes: &[f64], x: f64) -> (usize, f64) {
+    if nodes.len() < 2 || x <= nodes[0] {
+        return (0, 0.0);
+    }
+    let last = nodes.len() - 1;
+    if x >= nodes[last] {
+        return (last - 1, 1.0);
+    }
+    let i = nodes.partition_point(|&n| n <= x) - 1;
+    (i, (x - nodes[i]) / (nodes[i + 1] - nodes[i]))
+}
+
+/// Linear interpolation of `ys` over `xs`, flat outside the nodes.
+pub(crate) fn interpolate(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    if ys.len() == 1 {
+        return ys[0];
+    }
+    let (i, w) = bracket(xs, x);
+    ys[i] + w * (ys[i + 1] - ys[i])
+}
+
+impl VolSurface {
+    pub fn new(
+        s: f64,
+        r: f64,
+        q: f64,
+        expiries: Vec<f64>,
+        moneyness: Vec<f64>,
+        vols: Vec<Vec<f64>>,
+    ) -> Self {
+        assert_eq!(vols.len(), expiries.len(), "one row of vols per expiry");
+        assert!(
+            vols.iter().all(|row| row.len() == moneyness.len()),
+            "one vol per moneyness node"
+        );
+        Self {
+            s,
+            r,
+            q,
+            expiries,
+            moneyness,
+            vols,
+        }
+    }
+
+    /// The implied vol surface of `model` on a grid of expiries and log-forward-moneyness, with
+    /// each node implied from the model price of the out-of-the-money option there. Nodes whose
+    /// price admits no implied vol are NaN.
+    pub fn from_model(
+        model: &dyn PricingModel,
+        s: f64,
+        r: f64,
+        q: f64,
+        expiries: Vec<f64>,
+        moneyness: Vec<f64>,
+    ) -> Self {
+        let vols = expiries
+            .iter()
+            .map(|&t| {
+                let forward = s * ((r - q) * t).exp();
+                moneyness
+                    .iter()
+                    .map(|&m| {
+                        let option = OptionInputs::new(m >= 0.0, s, forward * m.exp(), r, q, t);
+                        let implied = option.clone().with_price(model.price(&option));
+                        implied.implied_vol()
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::new(s, r, q, expiries, moneyness, vols)
+    }
+
+    /// A surface with the same vol everywhere.
+    pub fn flat(s: f64, r: f64, q: f64, vol: f64) -> Self {
+        Self::new(s, r, q, vec![1.0], vec![0.0], vec![vec![vol]])
+    }
+
+    #[inline(always)]
+    pub fn forward(&self, t: f64) -> f64 {
+        self.s * ((self.r - self.q) * t).exp()
+    }
+
+    fn slice_vol(&self, i: usize, m: f64) -> f64 {
+        interpolate(&self.moneyness, &self.vols[i], m)
+    }
+
+    /// Implied vol at log-forward-moneyness `m` and expiry `t`.
+    pub fn vol_at_moneyness(&self, m: f64, t: f64) -> f64 {
+        let (i, w) = bracket(&self.expiries, t);
+        if self.expiries.len() == 1 || w == 0.0 || w == 1.0 {
+            let i = if w == 1.0 { i + 1 } else { i };
+            return self.slice_vol(i, m);
+        }
+
+        let (t0, t1) = (self.expiries[i], self.expiries[i + 1]);
+        let w0 = self.slice_vol(i, m).powi(2) * t0;
+        let w1 = self.slice_vol(i + 1, m).powi(2) * t1;
+        ((w0 + w * (w1 - w0)) / t).sqrt()
+    }
+
+    /// Implied vol at strike `k` and expiry `t`.
+    pub fn vol(&self, k: f64, t: f64) -> f64 {
+        self.vol_at_moneyness((k / self.forward(t)).ln(), t)
+    }
+
+    /// BSM inputs for an option on this surface, with the surface vol applied.
+    pub fn option(&self, is_call: bool, k: f64, t: f64) -> OptionInputs {
+        OptionInputs::new(is_call, self.s, k, self.r, self.q, t).with_implied_vol(self.vol(k, t))
+    }
+
+    /// A copy of the surface with `bump` added at every node, floored at a tenth of a vol point.
+    pub fn bumped(&self, bump: &SurfaceBump) -> Self {
+        let mut surface = self.clone();
+        for (row, &t) in surface.vols.iter_mut().zip(&self.expiries) {
+            for (vol, &m) in row.iter_mut().zip(&self.moneyness) {
+                *vol = (*vol + bump.shift(m, t)).max(0.001);
+            }
+        }
+        surface
+    }
+
+    /// The smile at expiry `t`.
+    pub fn smile(&self, t: f64) -> SurfaceSmile<'_> {
+        SurfaceSmile { surface: self, t }
+    }
+}
+
+/// Greeks that account for the smile moving with the underlying.
+impl OptionInputs {
+    /// Delta under sticky-moneyness dynamics, where the smile moves with spot so the option's vol
+    /// changes by `dvol/dK * -K/S` per unit spot move. The second term is the correction hedgers
+    /// add to the plain, sticky-strike delta.
+    pub fn smile_delta(&self, smile: &impl Smile) -> f64 {
+        let dvol_ds = -smile.dvol_dk(self.k) * self.k / self.s;
+        // vega is per vol point
+        self.delta() + 100.0 * self.vega() * dvol_ds
+    }
+}
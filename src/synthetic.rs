@@ -0,0 +1,138 @@
+//! Synthetic option chains for testing.
+//!
+//! Theoretical prices come from a [`VolSurface`], so the chain is free of static arbitrage
+//! whenever the surface is. Quotes are then built around each theoretical price according to a
+//! [`NoiseModel`] such that the bid never exceeds and the ask never falls below the theoretical
+//! value, keeping an arbitrage-free set of prices inside every quote.
+
+use rand::Rng;
+
+use crate::surface::VolSurface;
+use crate::tick::{round_to_tick, Rounding};
+
+/// How quotes are placed around the theoretical price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseModel {
+    /// Half of the bid/ask spread expressed in vol, e.g. 0.005 for half a vol point
+    pub half_spread_vol: f64,
+
+    /// Minimum half spread in price units
+    pub min_half_spread: f64,
+
+    /// How far the quote centre may wander from the theoretical price, as a fraction of the half
+    /// spread in [0, 1)
+    pub skew: f64,
+
+    /// Price grid the quotes are rounded outwards to
+    pub tick: Option<f64>,
+}
+
+impl NoiseModel {
+    pub fn new(half_spread_vol: f64, min_half_spread: f64) -> Self {
+        Self {
+            half_spread_vol,
+            min_half_spread,
+            skew: 0.0,
+            tick: None,
+        }
+    }
+
+    pub fn with_skew(mut self, skew: f64) -> Self {
+        self.skew = skew.clamp(0.0, 0.999);
+        self
+    }
+
+    pub fn with_tick(mut self, tick: f64) -> Self {
+        self.tick = Some(tick);
+        self
+    }
+}
+
+/// A generated market quote together with the truth it was generated from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticQuote {
+    pub is_call: bool,
+    pub k: f64,
+    pub t: f64,
+    pub bid: f64,
+    pub ask: f64,
+
+    /// Theoretical price from the surface
+    pub price: f64,
+
+    /// Surface implied vol
+    pub implied_vol: f64,
+}
+
+impl SyntheticQuote {
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+}
+
+pub struct ChainGenerator<'a> {
+    pub surface: &'a VolSurface,
+    pub noise: NoiseModel,
+}
+
+impl<'a> ChainGenerator<'a> {
+    pub fn new(surface: &'a VolSurface, noise: NoiseModel) -> Self {
+        Self { surface, noise }
+    }
+
+    /// Quote a single option.
+    pub fn quote<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        is_call: bool,
+        k: f64,
+        t: f64,
+    ) -> SyntheticQuote {
+        let option = self.surface.option(is_call, k, t);
+        let price = option.price();
+
+        // vega is per vol point
+        let half_spread =
+            (option.vega() * 100.0 * self.noise.half_spread_vol).max(self.noise.min_half_spread);
+        let u = if self.noise.skew > 0.0 {
+            rng.gen_range(-self.noise.skew..self.noise.skew)
+        } else {
+            0.0
+        };
+
+        let mut bid = (price - half_spread * (1.0 + u)).max(0.0);
+        let mut ask = price + half_spread * (1.0 - u);
+        if let Some(tick) = self.noise.tick {
+            bid = round_to_tick(bid, tick, Rounding::Down);
+            ask = round_to_tick(ask, tick, Rounding::Up);
+        }
+
+        SyntheticQuote {
+            is_call,
+            k,
+            t,
+            bid,
+            ask,
+            price,
+            implied_vol: option.implied_vol(),
+        }
+    }
+
+    /// Quote calls and puts at every strike and expiry, ordered by expiry, then strike, then
+    /// calls before puts.
+    pub fn chain<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        strikes: &[f64],
+        expiries: &[f64],
+    ) -> Vec<SyntheticQuote> {
+        let mut quotes = Vec::with_capacity(2 * strikes.len() * expiries.len());
+        for &t in expiries {
+            for &k in strikes {
+                quotes.push(self.quote(rng, true, k, t));
+                quotes.push(self.quote(rng, false, k, t));
+            }
+        }
+        quotes
+    }
+}
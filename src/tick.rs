@@ -0,0 +1,81 @@
+//! Exchange tick sizes and rounding of prices to them.
+
+/// Direction in which to round to the tick grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Nearest,
+
+    /// Towards lower prices, as for a bid
+    Down,
+
+    /// Towards higher prices, as for an ask
+    Up,
+}
+
+/// Round `price` to a multiple of `tick`. Prices within a millionth of a tick of the grid are
+/// treated as on it, so floating point noise does not move them a whole tick.
+pub fn round_to_tick(price: f64, tick: f64, rounding: Rounding) -> f64 {
+    let ticks = price / tick;
+    let nearest = ticks.round();
+    let n = if (ticks - nearest).abs() < 1e-6 {
+        nearest
+    } else {
+        match rounding {
+            Rounding::Nearest => nearest,
+            Rounding::Down => ticks.floor(),
+            Rounding::Up => ticks.ceil(),
+        }
+    };
+    n * tick
+}
+
+/// Tick sizes that step up with the price, as on most options exchanges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickTable {
+    /// Pairs of a price and the tick that applies below it, ascending by price
+    pub tiers: Vec<(f64, f64)>,
+
+    /// Tick at and above the last tier's price
+    pub tick: f64,
+}
+
+impl TickTable {
+    pub fn new(tiers: Vec<(f64, f64)>, tick: f64) -> Self {
+        Self { tiers, tick }
+    }
+
+    /// The same tick at every price.
+    pub fn uniform(tick: f64) -> Self {
+        Self::new(Vec::new(), tick)
+    }
+
+    /// Tick that applies at `price`.
+    pub fn tick_at(&self, price: f64) -> f64 {
+        self.tiers
+            .iter()
+            .find(|&&(below, _)| price < below)
+            .map_or(self.tick, |&(_, tick)| tick)
+    }
+
+    /// Round `price` to the tick grid that applies there. Rounding up across a tier boundary
+    /// lands on the boundary itself, which is on both grids.
+    pub fn round(&self, price: f64, rounding: Rounding) -> f64 {
+        round_to_tick(price, self.tick_at(price), rounding)
+    }
+
+    /// Highest quotable price at or below `price`.
+    pub fn bid(&self, price: f64) -> f64 {
+        self.round(price, Rounding::Down)
+    }
+
+    /// Lowest quotable price at or above `price`.
+    pub fn ask(&self, price: f64) -> f64 {
+        self.round(price, Rounding::Up)
+    }
+
+    /// Edge of trading at `price` against theoretical value `theo`, in ticks at `price`. Positive
+    /// when selling at `price` is worth more than `theo`, i.e. `price` is above it.
+    pub fn edge_in_ticks(&self, theo: f64, price: f64) -> f64 {
+        (price - theo) / self.tick_at(price)
+    }
+}
@@ -0,0 +1,131 @@
+//! Trinomial trees.
+//!
+//! Each step the spot moves up by `u`, stays, or moves down by `1 / u`. The extra branch lets the
+//! tree match the first two moments with a free stretch parameter, which smooths the convergence
+//! of American and barrier-style payoffs compared with a [`BinomialTree`] of the same depth, and
+//! the three nodes one step in give delta and gamma from the tree itself.
+//!
+//! [`BinomialTree`]: crate::binomial::BinomialTree
+
+use crate::binomial::Exercise;
+use crate::OptionInputs;
+
+/// How the moves and branch probabilities are set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parameterization {
+    /// Boyle (1986): `u = exp(vol * sqrt(2 dt))` with probabilities matching the forward of
+    /// half steps
+    Boyle,
+
+    /// Kamrad and Ritchken (1991): `u = exp(lambda * vol * sqrt(dt))`, matching mean and
+    /// variance of the log return for a stretch `lambda >= 1`; `lambda = sqrt(3 / 2)` gives
+    /// the fastest convergence for European options
+    KamradRitchken { lambda: f64 },
+}
+
+/// Price and tree Greeks of an option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeValue {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrinomialTree {
+    pub steps: usize,
+    pub exercise: Exercise,
+    pub parameterization: Parameterization,
+}
+
+impl TrinomialTree {
+    pub fn new(steps: usize, exercise: Exercise) -> Self {
+        Self {
+            steps: steps.max(1),
+            exercise,
+            parameterization: Parameterization::Boyle,
+        }
+    }
+
+    pub fn with_parameterization(mut self, parameterization: Parameterization) -> Self {
+        self.parameterization = parameterization;
+        self
+    }
+
+    /// Up move and the up, middle, and down probabilities over a step of `dt`.
+    fn branches(&self, option: &OptionInputs, dt: f64) -> (f64, f64, f64, f64) {
+        let (b, vol) = (option.carry(), option.implied_vol);
+        match self.parameterization {
+            Parameterization::Boyle => {
+                let half = (vol * (0.5 * dt).sqrt()).exp();
+                let growth = (0.5 * b * dt).exp();
+                let width = half - 1.0 / half;
+                let up = ((growth - 1.0 / half) / width).powi(2);
+                let down = ((half - growth) / width).powi(2);
+                (half * half, up, 1.0 - up - down, down)
+            }
+            Parameterization::KamradRitchken { lambda } => {
+                let drift = (b - 0.5 * vol * vol) * dt.sqrt() / (2.0 * lambda * vol);
+                let spread = 0.5 / (lambda * lambda);
+                (
+                    (lambda * vol * dt.sqrt()).exp(),
+                    spread + drift,
+                    1.0 - 2.0 * spread,
+                    spread - drift,
+                )
+            }
+        }
+    }
+
+    /// Price, delta, and gamma of `option` at its implied vol.
+    pub fn value(&self, option: &OptionInputs) -> TreeValue {
+        let n = self.steps;
+        let dt = option.t / n as f64;
+        let (u, pu, pm, pd) = self.branches(option, dt);
+        let discount = (-option.r * dt).exp();
+        let (pu, pm, pd) = (discount * pu, discount * pm, discount * pd);
+
+        let sign = option.sign();
+        let payoff = |s: f64| (sign * (s - option.k)).max(0.0);
+        // node j of step i has spot s * u^(j - i)
+        let spot = |i: usize, j: usize| option.s * u.powi(j as i32 - i as i32);
+        let mut values: Vec<f64> = (0..=2 * n).map(|j| payoff(spot(n, j))).collect();
+
+        let mut first = [0.0; 3];
+        for i in (0..n).rev() {
+            for j in 0..=2 * i {
+                let hold = pu * values[j + 2] + pm * values[j + 1] + pd * values[j];
+                values[j] = match self.exercise {
+                    Exercise::European => hold,
+                    Exercise::American => hold.max(payoff(spot(i, j))),
+                };
+            }
+            if i == 1 {
+                first.copy_from_slice(&values[..3]);
+            }
+        }
+
+        let price = values[0];
+        if n == 1 {
+            return TreeValue {
+                price,
+                delta: f64::NAN,
+                gamma: f64::NAN,
+            };
+        }
+        let [down, middle, up] = first;
+        let (s_down, s_up) = (option.s / u, option.s * u);
+        let delta = (up - down) / (s_up - s_down);
+        let gamma = ((up - middle) / (s_up - option.s) - (middle - down) / (option.s - s_down))
+            / (0.5 * (s_up - s_down));
+        TreeValue {
+            price,
+            delta,
+            gamma,
+        }
+    }
+
+    pub fn price(&self, option: &OptionInputs) -> f64 {
+        self.value(option).price
+    }
+}
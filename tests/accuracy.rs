@@ -0,0 +1,11 @@
+#![cfg(feature = "accuracy")]
+
+use blackscholes::accuracy::RoundTripGrid;
+
+#[test]
+fn default_grid_round_trips() {
+    let report = RoundTripGrid::default().run();
+    assert!(report.cases > 3000);
+    assert_eq!(report.failures, 0);
+    assert!(report.max_relative_error < 1e-9, "{:?}", report.worst);
+}
@@ -0,0 +1,165 @@
+use blackscholes::american::{self, Dividend};
+use blackscholes::OptionInputs;
+
+fn call(s: f64, k: f64, t: f64) -> OptionInputs {
+    OptionInputs::new(true, s, k, 0.05, 0.0, t).with_implied_vol(0.3)
+}
+
+#[test]
+fn no_dividend_is_european() {
+    let option = call(100.0, 100.0, 1.0);
+    let report = american::decompose_call(&option, &[]);
+    assert!((report.european - option.price()).abs() < 1e-12);
+    assert_eq!(report.early_exercise_premium, 0.0);
+}
+
+#[test]
+fn small_dividend_never_exercised() {
+    let option = call(100.0, 100.0, 0.5);
+    let report = american::decompose_call(&option, &[Dividend::new(0.25, 0.5)]);
+    assert!(!report.dividends[0].may_exercise);
+    assert!(report.dividends[0].exercise_boundary.is_nan());
+    assert_eq!(report.early_exercise_premium, 0.0);
+}
+
+#[test]
+fn roll_geske_whaley_reference() {
+    // Haug, The Complete Guide to Option Pricing Formulas: S = 80, K = 82, T = 1/3, r = 6%,
+    // vol = 30%, D = 4 at 1/4 year gives 4.3860
+    let option = OptionInputs::new(true, 80.0, 82.0, 0.06, 0.0, 1.0 / 3.0).with_implied_vol(0.3);
+    let report = american::decompose_call(&option, &[Dividend::new(0.25, 4.0)]);
+    assert!(report.dividends[0].may_exercise);
+    assert!(
+        (report.american - 4.3860).abs() < 5e-4,
+        "{}",
+        report.american
+    );
+    assert!(report.early_exercise_premium > 0.0);
+
+    // at the boundary, holding the call through the ex-date is worth exactly its intrinsic value
+    let boundary = report.dividends[0].exercise_boundary;
+    assert!(boundary > 82.0);
+    let held = OptionInputs::new(true, boundary - 4.0, 82.0, 0.06, 0.0, 1.0 / 3.0 - 0.25)
+        .with_implied_vol(0.3);
+    assert!(
+        (held.price() - (boundary - 82.0)).abs() < 1e-9,
+        "{boundary}"
+    );
+}
+
+#[test]
+fn several_dividends_bounded_by_european() {
+    let option = call(100.0, 90.0, 1.0);
+    let dividends = [Dividend::new(0.3, 3.0), Dividend::new(0.8, 3.0)];
+    let report = american::decompose_call(&option, &dividends);
+    assert_eq!(report.dividends.len(), 2);
+    assert!(report.american >= report.european);
+    assert!(report.american >= option.s - option.k);
+}
+
+#[test]
+fn ex_dividend_advice_flags_deep_itm_calls() {
+    use blackscholes::portfolio::Position;
+
+    let dividends = [Dividend::new(0.02, 2.0), Dividend::new(0.27, 2.0)];
+    let deep = Position::new("deep", call(150.0, 100.0, 0.3), -5.0).with_multiplier(100.0);
+    let advice = american::ex_dividend_advice(&deep, &dividends);
+    assert_eq!(advice.len(), 2);
+    assert!(advice[0].exercise);
+    assert!(advice[0].gain > 0.0);
+    assert!(
+        (advice[0].gain / 500.0 - (advice[0].exercise_value - advice[0].hold_value)).abs() < 1e-9
+    );
+
+    let atm = Position::new("atm", call(100.0, 100.0, 0.3), 1.0);
+    assert!(american::ex_dividend_advice(&atm, &dividends)
+        .iter()
+        .all(|a| !a.exercise && a.gain < 0.0));
+}
+
+/// American price on a Cox-Ross-Rubinstein tree.
+fn binomial(option: &OptionInputs, steps: usize) -> f64 {
+    let dt = option.t / steps as f64;
+    let u = (option.implied_vol * dt.sqrt()).exp();
+    let p = (((option.r - option.q) * dt).exp() - 1.0 / u) / (u - 1.0 / u);
+    let disc = (-option.r * dt).exp();
+    let payoff = |s: f64| {
+        if option.is_call {
+            (s - option.k).max(0.0)
+        } else {
+            (option.k - s).max(0.0)
+        }
+    };
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|j| payoff(option.s * u.powi(2 * j as i32 - steps as i32)))
+        .collect();
+    for n in (0..steps).rev() {
+        for j in 0..=n {
+            let hold = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+            values[j] = hold.max(payoff(option.s * u.powi(2 * j as i32 - n as i32)));
+        }
+    }
+    values[0]
+}
+
+#[test]
+fn bjerksund_stensland_near_binomial() {
+    for (is_call, s, q) in [
+        (false, 90.0, 0.0),
+        (false, 100.0, 0.02),
+        (false, 110.0, 0.0),
+        (true, 100.0, 0.08),
+        (true, 120.0, 0.08),
+    ] {
+        let option = OptionInputs::new(is_call, s, 100.0, 0.08, q, 0.5).with_implied_vol(0.25);
+        let american = option.price_american();
+        assert!(american >= option.price() - 1e-12);
+        let tree = binomial(&option, 2000);
+        // the approximation exercises suboptimally, so it is a lower bound a few cents short
+        assert!(
+            american < tree && tree - american < 0.08,
+            "{american} vs {tree}"
+        );
+    }
+
+    // without a dividend the call is European
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    assert!((option.price_american() - option.price()).abs() < 1e-12);
+
+    // deep in the money the put is exercised immediately
+    let option = OptionInputs::new(false, 50.0, 100.0, 0.1, 0.0, 1.0).with_implied_vol(0.2);
+    assert_eq!(option.price_american(), 50.0);
+}
+
+#[test]
+fn barone_adesi_whaley_near_binomial() {
+    for (is_call, s, q) in [
+        (false, 90.0, 0.0),
+        (false, 100.0, 0.02),
+        (true, 100.0, 0.08),
+        (true, 110.0, 0.12),
+    ] {
+        let option = OptionInputs::new(is_call, s, 100.0, 0.08, q, 0.5).with_implied_vol(0.25);
+        let approx = american::barone_adesi_whaley(&option);
+        assert_eq!(approx.european, option.price());
+        assert!(approx.early_exercise_premium > 0.0);
+        let tree = binomial(&option, 2000);
+        assert!(
+            (approx.american - tree).abs() < 0.08,
+            "{} vs {tree}",
+            approx.american
+        );
+    }
+
+    // at the critical spot the option is worth its intrinsic value
+    let option = OptionInputs::new(false, 100.0, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+    let critical = american::barone_adesi_whaley(&option).critical_spot;
+    let at = OptionInputs::new(false, critical, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+    let approx = american::barone_adesi_whaley(&at);
+    assert!((approx.american - (100.0 - critical)).abs() < 1e-9);
+
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    let approx = american::barone_adesi_whaley(&option);
+    assert_eq!(approx.early_exercise_premium, 0.0);
+    assert_eq!(approx.critical_spot, f64::INFINITY);
+}
@@ -0,0 +1,123 @@
+use blackscholes::asian::{AsianOption, Averaging};
+use blackscholes::OptionInputs;
+
+#[test]
+fn geometric_reference_and_limits() {
+    // Haug, The Complete Guide to Option Pricing Formulas: cost of carry 8% gives 4.6922
+    let put = AsianOption::new(false, 80.0, 85.0, 0.05, -0.03, 0.25, Averaging::Continuous)
+        .with_implied_vol(0.2);
+    assert!((put.geometric_price() - 4.6922).abs() < 1e-4);
+    // the average's vol is the underlying's over the square root of three
+    let vol = put.geometric_black76().implied_vol();
+    assert!((vol - 0.2 / 3.0_f64.sqrt()).abs() < 1e-12);
+
+    // a single fixing at expiry is the vanilla
+    let single = AsianOption::new(true, 100.0, 95.0, 0.04, 0.01, 1.0, Averaging::Discrete(1))
+        .with_implied_vol(0.3);
+    let vanilla = OptionInputs::new(true, 100.0, 95.0, 0.04, 0.01, 1.0).with_implied_vol(0.3);
+    assert!((single.geometric_price() - vanilla.price()).abs() < 1e-10);
+    assert!((single.geometric_delta() - vanilla.delta()).abs() < 1e-10);
+
+    // many fixings approach continuous averaging, here over the last half year
+    let window = |averaging| {
+        AsianOption::new(true, 100.0, 95.0, 0.04, 0.01, 1.0, averaging)
+            .with_averaging_start(0.5)
+            .with_implied_vol(0.3)
+    };
+    let continuous = window(Averaging::Continuous).geometric_price();
+    let coarse = window(Averaging::Discrete(10)).geometric_price();
+    let fine = window(Averaging::Discrete(1000)).geometric_price();
+    assert!((fine - continuous).abs() < 1e-2);
+    assert!((fine - continuous).abs() < (coarse - continuous).abs());
+    assert!(continuous < vanilla.price());
+}
+
+#[test]
+fn geometric_greeks_match_finite_differences() {
+    for (is_call, averaging) in [
+        (true, Averaging::Continuous),
+        (false, Averaging::Discrete(12)),
+    ] {
+        let option = |s: f64, r: f64, vol: f64| {
+            AsianOption::new(is_call, s, 100.0, r, 0.02, 0.8, averaging)
+                .with_averaging_start(0.2)
+                .with_implied_vol(vol)
+                .geometric_price()
+        };
+        let base = AsianOption::new(is_call, 100.0, 100.0, 0.05, 0.02, 0.8, averaging)
+            .with_averaging_start(0.2)
+            .with_implied_vol(0.25);
+        let h = 1e-4;
+        let delta = (option(100.0 + h, 0.05, 0.25) - option(100.0 - h, 0.05, 0.25)) / (2.0 * h);
+        let gamma = (option(100.0 + 1e-2, 0.05, 0.25) - 2.0 * base.geometric_price()
+            + option(100.0 - 1e-2, 0.05, 0.25))
+            / 1e-4;
+        let vega = (option(100.0, 0.05, 0.25 + h) - option(100.0, 0.05, 0.25 - h)) / (2.0 * h);
+        let rho = (option(100.0, 0.05 + h, 0.25) - option(100.0, 0.05 - h, 0.25)) / (2.0 * h);
+        assert!((base.geometric_delta() - delta).abs() < 1e-7);
+        assert!((base.geometric_gamma() - gamma).abs() < 1e-5);
+        assert!((base.geometric_vega() - 0.01 * vega).abs() < 1e-7);
+        assert!((base.geometric_rho() - 0.01 * rho).abs() < 1e-7);
+    }
+}
+
+#[test]
+fn turnbull_wakeman_is_close_to_simulation() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    let mut rng = StdRng::seed_from_u64(17);
+    let (r, q, vol, t) = (0.05, 0.01, 0.3, 1.0);
+    for (is_call, k) in [(true, 100.0), (false, 95.0)] {
+        let option = AsianOption::new(is_call, 100.0, k, r, q, t, Averaging::Discrete(12))
+            .with_averaging_start(0.25)
+            .with_implied_vol(vol);
+        let times = option.fixing_times();
+        let paths = 40_000;
+        let sign = if is_call { 1.0 } else { -1.0 };
+        let payoffs: Vec<f64> = (0..paths)
+            .map(|_| {
+                let (mut log_s, mut now, mut sum) = (100.0_f64.ln(), 0.0, 0.0);
+                for &fixing in &times {
+                    let dt = fixing - now;
+                    let z: f64 = StandardNormal.sample(&mut rng);
+                    log_s += (r - q - 0.5 * vol * vol) * dt + vol * dt.sqrt() * z;
+                    sum += log_s.exp();
+                    now = fixing;
+                }
+                (-r * t).exp() * (sign * (sum / times.len() as f64 - k)).max(0.0)
+            })
+            .collect();
+        let mean = payoffs.iter().sum::<f64>() / paths as f64;
+        let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (paths - 1) as f64;
+        let std_error = (variance / paths as f64).sqrt();
+        let price = option.arithmetic_price();
+        assert!(
+            (price - mean).abs() < 4.0 * std_error + 0.02,
+            "{price} {mean}"
+        );
+
+        // the arithmetic average exceeds the geometric, and averaging lowers the effective vol
+        assert_eq!(option.geometric_price() < price, is_call);
+        assert!(option.arithmetic_black76().implied_vol() < vol);
+    }
+
+    // continuous averaging over the window is the limit of frequent fixings
+    let window = |averaging| {
+        AsianOption::new(true, 100.0, 100.0, r, q, t, averaging)
+            .with_averaging_start(0.25)
+            .with_implied_vol(vol)
+    };
+    let continuous = window(Averaging::Continuous);
+    let fine = window(Averaging::Discrete(2000));
+    let (first, second) = continuous.arithmetic_moments();
+    let (fine_first, fine_second) = fine.arithmetic_moments();
+    assert!((first - fine_first).abs() < 1e-4 && (second - fine_second).abs() < 1e-3);
+    assert!((continuous.arithmetic_price() - fine.arithmetic_price()).abs() < 1e-2);
+    // a single fixing is the vanilla exactly
+    let single =
+        AsianOption::new(true, 100.0, 90.0, r, q, t, Averaging::Discrete(1)).with_implied_vol(vol);
+    let vanilla = OptionInputs::new(true, 100.0, 90.0, r, q, t).with_implied_vol(vol);
+    assert!((single.arithmetic_price() - vanilla.price()).abs() < 1e-10);
+}
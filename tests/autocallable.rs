@@ -0,0 +1,79 @@
+use blackscholes::autocallable::{Autocallable, KnockIn};
+use blackscholes::mc::{CorrelatedAssets, MonteCarlo};
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn assets(rho: f64) -> CorrelatedAssets {
+    let correlation = (0..3)
+        .map(|i| (0..3).map(|j| if i == j { 1.0 } else { rho }).collect())
+        .collect();
+    CorrelatedAssets::new(
+        vec![100.0, 50.0, 80.0],
+        vec![0.25, 0.3, 0.2],
+        correlation,
+        0.03,
+        2.0,
+    )
+    .with_dividends(vec![0.01, 0.0, 0.02])
+}
+
+#[test]
+fn degenerate_notes_are_bonds_and_puts() {
+    let simulation = MonteCarlo::new(2_000).with_steps(8);
+    let mut rng = StdRng::seed_from_u64(3);
+
+    // a note that pays every coupon, never calls, and never knocks in is a coupon bond
+    let bond = Autocallable::new(assets(0.5), 4, 0.02, 0.0)
+        .with_notional(100.0)
+        .with_coupon_barrier(0.0)
+        .with_autocall_barrier(f64::INFINITY);
+    let price = bond.price(&simulation, &mut rng).unwrap();
+    let coupons: f64 = (1..=4).map(|i| 2.0 * (-0.03 * 0.5 * i as f64).exp()).sum();
+    assert!((price.price - coupons - 100.0 * (-0.03_f64 * 2.0).exp()).abs() < 1e-9);
+    assert!(price.std_error < 1e-9);
+
+    // paths whose steps miss the observations are rejected
+    let misaligned = MonteCarlo::new(100).with_steps(6);
+    assert!(bond.price(&misaligned, &mut rng).is_none());
+
+    // without a put strike nothing is lost on a knock-in
+    let unprotected = Autocallable::new(assets(0.5), 4, 0.0, f64::INFINITY)
+        .with_autocall_barrier(f64::INFINITY)
+        .with_put_strike(0.0);
+    let price = unprotected.price(&simulation, &mut rng).unwrap();
+    assert!((price.price - (-0.03_f64 * 2.0).exp()).abs() < 1e-12);
+
+    // on one asset with the put knocked in at its strike, the note is a bond less a put
+    let single = CorrelatedAssets::new(vec![100.0], vec![0.25], vec![vec![1.0]], 0.03, 2.0)
+        .with_dividends(vec![0.01]);
+    let note = Autocallable::new(single, 4, 0.0, 1.0).with_autocall_barrier(f64::INFINITY);
+    let price = MonteCarlo::new(40_000)
+        .with_steps(4)
+        .price_correlated(&note.assets, &note, &mut rng)
+        .unwrap();
+    let put = OptionInputs::new(false, 100.0, 100.0, 0.03, 0.01, 2.0).with_implied_vol(0.25);
+    let expected = (-0.03_f64 * 2.0).exp() - put.price() / 100.0;
+    assert!((price.price - expected).abs() < 4.0 * price.std_error);
+}
+
+#[test]
+fn worst_of_features() {
+    let simulation = MonteCarlo::new(5_000).with_steps(8 * 4);
+    let note = |rho: f64| Autocallable::new(assets(rho), 8, 0.025, 0.6).with_coupon_barrier(0.7);
+    let price = |note: &Autocallable| {
+        // the same draws for every note
+        let mut rng = StdRng::seed_from_u64(17);
+        note.price(&simulation, &mut rng).unwrap().price
+    };
+
+    // the holder is short the put on the worst performer, which is worth less the more the
+    // assets move together
+    let base = price(&note(0.5));
+    assert!(price(&note(0.1)) < base && base < price(&note(0.9)));
+
+    // memory coupons and European knock-ins are worth more to the holder
+    assert!(price(&note(0.5).with_memory(true)) > base);
+    assert!(price(&note(0.5).with_knock_in(KnockIn::Steps)) < base);
+    assert!(base < 1.0 + 8.0 * 0.025);
+}
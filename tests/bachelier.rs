@@ -0,0 +1,66 @@
+use blackscholes::bachelier::{lognormal_to_normal_vol, normal_to_lognormal_vol};
+use blackscholes::{BachelierInputs, OptionInputs};
+
+#[test]
+fn put_call_parity() {
+    let call = BachelierInputs::new(true, 1.5, 2.0, 0.03, 2.0).with_normal_vol(0.8);
+    let put = BachelierInputs::new(false, 1.5, 2.0, 0.03, 2.0).with_normal_vol(0.8);
+    let forward_value = (1.5 - 2.0) * call.rate_discount();
+    assert!((call.price() - put.price() - forward_value).abs() < 1e-12);
+    assert!((call.delta() - put.delta() - call.rate_discount()).abs() < 1e-12);
+}
+
+#[test]
+fn small_vol_limit_matches_bsm() {
+    // For small lognormal vol near the money, sigma_n = sigma * F gives the same distribution.
+    let (f, r, t, vol) = (100.0, 0.02, 0.25, 0.002);
+    for is_call in [true, false] {
+        for k in [99.9, 100.0, 100.1] {
+            let bsm = OptionInputs::new(is_call, f, k, r, r, t).with_implied_vol(vol);
+            let normal = BachelierInputs::new(is_call, f, k, r, t).with_normal_vol(vol * f);
+
+            let close = |a: f64, b: f64, tol: f64| (a - b).abs() <= tol * b.abs().max(1e-3);
+            assert!(close(normal.price(), bsm.price(), 1e-2));
+            assert!(close(normal.delta(), bsm.delta(), 1e-2));
+            assert!(close(normal.gamma(), bsm.gamma(), 1e-2));
+            assert!(close(normal.vega() * f, bsm.vega(), 1e-2));
+            assert!(close(normal.theta(), bsm.theta(), 1e-2));
+        }
+    }
+}
+
+#[test]
+fn normal_vol_round_trips() {
+    // negative forwards and strikes are fine under the normal model
+    for (f, k) in [(1.5, 2.0), (-0.4, 0.1), (0.02, 0.02), (0.5, -0.5)] {
+        for is_call in [true, false] {
+            let option = BachelierInputs::new(is_call, f, k, 0.03, 1.5).with_normal_vol(0.6);
+            let solved = BachelierInputs::new(is_call, f, k, 0.03, 1.5).with_price(option.price());
+            assert!(
+                (solved.normal_vol() - 0.6).abs() < 1e-10,
+                "{f} {k} {is_call}"
+            );
+            assert!((solved.delta() - option.delta()).abs() < 1e-10);
+        }
+    }
+
+    // a price at or below intrinsic value admits no vol
+    let solved = BachelierInputs::new(true, 2.0, 1.0, 0.0, 1.0).with_price(0.9);
+    assert!(solved.normal_vol().is_nan());
+    assert_eq!(solved.price(), 0.9);
+}
+
+#[test]
+fn vol_conversions_match_prices() {
+    let (f, t) = (100.0, 0.75);
+    for k in [80.0, 100.0, 120.0] {
+        let normal = lognormal_to_normal_vol(f, k, t, 0.25);
+        let bsm = OptionInputs::new(true, f, k, 0.0, 0.0, t).with_implied_vol(0.25);
+        let bachelier = BachelierInputs::new(true, f, k, 0.0, t).with_normal_vol(normal);
+        assert!((bachelier.price() - bsm.price()).abs() < 1e-6 * bsm.price());
+        assert!((normal_to_lognormal_vol(f, k, t, normal) - 0.25).abs() < 1e-7);
+    }
+    // at the money the normal vol is close to the lognormal vol times the forward
+    assert!((lognormal_to_normal_vol(f, f, t, 0.25) / (0.25 * f) - 1.0).abs() < 0.01);
+    assert!(normal_to_lognormal_vol(-1.0, 1.0, t, 1.0).is_nan());
+}
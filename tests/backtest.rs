@@ -0,0 +1,51 @@
+use blackscholes::backtest::{Observation, RollStrategy};
+use blackscholes::calendar::Date;
+use blackscholes::OptionInputs;
+
+fn daily(days: i32, spot: impl Fn(i32) -> f64) -> Vec<Observation> {
+    let start = Date::from_ymd(2024, 1, 2).unwrap();
+    (0..=days)
+        .map(|i| Observation::new(start.add_days(i), spot(i), 0.2))
+        .collect()
+}
+
+#[test]
+fn strike_from_delta_round_trips() {
+    for (is_call, delta) in [(true, 0.3), (false, -0.25)] {
+        let k = OptionInputs::strike_from_delta(is_call, 100.0, 0.03, 0.01, 0.5, 0.2, delta);
+        let option = OptionInputs::new(is_call, 100.0, k, 0.03, 0.01, 0.5).with_implied_vol(0.2);
+        assert!((option.delta() - delta).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn short_calls_on_flat_market_collect_premium() {
+    let strategy = RollStrategy::new(true, 0.3, 30, -1.0).with_rates(0.0, 0.0);
+    let report = strategy.backtest(&daily(90, |_| 100.0));
+
+    assert_eq!(report.steps.len(), 91);
+    assert_eq!(report.trades.len(), 3);
+    for trade in &report.trades {
+        assert_eq!(trade.exit.days_since(trade.entry), 30);
+        assert_eq!(trade.exit_price, 0.0);
+        assert!(trade.pnl > 0.0);
+    }
+    let premium: f64 = report.trades.iter().map(|t| t.pnl).sum();
+    assert!((report.total_pnl() - premium).abs() < 1e-9);
+    assert_eq!(report.max_drawdown(), 0.0);
+    assert!(report.steps[0].delta < 0.0 && report.steps[0].theta > 0.0);
+}
+
+#[test]
+fn covered_call_drawdown_on_sell_off() {
+    let strategy = RollStrategy::new(true, 0.3, 30, -1.0)
+        .with_underlying(1.0)
+        .with_roll_days(7)
+        .with_strike_increment(5.0);
+    let report = strategy.backtest(&daily(28, |i| 100.0 - i as f64));
+
+    assert_eq!(report.trades.len(), 4);
+    assert!(report.trades.iter().all(|t| t.strike % 5.0 == 0.0));
+    assert!(report.total_pnl() < 0.0);
+    assert!((report.max_drawdown() + report.total_pnl()).abs() < 1e-9);
+}
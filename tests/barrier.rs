@@ -0,0 +1,209 @@
+use blackscholes::barrier::{BarrierOption, Direction, DoubleBarrierOption, Knock};
+
+fn barrier(is_call: bool, k: f64, direction: Direction, knock: Knock, h: f64) -> BarrierOption {
+    BarrierOption::new(is_call, 100.0, k, 0.08, 0.04, 0.5, direction, knock, h)
+        .with_rebate(3.0)
+        .with_implied_vol(0.25)
+}
+
+#[test]
+fn haug_reference_values() {
+    // Haug, The Complete Guide to Option Pricing Formulas, table 4-13 with vol 25%
+    use Direction::{Down, Up};
+    use Knock::{In, Out};
+    let cases = [
+        (true, Down, Out, 95.0, [9.0246, 6.7924, 4.8759]),
+        (true, Up, Out, 105.0, [2.6789, 2.3580, 2.3453]),
+        (true, Down, In, 95.0, [7.7627, 4.0109, 2.0576]),
+        (true, Up, In, 105.0, [14.1112, 8.4482, 4.5910]),
+        (false, Down, Out, 95.0, [2.2798, 2.2947, 2.6252]),
+        (false, Up, Out, 105.0, [3.7760, 5.4932, 7.5187]),
+        (false, Down, In, 95.0, [2.9586, 6.5677, 11.9752]),
+        (false, Up, In, 105.0, [1.4653, 3.3721, 7.0846]),
+    ];
+    for (is_call, direction, knock, h, prices) in cases {
+        for (k, expected) in [90.0, 100.0, 110.0].into_iter().zip(prices) {
+            let price = barrier(is_call, k, direction, knock, h).price();
+            assert!(
+                (price - expected).abs() < 1e-4,
+                "{is_call} {direction:?} {knock:?} {k}: {price} != {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn in_plus_out_is_vanilla() {
+    for is_call in [true, false] {
+        for (direction, h) in [(Direction::Down, 92.0), (Direction::Up, 108.0)] {
+            for k in [85.0, 100.0, 115.0] {
+                let option = |knock| {
+                    BarrierOption::new(is_call, 100.0, k, 0.03, 0.01, 1.0, direction, knock, h)
+                        .with_implied_vol(0.3)
+                };
+                let (knock_in, knock_out) = (option(Knock::In), option(Knock::Out));
+                let vanilla = knock_in.vanilla().price();
+                let sum = knock_in.price() + knock_out.price();
+                assert!(
+                    (sum - vanilla).abs() < 1e-8,
+                    "{is_call} {direction:?} {k} {sum} {vanilla}"
+                );
+            }
+        }
+    }
+
+    // through the barrier the option has knocked already
+    let breached = barrier(true, 100.0, Direction::Down, Knock::Out, 101.0);
+    assert!(breached.is_breached());
+    assert_eq!(breached.price(), 3.0);
+    let knocked_in = barrier(true, 100.0, Direction::Down, Knock::In, 101.0);
+    assert_eq!(knocked_in.price(), knocked_in.vanilla().price());
+}
+
+fn corridor(is_call: bool, knock: Knock, lower: f64, upper: f64, vol: f64) -> DoubleBarrierOption {
+    DoubleBarrierOption::new(is_call, 100.0, 100.0, 0.1, 0.0, 0.25, knock, lower, upper)
+        .with_implied_vol(vol)
+}
+
+#[test]
+fn double_barrier_matches_eigenfunction_expansion() {
+    // reference values from the sine series solution of the killed diffusion; the calls agree
+    // with Haug's table 4-22 to its four decimals
+    let calls = [
+        (50.0, 150.0, 4.351472431964533),
+        (60.0, 140.0, 4.350456116420757),
+        (70.0, 130.0, 4.313878795002374),
+        (80.0, 120.0, 3.751600268872127),
+        (90.0, 110.0, 1.205464828914904),
+    ];
+    for (lower, upper, expected) in calls {
+        let price = corridor(true, Knock::Out, lower, upper, 0.15).price();
+        assert!((price - expected).abs() < 1e-8, "{lower} {upper} {price}");
+    }
+    let puts = [
+        (50.0, 150.0, 3.785486151293712),
+        (80.0, 120.0, 2.686631629863277),
+        (90.0, 110.0, 0.3448949519120652),
+    ];
+    for (lower, upper, expected) in puts {
+        let price = corridor(false, Knock::Out, lower, upper, 0.25).price();
+        assert!((price - expected).abs() < 1e-8, "{lower} {upper} {price}");
+    }
+
+    // strikes outside the corridor
+    let call =
+        DoubleBarrierOption::new(true, 100.0, 85.0, 0.05, 0.03, 1.0, Knock::Out, 90.0, 130.0);
+    assert!((call.with_implied_vol(0.3).price() - 0.8083661983842997).abs() < 1e-8);
+    let put = DoubleBarrierOption::new(
+        false,
+        100.0,
+        140.0,
+        0.05,
+        0.03,
+        1.0,
+        Knock::Out,
+        80.0,
+        125.0,
+    );
+    assert!((put.with_implied_vol(0.3).price() - 5.16645801853001).abs() < 1e-8);
+}
+
+#[test]
+fn double_barrier_limits() {
+    for is_call in [true, false] {
+        let knock_in = corridor(is_call, Knock::In, 85.0, 115.0, 0.3);
+        let knock_out = corridor(is_call, Knock::Out, 85.0, 115.0, 0.3);
+        let vanilla = knock_in.vanilla().price();
+        assert!((knock_in.price() + knock_out.price() - vanilla).abs() < 1e-10);
+
+        // a distant upper barrier leaves the single down-and-out
+        let far = corridor(is_call, Knock::Out, 85.0, 1e4, 0.3).price();
+        let single = BarrierOption::new(
+            is_call,
+            100.0,
+            100.0,
+            0.1,
+            0.0,
+            0.25,
+            Direction::Down,
+            Knock::Out,
+            85.0,
+        )
+        .with_implied_vol(0.3)
+        .price();
+        assert!((far - single).abs() < 1e-8, "{far} {single}");
+    }
+
+    let breached = corridor(true, Knock::Out, 100.0, 120.0, 0.2);
+    assert!(breached.is_breached());
+    assert_eq!(breached.price(), 0.0);
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    use Direction::{Down, Up};
+    use Knock::{In, Out};
+    for is_call in [true, false] {
+        for (direction, h) in [(Down, 95.0), (Up, 105.0)] {
+            for knock in [In, Out] {
+                for k in [90.0, 100.0, 110.0] {
+                    let option = barrier(is_call, k, direction, knock, h);
+                    let at = |s: f64, vol: f64| {
+                        BarrierOption {
+                            s,
+                            ..option.clone()
+                        }
+                        .with_implied_vol(vol)
+                        .price()
+                    };
+                    let (ds, dv) = (1e-3, 1e-5);
+                    let delta = (at(100.0 + ds, 0.25) - at(100.0 - ds, 0.25)) / (2.0 * ds);
+                    let gamma = (at(100.0 + ds, 0.25) - 2.0 * at(100.0, 0.25)
+                        + at(100.0 - ds, 0.25))
+                        / (ds * ds);
+                    let vega = (at(100.0, 0.25 + dv) - at(100.0, 0.25 - dv)) / (2.0 * dv) * 0.01;
+                    assert!((option.delta() - delta).abs() < 1e-6);
+                    assert!((option.gamma() - gamma).abs() < 1e-6);
+                    assert!((option.vega() - vega).abs() < 1e-6);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn greeks_next_to_the_barrier() {
+    // a hair above the barrier the down-and-out call is worth about its rebate but very
+    // sensitive to the spot; the closed-form Greeks stay smooth where bumps would straddle it
+    let option = |s: f64| {
+        BarrierOption::new(
+            true,
+            s,
+            100.0,
+            0.08,
+            0.04,
+            0.5,
+            Direction::Down,
+            Knock::Out,
+            95.0,
+        )
+        .with_implied_vol(0.25)
+    };
+    let near = option(95.0001);
+    assert!(near.price() < 1e-3);
+    let delta = (option(95.0002).price() - near.price()) / 1e-4;
+    assert!((near.delta() - delta).abs() < 1e-3 * delta);
+    assert!(near.gamma() < 0.0);
+
+    // once breached a knock-out is a fixed rebate and a knock-in the vanilla
+    let breached = option(94.0);
+    assert_eq!(
+        (breached.delta(), breached.gamma(), breached.vega()),
+        (0.0, 0.0, 0.0)
+    );
+    let knocked_in = BarrierOption {
+        knock: Knock::In,
+        ..breached
+    };
+    assert_eq!(knocked_in.delta(), knocked_in.vanilla().delta());
+}
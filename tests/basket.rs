@@ -0,0 +1,138 @@
+use blackscholes::basket::{BasketApproximation, BasketOption};
+use blackscholes::correlation::CorrelationRisk;
+use blackscholes::{quad, Black76Inputs, OptionInputs};
+
+const LOGNORMAL: BasketApproximation = BasketApproximation::Lognormal;
+const SHIFTED: BasketApproximation = BasketApproximation::ShiftedLognormal;
+
+fn two_assets(is_call: bool, weights: [f64; 2], k: f64, rho: f64) -> BasketOption {
+    BasketOption::new(
+        is_call,
+        vec![100.0, 90.0],
+        weights.to_vec(),
+        vec![0.3, 0.2],
+        vec![vec![1.0, rho], vec![rho, 1.0]],
+        k,
+        0.04,
+        1.0,
+    )
+    .with_dividends(vec![0.01, 0.02])
+}
+
+/// Price of a two-asset basket by conditioning on the second asset, given which the first is
+/// lognormal and the option is a Black-76 option on it.
+fn conditional_price(option: &BasketOption) -> f64 {
+    let f = option.weighted_forwards();
+    let (w1, vol1, vol2) = (option.weights[0], option.vols[0], option.vols[1]);
+    let rho = option.correlation[0][1];
+    let (t, sd) = (option.t, option.t.sqrt());
+    let sign = if option.is_call { 1.0 } else { -1.0 };
+    let conditional = |z: f64| {
+        let leg2 = f[1] * (vol2 * sd * z - 0.5 * vol2 * vol2 * t).exp();
+        let vol = vol1 * (1.0 - rho * rho).sqrt();
+        let leg1 = f[0] * (rho * vol1 * sd * z - 0.5 * rho * rho * vol1 * vol1 * t).exp();
+        // the option on w1 S1 struck at K - w2 S2, per unit of w1
+        let strike = (option.k - leg2) / w1;
+        let forward = leg1 / w1;
+        let value = if strike <= 0.0 {
+            (sign * (forward - strike)).max(0.0)
+        } else {
+            Black76Inputs::new(option.is_call, forward, strike, 0.0, t)
+                .with_implied_vol(vol)
+                .price()
+        };
+        w1 * value * (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+    };
+    quad::integrate_legendre(conditional, -9.0, 9.0, 200) * (-option.r * t).exp()
+}
+
+#[test]
+fn reduces_to_bsm() {
+    let single = BasketOption::new(
+        true,
+        vec![100.0],
+        vec![2.0],
+        vec![0.25],
+        vec![vec![1.0]],
+        210.0,
+        0.05,
+        0.5,
+    )
+    .with_dividends(vec![0.02]);
+    let vanilla = OptionInputs::new(true, 100.0, 105.0, 0.05, 0.02, 0.5).with_implied_vol(0.25);
+    for approximation in [LOGNORMAL, SHIFTED] {
+        let price = single.clone().with_approximation(approximation).price();
+        assert!((price - 2.0 * vanilla.price()).abs() < 1e-9);
+    }
+    assert!((single.effective_vol() - 0.25).abs() < 1e-12);
+
+    // perfectly correlated assets with one vol make a lognormal basket
+    let basket = BasketOption::new(
+        false,
+        vec![60.0, 40.0],
+        vec![1.0, 1.0],
+        vec![0.2, 0.2],
+        vec![vec![1.0, 1.0], vec![1.0, 1.0]],
+        95.0,
+        0.03,
+        1.0,
+    );
+    let vanilla = OptionInputs::new(false, 100.0, 95.0, 0.03, 0.0, 1.0).with_implied_vol(0.2);
+    assert!((basket.price() - vanilla.price()).abs() < 1e-9);
+}
+
+#[test]
+fn moments_match_the_conditional_integral() {
+    // the conditional price at zero strike is the forward
+    let option = two_assets(true, [1.0, 1.0], 0.0, 0.5);
+    let forward = option.forward() * (-0.04_f64).exp();
+    assert!((conditional_price(&option) - forward).abs() < 1e-9);
+
+    for (weights, k, rho) in [([1.0, 1.0], 190.0, 0.5), ([0.5, 0.5], 90.0, -0.3)] {
+        for is_call in [true, false] {
+            let option = two_assets(is_call, weights, k, rho);
+            let exact = conditional_price(&option);
+            let lognormal = option.price();
+            let shifted = option.clone().with_approximation(SHIFTED).price();
+            assert!((lognormal - exact).abs() < 5e-2 * exact);
+            assert!((shifted - exact).abs() < 0.5 * (lognormal - exact).abs());
+        }
+    }
+}
+
+#[test]
+fn shifted_lognormal_handles_spreads() {
+    // a basket long one asset and short another can finish negative and has no lognormal fit
+    for (k, rho) in [(10.0, 0.8), (-5.0, 0.5), (20.0, 0.2)] {
+        for is_call in [true, false] {
+            let option = two_assets(is_call, [1.0, -1.0], k, rho).with_approximation(SHIFTED);
+            let exact = conditional_price(&option);
+            assert!((option.price() - exact).abs() < 2.5e-2 * exact);
+        }
+    }
+
+    // put-call parity holds
+    let call = two_assets(true, [1.0, -1.0], 10.0, 0.8).with_approximation(SHIFTED);
+    let put = two_assets(false, [1.0, -1.0], 10.0, 0.8).with_approximation(SHIFTED);
+    let forward = (call.forward() - 10.0) * (-0.04_f64).exp();
+    assert!((call.price() - put.price() - forward).abs() < 1e-10);
+
+    // a basket with a negative forward has no lognormal fit at all
+    let short = two_assets(true, [1.0, -1.2], -5.0, 0.5);
+    assert!(short.forward() < 0.0 && short.price().is_nan());
+    let short = short.with_approximation(SHIFTED);
+    assert!((short.price() - conditional_price(&short)).abs() < 2.5e-2 * short.price());
+}
+
+#[test]
+fn correlation_risk() {
+    let call = two_assets(true, [1.0, 1.0], 190.0, 0.5);
+    assert!(call.cega() > 0.0);
+    let spread = two_assets(true, [1.0, -1.0], 10.0, 0.5).with_approximation(SHIFTED);
+    assert!(spread.cega() < 0.0);
+
+    // correlation raises the basket's vol, and the shift is clamped to a valid correlation
+    let perfect = call.shift_correlation(1.0);
+    assert_eq!(perfect.correlation, vec![vec![1.0, 1.0], vec![1.0, 1.0]]);
+    assert!(call.effective_vol() < perfect.effective_vol());
+}
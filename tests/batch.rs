@@ -0,0 +1,122 @@
+use blackscholes::batch::{self, BatchBuffer, LANES, MAX_ULPS};
+use blackscholes::OptionInputs;
+
+#[test]
+fn vector_path_matches_scalar_within_documented_bound() {
+    let report = batch::differential_check();
+    assert!(report.cases > 5000);
+    assert!(report.within_bound(), "{:?}", report);
+    assert!(report.max_ulps <= MAX_ULPS);
+}
+
+#[test]
+fn remainder_lanes_are_priced() {
+    let mut buffer = BatchBuffer::new();
+    for i in 0..(2 * LANES + 3) {
+        let k = 90.0 + i as f64;
+        buffer
+            .push(&OptionInputs::new(i % 2 == 0, 100.0, k, 0.05, 0.0, 0.5).with_implied_vol(0.25));
+    }
+    let inputs = buffer.as_inputs();
+    let mut out = vec![0.0; inputs.len()];
+    batch::price(&inputs, &mut out);
+    for (i, p) in out.iter().enumerate() {
+        assert!((p - inputs.option(i).price()).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn chunks_cover_batch_in_order() {
+    let mut buffer = BatchBuffer::new();
+    for i in 0..103 {
+        buffer.push(
+            &OptionInputs::new(false, 100.0, 50.0 + i as f64, 0.02, 0.01, 1.0)
+                .with_implied_vol(0.3),
+        );
+    }
+    let inputs = buffer.as_inputs();
+    let mut all = vec![0.0; inputs.len()];
+    batch::price(&inputs, &mut all);
+
+    let chunks = batch::price_chunks(inputs, 30);
+    assert_eq!(chunks.len(), 4);
+    let mut next = 0;
+    for chunk in chunks {
+        assert_eq!(chunk.start, next);
+        assert_eq!(chunk.prices, all[next..next + chunk.prices.len()]);
+        next += chunk.prices.len();
+    }
+    assert_eq!(next, 103);
+}
+
+/// The vector path's normal CDF differs from the scalar one in the last few digits.
+fn close(batch: f64, scalar: f64) -> bool {
+    (batch - scalar).abs() < 1e-10 * scalar.abs().max(1.0)
+}
+
+#[test]
+fn selected_greeks_match_scalar() {
+    use blackscholes::greeks::GreekSet;
+
+    let mut buffer = BatchBuffer::new();
+    for (i, k) in [80.0, 95.0, 100.0, 110.0, 130.0, 150.0]
+        .into_iter()
+        .enumerate()
+    {
+        let option = OptionInputs::new(i % 2 == 0, 100.0, k, 0.04, 0.01, 0.25 + 0.2 * i as f64)
+            .with_implied_vol(0.2 + 0.05 * i as f64);
+        buffer.push(&option);
+    }
+    let inputs = buffer.as_inputs();
+    let greeks = batch::greeks(
+        &inputs,
+        GreekSet::VOMMA | GreekSet::DELTA | GreekSet::THETA | GreekSet::SPEED,
+    );
+    assert!(greeks.gamma.is_empty() && greeks.vega.is_empty());
+    assert_eq!(greeks.delta.len(), inputs.len());
+
+    for i in 0..inputs.len() {
+        let option = inputs.option(i);
+        assert!(close(greeks.delta[i], option.delta()));
+        assert!(close(greeks.theta[i], option.theta()));
+        assert!(close(greeks.vomma[i], option.vomma()));
+    }
+
+    let all = batch::greeks(&inputs, GreekSet::all());
+    let option = inputs.option(5);
+    for (batch, scalar) in [
+        (all.gamma[5], option.gamma()),
+        (all.vega[5], option.vega()),
+        (all.rho[5], option.rho()),
+        (all.vanna[5], option.vanna()),
+    ] {
+        assert!(close(batch, scalar), "{batch} vs {scalar}");
+    }
+}
+
+#[test]
+fn calendar_grid_matches_batch() {
+    use blackscholes::batch::CalendarGrid;
+
+    let strikes = [90.0, 100.0, 110.0];
+    let expiries = [0.1, 0.25, 0.5, 1.0, 2.0];
+    let vols: Vec<f64> = (0..15).map(|i| 0.15 + 0.01 * i as f64).collect();
+    for is_call in [true, false] {
+        let grid = CalendarGrid::new(is_call, 100.0, 0.04, 0.01, &strikes, &expiries, &vols);
+        let mut out = vec![0.0; grid.len()];
+        batch::price_calendar(&grid, &mut out);
+
+        let mut buffer = BatchBuffer::new();
+        for (i, &k) in strikes.iter().enumerate() {
+            for (j, &t) in expiries.iter().enumerate() {
+                let option = OptionInputs::new(is_call, 100.0, k, 0.04, 0.01, t);
+                buffer.push(&option.with_implied_vol(vols[i * expiries.len() + j]));
+            }
+        }
+        let mut expected = vec![0.0; buffer.s.len()];
+        batch::price(&buffer.as_inputs(), &mut expected);
+        for (a, b) in out.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-12, "{a} vs {b}");
+        }
+    }
+}
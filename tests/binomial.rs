@@ -0,0 +1,92 @@
+use blackscholes::binomial::{BinomialTree, Exercise};
+use blackscholes::OptionInputs;
+
+#[test]
+fn european_converges_to_bsm() {
+    for is_call in [true, false] {
+        let option =
+            OptionInputs::new(is_call, 100.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+        let coarse = BinomialTree::new(50, Exercise::European).price(&option);
+        let fine = BinomialTree::new(2000, Exercise::European).price(&option);
+        assert!((fine - option.price()).abs() < 5e-3);
+        assert!((fine - option.price()).abs() < (coarse - option.price()).abs());
+    }
+}
+
+#[test]
+fn american_exercise() {
+    // without dividends an American call is never exercised early
+    let call = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    let american = BinomialTree::new(500, Exercise::American);
+    let european = BinomialTree::new(500, Exercise::European);
+    assert!((american.price(&call) - european.price(&call)).abs() < 1e-12);
+
+    let put = OptionInputs::new(false, 90.0, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+    let price = american.price(&put);
+    assert!(price > european.price(&put) + 1.0);
+    assert!(price > 10.0);
+    assert!((price - put.price_american()).abs() < 0.08);
+}
+
+#[test]
+fn discrete_dividends_drop_the_spot_on_ex_dates() {
+    use blackscholes::american::Dividend;
+
+    // references integrate the option value just after the ex-date over the lognormal spot
+    // before it, exercising there if it pays
+    let cases = [
+        (
+            100.0,
+            0.06,
+            0.3,
+            Dividend::new(0.5, 7.0),
+            [11.106242462849204, 11.656463453452516],
+        ),
+        (
+            90.0,
+            0.05,
+            0.25,
+            Dividend::new(0.9, 5.0),
+            [14.98791127133915, 17.519276761068311],
+        ),
+    ];
+    for (k, r, vol, dividend, [european, american]) in cases {
+        let call = OptionInputs::new(true, 100.0, k, r, 0.0, 1.0).with_implied_vol(vol);
+        let price =
+            |exercise| BinomialTree::new(2000, exercise).price_with_dividends(&call, &[dividend]);
+        assert!((price(Exercise::European) - european).abs() < 5e-3);
+        assert!((price(Exercise::American) - american).abs() < 5e-3);
+    }
+
+    // dividends after expiry change nothing, and an early dividend makes puts dearer
+    let put = OptionInputs::new(false, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    let tree = BinomialTree::new(500, Exercise::American);
+    let late = tree.price_with_dividends(&put, &[Dividend::new(1.5, 3.0)]);
+    assert_eq!(late, tree.price(&put));
+    assert!(tree.price_with_dividends(&put, &[Dividend::new(0.3, 3.0)]) > tree.price(&put) + 1.0);
+}
+
+#[test]
+fn extrapolation_beats_twice_the_steps() {
+    use blackscholes::extrapolation::richardson;
+
+    // an error linear in the step is removed exactly
+    let price = |h: f64| 5.0 + 0.3 * h;
+    assert!((richardson(price(0.2), price(0.1), 2.0, 1.0) - 5.0).abs() < 1e-12);
+
+    // references extrapolated from smoothed trees of 20000 and 40000 steps
+    for (s, reference) in [
+        (90.0, 11.133181542100287),
+        (100.0, 5.522363508970075),
+        (110.0, 2.4532641493200367),
+    ] {
+        let put = OptionInputs::new(false, s, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+        let extrapolated = BinomialTree::new(400, Exercise::American).price_extrapolated(&put);
+        let plain = BinomialTree::new(800, Exercise::American).price(&put);
+        assert!(
+            (extrapolated - reference).abs() < 3e-4,
+            "{s} {extrapolated}"
+        );
+        assert!((extrapolated - reference).abs() < (plain - reference).abs());
+    }
+}
@@ -0,0 +1,113 @@
+use blackscholes::{Black76Inputs, OptionInputs};
+
+// Black-76 on a forward is BSM on a spot with the dividend yield equal to the rate.
+fn pair(is_call: bool, k: f64) -> (Black76Inputs, OptionInputs) {
+    let (f, r, t, vol) = (100.0, 0.05, 0.5, 0.25);
+    (
+        Black76Inputs::new(is_call, f, k, r, t).with_implied_vol(vol),
+        OptionInputs::new(is_call, f, k, r, r, t).with_implied_vol(vol),
+    )
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-10 * (1.0 + b.abs()), "{} != {}", a, b);
+}
+
+#[test]
+fn greeks_match_bsm_with_q_equal_r() {
+    for is_call in [true, false] {
+        for k in [80.0, 100.0, 125.0] {
+            let (black, bsm) = pair(is_call, k);
+            assert_close(black.price(), bsm.price());
+            assert_close(black.delta(), bsm.delta());
+            assert_close(black.gamma(), bsm.gamma());
+            assert_close(black.theta(), bsm.theta());
+            assert_close(black.vega(), bsm.vega());
+            assert_close(black.lambda(), bsm.lambda());
+            assert_close(black.vanna(), bsm.vanna());
+            assert_close(black.charm(), bsm.charm());
+            assert_close(black.veta(), bsm.veta());
+            assert_close(black.vomma(), bsm.vomma());
+            assert_close(black.speed(), bsm.speed());
+            assert_close(black.zomma(), bsm.zomma());
+            assert_close(black.color(), bsm.color());
+            assert_close(black.ultima(), bsm.ultima());
+            assert_close(black.dual_delta(), bsm.dual_delta());
+            assert_close(black.dual_gamma(), bsm.dual_gamma());
+        }
+    }
+}
+
+#[test]
+fn rho_moves_rate_and_carry_together() {
+    // With the forward held fixed a rate bump is a joint bump of r and q in BSM terms.
+    for is_call in [true, false] {
+        let (black, bsm) = pair(is_call, 110.0);
+        assert_close(black.rho(), bsm.rho() + 0.01 * bsm.epsilon());
+    }
+}
+
+#[test]
+fn implied_vol_round_trips() {
+    for is_call in [true, false] {
+        for k in [70.0, 100.0, 140.0] {
+            let option = Black76Inputs::new(is_call, 100.0, k, 0.05, 0.5).with_implied_vol(0.3);
+            let solved =
+                Black76Inputs::new(is_call, 100.0, k, 0.05, 0.5).with_price(option.price());
+            assert_close(solved.implied_vol(), 0.3);
+            assert_close(solved.delta(), option.delta());
+        }
+    }
+
+    // a price below the discounted intrinsic value admits no vol
+    let solved = Black76Inputs::new(true, 100.0, 80.0, 0.05, 0.5).with_price(15.0);
+    assert!(solved.implied_vol().is_nan());
+    assert_eq!(solved.price(), 15.0);
+}
+
+#[test]
+fn shifted_lognormal_handles_negative_rates() {
+    let (f, k, r, t, shift, vol) = (-0.002, 0.001, 0.01, 2.0, 0.03, 0.2);
+    let shifted = |f: f64| {
+        Black76Inputs::new(true, f, k, r, t)
+            .with_shift(shift)
+            .with_implied_vol(vol)
+    };
+    let option = shifted(f);
+    let plain = Black76Inputs::new(true, f + shift, k + shift, r, t).with_implied_vol(vol);
+    assert_close(option.price(), plain.price());
+    assert_close(option.vega(), plain.vega());
+
+    // Greeks are with respect to the unshifted forward
+    let h = 1e-6;
+    let fd_delta = (shifted(f + h).price() - shifted(f - h).price()) / (2.0 * h);
+    let fd_gamma = (shifted(f + h).delta() - shifted(f - h).delta()) / (2.0 * h);
+    assert!((option.delta() - fd_delta).abs() < 1e-8);
+    assert!((option.gamma() - fd_gamma).abs() < 1e-4 * option.gamma());
+
+    let solved = Black76Inputs::new(true, f, k, r, t)
+        .with_shift(shift)
+        .with_price(option.price());
+    assert_close(solved.implied_vol(), vol);
+    assert_eq!(solved.shifted_strike(), k + shift);
+
+    // the shift can be set in either order
+    let late = Black76Inputs::new(true, f, k, r, t)
+        .with_implied_vol(vol)
+        .with_shift(shift);
+    assert_close(late.price(), option.price());
+    assert_close(late.delta(), option.delta());
+}
+
+#[test]
+fn spot_options_shift_on_their_forward() {
+    let option = OptionInputs::new(false, 100.0, 95.0, 0.04, 0.01, 0.75).with_implied_vol(0.3);
+    assert_close(option.shifted(0.0).price(), option.price());
+
+    // a put on a spread struck below zero, solved back from its shifted price
+    let spread = OptionInputs::new(false, 2.0, -1.0, 0.04, 0.04, 1.0).with_implied_vol(0.25);
+    let shifted = spread.shifted(10.0);
+    assert!(shifted.price() > 0.0);
+    let quoted = OptionInputs::new(false, 2.0, -1.0, 0.04, 0.04, 1.0).shifted(10.0);
+    assert_close(quoted.with_price(shifted.price()).implied_vol(), 0.25);
+}
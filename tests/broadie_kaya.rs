@@ -0,0 +1,85 @@
+use blackscholes::broadie_kaya::BroadieKaya;
+use blackscholes::heston::{Heston, HestonOption};
+use blackscholes::ncx2;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn variance_moments_match_the_square_root_process() {
+    let model = Heston::new(0.03, 1.2, 0.05, 0.6, -0.7);
+    let t = 2.0;
+    let sampler = BroadieKaya::new(model, t);
+    let decay = (-model.kappa * t).exp();
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let n = 40_000;
+    let terminal = (0..n)
+        .map(|_| sampler.sample_variance(&mut rng))
+        .sum::<f64>()
+        / n as f64;
+    let expected_terminal = model.theta + (model.v0 - model.theta) * decay;
+    assert!(
+        (terminal / expected_terminal - 1.0).abs() < 0.035,
+        "{terminal}"
+    );
+
+    // the conditional means of the integrated variance average to its unconditional mean over
+    // the noncentral chi-square density of the terminal variance, integrated in the cube root
+    // to handle its singularity at zero
+    let sigma2 = model.sigma * model.sigma;
+    let scale = sigma2 * (1.0 - decay) / (4.0 * model.kappa);
+    let dof = 4.0 * model.kappa * model.theta / sigma2;
+    let noncentrality = model.v0 * decay / scale;
+    let h = 5e-4;
+    let integrated: f64 = (0..20_000)
+        .map(|i| {
+            let u = (i as f64 + 0.5) * h;
+            let x = u * u * u;
+            let weight = ncx2::pdf(x, dof, noncentrality) * 3.0 * u * u * h;
+            weight * sampler.integrated_variance_moments(scale * x).0
+        })
+        .sum();
+    let expected_integrated =
+        model.theta * t + (model.v0 - model.theta) * (1.0 - decay) / model.kappa;
+    assert!(
+        (integrated / expected_integrated - 1.0).abs() < 1e-6,
+        "{integrated}"
+    );
+
+    // the integrated variance is pinned near the average of its ends over short horizons
+    let short = BroadieKaya::new(model, 0.01);
+    let (mean, variance) = short.integrated_variance_moments(0.04);
+    assert!((mean / (0.5 * (0.03 + 0.04) * 0.01) - 1.0).abs() < 0.01);
+    assert!(variance.sqrt() < 0.1 * mean);
+}
+
+#[test]
+fn prices_agree_with_semi_analytic() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let models = [
+        // Fang and Oosterlee's parameters break the Feller condition
+        Heston::new(0.0175, 1.5768, 0.0398, 0.5751, -0.5711),
+        Heston::new(0.04, 2.0, 0.04, 0.3, -0.5),
+    ];
+    for model in models {
+        for (is_call, k) in [(true, 100.0), (true, 120.0), (false, 85.0)] {
+            let option = HestonOption::new(is_call, 100.0, k, 0.03, 0.01, 1.0, model);
+            let mc = option.monte_carlo(20_000, &mut rng);
+            assert!(mc.z_score().abs() < 4.0, "{is_call} {k} {mc:?}");
+        }
+    }
+}
+
+#[test]
+fn a_variance_pinned_at_zero_is_drawn_as_zero() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let sampler = BroadieKaya::new(Heston::new(0.0, 1.5, 0.0, 0.4, -0.5), 1.0);
+    for _ in 0..100 {
+        let v_t = sampler.sample_variance(&mut rng);
+        assert_eq!(v_t, 0.0);
+        assert_eq!(sampler.sample_integrated_variance(v_t, &mut rng), 0.0);
+    }
+    // with no variance the spot grows at the carry
+    let s_t = sampler.sample_spot(100.0, 0.03, 0.01, &mut rng);
+    assert!((s_t - 100.0 * 0.02f64.exp()).abs() < 1e-9, "{s_t}");
+}
@@ -0,0 +1,56 @@
+#![cfg(all(unix, target_endian = "little"))]
+
+use blackscholes::bulk::{self, BulkRecord};
+use blackscholes::OptionInputs;
+
+#[test]
+fn prices_memory_mapped_file() {
+    let dir = std::env::temp_dir();
+    let input = dir.join(format!("bs-bulk-in-{}.bin", std::process::id()));
+    let output = dir.join(format!("bs-bulk-out-{}.bin", std::process::id()));
+
+    let records: Vec<BulkRecord> = (0..1001)
+        .map(|i| {
+            BulkRecord::new(
+                i % 3 == 0,
+                100.0,
+                60.0 + 0.08 * i as f64,
+                0.03,
+                0.01,
+                0.75,
+                0.25,
+            )
+        })
+        .collect();
+    bulk::write_input(&input, &records).unwrap();
+
+    assert_eq!(bulk::price_file(&input, &output).unwrap(), 1001);
+    let prices = bulk::read_output(&output).unwrap();
+    assert_eq!(prices.len(), 1001);
+    for (r, p) in records.iter().zip(&prices) {
+        let expected = OptionInputs::new(r.is_call == 1, r.s, r.k, r.r, r.q, r.t)
+            .with_implied_vol(r.implied_vol)
+            .price();
+        assert!((p - expected).abs() < 1e-10);
+    }
+
+    // writing over the input would truncate it while it is mapped
+    assert!(bulk::price_file(&input, &input).is_err());
+    assert_eq!(std::fs::metadata(&input).unwrap().len(), 16 + 56 * 1001);
+
+    std::fs::write(&input, b"not a bulk file!").unwrap();
+    assert!(bulk::price_file(&input, &output).is_err());
+
+    // record counts beyond the file, including ones whose size overflows, are rejected
+    for n in [1002, u64::MAX / 56 + 2, u64::MAX] {
+        let mut bytes = bulk::INPUT_MAGIC.to_vec();
+        bytes.extend_from_slice(&n.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 56]);
+        std::fs::write(&input, bytes).unwrap();
+        let error = bulk::price_file(&input, &output).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    std::fs::remove_file(input).unwrap();
+    std::fs::remove_file(output).unwrap();
+}
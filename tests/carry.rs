@@ -0,0 +1,87 @@
+use blackscholes::{Black76Inputs, OptionInputs};
+
+#[test]
+fn zero_carry_is_black76() {
+    let option =
+        OptionInputs::cost_of_carry(true, 80.0, 85.0, 0.04, 0.0, 0.5).with_implied_vol(0.3);
+    let futures = Black76Inputs::new(true, 80.0, 85.0, 0.04, 0.5).with_implied_vol(0.3);
+    assert_eq!(option.carry(), 0.0);
+    assert!((option.price() - futures.price()).abs() < 1e-12);
+}
+
+#[test]
+fn storage_cost_raises_calls() {
+    let stored = OptionInputs::commodity(true, 60.0, 65.0, 0.03, 0.05, 0.01, 1.0);
+    assert!((stored.q + 0.04).abs() < 1e-15);
+    assert!((stored.carry() - 0.07).abs() < 1e-15);
+
+    let plain = OptionInputs::new(true, 60.0, 65.0, 0.03, 0.0, 1.0).with_implied_vol(0.25);
+    let stored = stored.with_implied_vol(0.25);
+    assert!(stored.price() > plain.price());
+
+    // the price still inverts with a negative yield
+    let implied = OptionInputs::commodity(true, 60.0, 65.0, 0.03, 0.05, 0.01, 1.0)
+        .with_price(stored.price())
+        .implied_vol();
+    assert!((implied - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn generalized_constructor_covers_underlyings() {
+    use blackscholes::carry::Carry;
+
+    let (s, k, r, t) = (100.0, 95.0, 0.05, 0.75);
+    let price = |carry: Carry| {
+        OptionInputs::generalized(false, s, k, r, carry, t)
+            .with_implied_vol(0.2)
+            .price()
+    };
+    let direct = |q: f64| {
+        OptionInputs::new(false, s, k, r, q, t)
+            .with_implied_vol(0.2)
+            .price()
+    };
+
+    assert_eq!(price(Carry::Stock), direct(0.0));
+    assert_eq!(price(Carry::Dividend(0.02)), direct(0.02));
+    assert_eq!(price(Carry::Futures), direct(r));
+    assert_eq!(price(Carry::Fx { foreign_rate: 0.01 }), direct(0.01));
+    let commodity = Carry::Commodity {
+        storage_cost: 0.03,
+        convenience_yield: 0.01,
+    };
+    assert!((commodity.yield_for(r) + 0.02).abs() < 1e-15);
+    assert_eq!(price(Carry::Custom(0.0)), price(Carry::Futures));
+}
+
+#[test]
+fn funding_curve_sets_the_forward() {
+    use blackscholes::carry::{FundingCurve, FundingPrint};
+
+    // one basis point every 8 hours is about 11% a year
+    let print = FundingPrint::new(0.0, 0.0001, 8.0);
+    assert!((print.annualized() - 0.0001 * 3.0 * 365.25).abs() < 1e-15);
+
+    let prints = [
+        FundingPrint::new(0.0, 0.0001, 8.0),
+        FundingPrint::new(1.0 / 1095.75, 0.0003, 8.0),
+    ];
+    let curve = FundingCurve::from_prints(&prints, 1.0 / 1095.75).unwrap();
+    // the older print carries half the weight
+    let expected = (2.0 * prints[0].annualized() + prints[1].annualized()) / 3.0;
+    assert!((curve.average(0.5) - expected).abs() < 1e-12);
+    assert!(FundingCurve::from_prints(&[], 1.0).is_none());
+
+    let (s, k, r, t) = (60000.0, 65000.0, 0.05, 0.25);
+    let option = curve.option(true, s, k, r, t).with_implied_vol(0.6);
+    let forward = curve.forward(s, t);
+    assert!((s * ((r - option.q) * t).exp() / forward - 1.0).abs() < 1e-12);
+
+    // rich funding decays towards its long-run level
+    let decaying = FundingCurve::mean_reverting(0.3, 0.1, 4.0, vec![0.25, 0.5, 1.0]);
+    assert!(decaying.rates.windows(2).all(|w| w[0] > w[1]));
+    assert!(decaying.average(2.0) < decaying.average(0.1));
+    let stepped = FundingCurve::new(vec![0.5, 1.0], vec![0.2, 0.1]);
+    assert!((stepped.average(0.75) - (0.2 * 0.5 + 0.1 * 0.25) / 0.75).abs() < 1e-15);
+    assert!((stepped.average(2.0) - 0.125).abs() < 1e-15);
+}
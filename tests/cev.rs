@@ -0,0 +1,76 @@
+use blackscholes::cev::{Cev, CevOption};
+use blackscholes::sabr::Sabr;
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+#[test]
+fn unit_elasticity_is_bsm_and_parity_holds() {
+    let bsm = OptionInputs::new(true, 100.0, 110.0, 0.05, 0.02, 1.0).with_implied_vol(0.25);
+    let exact = CevOption::new(true, 100.0, 110.0, 0.05, 0.02, 1.0, Cev::new(0.25, 1.0));
+    assert_eq!(exact.price(), bsm.price());
+    let near = Cev::with_local_vol(0.25, 100.0, 0.999);
+    let near = CevOption::new(true, 100.0, 110.0, 0.05, 0.02, 1.0, near);
+    assert!(
+        (near.price() - bsm.price()).abs() < 0.01,
+        "{}",
+        near.price()
+    );
+
+    let model = Cev::with_local_vol(0.3, 100.0, 0.5);
+    for (r, q) in [(0.05, 0.02), (0.03, 0.03)] {
+        let call = CevOption::new(true, 100.0, 95.0, r, q, 2.0, model).price();
+        let put = CevOption::new(false, 100.0, 95.0, r, q, 2.0, model).price();
+        let forward = 100.0 * (-q * 2.0_f64).exp() - 95.0 * (-r * 2.0_f64).exp();
+        assert!((call - put - forward).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn agrees_with_monte_carlo() {
+    let (s, k, r, t) = (100.0, 90.0, 0.05, 1.0);
+    let model = Cev::with_local_vol(0.25, s, 0.5);
+    let exact = CevOption::new(false, s, k, r, 0.0, t, model).price();
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let (paths, steps) = (20000, 50);
+    let dt = t / steps as f64;
+    let payoffs: Vec<f64> = (0..paths)
+        .map(|_| {
+            let mut x: f64 = s;
+            for _ in 0..steps {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                x += r * x * dt + model.sigma * x.powf(model.beta) * (dt.sqrt() * z);
+                x = x.max(0.0);
+            }
+            (k - x).max(0.0) * (-r * t).exp()
+        })
+        .collect();
+    let mean = payoffs.iter().sum::<f64>() / paths as f64;
+    let var = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (paths - 1) as f64;
+    let std_error = (var / paths as f64).sqrt();
+    assert!(
+        (mean - exact).abs() < 4.0 * std_error,
+        "{mean} {exact} {std_error}"
+    );
+}
+
+#[test]
+fn elasticity_below_one_skews_implied_vols() {
+    let (s, t) = (100.0, 0.5);
+    let model = Cev::with_local_vol(0.2, s, 0.4);
+    let vol = |k: f64| CevOption::new(k >= s, s, k, 0.0, 0.0, t, model).implied_vol();
+    assert!(vol(80.0) > vol(100.0) && vol(100.0) > vol(120.0));
+    assert!((vol(100.0) - 0.2).abs() < 1e-3);
+
+    for k in [80.0, 100.0, 120.0] {
+        assert!(
+            (model.approximate_implied_vol(s, k, t) - vol(k)).abs() < 1e-3,
+            "{k}"
+        );
+        // SABR without vol of vol is CEV
+        let sabr = Sabr::new(model.sigma, model.beta, 0.0, 0.0);
+        assert!((sabr.implied_vol(s, k, t) - vol(k)).abs() < 1e-3, "{k}");
+    }
+}
@@ -0,0 +1,151 @@
+use blackscholes::chain::{OptionChain, OptionQuote, OutlierRejection};
+use blackscholes::OptionInputs;
+
+fn quote_at(vol: f64, half_spread: f64, venue: &str) -> OptionQuote {
+    let price = OptionInputs::new(true, 100.0, 105.0, 0.03, 0.0, 0.5)
+        .with_implied_vol(vol)
+        .price();
+    OptionQuote::new(true, 105.0, 0.5, price - half_spread, price + half_spread).with_venue(venue)
+}
+
+#[test]
+fn aggregates_venues_and_rejects_outlier() {
+    let chain = OptionChain::new(
+        100.0,
+        0.03,
+        0.0,
+        vec![
+            quote_at(0.20, 0.05, "A"),
+            quote_at(0.21, 0.05, "B").with_size(3.0),
+            quote_at(0.205, 0.05, "C"),
+            quote_at(0.35, 0.05, "D"),
+            OptionQuote::new(false, 95.0, 0.5, 2.0, 2.2),
+        ],
+    );
+
+    let aggregated = chain.aggregate(&OutlierRejection::default());
+    assert_eq!(aggregated.len(), 2);
+    let call = aggregated.iter().find(|a| a.is_call).unwrap();
+    assert_eq!(call.accepted, 3);
+    assert_eq!(call.rejected, 1);
+    // Venue B carries three times the weight
+    assert!(call.implied_vol > 0.205 && call.implied_vol < 0.21);
+
+    let solved = chain.solve();
+    assert!((solved[0].mid_vol - 0.2).abs() < 1e-4);
+    assert!(solved[0].bid_vol < solved[0].mid_vol && solved[0].mid_vol < solved[0].ask_vol);
+}
+
+#[test]
+fn implied_carry_from_parity() {
+    let (s, r, q) = (100.0, 0.04, 0.015);
+    let mut quotes = Vec::new();
+    for t in [0.25, 1.0] {
+        for k in [80.0, 90.0, 100.0, 110.0, 120.0] {
+            for is_call in [true, false] {
+                let p = OptionInputs::new(is_call, s, k, r, q, t)
+                    .with_implied_vol(0.25)
+                    .price();
+                quotes.push(OptionQuote::new(is_call, k, t, p - 0.05, p + 0.05));
+            }
+        }
+    }
+    // a stale put mid at one strike
+    quotes.push(OptionQuote::new(true, 95.0, 0.25, 9.0, 9.2));
+    quotes.push(OptionQuote::new(false, 95.0, 0.25, 0.5, 0.6));
+
+    let chain = OptionChain::new(s, 0.0, 0.0, quotes);
+    let carry = chain.implied_carry(3.0);
+    assert_eq!(carry.len(), 2);
+    assert_eq!(carry[0].rejected, 1);
+    assert_eq!(carry[1].accepted, 5);
+    for c in &carry {
+        assert!((c.r - r).abs() < 1e-8, "{}", c.r);
+        assert!((c.q - q).abs() < 1e-8, "{}", c.q);
+        assert!((c.forward - s * ((r - q) * c.t).exp()).abs() < 1e-6);
+    }
+    assert_eq!(carry[1].option(true, 100.0).q, carry[1].q);
+}
+
+#[test]
+fn diagnostics_flag_bad_quotes() {
+    let quote = |is_call: bool, k: f64| {
+        let p = OptionInputs::new(is_call, 100.0, k, 0.03, 0.0, 0.5)
+            .with_implied_vol(0.25)
+            .price();
+        OptionQuote::new(is_call, k, 0.5, p - 0.05, p + 0.05)
+    };
+    let mut quotes: Vec<OptionQuote> = [90.0, 100.0, 110.0]
+        .iter()
+        .flat_map(|&k| [quote(true, k), quote(false, k)])
+        .collect();
+    let clean = OptionChain::new(100.0, 0.03, 0.0, quotes.clone()).diagnostics(0.01);
+    assert_eq!(
+        (
+            clean.failed_solves,
+            clean.parity_violations,
+            clean.monotonicity_breaks
+        ),
+        (0, 0, 0)
+    );
+    assert!(clean
+        .mean_vol_spread
+        .is_some_and(|spread| spread > 0.0 && spread < 0.01));
+
+    // a call at 110 priced above the 100 call breaks monotonicity and parity, and a put below
+    // intrinsic cannot be solved
+    quotes[4] = OptionQuote::new(true, 110.0, 0.5, 12.0, 12.2);
+    quotes.push(OptionQuote::new(false, 130.0, 0.5, 20.0, 20.2));
+    let dirty = OptionChain::new(100.0, 0.03, 0.0, quotes).diagnostics(0.01);
+    assert_eq!(dirty.quotes, 7);
+    assert_eq!(dirty.failed_solves, 1);
+    assert_eq!(dirty.parity_violations, 1);
+    assert_eq!(dirty.monotonicity_breaks, 1);
+
+    // a chain with nothing to solve has no mean spread, and NaN quotes do not upset the sorts
+    let broken = OptionChain::new(
+        100.0,
+        0.03,
+        0.0,
+        vec![
+            OptionQuote::new(true, f64::NAN, 0.5, 1.0, 1.2),
+            OptionQuote::new(true, 100.0, 0.5, f64::NAN, f64::NAN),
+            OptionQuote::new(false, 100.0, f64::NAN, 1.0, 1.2),
+        ],
+    );
+    let diagnostics = broken.diagnostics(0.01);
+    assert_eq!(diagnostics.mean_vol_spread, None);
+    assert_eq!(diagnostics.failed_solves, 3);
+    broken.aggregate(&OutlierRejection::default());
+}
+
+#[test]
+fn bounds_flag_stale_quotes() {
+    let quote = |is_call: bool, k: f64| {
+        let p = OptionInputs::new(is_call, 100.0, k, 0.03, 0.0, 0.5)
+            .with_implied_vol(0.25)
+            .price();
+        OptionQuote::new(is_call, k, 0.5, p - 0.05, p + 0.05)
+    };
+    let mut quotes: Vec<OptionQuote> = [80.0, 90.0, 100.0, 110.0, 120.0]
+        .iter()
+        .flat_map(|&k| [quote(true, k), quote(false, k)])
+        .collect();
+    let chain = OptionChain::new(100.0, 0.03, 0.0, quotes.clone());
+    assert!(chain.check_bounds(0.01).iter().all(|f| !f.any()));
+
+    // a stale 100 call left over from a higher spot breaks convexity and parity but stays in
+    // order, and a 120 put quoted under intrinsic breaks its price bounds
+    quotes[4] = OptionQuote::new(true, 100.0, 0.5, 9.6, 9.8);
+    quotes[9] = OptionQuote::new(false, 120.0, 0.5, 15.0, 15.5);
+    let chain = OptionChain::new(100.0, 0.03, 0.0, quotes);
+    let flags = chain.check_bounds(0.01);
+    assert!(flags[4].convexity && flags[4].parity && !flags[4].price_bounds);
+    assert!(flags[5].parity);
+    assert!(flags[9].price_bounds);
+    assert!(!flags[0].any() && !flags[2].any());
+    assert_eq!(
+        chain.within_bounds(0.01).quotes.len(),
+        10 - flags.iter().filter(|f| f.any()).count()
+    );
+}
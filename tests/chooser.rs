@@ -0,0 +1,62 @@
+use blackscholes::chooser::ChooserOption;
+use blackscholes::{quad, OptionInputs};
+
+/// Price by integrating the better of the call and put over the spot at the choice date.
+fn integrated_price(option: &ChooserOption) -> f64 {
+    let (vol, t) = (option.implied_vol, option.choice_time);
+    let drift = option.r - option.q - 0.5 * vol * vol;
+    let value = |z: f64| {
+        let s = option.s * (drift * t + vol * t.sqrt() * z).exp();
+        let at = |is_call: bool, k: f64, expiry: f64| {
+            OptionInputs::new(is_call, s, k, option.r, option.q, expiry - t)
+                .with_implied_vol(vol)
+                .price()
+        };
+        let better =
+            at(true, option.call_k, option.call_t).max(at(false, option.put_k, option.put_t));
+        better * (-0.5 * z * z).exp()
+    };
+    let kink = ((option.critical_spot() / option.s).ln() - drift * t) / (vol * t.sqrt());
+    let integral = quad::integrate_legendre(value, -10.0, kink, 96)
+        + quad::integrate_legendre(value, kink, 10.0, 96);
+    integral / (2.0 * std::f64::consts::PI).sqrt() * (-option.r * t).exp()
+}
+
+#[test]
+fn haug_reference_values() {
+    // Haug, The Complete Guide to Option Pricing Formulas, sections 4.6.1 and 4.6.2
+    let simple = ChooserOption::simple(50.0, 50.0, 0.08, 0.0, 0.25, 0.5).with_implied_vol(0.25);
+    assert!(simple.is_simple());
+    assert!((simple.price() - 6.1071).abs() < 1e-4);
+    let complex = ChooserOption::complex(50.0, 0.1, 0.05, 0.25, 55.0, 0.5, 48.0, 0.5833)
+        .with_implied_vol(0.35);
+    assert!((complex.price() - 6.0508).abs() < 1e-4);
+}
+
+#[test]
+fn matches_integrated_choice() {
+    let cases = [
+        ChooserOption::simple(100.0, 105.0, 0.05, 0.03, 0.4, 1.0),
+        ChooserOption::complex(100.0, 0.05, 0.03, 0.4, 110.0, 1.0, 95.0, 0.75),
+        ChooserOption::complex(100.0, 0.02, 0.0, 0.1, 90.0, 0.5, 120.0, 1.5),
+    ];
+    for option in cases {
+        let option = option.with_implied_vol(0.3);
+        assert!((option.price() - integrated_price(&option)).abs() < 1e-9);
+
+        // the choice is worth more than either option and less than both
+        let (call, put) = (option.call().price(), option.put().price());
+        assert!(option.price() > call.max(put) && option.price() < call + put);
+    }
+}
+
+#[test]
+fn choice_date_limits() {
+    // choosing today gives the better option, choosing at expiry the straddle
+    let option = |choice_time: f64| {
+        ChooserOption::simple(100.0, 100.0, 0.04, 0.01, choice_time, 0.5).with_implied_vol(0.2)
+    };
+    let (call, put) = (option(0.0).call().price(), option(0.0).put().price());
+    assert!((option(1e-12).price() - call.max(put)).abs() < 1e-6);
+    assert!((option(0.5).price() - (call + put)).abs() < 1e-12);
+}
@@ -0,0 +1,44 @@
+use blackscholes::compare::{self, PricingModel};
+use blackscholes::fourier::Gbm;
+use blackscholes::heston::{Bates, Heston};
+use blackscholes::OptionInputs;
+
+#[test]
+fn compares_models_on_one_contract() {
+    let contract = OptionInputs::new(false, 100.0, 90.0, 0.02, 0.0, 0.5);
+    let heston = Heston::new(0.04, 2.0, 0.04, 0.5, -0.7);
+    let bates = Bates::new(heston, 0.3, -0.15, 0.1);
+    let bsm = Gbm { vol: 0.2 };
+    let models: [&dyn PricingModel; 3] = [&bsm, &heston, &bates];
+
+    let report = compare::compare(&contract, &models).unwrap();
+    assert_eq!(report.results.len(), 3);
+    assert_eq!(report.results[0].name, "BSM");
+
+    let bsm_result = &report.results[0];
+    let exact = contract.clone().with_implied_vol(0.2);
+    assert!((bsm_result.price - exact.price()).abs() < 1e-12);
+    assert!((bsm_result.delta - exact.delta()).abs() < 1e-5);
+    assert!((bsm_result.gamma - exact.gamma()).abs() < 1e-4);
+    assert!((bsm_result.implied_vol - 0.2).abs() < 1e-10);
+    assert!((bsm_result.vega - exact.vega()).abs() < 1e-9);
+    assert!((bsm_result.theta - exact.theta()).abs() < 1e-4);
+
+    // negative spot-vol correlation and downward jumps both richen the downside put
+    assert!(report.results[1].implied_vol > 0.2);
+    assert!(report.results[2].price > report.results[1].price);
+    let price = report.price.unwrap();
+    assert_eq!(price.max, report.results[2].price);
+    assert!(price.range() > 0.0 && price.std_dev > 0.0);
+    assert!(report.vega.unwrap().range() > 0.0);
+    assert_eq!(
+        report.theta.unwrap().max,
+        report
+            .results
+            .iter()
+            .map(|r| r.theta)
+            .fold(f64::MIN, f64::max)
+    );
+
+    assert!(compare::compare(&contract, &[]).is_none());
+}
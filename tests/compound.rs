@@ -0,0 +1,74 @@
+use blackscholes::compound::CompoundOption;
+use blackscholes::{quad, OptionInputs};
+
+fn compound(is_call: bool, underlying_is_call: bool, k: f64, underlying_k: f64) -> CompoundOption {
+    let underlying = OptionInputs::new(underlying_is_call, 500.0, underlying_k, 0.08, 0.05, 0.5)
+        .with_implied_vol(0.35);
+    CompoundOption::new(is_call, k, 0.25, underlying)
+}
+
+/// Price by integrating the compound's payoff over the lognormal spot at its expiry.
+fn integrated_price(option: &CompoundOption) -> f64 {
+    let u = &option.underlying;
+    let (vol, t) = (u.implied_vol, option.t);
+    let sign = if option.is_call { 1.0 } else { -1.0 };
+    let payoff = |z: f64| {
+        let s = u.s * ((u.r - u.q - 0.5 * vol * vol) * t + vol * t.sqrt() * z).exp();
+        let value = OptionInputs::new(u.is_call, s, u.k, u.r, u.q, u.t - t)
+            .with_implied_vol(vol)
+            .price();
+        (sign * (value - option.k)).max(0.0) * (-0.5 * z * z).exp()
+    };
+    // split at the critical spot, where the payoff kinks
+    let kink = option.critical_spot().map_or(0.0, |critical| {
+        ((critical / u.s).ln() - (u.r - u.q - 0.5 * vol * vol) * t) / (vol * t.sqrt())
+    });
+    let integral = quad::integrate_legendre(payoff, -10.0, kink, 96)
+        + quad::integrate_legendre(payoff, kink, 10.0, 96);
+    integral / (2.0 * std::f64::consts::PI).sqrt() * (-u.r * t).exp()
+}
+
+#[test]
+fn matches_integrated_payoff() {
+    for underlying_is_call in [true, false] {
+        for is_call in [true, false] {
+            for (k, underlying_k) in [(50.0, 520.0), (20.0, 480.0), (5.0, 450.0)] {
+                let option = compound(is_call, underlying_is_call, k, underlying_k);
+                let expected = integrated_price(&option);
+                assert!((option.price() - expected).abs() < 1e-8, "{expected}");
+
+                let bumped = |s: f64| {
+                    let mut option = option.clone();
+                    option.underlying =
+                        OptionInputs::new(underlying_is_call, s, underlying_k, 0.08, 0.05, 0.5)
+                            .with_implied_vol(0.35);
+                    option.price()
+                };
+                let delta = (bumped(500.01) - bumped(499.99)) / 0.02;
+                assert!((option.delta() - delta).abs() < 1e-6);
+            }
+        }
+    }
+}
+
+#[test]
+fn parity_and_worthless_puts() {
+    // a call less a put on the same option is a forward purchase of it
+    for underlying_is_call in [true, false] {
+        let call = compound(true, underlying_is_call, 30.0, 500.0);
+        let put = compound(false, underlying_is_call, 30.0, 500.0);
+        let forward = call.underlying.price() - 30.0 * (-0.08_f64 * 0.25).exp();
+        assert!((call.price() - put.price() - forward).abs() < 1e-9);
+    }
+
+    // a put is never worth more than its strike discounted, so a call on it struck above that
+    // is never exercised
+    let call = compound(true, false, 600.0, 500.0);
+    assert_eq!(
+        (call.critical_spot(), call.price(), call.delta()),
+        (None, 0.0, 0.0)
+    );
+    let put = compound(false, false, 600.0, 500.0);
+    let forward = 600.0 * (-0.08_f64 * 0.25).exp() - put.underlying.price();
+    assert!((put.price() - forward).abs() < 1e-12);
+}
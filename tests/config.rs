@@ -0,0 +1,53 @@
+use blackscholes::config::{DayCount, GreekScaling, ModelConfig, ThetaUnit, CONFIG_VERSION};
+use blackscholes::OptionInputs;
+
+#[test]
+fn default_matches_crate_conventions() {
+    let config = ModelConfig::default();
+    assert!(config.check_version().is_ok());
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.02, 0.5).with_implied_vol(0.2);
+    assert_eq!(config.delta(&option), option.delta());
+    assert!((config.vega(&option) - option.vega()).abs() < 1e-15);
+    assert!((config.theta(&option) - option.theta()).abs() < 1e-15);
+    assert_eq!(config.year_fraction(365.25), 1.0);
+
+    let other = config
+        .with_day_count(DayCount::Business252)
+        .with_greeks(GreekScaling {
+            vega: 1.0,
+            rho: 0.0001,
+            theta: ThetaUnit::PerYear,
+        });
+    assert_ne!(other.fingerprint(), config.fingerprint());
+    assert_eq!(other.fingerprint(), other.fingerprint());
+    let negative_zero = config.with_greeks(GreekScaling {
+        rho: -0.0,
+        ..config.greeks
+    });
+    let zero = config.with_greeks(GreekScaling {
+        rho: 0.0,
+        ..config.greeks
+    });
+    assert_eq!(negative_zero, zero);
+    assert_eq!(negative_zero.fingerprint(), zero.fingerprint());
+    assert!((other.vega(&option) - 100.0 * option.vega()).abs() < 1e-12);
+    assert!((other.theta(&option) - 365.25 * option.theta()).abs() < 1e-12);
+
+    let future = ModelConfig {
+        version: CONFIG_VERSION + 1,
+        ..config
+    };
+    assert!(future.check_version().is_err());
+    assert_ne!(future.fingerprint(), config.fingerprint());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let config = ModelConfig::default().with_day_count(DayCount::Actual360);
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains(&format!("\"version\":{CONFIG_VERSION}")));
+    let read: ModelConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, config);
+    assert_eq!(read.fingerprint(), config.fingerprint());
+}
@@ -0,0 +1,44 @@
+use blackscholes::const_eval;
+use blackscholes::OptionInputs;
+
+const TABLE: [f64; 5] = {
+    let mut table = [0.0; 5];
+    let mut i = 0;
+    while i < 5 {
+        table[i] = const_eval::price(false, 100.0, 80.0 + 10.0 * i as f64, 0.03, 0.01, 0.5, 0.25);
+        i += 1;
+    }
+    table
+};
+
+#[test]
+fn elementary_functions_match_std() {
+    for x in [-700.0, -30.0, -1.5, -1e-9, 0.0, 0.3, 1.0, 2.5, 100.0, 700.0] {
+        let (a, b) = (const_eval::exp(x), x.exp());
+        assert!((a - b).abs() <= 4.0 * f64::EPSILON * b, "exp({x})");
+    }
+    for x in [1e-300, 1e-5, 0.5, 1.0, 1.5, 2.0, 10.0, 1e10, 1e300] {
+        let (a, b) = (const_eval::ln(x), x.ln());
+        assert!(
+            (a - b).abs() <= 4.0 * f64::EPSILON * b.abs().max(1.0),
+            "ln({x})"
+        );
+        let (a, b) = (const_eval::sqrt(x), x.sqrt());
+        assert!((a - b).abs() <= f64::EPSILON * b, "sqrt({x})");
+    }
+    assert!(const_eval::ln(-1.0).is_nan());
+    assert_eq!(const_eval::exp(-800.0), 0.0);
+}
+
+#[test]
+fn compile_time_prices_match_runtime() {
+    for (i, &price) in TABLE.iter().enumerate() {
+        let option = OptionInputs::new(false, 100.0, 80.0 + 10.0 * i as f64, 0.03, 0.01, 0.5)
+            .with_implied_vol(0.25);
+        assert!(
+            (price - option.price()).abs() < 1e-12,
+            "{price} vs {}",
+            option.price()
+        );
+    }
+}
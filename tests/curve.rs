@@ -0,0 +1,44 @@
+use blackscholes::curve::{self, Compounding, RateCurve};
+
+#[test]
+fn rate_conversions_round_trip() {
+    for compounding in [
+        Compounding::Continuous,
+        Compounding::Simple,
+        Compounding::Periodic(2),
+        Compounding::Periodic(12),
+    ] {
+        let df = curve::discount_factor(0.045, 2.5, compounding);
+        assert!((curve::zero_rate(df, 2.5, compounding) - 0.045).abs() < 1e-14);
+    }
+    // 5% semi-annual is 4.9385% continuous
+    let df = curve::discount_factor(0.05, 1.0, Compounding::Periodic(2));
+    let continuous = curve::zero_rate(df, 1.0, Compounding::Continuous);
+    assert!((continuous - 2.0 * 1.025_f64.ln()).abs() < 1e-15);
+
+    let forward = curve::forward_rate(0.97, 1.0, 0.93, 2.0, Compounding::Simple);
+    assert!((forward - (0.97 / 0.93 - 1.0)).abs() < 1e-15);
+    assert_eq!(curve::schedule(0.0, 1.25, 2), vec![0.25, 0.75, 1.25]);
+}
+
+#[test]
+fn swaps_fras_and_bonds_on_a_curve() {
+    let curve = RateCurve::new(vec![0.5, 1.0, 5.0], vec![0.03, 0.035, 0.04]);
+    let rebuilt = RateCurve::from_discount_factors(
+        curve.times.clone(),
+        &[0.5, 1.0, 5.0].map(|t| curve.discount_factor(t)),
+    );
+    assert!((rebuilt.zero_rate(3.0) - curve.zero_rate(3.0)).abs() < 1e-15);
+
+    // a bond paying the par rate is worth par
+    let par = curve.par_rate(0.0, 5.0, 2);
+    assert!((curve.bond_price(par, 5.0, 2) - 1.0).abs() < 1e-14);
+
+    // a FRA struck at the forward is worth nothing
+    let forward = curve.forward_rate(1.0, 1.5, Compounding::Simple);
+    assert!(curve.fra_value(1.0, 1.5, forward, 1e6).abs() < 1e-9);
+    assert!(curve.fra_value(1.0, 1.5, forward - 0.001, 1e6) > 0.0);
+
+    let annuity = curve.annuity(0.0, &[1.0, 2.0]);
+    assert!((annuity - curve.discount_factor(1.0) - curve.discount_factor(2.0)).abs() < 1e-15);
+}
@@ -0,0 +1,77 @@
+use blackscholes::digital::Payout;
+use blackscholes::OptionInputs;
+
+fn option(is_call: bool, s: f64, t: f64) -> OptionInputs {
+    OptionInputs::new(is_call, s, 100.0, 0.05, 0.02, t).with_implied_vol(0.25)
+}
+
+fn assert_close(a: f64, b: f64, tol: f64) {
+    assert!((a - b).abs() < tol * (1.0 + b.abs()), "{a} != {b}");
+}
+
+#[test]
+fn digitals_decompose_the_vanilla() {
+    for is_call in [true, false] {
+        let o = option(is_call, 105.0, 0.5);
+        let cash = o.digital(Payout::Cash(1.0));
+        let asset = o.digital(Payout::Asset);
+        // a call is an asset digital less K cash digitals, a put the reverse
+        let sign = o.sign();
+        assert_close(sign * (asset.price - 100.0 * cash.price), o.price(), 1e-12);
+        assert_close(sign * (asset.delta - 100.0 * cash.delta), o.delta(), 1e-12);
+        assert_close(sign * (asset.gamma - 100.0 * cash.gamma), o.gamma(), 1e-12);
+        assert_close(sign * (asset.vega - 100.0 * cash.vega), o.vega(), 1e-12);
+    }
+    // a cash call and put together pay the amount for sure
+    let call = option(true, 105.0, 0.5).digital(Payout::Cash(10.0));
+    let put = option(false, 105.0, 0.5).digital(Payout::Cash(10.0));
+    assert_close(
+        call.price + put.price,
+        10.0 * (-0.05_f64 * 0.5).exp(),
+        1e-12,
+    );
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    for is_call in [true, false] {
+        for payout in [Payout::Cash(5.0), Payout::Asset] {
+            let value = |s: f64, vol: f64| {
+                OptionInputs::new(is_call, s, 100.0, 0.05, 0.02, 0.5)
+                    .with_implied_vol(vol)
+                    .digital(payout)
+            };
+            let o = value(97.0, 0.25);
+            let h = 1e-3;
+            let (up, down) = (value(97.0 + h, 0.25), value(97.0 - h, 0.25));
+            assert_close(o.delta, (up.price - down.price) / (2.0 * h), 1e-6);
+            assert_close(o.gamma, (up.delta - down.delta) / (2.0 * h), 1e-6);
+            let e = 1e-6;
+            let fd = 0.01 * (value(97.0, 0.25 + e).price - value(97.0, 0.25 - e).price) / (2.0 * e);
+            assert_close(o.vega, fd, 1e-6);
+        }
+    }
+}
+
+#[test]
+fn call_spread_smooths_near_expiry() {
+    for is_call in [true, false] {
+        for payout in [Payout::Cash(1.0), Payout::Asset] {
+            let o = option(is_call, 100.2, 0.5);
+            let exact = o.digital(payout);
+            let tight = o.digital_spread(payout, 0.01);
+            assert_close(tight.price, exact.price, 1e-5);
+            assert_close(tight.delta, exact.delta, 1e-4);
+
+            // a day from expiry the exact gamma peaks far above the spread's
+            let peak = |f: &dyn Fn(&OptionInputs) -> f64| {
+                (0..=80)
+                    .map(|i| f(&option(is_call, 98.0 + 0.05 * i as f64, 1.0 / 365.0)).abs())
+                    .fold(0.0, f64::max)
+            };
+            let exact = peak(&|o| o.digital(Payout::Cash(1.0)).gamma);
+            let smooth = peak(&|o| o.digital_spread(Payout::Cash(1.0), 4.0).gamma);
+            assert!(smooth < 0.6 * exact, "{smooth} {exact}");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use blackscholes::dispersion::{self, DispersionMarket, DispersionTrade};
+use blackscholes::portfolio::{Portfolio, Position};
+use blackscholes::OptionInputs;
+
+const WEIGHTS: [f64; 3] = [0.5, 0.3, 0.2];
+
+fn straddle(label: &str, s: f64, quantity: f64) -> Portfolio {
+    let leg = |is_call| OptionInputs::new(is_call, s, s, 0.03, 0.01, 0.5);
+    Portfolio::new()
+        .with_position(Position::new(format!("{label} C"), leg(true), quantity))
+        .with_position(Position::new(format!("{label} P"), leg(false), quantity))
+}
+
+/// Short an index straddle against long component straddles of the same notional.
+fn trade() -> DispersionTrade {
+    let spots = [50.0, 80.0, 120.0];
+    let components = spots
+        .iter()
+        .zip(WEIGHTS)
+        .enumerate()
+        .map(|(i, (&s, w))| straddle(&format!("S{i}"), s, w * 100.0 / s))
+        .collect();
+    DispersionTrade::new(straddle("IDX", 100.0, -1.0), components, WEIGHTS.to_vec())
+}
+
+fn market(index_spot: f64, index_vol: f64, spots: [f64; 3], vols: [f64; 3]) -> DispersionMarket {
+    DispersionMarket::new(index_spot, index_vol, spots.to_vec(), vols.to_vec())
+}
+
+#[test]
+fn implied_correlation() {
+    let vols = [0.3, 0.25, 0.4];
+    for rho in [-0.2, 0.0, 0.45, 1.0] {
+        let vol = dispersion::index_vol(&WEIGHTS, &vols, rho);
+        let implied = dispersion::implied_correlation(vol, &WEIGHTS, &vols).unwrap();
+        assert!((implied - rho).abs() < 1e-12);
+    }
+    // perfectly correlated components make the index vol the weighted vol
+    let weighted: f64 = WEIGHTS.iter().zip(vols).map(|(w, v)| w * v).sum();
+    assert!((dispersion::index_vol(&WEIGHTS, &vols, 1.0) - weighted).abs() < 1e-12);
+
+    // a single component implies no correlation
+    assert!(dispersion::implied_correlation(0.3, &[1.0], &[0.3]).is_none());
+    assert!(dispersion::implied_correlation(0.3, &[], &[]).is_none());
+
+    // over a short horizon the index vol is the vol of the basket of its components
+    let start = market(100.0, 0.24, [50.0, 80.0, 120.0], vols);
+    let basket = start.basket(&WEIGHTS, 0.45, true, 100.0, 0.0, 1e-4);
+    assert!((basket.forward() - 100.0).abs() < 1e-12);
+    let vol = dispersion::index_vol(&WEIGHTS, &vols, 0.45);
+    assert!((basket.effective_vol() - vol).abs() < 1e-5);
+}
+
+#[test]
+fn short_correlation() {
+    let trade = trade();
+    let start = market(100.0, 0.24, [50.0, 80.0, 120.0], [0.3, 0.25, 0.4]);
+    let rho = trade.implied_correlation(&start).unwrap();
+    assert!(rho > 0.0 && rho < 1.0);
+
+    // the trade loses as implied correlation rises with the component vols held
+    let bumped = |shift: f64| {
+        let vol = dispersion::index_vol(&WEIGHTS, &start.vols, rho + shift);
+        trade.value(&market(100.0, vol, [50.0, 80.0, 120.0], [0.3, 0.25, 0.4]))
+    };
+    let exposure = trade.correlation_exposure(&start);
+    assert!(exposure < 0.0);
+    assert!((exposure - (bumped(1e-4) - bumped(-1e-4)) / 0.02).abs() < 1e-6);
+    assert!(trade.component_vega(&start) > 0.0);
+}
+
+#[test]
+fn explain_attributes_correlation() {
+    let trade = trade();
+    let start = market(100.0, 0.24, [50.0, 80.0, 120.0], [0.3, 0.25, 0.4]);
+
+    // a pure move in implied correlation is all in the correlation term
+    let vol = dispersion::index_vol(
+        &WEIGHTS,
+        &start.vols,
+        trade.implied_correlation(&start).unwrap() - 0.1,
+    );
+    let decorrelated = market(100.0, vol, [50.0, 80.0, 120.0], [0.3, 0.25, 0.4]);
+    let e = trade.explain(&start, &decorrelated, 0.0);
+    let actual = trade.value(&decorrelated) - trade.value(&start);
+    assert!(actual > 0.0);
+    assert!((e.correlation - actual).abs() < 1e-12);
+    assert!(e.index.actual.abs() < 1e-12 && e.components.actual.abs() < 1e-12);
+
+    // a general move is explained in full by the legs and the correlation term
+    let end = market(101.5, 0.26, [49.0, 82.0, 121.0], [0.32, 0.26, 0.41]);
+    let dt = 5.0 / 365.0;
+    let e = trade.explain(&start, &end, dt);
+    let aged = |t: f64| {
+        let mut trade = trade.clone();
+        for leg in std::iter::once(&mut trade.index).chain(&mut trade.components) {
+            for position in &mut leg.positions {
+                position.option.t = t;
+            }
+        }
+        trade
+    };
+    let actual = aged(0.5 - dt).value(&end) - trade.value(&start);
+    assert!((e.actual() - actual).abs() < 1e-9);
+    assert!(e.residual().abs() < 0.05 * actual.abs().max(e.correlation.abs()));
+    let implied = dispersion::implied_correlation(0.26, &WEIGHTS, &end.vols).unwrap();
+    assert_eq!(
+        e.correlation < 0.0,
+        implied > trade.implied_correlation(&start).unwrap()
+    );
+
+    // a single-component trade explains the index against its component's vol
+    let single = DispersionTrade::new(
+        straddle("IDX", 100.0, -1.0),
+        vec![straddle("S0", 100.0, 1.0)],
+        vec![1.0],
+    );
+    let one =
+        |s: f64, vol: f64, spot_vol: f64| DispersionMarket::new(s, vol, vec![s], vec![spot_vol]);
+    let e = single.explain(&one(100.0, 0.24, 0.25), &one(101.0, 0.26, 0.25), dt);
+    assert!(e.actual().is_finite() && e.correlation.is_finite());
+}
@@ -0,0 +1,50 @@
+use blackscholes::events::{Event, EventTermStructure};
+
+#[test]
+fn decomposition_round_trips() {
+    // earnings in three weeks and again in three months
+    let truth = EventTermStructure::new(0.25, vec![Event::new(0.3, 0.06), Event::new(0.06, 0.08)]);
+    assert_eq!(truth.events[0].time, 0.06);
+
+    let expiries = [0.04, 0.1, 0.25, 0.5, 1.0];
+    let vols = truth.term_structure(&expiries);
+    assert!((vols[0] - 0.25).abs() < 1e-15);
+    assert!(vols[1] > vols[2] && vols[2] > 0.25);
+
+    let fitted = EventTermStructure::from_term_structure(0.25, &expiries, &vols, &[0.06, 0.3]);
+    for (fit, event) in fitted.events.iter().zip(&truth.events) {
+        assert!((fit.implied_move - event.implied_move).abs() < 1e-12);
+    }
+    assert!((fitted.vol(0.7) - truth.vol(0.7)).abs() < 1e-12);
+}
+
+#[test]
+fn events_between_expiries_share_variance() {
+    let expiries = [0.1, 0.5];
+    let vols = [0.3, 0.35];
+    let fitted = EventTermStructure::from_term_structure(0.3, &expiries, &vols, &[0.2, 0.4]);
+    let moves: Vec<f64> = fitted.events.iter().map(|e| e.implied_move).collect();
+    assert_eq!(moves[0], moves[1]);
+    assert!((fitted.vol(0.5) - 0.35).abs() < 1e-12);
+
+    // a term structure falling below the base vol leaves no room for an event
+    let fitted = EventTermStructure::from_term_structure(0.3, &[0.5], &[0.25], &[0.2]);
+    assert!(fitted.events[0].implied_move.is_nan());
+}
+
+#[test]
+fn vol_crush_after_the_event() {
+    let structure = EventTermStructure::new(0.2, vec![Event::new(0.05, 0.07)]);
+    let before = structure.vol(0.1);
+    let after = structure.after(0.05);
+    assert!(after.events.is_empty());
+    assert!((after.vol(0.05) - 0.2).abs() < 1e-15);
+    assert!(before > after.vol(0.05));
+
+    // the expected absolute move is what a straddle prices
+    let event = structure.events[0];
+    assert!((event.expected_abs_move() - 0.07 * 0.7978845608).abs() < 1e-9);
+
+    let bigger = structure.with_move(0, 0.1);
+    assert!(bigger.vol(0.1) > before);
+}
@@ -0,0 +1,60 @@
+use blackscholes::correlation::CorrelationRisk;
+use blackscholes::exchange::ExchangeOption;
+use blackscholes::spread::SpreadOption;
+use blackscholes::OptionInputs;
+
+#[test]
+fn reduces_to_vanilla_and_kirk() {
+    // a riskless second asset grows at its carry, leaving a vanilla call struck at its forward
+    let r = 0.05;
+    let riskless = ExchangeOption::new(22.0, 20.0, 0.06, 0.04, 0.5, 0.2, 0.0, 0.3);
+    let strike = 20.0 * ((r - 0.04) * 0.5_f64).exp();
+    let vanilla = OptionInputs::new(true, 22.0, strike, r, 0.06, 0.5).with_implied_vol(0.2);
+    assert!((riskless.price() - vanilla.price()).abs() < 1e-10);
+    assert!((riskless.delta1() - vanilla.delta()).abs() < 1e-10);
+
+    // Kirk's spread option is exact at zero strike
+    let option = ExchangeOption::new(22.0, 20.0, 0.06, 0.04, 0.1, 0.2, 0.25, -0.5);
+    let forward = |s: f64, q: f64| s * ((r - q) * option.t).exp();
+    let spread = SpreadOption::new(
+        true,
+        forward(22.0, 0.06),
+        forward(20.0, 0.04),
+        0.0,
+        r,
+        option.t,
+        0.2,
+        0.25,
+        -0.5,
+    );
+    assert!((spread.price() - option.price()).abs() < 1e-12);
+}
+
+#[test]
+fn greeks_and_correlation() {
+    let option = ExchangeOption::new(100.0, 95.0, 0.01, 0.03, 0.75, 0.3, 0.25, 0.6);
+    // the price is homogeneous of degree one in the two spots
+    let euler = 100.0 * option.delta1() + 95.0 * option.delta2();
+    assert!((euler - option.price()).abs() < 1e-10);
+
+    let h = 1e-4;
+    let bump = |ds1: f64, ds2: f64| {
+        ExchangeOption {
+            s1: option.s1 + ds1,
+            s2: option.s2 + ds2,
+            ..option.clone()
+        }
+        .price()
+    };
+    assert!((option.delta1() - (bump(h, 0.0) - bump(-h, 0.0)) / (2.0 * h)).abs() < 1e-8);
+    assert!((option.delta2() - (bump(0.0, h) - bump(0.0, -h)) / (2.0 * h)).abs() < 1e-8);
+
+    // higher correlation lowers the vol of the ratio and so the value
+    assert!(option.cega() < 0.0);
+    let perfectly = ExchangeOption {
+        rho: 1.0,
+        vol2: 0.3,
+        ..option.clone()
+    };
+    assert!(perfectly.effective_vol().abs() < 1e-12);
+}
@@ -0,0 +1,48 @@
+use blackscholes::expiry::{ExerciseAction, ExerciseRules, Settlement};
+use blackscholes::{OptionInputs, Portfolio, Position};
+
+fn expiring(is_call: bool, k: f64) -> OptionInputs {
+    OptionInputs::new(is_call, 100.0, k, 0.0, 0.0, 1.0 / 365.25).with_implied_vol(0.2)
+}
+
+fn book() -> Portfolio {
+    Portfolio::new()
+        .with_position(Position::new("long C95", expiring(true, 95.0), 2.0).with_multiplier(100.0))
+        .with_position(
+            Position::new("short P105", expiring(false, 105.0), -1.0).with_multiplier(100.0),
+        )
+        .with_position(Position::new("long P90", expiring(false, 90.0), 5.0).with_multiplier(100.0))
+        .with_position(
+            Position::new("short C100.005", expiring(true, 100.005), -1.0).with_multiplier(100.0),
+        )
+}
+
+#[test]
+fn physical_settlement() {
+    let report = ExerciseRules::default().process(&book(), 100.01);
+    let actions: Vec<ExerciseAction> = report.outcomes.iter().map(|o| o.action).collect();
+    assert_eq!(
+        actions,
+        [
+            ExerciseAction::Exercised,
+            ExerciseAction::Assigned,
+            ExerciseAction::Expired,
+            ExerciseAction::Expired
+        ]
+    );
+    // +200 shares from the calls, +100 from the put assignment
+    assert_eq!(report.shares(), 300.0);
+    assert_eq!(report.cash(), -200.0 * 95.0 - 100.0 * 105.0);
+    let intrinsic = 200.0 * 5.01 - 100.0 * 4.99;
+    assert!((report.value() - intrinsic).abs() < 1e-9);
+}
+
+#[test]
+fn cash_settlement_and_instructions() {
+    let rules = ExerciseRules::new(0.0, Settlement::Cash).with_do_not_exercise("long C95");
+    let report = rules.process(&book(), 100.01);
+    assert_eq!(report.outcomes[0].action, ExerciseAction::Expired);
+    assert_eq!(report.outcomes[3].action, ExerciseAction::Assigned);
+    assert_eq!(report.shares(), 0.0);
+    assert!((report.cash() - (-100.0 * 4.99 - 100.0 * 0.005)).abs() < 1e-9);
+}
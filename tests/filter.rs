@@ -0,0 +1,35 @@
+use blackscholes::filter::{ewma, kalman, Ewma, KalmanFilter};
+
+// Deterministic bid/ask bounce around a vol of 0.2
+fn noisy() -> Vec<f64> {
+    (0..200)
+        .map(|i| 0.2 + if i % 2 == 0 { 0.01 } else { -0.01 })
+        .collect()
+}
+
+#[test]
+fn ewma_smooths_bounce_and_skips_failed_solves() {
+    let mut series = noisy();
+    series[50] = f64::NAN;
+    let smoothed = ewma(&series, 0.1);
+    assert_eq!(smoothed[50], smoothed[49]);
+    assert!((smoothed[199] - 0.2).abs() < 0.001);
+
+    let mut filter = Ewma::from_half_life(1.0);
+    assert!((filter.alpha - 0.5).abs() < 1e-12);
+    filter.update(1.0);
+    assert_eq!(filter.update_after(0.0, 2.0), 0.25);
+}
+
+#[test]
+fn kalman_tracks_level_and_rejects_outliers() {
+    let smoothed = kalman(&noisy(), 1e-6, 1e-4);
+    assert!((smoothed[199] - 0.2).abs() < 0.002);
+
+    let mut filter = KalmanFilter::new(1e-6, 1e-4).with_outlier_threshold(4.0);
+    for x in noisy() {
+        filter.update(x);
+    }
+    let before = filter.value();
+    assert_eq!(filter.update(1.5), before);
+}
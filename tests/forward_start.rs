@@ -0,0 +1,71 @@
+use blackscholes::forward_start::{self, ForwardStartOption, Ratchet};
+use blackscholes::mc::MonteCarlo;
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn haug_forward_start() {
+    // Haug's example: strike 10% out of the money, set in three months
+    let option =
+        ForwardStartOption::new(true, 60.0, 1.1, 0.08, 0.04, 0.25, 1.0).with_implied_vol(0.3);
+    assert!((option.price() - 4.4064).abs() < 1e-4);
+
+    // starting today it is a vanilla option
+    for is_call in [true, false] {
+        let today =
+            ForwardStartOption::new(is_call, 60.0, 1.1, 0.08, 0.04, 0.0, 1.0).with_implied_vol(0.3);
+        let vanilla = OptionInputs::new(is_call, 60.0, 66.0, 0.08, 0.04, 1.0).with_implied_vol(0.3);
+        assert!((today.price() - vanilla.price()).abs() < 1e-10);
+    }
+
+    // the price is linear in the spot, and vega and rho match finite differences
+    let bumped = |f: &dyn Fn(&mut ForwardStartOption)| {
+        let mut option = option.clone();
+        f(&mut option);
+        option.price()
+    };
+    assert!((option.delta() - option.price() / 60.0).abs() < 1e-12);
+    let vega = (bumped(&|o| o.implied_vol += 1e-5) - bumped(&|o| o.implied_vol -= 1e-5)) / 2e-3;
+    assert!((option.vega() - vega).abs() < 1e-6);
+    let rho = (bumped(&|o| o.r += 1e-5) - bumped(&|o| o.r -= 1e-5)) / 2e-3;
+    assert!((option.rho() - rho).abs() < 1e-6);
+}
+
+#[test]
+fn ratchet_matches_simulation() {
+    let resets = [0.25, 0.5, 0.75, 1.0];
+    let ratchet = Ratchet::new(true, 100.0, 1.0, 0.05, 0.02, &resets).with_implied_vol(0.25);
+    let flat = Ratchet::new(true, 100.0, 1.0, 0.05, 0.02, &resets).with_term_structure(&[0.25; 4]);
+    assert!((ratchet.price() - flat.price()).abs() < 1e-10);
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.02, 1.0).with_implied_vol(0.25);
+    let paths = MonteCarlo::new(100_000).paths_at(&option, &resets, &mut rng);
+    let samples: Vec<f64> = paths
+        .iter()
+        .map(|path| {
+            path.windows(2)
+                .zip(resets)
+                .map(|(step, t)| (step[1] - step[0]).max(0.0) * (-0.05 * t).exp())
+                .sum()
+        })
+        .collect();
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let error = (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n / n).sqrt();
+    assert!((ratchet.price() - mean).abs() < 4.0 * error);
+
+    // the forward vols of a rising term structure recover the quoted vols
+    let vols = [0.2, 0.22, 0.23, 0.25];
+    let rising = Ratchet::new(false, 100.0, 0.95, 0.05, 0.02, &resets).with_term_structure(&vols);
+    let total: f64 = rising
+        .legs
+        .iter()
+        .map(|leg| leg.implied_vol.powi(2) * (leg.t - leg.start))
+        .sum();
+    assert!((total - 0.25 * 0.25).abs() < 1e-12);
+    let expected = forward_start::forward_vol(0.22, 0.5, 0.23, 0.75);
+    assert!((rising.legs[2].implied_vol - expected).abs() < 1e-15);
+    assert!(rising.delta() > 0.0 && rising.vega() > 0.0);
+}
@@ -0,0 +1,82 @@
+use blackscholes::fourier::{CarrMadan, CharacteristicFn, Complex64, CosPricer, Gbm};
+use blackscholes::OptionInputs;
+
+/// A model defined outside the crate, here variance gamma.
+struct VarianceGamma {
+    sigma: f64,
+    nu: f64,
+    theta: f64,
+}
+
+impl CharacteristicFn for VarianceGamma {
+    fn cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let i = Complex64::i();
+        let omega =
+            (1.0 - self.theta * self.nu - 0.5 * self.sigma.powi(2) * self.nu).ln() / self.nu;
+        let base = 1.0 - i * self.theta * self.nu * u + 0.5 * self.sigma.powi(2) * self.nu * u * u;
+        (i * u * omega * t).exp() * base.powf(-t / self.nu)
+    }
+}
+
+#[test]
+fn cos_matches_black_scholes() {
+    let pricer = CosPricer::default();
+    let model = Gbm { vol: 0.25 };
+    for is_call in [true, false] {
+        let ks = [60.0, 90.0, 100.0, 110.0, 150.0];
+        let prices = pricer.prices(&model, is_call, 100.0, &ks, 0.03, 0.01, 0.75);
+        for (&k, price) in ks.iter().zip(prices) {
+            let expected = OptionInputs::new(is_call, 100.0, k, 0.03, 0.01, 0.75)
+                .with_implied_vol(0.25)
+                .price();
+            assert!((price - expected).abs() < 1e-10, "{k}: {price} {expected}");
+        }
+    }
+}
+
+#[test]
+fn plug_in_model_is_martingale_and_arbitrage_free() {
+    let model = VarianceGamma {
+        sigma: 0.12,
+        nu: 0.2,
+        theta: -0.14,
+    };
+    assert!((model.cf(Complex64::new(0.0, -1.0), 1.0) - 1.0).norm() < 1e-12);
+
+    // Fang and Oosterlee (2008): S = 100, K = 90, T = 1, r = 0.1 gives 19.099354724
+    let price = CosPricer::default().price(&model, true, 100.0, 90.0, 0.1, 0.0, 1.0);
+    assert!((price - 19.099354724).abs() < 1e-6, "{price}");
+
+    let prices =
+        CosPricer::default().prices(&model, true, 100.0, &[90.0, 100.0, 110.0], 0.1, 0.0, 1.0);
+    assert!(prices.windows(2).all(|w| w[0] > w[1]));
+    assert!(prices[0] - 2.0 * prices[1] + prices[2] > 0.0);
+}
+
+#[test]
+fn carr_madan_matches_black_scholes_and_cos() {
+    let pricer = CarrMadan::default();
+    let model = Gbm { vol: 0.25 };
+    let grid = pricer.grid(&model, 100.0, 0.03, 0.01, 0.75);
+    assert_eq!(grid.calls.len(), 4096);
+
+    // at the grid node on the forward no interpolation is involved
+    let atm = grid
+        .moneyness
+        .iter()
+        .position(|&x| x.abs() < 1e-12)
+        .unwrap();
+    let expected = OptionInputs::new(true, 100.0, grid.strikes[atm], 0.03, 0.01, 0.75)
+        .with_implied_vol(0.25)
+        .price();
+    assert!((grid.calls[atm] - expected).abs() < 1e-6);
+
+    let ks = [70.0, 95.0, 100.0, 125.0];
+    for is_call in [true, false] {
+        let fft = pricer.prices(&model, is_call, 100.0, &ks, 0.03, 0.01, 0.75);
+        let cos = CosPricer::default().prices(&model, is_call, 100.0, &ks, 0.03, 0.01, 0.75);
+        for (a, b) in fft.iter().zip(&cos) {
+            assert!((a - b).abs() < 1e-6, "{a} {b}");
+        }
+    }
+}
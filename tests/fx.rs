@@ -0,0 +1,174 @@
+use blackscholes::fx::{strike_for_delta, DeltaSmile, DeltaType, FxOption, PremiumQuote};
+use blackscholes::surface::Smile;
+use blackscholes::OptionInputs;
+
+fn eurusd(is_call: bool) -> FxOption {
+    FxOption::new(is_call, 1.0850, 1.1000, 0.045, 0.03, 0.5).with_implied_vol(0.08)
+}
+
+#[test]
+fn garman_kohlhagen_is_bsm_with_the_foreign_rate() {
+    let fx = eurusd(true);
+    let bsm = OptionInputs::new(true, 1.0850, 1.1000, 0.045, 0.03, 0.5).with_implied_vol(0.08);
+    assert_eq!(fx.premium(PremiumQuote::DomesticPips), bsm.price());
+    assert_eq!(fx.foreign_rate(), 0.03);
+
+    let (s, k) = (1.0850, 1.1000);
+    let pips = fx.premium(PremiumQuote::DomesticPips);
+    assert!((fx.premium(PremiumQuote::ForeignPercent) - pips / s).abs() < 1e-15);
+    assert!((fx.premium(PremiumQuote::DomesticPercent) - pips / k).abs() < 1e-15);
+    assert!((fx.premium(PremiumQuote::ForeignPips) - pips / (s * k)).abs() < 1e-15);
+
+    let solved = FxOption::new(true, s, k, 0.045, 0.03, 0.5).with_premium(
+        fx.premium(PremiumQuote::ForeignPercent),
+        PremiumQuote::ForeignPercent,
+    );
+    assert!((solved.option.implied_vol() - 0.08).abs() < 1e-10);
+}
+
+#[test]
+fn delta_conventions() {
+    for is_call in [true, false] {
+        let fx = eurusd(is_call);
+        let o = &fx.option;
+        let spot = fx.delta(DeltaType::Spot);
+        let forward = fx.delta(DeltaType::Forward);
+        assert!((spot - forward * o.dividend_discount()).abs() < 1e-15);
+
+        // premium adjustment removes the premium's own foreign currency exposure
+        let undiscounted = o.price() / o.rate_discount();
+        let pa_forward = fx.delta(DeltaType::PremiumAdjustedForward);
+        assert!((pa_forward - (forward - undiscounted / fx.forward())).abs() < 1e-12);
+        let pa_spot = fx.delta(DeltaType::PremiumAdjustedSpot);
+        assert!((pa_spot - pa_forward * o.dividend_discount()).abs() < 1e-12);
+    }
+
+    // the foreign rate acts as the dividend yield
+    let fx = eurusd(true);
+    let h = 1e-6;
+    let bump = |rf: f64| {
+        FxOption::new(true, 1.0850, 1.1000, 0.045, rf, 0.5)
+            .with_implied_vol(0.08)
+            .premium(PremiumQuote::DomesticPips)
+    };
+    let fd = 0.01 * (bump(0.03 + h) - bump(0.03 - h)) / (2.0 * h);
+    assert!((fx.foreign_rho() - fd).abs() < 1e-8);
+}
+
+#[test]
+fn strikes_for_delta_hit_their_delta() {
+    let (s, rd, rf, t, vol) = (1.0850, 0.045, 0.03, 0.5, 0.09);
+    for delta_type in [
+        DeltaType::Spot,
+        DeltaType::Forward,
+        DeltaType::PremiumAdjustedSpot,
+        DeltaType::PremiumAdjustedForward,
+    ] {
+        for delta in [-0.1, -0.25, 0.25, 0.1] {
+            let k = strike_for_delta(delta_type, delta, s, rd, rf, t, vol);
+            let option = FxOption::new(delta > 0.0, s, k, rd, rf, t).with_implied_vol(vol);
+            let hit = option.delta(delta_type);
+            assert!((hit - delta).abs() < 1e-10, "{delta_type:?} {delta} {hit}");
+        }
+    }
+}
+
+#[test]
+fn delta_smile_reprices_its_pillars() {
+    for delta_type in [DeltaType::Forward, DeltaType::PremiumAdjustedSpot] {
+        let smile = DeltaSmile::from_quotes(
+            1.0850, 0.045, 0.03, 0.5, delta_type, 0.08, -0.01, 0.003, -0.018, 0.009,
+        );
+        assert_eq!(smile.strikes.len(), 5);
+        assert!(smile.strikes.windows(2).all(|w| w[0] < w[1]));
+
+        for pillar in &smile.pillars {
+            let k = strike_for_delta(
+                delta_type,
+                pillar.delta,
+                1.0850,
+                0.045,
+                0.03,
+                0.5,
+                pillar.vol,
+            );
+            assert!((smile.vol(k) - pillar.vol).abs() < 1e-10, "{pillar:?}");
+        }
+        // the atm strike gets the atm vol and the skew favours puts
+        assert!((smile.vol(smile.strikes[2]) - 0.08).abs() < 1e-10);
+        assert!(smile.vol(1.0) > smile.vol(1.2));
+        let call = smile.option(true, 1.12);
+        assert!((call.option.implied_vol() - smile.vol(1.12)).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn quanto_options() {
+    use blackscholes::correlation::CorrelationRisk;
+    use blackscholes::fx::QuantoOption;
+    use blackscholes::mc::{CorrelatedAssets, MonteCarlo};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let (r_d, r_f) = (0.04, 0.01);
+    let quanto = |is_call: bool| {
+        QuantoOption::new(is_call, 3000.0, 3100.0, r_d, r_f, 0.75)
+            .with_dividend(0.02)
+            .with_implied_vol(0.2)
+            .with_fx(0.1, -0.4)
+            .with_quanto_rate(0.01)
+    };
+
+    // uncorrelated, the quanto is the foreign option discounted at the domestic rate
+    let uncorrelated = quanto(true).with_fx(0.1, 0.0);
+    let foreign = OptionInputs::cost_of_carry(true, 3000.0, 3100.0, r_d, r_f - 0.02, 0.75)
+        .with_implied_vol(0.2);
+    assert!((uncorrelated.price() - 0.01 * foreign.price()).abs() < 1e-12);
+    assert!((quanto(true).drift_adjustment() - 0.008).abs() < 1e-15);
+
+    // simulate under the foreign measure, where the exchange rate drifts at its carry plus its
+    // variance, and convert the payoff back at the simulated rate
+    let fx_carry = r_d - r_f + 0.1 * 0.1;
+    let assets = CorrelatedAssets::new(
+        vec![3000.0, 1.3],
+        vec![0.2, 0.1],
+        vec![vec![1.0, -0.4], vec![-0.4, 1.0]],
+        r_f,
+        0.75,
+    )
+    .with_dividends(vec![0.02, r_f - fx_carry]);
+    let mut rng = StdRng::seed_from_u64(9);
+    for is_call in [true, false] {
+        let sign = if is_call { 1.0 } else { -1.0 };
+        let payoff = |paths: &[Vec<f64>]| {
+            let domestic = 0.01 * (sign * (paths[0][1] - 3100.0)).max(0.0);
+            domestic * 1.3 / paths[1][1]
+        };
+        let mc = MonteCarlo::new(200_000).with_antithetic(true);
+        let price = mc.price_correlated(&assets, &payoff, &mut rng).unwrap();
+        let expected = quanto(is_call).price();
+        assert!(
+            ((price.price - expected) / price.std_error).abs() < 4.0,
+            "{price:?} {expected}"
+        );
+    }
+
+    // Greeks against bumps
+    let option = quanto(true);
+    let bumped = |f: &dyn Fn(&mut QuantoOption, f64), h: f64| {
+        let (mut up, mut down) = (option.clone(), option.clone());
+        f(&mut up, h);
+        f(&mut down, -h);
+        (up.price() - down.price()) / (2.0 * h)
+    };
+    let vega = 0.01 * bumped(&|o, h| o.asset_vol += h, 1e-5);
+    let fx_vega = 0.01 * bumped(&|o, h| o.fx_vol += h, 1e-5);
+    let delta = bumped(&|o, h| o.s += h, 1e-2);
+    assert!((option.vega() - vega).abs() < 1e-8);
+    assert!((option.fx_vega() - fx_vega).abs() < 1e-8);
+    assert!((option.delta() - delta).abs() < 1e-7);
+
+    // a call gains as the asset's correlation with its own currency falls
+    let cega = 0.01 * bumped(&|o, h| o.correlation += h, 1e-5);
+    assert!((option.cega() - cega).abs() < 1e-8 && cega < 0.0);
+}
@@ -0,0 +1,60 @@
+use blackscholes::digital::Payout;
+use blackscholes::gap::GapOption;
+use blackscholes::OptionInputs;
+
+fn gap(is_call: bool, s: f64, trigger: f64, payoff_strike: f64) -> GapOption {
+    let option = OptionInputs::new(is_call, s, trigger, 0.09, 0.02, 0.5).with_implied_vol(0.2);
+    GapOption::new(option, payoff_strike)
+}
+
+#[test]
+fn haug_gap_call() {
+    // Haug's example pays S - 57 above 50, a jump that makes the call worth less than nothing
+    let option = OptionInputs::new(true, 50.0, 50.0, 0.09, 0.0, 0.5).with_implied_vol(0.2);
+    let price = GapOption::new(option, 57.0).price();
+    assert!((price + 0.0053).abs() < 1e-4);
+}
+
+#[test]
+fn builds_on_vanillas_and_digitals() {
+    for is_call in [true, false] {
+        // with the strikes together it is the vanilla
+        let vanilla =
+            OptionInputs::new(is_call, 100.0, 105.0, 0.09, 0.02, 0.5).with_implied_vol(0.2);
+        let same = gap(is_call, 100.0, 105.0, 105.0).value();
+        assert!((same.price - vanilla.price()).abs() < 1e-10);
+        assert!((same.delta - vanilla.delta()).abs() < 1e-10);
+        assert!((same.gamma - vanilla.gamma()).abs() < 1e-10);
+        assert!((same.vega - vanilla.vega()).abs() < 1e-10);
+
+        // a gap past the vanilla by the payout is a cash digital
+        let sign = vanilla.sign();
+        let digital = vanilla.digital(Payout::Cash(3.0));
+        let gapped = gap(is_call, 100.0, 105.0, 105.0 - sign * 3.0).value();
+        assert!((gapped.price - vanilla.price() - digital.price).abs() < 1e-10);
+        assert!((gapped.delta - vanilla.delta() - digital.delta).abs() < 1e-10);
+        assert!((gapped.vega - vanilla.vega() - digital.vega).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    for is_call in [true, false] {
+        for (trigger, payoff_strike) in [(105.0, 100.0), (95.0, 102.0)] {
+            let option = gap(is_call, 100.0, trigger, payoff_strike);
+            let bumped = |s: f64, vol: f64| {
+                let o = &option.option;
+                let o = OptionInputs::new(o.is_call, s, o.k, o.r, o.q, o.t).with_implied_vol(vol);
+                GapOption::new(o, payoff_strike).price()
+            };
+            let h = 1e-3;
+            let delta = (bumped(100.0 + h, 0.2) - bumped(100.0 - h, 0.2)) / (2.0 * h);
+            let gamma =
+                (bumped(100.0 + h, 0.2) - 2.0 * option.price() + bumped(100.0 - h, 0.2)) / (h * h);
+            let vega = (bumped(100.0, 0.2 + 1e-5) - bumped(100.0, 0.2 - 1e-5)) / 2e-3;
+            assert!((option.delta() - delta).abs() < 1e-7);
+            assert!((option.gamma() - gamma).abs() < 1e-5);
+            assert!((option.vega() - vega).abs() < 1e-7);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use blackscholes::OptionInputs;
+
+fn with_vol(s: f64, vol: f64) -> f64 {
+    OptionInputs::new(true, s, 105.0, 0.03, 0.01, 0.4)
+        .with_implied_vol(vol)
+        .price()
+}
+
+#[test]
+fn shadow_greeks_match_bumps_along_spot_vol_path() {
+    let (s0, vol0, beta) = (100.0, 0.22, -0.6);
+    let option = OptionInputs::new(true, s0, 105.0, 0.03, 0.01, 0.4).with_implied_vol(vol0);
+
+    // vol moves with log spot
+    let along = |s: f64| with_vol(s, vol0 + beta * (s / s0).ln());
+    let h = 0.01;
+    let delta = (along(s0 + h) - along(s0 - h)) / (2.0 * h);
+    let gamma = (along(s0 + h) - 2.0 * along(s0) + along(s0 - h)) / (h * h);
+    assert!((option.shadow_delta(beta) - delta).abs() < 1e-6);
+    assert!((option.shadow_gamma(beta) - gamma).abs() < 1e-4);
+    assert!(option.shadow_delta(beta) < option.delta());
+
+    // spot moves with vol
+    let spot_beta = -2.0;
+    let dv = 1e-4;
+    let vega = (with_vol(s0 * (1.0 + spot_beta * dv), vol0 + dv)
+        - with_vol(s0 * (1.0 - spot_beta * dv), vol0 - dv))
+        / (2.0 * dv)
+        * 0.01;
+    assert!((option.shadow_vega(spot_beta) - vega).abs() < 1e-6);
+    assert_eq!(option.shadow_vega(0.0), option.vega());
+}
+
+#[test]
+fn all_greeks_computes_only_the_requested_set() {
+    use blackscholes::greeks::GreekSet;
+
+    let option = OptionInputs::new(false, 100.0, 95.0, 0.03, 0.01, 0.5).with_implied_vol(0.25);
+    let greeks = option.all_greeks(GreekSet::DELTA | GreekSet::THIRD_ORDER);
+    assert_eq!(greeks.delta, option.delta());
+    assert_eq!(greeks.speed, option.speed());
+    assert_eq!(greeks.ultima, option.ultima());
+    assert!(greeks.gamma.is_nan() && greeks.vega.is_nan() && greeks.vanna.is_nan());
+
+    let all = option.all_greeks(GreekSet::all());
+    assert_eq!(all.vomma, option.vomma());
+    assert_eq!(all.dual_gamma, option.dual_gamma());
+    assert_eq!(
+        GreekSet::FIRST_ORDER | GreekSet::SECOND_ORDER | GreekSet::THIRD_ORDER,
+        GreekSet::all()
+    );
+}
@@ -0,0 +1,150 @@
+use blackscholes::chain::{OptionChain, OptionQuote};
+use blackscholes::fourier::{CharacteristicFn, Complex64, CosPricer};
+use blackscholes::heston::{Bates, Heston, HestonOption};
+
+fn fang_oosterlee() -> Heston {
+    Heston::new(0.0175, 1.5768, 0.0398, 0.5751, -0.5711)
+}
+
+#[test]
+fn heston_reference_price() {
+    // Fang and Oosterlee (2008): S = K = 100, T = 1, r = q = 0 gives 5.785155450
+    let model = fang_oosterlee();
+    assert!(!model.feller());
+    let price = CosPricer::new(512, 12.0).price(&model, true, 100.0, 100.0, 0.0, 0.0, 1.0);
+    assert!((price - 5.785155450).abs() < 1e-6, "{price}");
+}
+
+#[test]
+fn bates_without_jumps_is_heston() {
+    let heston = fang_oosterlee();
+    let bates = Bates::new(heston, 0.0, -0.1, 0.2);
+    let u = Complex64::new(1.3, 0.0);
+    assert!((bates.cf(u, 0.5) - heston.cf(u, 0.5)).norm() < 1e-12);
+
+    let jumpy = Bates::new(heston, 0.5, -0.1, 0.2);
+    assert!((jumpy.cf(Complex64::new(0.0, -1.0), 0.5) - 1.0).norm() < 1e-12);
+    // downward jumps raise out-of-the-money put prices
+    let pricer = CosPricer::default();
+    let jump_put = pricer.price(&jumpy, false, 100.0, 80.0, 0.0, 0.0, 0.25);
+    let heston_put = pricer.price(&heston, false, 100.0, 80.0, 0.0, 0.0, 0.25);
+    assert!(jump_put > heston_put);
+}
+
+#[test]
+fn bates_calibration_fits_its_own_prices() {
+    let truth = Bates::new(Heston::new(0.04, 2.0, 0.05, 0.4, -0.6), 0.3, -0.15, 0.1);
+    let pricer = CosPricer::new(128, 10.0);
+    let mut quotes = Vec::new();
+    for t in [0.1, 0.5, 1.0] {
+        for k in [80.0, 90.0, 100.0, 110.0, 120.0] {
+            let is_call = k >= 100.0;
+            let p = pricer.price(&truth, is_call, 100.0, k, 0.02, 0.0, t);
+            quotes.push(OptionQuote::new(is_call, k, t, p, p));
+        }
+    }
+    let chain = OptionChain::new(100.0, 0.02, 0.0, quotes);
+
+    let initial = Bates::new(Heston::new(0.03, 1.5, 0.04, 0.5, -0.4), 0.2, -0.1, 0.15);
+    let fit = Bates::calibrate(&chain, &initial, &pricer).unwrap();
+    assert!(fit.rmse < 2e-3, "{}", fit.rmse);
+    assert!(fit.evaluations > 0);
+
+    // a perfectly correlated start is pulled inside the domain
+    let correlated = Bates::new(Heston::new(0.03, 1.5, 0.04, 0.5, -1.0), 0.2, -0.1, 0.15);
+    let fit = Bates::calibrate(&chain, &correlated, &pricer).unwrap();
+    assert!(fit.rmse.is_finite() && fit.model.heston.rho.abs() < 1.0);
+
+    // nothing to fit
+    let empty = OptionChain::new(100.0, 0.02, 0.0, Vec::new());
+    assert!(Bates::calibrate(&empty, &initial, &pricer).is_none());
+}
+
+fn option(is_call: bool, s: f64, model: Heston) -> HestonOption {
+    HestonOption::new(is_call, s, 105.0, 0.03, 0.01, 0.75, model)
+}
+
+#[test]
+fn semi_analytic_price_agrees_with_cos() {
+    let model = fang_oosterlee();
+    let atm = HestonOption::new(true, 100.0, 100.0, 0.0, 0.0, 1.0, model);
+    assert!((atm.price() - 5.785155450).abs() < 1e-7, "{}", atm.price());
+
+    let model = Heston::new(0.04, 2.0, 0.05, 0.4, -0.6);
+    for is_call in [true, false] {
+        let cos = CosPricer::new(512, 12.0).price(&model, is_call, 100.0, 105.0, 0.03, 0.01, 0.75);
+        assert!((option(is_call, 100.0, model).price() - cos).abs() < 1e-7);
+    }
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    let model = Heston::new(0.04, 2.0, 0.05, 0.4, -0.6);
+    for is_call in [true, false] {
+        let o = option(is_call, 100.0, model);
+        let h = 0.01;
+        let up = option(is_call, 100.0 + h, model);
+        let down = option(is_call, 100.0 - h, model);
+        assert!((o.delta() - (up.price() - down.price()) / (2.0 * h)).abs() < 1e-6);
+        assert!((o.gamma() - (up.delta() - down.delta()) / (2.0 * h)).abs() < 1e-6);
+
+        let bump = |f: &dyn Fn(f64) -> Heston| {
+            let e = 1e-5;
+            (option(is_call, 100.0, f(e)).price() - option(is_call, 100.0, f(-e)).price())
+                / (2.0 * e)
+        };
+        let fd_v0 = bump(&|e| Heston {
+            v0: model.v0 + e,
+            ..model
+        });
+        let fd_theta = bump(&|e| Heston {
+            theta: model.theta + e,
+            ..model
+        });
+        assert!(
+            (o.vega_v0() - fd_v0).abs() < 1e-5 * fd_v0.abs(),
+            "{} {fd_v0}",
+            o.vega_v0()
+        );
+        assert!((o.vega_theta() - fd_theta).abs() < 1e-5 * fd_theta.abs());
+
+        let e = 1e-6;
+        let rate = |r: f64| HestonOption::new(is_call, 100.0, 105.0, r, 0.01, 0.75, model).price();
+        let fd_rho = 0.01 * (rate(0.03 + e) - rate(0.03 - e)) / (2.0 * e);
+        assert!((o.rho() - fd_rho).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn implied_vols_recover_bsm_and_skew() {
+    // with almost no vol of variance and v0 = theta the variance stays at 0.04
+    let flat = Heston::new(0.04, 1.0, 0.04, 1e-4, 0.0);
+    for vol in flat.smile(100.0, &[80.0, 100.0, 125.0], 0.03, 0.01, 1.0) {
+        assert!((vol - 0.2).abs() < 1e-5, "{vol}");
+    }
+
+    // parity makes the call and put vols at one strike agree
+    let model = Heston::new(0.04, 2.0, 0.05, 0.4, -0.6);
+    let call = option(true, 100.0, model);
+    let put = option(false, 100.0, model);
+    assert!((call.implied_vol() - put.implied_vol()).abs() < 1e-8);
+    assert!((call.to_bsm().price() - call.price()).abs() < 1e-12);
+
+    // negative correlation skews the smile down in strike
+    let smile = model.smile(100.0, &[80.0, 100.0, 120.0], 0.03, 0.01, 0.75);
+    assert!(smile[0] > smile[1] && smile[1] > smile[2], "{smile:?}");
+}
+
+#[test]
+fn long_run_vega_is_finite_without_long_run_variance() {
+    let model = Heston::new(0.04, 2.0, 0.0, 0.4, -0.6);
+    let o = option(true, 100.0, model);
+    let e = 1e-6;
+    let up = option(true, 100.0, Heston { theta: e, ..model });
+    let fd_theta = (up.price() - o.price()) / e;
+    assert!(o.vega_theta().is_finite() && o.vega_theta() > 0.0);
+    assert!(
+        (o.vega_theta() - fd_theta).abs() < 1e-4 * fd_theta,
+        "{fd_theta}"
+    );
+}
@@ -0,0 +1,58 @@
+use blackscholes::hybrid::StochasticRates;
+use blackscholes::OptionInputs;
+
+#[test]
+fn forward_variance_matches_quadrature() {
+    let (vol, t) = (0.2, 10.0);
+    for a in [0.0, 1e-9, 0.05, 0.5] {
+        let rates = StochasticRates::new(a, 0.01, -0.3);
+        let b = |s: f64| {
+            if a == 0.0 {
+                t - s
+            } else {
+                (1.0 - (-a * (t - s)).exp()) / a
+            }
+        };
+        let n = 20000;
+        let h = t / n as f64;
+        let integrand = |s: f64| {
+            vol * vol
+                + 2.0 * rates.correlation * vol * rates.rate_vol * b(s)
+                + (rates.rate_vol * b(s)).powi(2)
+        };
+        // Simpson's rule
+        let sum: f64 = (0..=n)
+            .map(|i| {
+                let w = if i == 0 || i == n {
+                    1.0
+                } else if i % 2 == 1 {
+                    4.0
+                } else {
+                    2.0
+                };
+                w * integrand(i as f64 * h)
+            })
+            .sum();
+        let quadrature = sum * h / 3.0;
+        assert!(
+            (rates.forward_variance(vol, t) - quadrature).abs() < 1e-10,
+            "{a}"
+        );
+    }
+}
+
+#[test]
+fn adjustment_grows_with_expiry() {
+    let rates = StochasticRates::new(0.03, 0.01, 0.2);
+    let still = StochasticRates::new(0.03, 0.0, 0.2);
+    assert_eq!(still.effective_vol(0.2, 5.0), 0.2);
+
+    let adjustment = |t: f64| rates.effective_vol(0.2, t) - 0.2;
+    assert!(adjustment(0.5) < 1e-3);
+    assert!(adjustment(10.0) > adjustment(5.0));
+
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.03, 0.01, 10.0).with_implied_vol(0.2);
+    let adjusted = rates.adjust(&option);
+    assert!(adjusted.price() > option.price());
+    assert_eq!(adjusted.implied_vol(), rates.effective_vol(0.2, 10.0));
+}
@@ -0,0 +1,100 @@
+use blackscholes::calendar::Date;
+use blackscholes::import::{CsvMapping, ExpiryFormat, ImportErrorKind, VolUnit};
+use blackscholes::{OptionInputs, Portfolio};
+
+#[test]
+fn generic_schema() {
+    let export = "symbol,quantity,type,spot,strike,expiry,iv\n\
+                  SPX C4000,1,C,4000,4000,0.25,20%\n\
+                  SPX P3800,-2,P,4000,3800,0.25,0.24\n";
+    let portfolio = Portfolio::from_csv(export.as_bytes(), &CsvMapping::default()).unwrap();
+
+    assert_eq!(portfolio.len(), 2);
+    let call = &portfolio.positions[0];
+    assert!(call.option.is_call);
+    assert_eq!(call.option.implied_vol(), 0.2);
+    let put = &portfolio.positions[1];
+    assert!(!put.option.is_call);
+    assert!((put.option.implied_vol() - 0.24).abs() < 1e-12);
+    assert_eq!(put.quantity, -2.0);
+}
+
+#[test]
+fn vol_units_are_explicit() {
+    let export = "symbol,quantity,type,spot,strike,expiry,iv\n\
+                  SPX C4000,1,C,4000,4000,0.25,20%\n\
+                  SPX P3800,-2,P,4000,3800,0.25,24\n";
+    let mapping = CsvMapping {
+        vol_unit: VolUnit::Percent,
+        ..CsvMapping::default()
+    };
+    let portfolio = Portfolio::from_csv(export.as_bytes(), &mapping).unwrap();
+    assert!((portfolio.positions[0].option.implied_vol() - 0.2).abs() < 1e-12);
+    assert!((portfolio.positions[1].option.implied_vol() - 0.24).abs() < 1e-12);
+
+    // a decimal mapping takes a bare 2.5 at its word rather than guessing
+    let export = "symbol,quantity,type,spot,strike,expiry,iv\nX,1,C,100,100,0.25,2.5\n";
+    let portfolio = Portfolio::from_csv(export.as_bytes(), &CsvMapping::default()).unwrap();
+    assert_eq!(portfolio.positions[0].option.implied_vol(), 2.5);
+}
+
+#[test]
+fn unknown_option_types_are_rejected() {
+    let export = "symbol,quantity,type,spot,strike,expiry,iv\n\
+                  X,1,put,100,100,0.25,0.2\n\
+                  Y,1,STOCK,100,100,0.25,0.2\n";
+    let err = Portfolio::from_csv(export.as_bytes(), &CsvMapping::default()).unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.kind, ImportErrorKind::InvalidOptionType("STOCK".into()));
+    assert_eq!(err.to_string(), "line 3: unknown option type `STOCK`");
+}
+
+#[test]
+fn broker_export_with_prices_and_dates() {
+    let price = OptionInputs::new(true, 50.0, 55.0, 0.04, 0.0, 30.0 / 365.25)
+        .with_implied_vol(0.35)
+        .price();
+    let export = format!(
+        "\"Description\";\"Position\";\"Right\";\"Und. Last\";\"Strike\";\"Expiry\";\"Mark\"\n\
+         \"XYZ Jan 55 Call\";\"(1,000)\";\"CALL\";\"$50.00\";55;01/31/2025;{}\n",
+        price
+    );
+
+    let mut mapping = CsvMapping {
+        delimiter: ';',
+        label: "Description".into(),
+        quantity: "Position".into(),
+        option_type: "Right".into(),
+        spot: "Und. Last".into(),
+        strike: "Strike".into(),
+        expiry: "Expiry".into(),
+        expiry_format: ExpiryFormat::Date(Date::from_ymd(2025, 1, 1).unwrap()),
+        vol: None,
+        price: Some("Mark".into()),
+        default_rate: 0.04,
+        ..CsvMapping::default()
+    };
+    let portfolio = Portfolio::from_csv(export.as_bytes(), &mapping).unwrap();
+    let position = &portfolio.positions[0];
+    assert_eq!(position.label, "XYZ Jan 55 Call");
+    assert_eq!(position.quantity, -1000.0);
+    assert!((position.option.implied_vol() - 0.35).abs() < 1e-8);
+
+    mapping.strike = "Strike Price".into();
+    let err = Portfolio::from_csv(export.as_bytes(), &mapping).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(
+        err.kind,
+        ImportErrorKind::MissingColumn("Strike Price".into())
+    );
+}
+
+#[test]
+fn date_round_trip() {
+    let d = Date::parse("2024-02-29").unwrap();
+    assert_eq!(d.ymd(), (2024, 2, 29));
+    assert_eq!(d.weekday(), 3);
+    assert_eq!(Date::parse("20240301").unwrap().days_since(d), 1);
+    assert!(Date::parse("2023-02-29").is_none());
+    assert_eq!(d.to_string(), "2024-02-29");
+}
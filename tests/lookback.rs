@@ -0,0 +1,127 @@
+use blackscholes::lookback::{LookbackOption, LookbackStrike};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+/// Monte Carlo price with the extremes between steps drawn exactly from the Brownian bridge, so
+/// that monitoring is continuous.
+fn simulate(option: &LookbackOption, paths: usize, rng: &mut StdRng) -> (f64, f64) {
+    let steps = 20;
+    let dt = option.t / steps as f64;
+    let vol = option.implied_vol;
+    let drift = (option.r - option.q - 0.5 * vol * vol) * dt;
+    let tracks_minimum = matches!(
+        (option.strike, option.is_call),
+        (LookbackStrike::Floating, true) | (LookbackStrike::Fixed(_), false)
+    );
+    let sign = if tracks_minimum { -1.0 } else { 1.0 };
+    let payoffs: Vec<f64> = (0..paths)
+        .map(|_| {
+            let mut x = option.s.ln();
+            let mut extreme = option.observed.ln();
+            for _ in 0..steps {
+                let z: f64 = rng.sample(StandardNormal);
+                let next = x + drift + vol * dt.sqrt() * z;
+                let u: f64 = rng.gen();
+                let reach = ((next - x).powi(2) - 2.0 * vol * vol * dt * u.ln()).sqrt();
+                let bridge = 0.5 * (x + next + sign * reach);
+                extreme = if tracks_minimum {
+                    extreme.min(bridge)
+                } else {
+                    extreme.max(bridge)
+                };
+                x = next;
+            }
+            let (s_t, extreme) = (x.exp(), extreme.exp());
+            let payoff = match (option.strike, option.is_call) {
+                (LookbackStrike::Floating, true) => s_t - extreme,
+                (LookbackStrike::Floating, false) => extreme - s_t,
+                (LookbackStrike::Fixed(k), true) => (extreme - k).max(0.0),
+                (LookbackStrike::Fixed(k), false) => (k - extreme).max(0.0),
+            };
+            (-option.r * option.t).exp() * payoff
+        })
+        .collect();
+    let mean = payoffs.iter().sum::<f64>() / paths as f64;
+    let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (paths - 1) as f64;
+    (mean, (variance / paths as f64).sqrt())
+}
+
+#[test]
+fn fixed_strike_reference_values() {
+    // Haug, The Complete Guide to Option Pricing Formulas: fixed-strike calls with half a year
+    // to expiry, rates and carry of 10%, and vols of 10%, 20%, and 30%
+    for (k, prices) in [
+        (95.0, [13.2687, 18.9263, 24.9857]),
+        (100.0, [8.5126, 14.1702, 20.2296]),
+    ] {
+        for (vol, expected) in [0.1, 0.2, 0.3].into_iter().zip(prices) {
+            let call = LookbackOption::new(true, 100.0, LookbackStrike::Fixed(k), 0.1, 0.0, 0.5)
+                .with_implied_vol(vol);
+            assert!(
+                (call.price() - expected).abs() < 1e-4,
+                "{k} {vol} {}",
+                call.price()
+            );
+        }
+    }
+}
+
+#[test]
+fn agrees_with_continuously_monitored_simulation() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let options = [
+        (true, LookbackStrike::Floating, 100.0),
+        (false, LookbackStrike::Floating, 112.0),
+        (true, LookbackStrike::Fixed(105.0), 100.0),
+        (true, LookbackStrike::Fixed(95.0), 108.0),
+        (false, LookbackStrike::Fixed(95.0), 100.0),
+        (false, LookbackStrike::Fixed(105.0), 92.0),
+    ];
+    for (is_call, strike, observed) in options {
+        let option = LookbackOption::new(is_call, 100.0, strike, 0.05, 0.02, 0.75)
+            .with_observed(observed)
+            .with_implied_vol(0.25);
+        let (mean, std_error) = simulate(&option, 20_000, &mut rng);
+        assert!(
+            (option.price() - mean).abs() < 4.0 * std_error,
+            "{is_call} {strike:?} {} {mean}",
+            option.price()
+        );
+    }
+}
+
+#[test]
+fn fixed_and_floating_strikes_are_related() {
+    let (s, r, q, t, vol) = (100.0, 0.05, 0.02, 1.0, 0.3);
+    let option = |is_call, strike, observed| {
+        LookbackOption::new(is_call, s, strike, r, q, t)
+            .with_observed(observed)
+            .with_implied_vol(vol)
+            .price()
+    };
+    let forward_less_strike = |k: f64| s * (-q * t).exp() - k * (-r * t).exp();
+
+    // with the strike below the maximum so far, the call is certain to pay the maximum less the
+    // final spot, a floating-strike put, plus the final spot less the strike
+    let floating_put = option(false, LookbackStrike::Floating, 110.0);
+    let fixed_call = option(true, LookbackStrike::Fixed(104.0), 110.0);
+    assert!((fixed_call - floating_put - forward_less_strike(104.0)).abs() < 1e-10);
+    let floating_call = option(true, LookbackStrike::Floating, 90.0);
+    let fixed_put = option(false, LookbackStrike::Fixed(96.0), 90.0);
+    assert!((fixed_put - floating_call + forward_less_strike(96.0)).abs() < 1e-10);
+
+    // the two fixed-strike formulas meet where the strike crosses the observed extreme
+    let below = option(true, LookbackStrike::Fixed(105.0 - 1e-9), 105.0);
+    let above = option(true, LookbackStrike::Fixed(105.0 + 1e-9), 105.0);
+    assert!((below - above).abs() < 1e-7);
+
+    // zero carry, where the formulas are singular, sits between carries either side of it
+    let carry = |q: f64| {
+        LookbackOption::new(true, s, LookbackStrike::Floating, r, q, t)
+            .with_implied_vol(vol)
+            .price()
+    };
+    let zero = carry(r);
+    assert!((zero - 0.5 * (carry(r - 1e-4) + carry(r + 1e-4))).abs() < 1e-6);
+}
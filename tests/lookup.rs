@@ -0,0 +1,32 @@
+use blackscholes::lookup::PriceTable;
+use blackscholes::OptionInputs;
+
+#[test]
+fn table_meets_its_tolerance() {
+    let table = PriceTable::with_tolerance(1e-6, 1.0, 0.02, 1.0);
+    assert!(table.max_error <= 1e-6);
+
+    for is_call in [true, false] {
+        for k in [80.0, 95.0, 100.0, 103.0, 120.0] {
+            for (t, vol) in [(0.1, 0.3), (0.5, 0.2), (1.0, 0.45)] {
+                let option = OptionInputs::new(is_call, 100.0, k, 0.03, 0.01, t);
+                let exact = option.with_implied_vol(vol).price();
+                let approx = table.price(is_call, 100.0, k, 0.03, 0.01, t, vol);
+                // the error is relative to the forward, about 100 here
+                assert!((approx - exact).abs() < 2e-4, "{approx} vs {exact}");
+            }
+        }
+    }
+}
+
+#[test]
+fn falls_back_to_exact_outside_the_table() {
+    let table = PriceTable::new(0.5, 0.05, 0.5, 64, 64);
+    // total vol of 0.01 lies below the table
+    assert!(!table.covers(0.0, 0.01));
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.0, 0.0, 0.01);
+    let exact = option.with_implied_vol(0.1).price();
+    let approx = table.price(true, 100.0, 100.0, 0.0, 0.0, 0.01, 0.1);
+    assert!((approx - exact).abs() < 1e-12);
+    assert!(table.normalized(0.6, 0.2).is_none());
+}
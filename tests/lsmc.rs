@@ -0,0 +1,42 @@
+use blackscholes::binomial::{BinomialTree, Exercise};
+use blackscholes::lsmc::{Basis, LongstaffSchwartz};
+use blackscholes::mc::MonteCarlo;
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn put() -> OptionInputs {
+    OptionInputs::new(false, 36.0, 40.0, 0.06, 0.0, 1.0).with_implied_vol(0.2)
+}
+
+#[test]
+fn american_put_matches_tree() {
+    // Longstaff and Schwartz (2001), table 1: S = 36, K = 40, r = 6%, vol = 20%, T = 1, which
+    // converged lattices put at 4.4867
+    let mc = MonteCarlo::new(20_000).with_steps(50).with_antithetic(true);
+    let tree = BinomialTree::new(2000, Exercise::American).price(&put());
+    for basis in [Basis::Laguerre(2), Basis::Monomial(3)] {
+        let lsmc = LongstaffSchwartz::new(mc, basis).price(&put(), &mut StdRng::seed_from_u64(3));
+        assert!((lsmc.price - tree).abs() < 0.03, "{} vs {tree}", lsmc.price);
+        assert!(lsmc.price > lsmc.european + 0.2);
+    }
+    assert!((tree - 4.4867).abs() < 1e-3);
+}
+
+#[test]
+fn bermudan_between_european_and_american() {
+    let mc = MonteCarlo::new(20_000).with_steps(50).with_antithetic(true);
+    let lsmc = |dates: Vec<f64>| {
+        LongstaffSchwartz::new(mc, Basis::Monomial(2))
+            .with_exercise_dates(dates)
+            .price(&put(), &mut StdRng::seed_from_u64(5))
+    };
+    // exercising only at expiry is European
+    let european = lsmc(vec![1.0]);
+    assert!((european.price - european.european).abs() < 4.0 * european.std_error);
+
+    let bermudan = lsmc(vec![0.25, 0.5, 0.75]);
+    let american = lsmc(Vec::new());
+    assert!(bermudan.price > european.price + 0.1);
+    assert!(bermudan.price < american.price);
+}
@@ -0,0 +1,42 @@
+use blackscholes::margin::SpanParameters;
+use blackscholes::{OptionInputs, Portfolio, Position};
+
+// Options on futures: BSM with the dividend yield set to the rate.
+fn futures_option(is_call: bool, k: f64) -> OptionInputs {
+    OptionInputs::new(is_call, 100.0, k, 0.03, 0.03, 0.25).with_implied_vol(0.3)
+}
+
+#[test]
+fn short_straddle_worst_case_is_extreme_or_full_move() {
+    let portfolio = Portfolio::new()
+        .with_position(Position::new("C", futures_option(true, 100.0), -1.0).with_multiplier(50.0))
+        .with_position(
+            Position::new("P", futures_option(false, 100.0), -1.0).with_multiplier(50.0),
+        );
+
+    let params = SpanParameters::new(0.1, 0.05);
+    let estimate = params.margin(&portfolio);
+
+    let scenarios = params.scenarios();
+    assert_eq!(scenarios[0].0.spot_shift, 0.0);
+    assert!((scenarios[13].0.spot_shift + 0.1).abs() < 1e-12);
+    assert_eq!(scenarios[15].1, 0.35);
+
+    // Short vol position loses most on a full move with vol up
+    assert!([10, 12].contains(&estimate.worst_scenario));
+    assert!(estimate.scan_risk > 0.0);
+    assert_eq!(estimate.requirement(), estimate.scan_risk);
+}
+
+#[test]
+fn short_option_minimum_applies_to_far_otm_shorts() {
+    let portfolio =
+        Portfolio::new().with_position(Position::new("C200", futures_option(true, 200.0), -10.0));
+    let estimate = SpanParameters::new(0.05, 0.02)
+        .with_short_option_minimum(0.5)
+        .margin(&portfolio);
+
+    assert!(estimate.scan_risk < 0.01);
+    assert_eq!(estimate.short_option_minimum, 5.0);
+    assert_eq!(estimate.requirement(), 5.0);
+}
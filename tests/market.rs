@@ -0,0 +1,70 @@
+use std::thread;
+
+use blackscholes::calendar::{Calendar, Date};
+use blackscholes::curve::RateCurve;
+use blackscholes::market::MarketContext;
+use blackscholes::surface::VolSurface;
+use blackscholes::OptionInputs;
+
+fn context() -> MarketContext {
+    let surface = VolSurface::new(
+        100.0,
+        0.0,
+        0.0,
+        vec![0.25, 1.0],
+        vec![-0.1, 0.0, 0.1],
+        vec![vec![0.25, 0.2, 0.18], vec![0.23, 0.2, 0.19]],
+    );
+    MarketContext::new(
+        Date::from_ymd(2024, 7, 1).unwrap(),
+        100.0,
+        RateCurve::new(vec![0.25, 1.0], vec![0.05, 0.04]),
+        RateCurve::flat(0.01),
+        surface,
+    )
+    .with_calendar(Calendar::new().with_holidays([Date::from_ymd(2024, 12, 25).unwrap()]))
+}
+
+#[test]
+fn handles_price_from_shared_context_across_threads() {
+    let context = context().shared();
+    let expiry = Date::from_ymd(2024, 12, 25).unwrap();
+
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            let handle = context.handle(true, 95.0 + 5.0 * i as f64, expiry);
+            thread::spawn(move || handle.price())
+        })
+        .collect();
+    let prices: Vec<f64> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+    // Christmas expiry moves back to the 24th
+    let t = 176.0 / 365.25;
+    let handle = context.handle(true, 100.0, expiry);
+    assert!((handle.t - t).abs() < 1e-12);
+
+    let r = 0.05 + (t - 0.25) / 0.75 * -0.01;
+    let f = 100.0 * ((r - 0.01) * t).exp();
+    let vol = context.surface.vol_at_moneyness((100.0 / f).ln(), t);
+    let expected = OptionInputs::new(true, 100.0, 100.0, r, 0.01, t).with_implied_vol(vol);
+    assert!((prices[1] - expected.price()).abs() < 1e-12);
+    assert!(prices.windows(2).all(|w| w[1] < w[0]));
+}
+
+#[test]
+fn business_day_arithmetic() {
+    let calendar = Calendar::new().with_holidays([Date::from_ymd(2024, 7, 4).unwrap()]);
+    let monday = Date::from_ymd(2024, 7, 1).unwrap();
+    assert_eq!(
+        calendar.business_days_between(monday, monday.add_days(7)),
+        4
+    );
+    assert_eq!(
+        calendar.add_business_days(monday, 3),
+        Date::from_ymd(2024, 7, 5).unwrap()
+    );
+    assert_eq!(
+        calendar.following(Date::from_ymd(2024, 7, 6).unwrap()),
+        Date::from_ymd(2024, 7, 8).unwrap()
+    );
+}
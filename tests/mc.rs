@@ -0,0 +1,210 @@
+use blackscholes::mc::MonteCarlo;
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn converges_to_bsm() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for is_call in [true, false] {
+        let option =
+            OptionInputs::new(is_call, 100.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+        let plain = MonteCarlo::new(100_000).price(&option, &mut rng);
+        assert!(plain.z_score().abs() < 4.0, "{plain:?}");
+        assert_eq!(plain.analytic, option.price());
+
+        let antithetic = MonteCarlo::new(100_000)
+            .with_antithetic(true)
+            .price(&option, &mut rng);
+        assert!(antithetic.z_score().abs() < 4.0, "{antithetic:?}");
+        assert!(antithetic.std_error < plain.std_error);
+    }
+}
+
+#[test]
+fn paths_cover_the_life_of_the_option() {
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    let mc = MonteCarlo::new(7).with_steps(12).with_antithetic(true);
+    let paths = mc.paths(&option, &mut StdRng::seed_from_u64(1));
+    assert_eq!(paths.len(), 7);
+    assert!(paths.iter().all(|p| p.len() == 13 && p[0] == 100.0));
+    // mirror images multiply back to the deterministic drift
+    let drift = (2.0 * (0.05 - 0.5 * 0.2 * 0.2) * 1.0f64).exp();
+    assert!((paths[0][12] * paths[1][12] / 100.0_f64.powi(2) - drift).abs() < 1e-12);
+}
+
+#[test]
+fn tiny_simulations_still_estimate_their_error() {
+    use blackscholes::asian::{AsianOption, Averaging};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 1.0).with_implied_vol(0.2);
+    let paired = MonteCarlo::new(2).with_antithetic(true);
+    assert_eq!(paired.paths(&option, &mut rng).len(), 6);
+    assert!(paired.price(&option, &mut rng).std_error.is_finite());
+
+    let asian = AsianOption::new(true, 100.0, 100.0, 0.05, 0.0, 1.0, Averaging::Discrete(4))
+        .with_implied_vol(0.3);
+    for mc in [MonteCarlo::new(1), paired] {
+        let controlled = mc.price_with_control(&asian, &mut rng);
+        assert!(controlled.std_error.is_finite(), "{controlled:?}");
+        assert!(controlled.plain_std_error.is_finite(), "{controlled:?}");
+    }
+}
+
+#[test]
+fn control_variates_cut_the_error() {
+    use blackscholes::asian::{AsianOption, Averaging};
+    use blackscholes::mc::ControlVariate;
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let option = AsianOption::new(true, 100.0, 100.0, 0.05, 0.01, 1.0, Averaging::Discrete(12))
+        .with_implied_vol(0.3);
+    assert_eq!(option.observation_times(500).len(), 12);
+    let controlled = MonteCarlo::new(20_000).price_with_control(&option, &mut rng);
+    assert!(
+        controlled.std_error < 0.05 * controlled.plain_std_error,
+        "{controlled:?}"
+    );
+    assert!((controlled.beta - 1.0).abs() < 0.1);
+
+    // the arithmetic average exceeds the geometric one, and Turnbull-Wakeman is close
+    assert!(controlled.price > option.geometric_price());
+    let approximation = option.arithmetic_price();
+    assert!((controlled.price - approximation).abs() < 0.01 * approximation);
+
+    // a continuous average is simulated on the steps' fixings, its control priced to match
+    let continuous = AsianOption::new(false, 100.0, 95.0, 0.05, 0.0, 0.5, Averaging::Continuous)
+        .with_implied_vol(0.25);
+    let discrete = AsianOption {
+        averaging: Averaging::Discrete(50),
+        ..continuous.clone()
+    };
+    assert_eq!(continuous.control_price(50), discrete.geometric_price());
+    let mc = MonteCarlo::new(20_000).with_steps(50).with_antithetic(true);
+    let price = mc.price_with_control(&continuous, &mut rng);
+    assert!(price.std_error < 0.05 * price.plain_std_error, "{price:?}");
+}
+
+#[test]
+fn bridge_weighted_barriers_are_monitored_continuously() {
+    use blackscholes::barrier::{BarrierOption, Direction, Knock};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    for knock in [Knock::Out, Knock::In] {
+        let option = BarrierOption::new(
+            true,
+            100.0,
+            100.0,
+            0.05,
+            0.0,
+            0.5,
+            Direction::Down,
+            knock,
+            92.0,
+        )
+        .with_implied_vol(0.25);
+        // a dozen steps would badly overprice a knock-out monitored only at the steps
+        let price = MonteCarlo::new(40_000)
+            .with_steps(12)
+            .price_with_control(&option, &mut rng);
+        let z_score = (price.price - option.price()) / price.std_error;
+        assert!(z_score.abs() < 4.0, "{price:?} {}", option.price());
+        // the vanilla tracks the knock-out closely but the knock-in only loosely
+        let reduction = if knock == Knock::Out { 0.5 } else { 1.0 };
+        assert!(price.std_error < reduction * price.plain_std_error);
+    }
+}
+
+#[test]
+fn correlated_paths() {
+    use blackscholes::basket::{BasketApproximation, BasketOption};
+    use blackscholes::exchange::ExchangeOption;
+    use blackscholes::mc::{cholesky, CorrelatedAssets};
+
+    let correlation = vec![
+        vec![1.0, 0.6, -0.3],
+        vec![0.6, 1.0, 0.2],
+        vec![-0.3, 0.2, 1.0],
+    ];
+    let lower = cholesky(&correlation).unwrap();
+    for i in 0..3 {
+        for j in 0..3 {
+            let product: f64 = (0..3).map(|k| lower[i][k] * lower[j][k]).sum();
+            assert!((product - correlation[i][j]).abs() < 1e-15);
+        }
+    }
+    let perfect = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+    assert_eq!(
+        cholesky(&perfect),
+        Some(vec![vec![1.0, 0.0], vec![1.0, 0.0]])
+    );
+    let invalid = vec![
+        vec![1.0, 0.9, 0.9],
+        vec![0.9, 1.0, -0.9],
+        vec![0.9, -0.9, 1.0],
+    ];
+    assert_eq!(cholesky(&invalid), None);
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let assets = CorrelatedAssets::new(
+        vec![100.0, 50.0, 80.0],
+        vec![0.2, 0.3, 0.25],
+        correlation,
+        0.03,
+        1.0,
+    )
+    .with_dividends(vec![0.01, 0.0, 0.02]);
+    let mc = MonteCarlo::new(4).with_steps(6);
+    let paths = mc.correlated_paths(&assets, &mut rng).unwrap();
+    assert_eq!(paths.len(), 4);
+    assert!(paths
+        .iter()
+        .all(|p| p.len() == 3 && p[1].len() == 7 && p[1][0] == 50.0));
+    let broken = CorrelatedAssets {
+        correlation: invalid,
+        ..assets.clone()
+    };
+    assert!(mc.correlated_paths(&broken, &mut rng).is_none());
+
+    // a best-of payoff is the second asset plus an exchange option, which prices exactly
+    let pair = CorrelatedAssets::new(
+        vec![100.0, 95.0],
+        vec![0.3, 0.2],
+        vec![vec![1.0, 0.4], vec![0.4, 1.0]],
+        0.05,
+        0.5,
+    )
+    .with_dividends(vec![0.02, 0.01]);
+    let best_of = |paths: &[Vec<f64>]| paths[0][1].max(paths[1][1]);
+    let price = MonteCarlo::new(100_000)
+        .with_antithetic(true)
+        .price_correlated(&pair, &best_of, &mut rng)
+        .unwrap();
+    assert!(price.analytic.is_nan());
+    let exchange = ExchangeOption::new(100.0, 95.0, 0.02, 0.01, 0.5, 0.3, 0.2, 0.4);
+    let exact = exchange.price() + 95.0 * (-0.01_f64 * 0.5).exp();
+    assert!(
+        ((price.price - exact) / price.std_error).abs() < 4.0,
+        "{price:?} {exact}"
+    );
+
+    // a basket reports its moment-matched price next to the simulated one
+    let basket = BasketOption::new(
+        true,
+        vec![100.0, 50.0, 80.0],
+        vec![0.5, 1.0, 0.5],
+        vec![0.2, 0.3, 0.25],
+        assets.correlation.clone(),
+        140.0,
+        0.03,
+        1.0,
+    )
+    .with_dividends(vec![0.01, 0.0, 0.02])
+    .with_approximation(BasketApproximation::ShiftedLognormal);
+    let price = MonteCarlo::new(100_000)
+        .price_correlated(&basket.assets(), &basket, &mut rng)
+        .unwrap();
+    assert_eq!(price.analytic, basket.price());
+    assert!((price.price - price.analytic).abs() < 0.01 * price.analytic + 4.0 * price.std_error);
+}
@@ -0,0 +1,57 @@
+use blackscholes::fourier::CosPricer;
+use blackscholes::merton::{Merton, MertonOption};
+use blackscholes::OptionInputs;
+
+fn option(is_call: bool, s: f64, model: Merton) -> MertonOption {
+    MertonOption::new(is_call, s, 100.0, 0.05, 0.02, 0.25, model)
+}
+
+#[test]
+fn no_jumps_is_bsm() {
+    let model = Merton::new(0.2, 0.0, -0.1, 0.15);
+    let merton = option(true, 105.0, model);
+    let bsm = OptionInputs::new(true, 105.0, 100.0, 0.05, 0.02, 0.25).with_implied_vol(0.2);
+    assert_eq!(merton.terms(), 1);
+    assert!((merton.price() - bsm.price()).abs() < 1e-10);
+    assert!((merton.delta() - bsm.delta()).abs() < 1e-10);
+    assert!((merton.implied_vol() - 0.2).abs() < 1e-8);
+}
+
+#[test]
+fn agrees_with_cos_and_parity() {
+    let model = Merton::new(0.2, 1.5, -0.1, 0.15);
+    let pricer = CosPricer::new(512, 12.0);
+    for is_call in [true, false] {
+        let cos = pricer.price(&model, is_call, 105.0, 100.0, 0.05, 0.02, 0.25);
+        assert!((option(is_call, 105.0, model).price() - cos).abs() < 1e-7);
+    }
+
+    let parity = option(true, 105.0, model).price() - option(false, 105.0, model).price();
+    let forward = 105.0 * (-0.02_f64 * 0.25).exp() - 100.0 * (-0.05_f64 * 0.25).exp();
+    assert!((parity - forward).abs() < 1e-10);
+
+    // downward jumps put a skew into short-dated implied vols
+    let low = MertonOption::new(false, 100.0, 85.0, 0.05, 0.02, 0.1, model);
+    let high = MertonOption::new(true, 100.0, 115.0, 0.05, 0.02, 0.1, model);
+    assert!(low.implied_vol() > high.implied_vol());
+}
+
+#[test]
+fn greeks_match_finite_differences() {
+    let model = Merton::new(0.25, 2.0, -0.05, 0.2);
+    for is_call in [true, false] {
+        let o = option(is_call, 100.0, model);
+        let h = 0.01;
+        let (up, down) = (
+            option(is_call, 100.0 + h, model),
+            option(is_call, 100.0 - h, model),
+        );
+        assert!((o.delta() - (up.price() - down.price()) / (2.0 * h)).abs() < 1e-6);
+        assert!((o.gamma() - (up.delta() - down.delta()) / (2.0 * h)).abs() < 1e-6);
+
+        let e = 1e-5;
+        let bump = |vol: f64| option(is_call, 100.0, Merton { vol, ..model }).price();
+        let fd = 0.01 * (bump(0.25 + e) - bump(0.25 - e)) / (2.0 * e);
+        assert!((o.vega() - fd).abs() < 1e-6);
+    }
+}
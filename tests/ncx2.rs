@@ -0,0 +1,113 @@
+use blackscholes::ncx2;
+
+// x, degrees of freedom, noncentrality, cdf, survival, and density, from a 50 digit
+// evaluation of the Poisson mixture
+const REFERENCE: [(f64, f64, f64, f64, f64, f64); 8] = [
+    (
+        1.0,
+        2.0,
+        1.0,
+        0.2671201962031798,
+        0.7328798037968203,
+        0.23287980379682022,
+    ),
+    (
+        5.0,
+        3.0,
+        2.0,
+        0.5934051800831557,
+        0.4065948199168444,
+        0.10044198178668672,
+    ),
+    (
+        50.0,
+        10.0,
+        40.0,
+        0.5287105342177714,
+        0.4712894657822287,
+        0.0295212234954492,
+    ),
+    (
+        0.1,
+        0.5,
+        3.0,
+        0.12216612404889259,
+        0.8778338759511074,
+        0.36239149282718586,
+    ),
+    (
+        400.0,
+        4.0,
+        300.0,
+        0.9953242850551889,
+        0.0046757149448111365,
+        0.0003412214024160624,
+    ),
+    (
+        250.0,
+        4.0,
+        300.0,
+        0.05482295697692074,
+        0.9451770430230793,
+        0.003518746706272074,
+    ),
+    (
+        1100.0,
+        20.0,
+        1000.0,
+        0.8943713948865141,
+        0.10562860511348597,
+        0.0027654245690401794,
+    ),
+    (
+        30.0,
+        3.0,
+        2.0,
+        0.999902375219713,
+        9.762478028696467e-05,
+        3.6701504886171695e-05,
+    ),
+];
+
+fn assert_relative(a: f64, b: f64, tol: f64) {
+    assert!((a - b).abs() <= tol * b.abs(), "{a} != {b}");
+}
+
+#[test]
+fn matches_high_precision_values() {
+    for (x, k, lambda, cdf, sf, pdf) in REFERENCE {
+        assert_relative(ncx2::cdf(x, k, lambda), cdf, 1e-11);
+        assert_relative(ncx2::sf(x, k, lambda), sf, 1e-11);
+        assert_relative(ncx2::pdf(x, k, lambda), pdf, 1e-11);
+    }
+    // no noncentrality is the central chi-square, whose cdf with 2 dof is 1 - exp(-x / 2)
+    assert_relative(ncx2::cdf(3.0, 2.0, 0.0), 1.0 - (-1.5_f64).exp(), 1e-14);
+    assert_eq!(ncx2::cdf(-1.0, 2.0, 1.0), 0.0);
+
+    // at zero the density is the limit of the two-dof central term, and the density a tiny
+    // step away agrees
+    assert_relative(ncx2::pdf(0.0, 2.0, 1.5), 0.5 * (-0.75_f64).exp(), 1e-14);
+    assert_relative(ncx2::pdf(0.0, 2.0, 1.5), ncx2::pdf(1e-12, 2.0, 1.5), 1e-9);
+    assert_eq!(ncx2::pdf(0.0, 3.0, 1.5), 0.0);
+    assert_eq!(ncx2::pdf(0.0, 1.0, 1.5), f64::INFINITY);
+}
+
+#[test]
+fn quantile_inverts_cdf() {
+    for (x, k, lambda, cdf, sf, _) in REFERENCE {
+        let p = 1.0 - sf;
+        let q = ncx2::quantile(if cdf < 0.5 { cdf } else { p }, k, lambda);
+        assert_relative(q, x, 1e-9);
+    }
+    assert_eq!(ncx2::quantile(0.0, 3.0, 2.0), 0.0);
+    assert!(ncx2::quantile(1.0, 3.0, 2.0).is_nan());
+
+    // the density integrates to the distribution function
+    let (k, lambda) = (5.0, 8.0);
+    let n = 4000;
+    let h = 20.0 / n as f64;
+    let integral: f64 = (0..n)
+        .map(|i| ncx2::pdf((i as f64 + 0.5) * h, k, lambda) * h)
+        .sum();
+    assert!((integral - ncx2::cdf(20.0, k, lambda)).abs() < 1e-6);
+}
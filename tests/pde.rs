@@ -0,0 +1,136 @@
+use blackscholes::binomial::Exercise;
+use blackscholes::pde::CrankNicolson;
+use blackscholes::OptionInputs;
+
+#[test]
+fn european_matches_bsm() {
+    for is_call in [true, false] {
+        let option =
+            OptionInputs::new(is_call, 100.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+        let grid = CrankNicolson::new(400, 200).solve(&option);
+        assert!((grid.price(100.0) - option.price()).abs() < 5e-3);
+        assert!((grid.delta(100.0) - option.delta()).abs() < 1e-3);
+        assert!((grid.gamma(100.0) - option.gamma()).abs() < 1e-4);
+        assert!((grid.theta(100.0) - option.theta()).abs() < 1e-3);
+
+        // the whole grid is available, at other spots too
+        let other = OptionInputs::new(is_call, 90.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+        assert!((grid.price(90.0) - other.price()).abs() < 5e-3);
+        assert_eq!(grid.values.len(), 201);
+    }
+}
+
+#[test]
+fn american_put_dominates_intrinsic() {
+    let put = OptionInputs::new(false, 90.0, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+    let pde = CrankNicolson::new(400, 400).with_exercise(Exercise::American);
+    let grid = pde.solve(&put);
+    assert!(grid
+        .values
+        .last()
+        .unwrap()
+        .iter()
+        .zip(&grid.spots)
+        .all(|(v, s)| *v >= (100.0 - s).max(0.0) - 1e-12));
+    assert!((grid.price(90.0) - put.price_american()).abs() < 0.08);
+    assert!(grid.price(90.0) > put.price() + 1.0);
+}
+
+#[test]
+fn concentrated_grid_resolves_the_strike() {
+    use blackscholes::pde::{concentrated_spots, SpotGrid};
+
+    let spots = concentrated_spots(0.0, 300.0, 100, &[80.0, 120.0], 5.0);
+    assert_eq!((spots.len(), spots[0], spots[100]), (101, 0.0, 300.0));
+    assert!(spots.windows(2).all(|w| w[1] > w[0]));
+    let spacing = |s: f64| {
+        let i = spots.partition_point(|&x| x < s);
+        spots[i] - spots[i - 1]
+    };
+    assert!(spacing(80.0) < 0.5 * spacing(100.0) && spacing(120.0) < 0.5 * spacing(250.0));
+
+    // a uniform grid's error swings with where the strike falls between nodes; packing the
+    // nodes around the strike keeps it small for every grid size
+    let option = OptionInputs::new(true, 98.0, 100.0, 0.03, 0.0, 0.25).with_implied_vol(0.2);
+    let worst_error = |grid| {
+        let mut worst: [f64; 3] = [0.0; 3];
+        for n in (60..=240).step_by(20) {
+            let solution = CrankNicolson::new(n, 200).with_grid(grid).solve(&option);
+            let errors = [
+                solution.price(98.0) - option.price(),
+                solution.delta(98.0) - option.delta(),
+                solution.gamma(98.0) - option.gamma(),
+            ];
+            for (w, e) in worst.iter_mut().zip(errors) {
+                *w = w.max(e.abs());
+            }
+        }
+        worst
+    };
+    let uniform = worst_error(SpotGrid::Uniform);
+    let concentrated = worst_error(SpotGrid::Concentrated { scale: 0.1 });
+    for (c, u) in concentrated.iter().zip(uniform) {
+        assert!(*c < 0.2 * u, "{concentrated:?} {uniform:?}");
+    }
+}
+
+#[test]
+fn rannacher_steps_remove_gamma_oscillations() {
+    let option = |s: f64| OptionInputs::new(true, s, 100.0, 0.05, 0.0, 0.1).with_implied_vol(0.2);
+    let worst_gamma_error = |pde: CrankNicolson| {
+        let grid = pde.solve(&option(100.0));
+        assert_eq!(grid.values.len(), 21);
+        [96.0, 98.0, 99.0, 100.0, 101.0, 102.0, 104.0]
+            .iter()
+            .map(|&s| (grid.gamma(s) - option(s).gamma()).abs())
+            .fold(0.0, f64::max)
+    };
+    // twenty steps over five weeks are long against 400 nodes
+    let plain = CrankNicolson::new(400, 20);
+    assert!(worst_gamma_error(plain) > 0.5 * option(100.0).gamma());
+    let damped = plain.with_rannacher_steps(2);
+    assert!(worst_gamma_error(damped) < 1e-3);
+
+    let price_error =
+        |pde: CrankNicolson| (pde.price(&option(100.0)) - option(100.0).price()).abs();
+    assert!(price_error(damped.with_smoothing(true)) < 0.5 * price_error(damped));
+}
+
+#[test]
+fn heston_adi_matches_the_semi_analytic_price() {
+    use blackscholes::heston::{Heston, HestonOption};
+    use blackscholes::pde::{AdiScheme, HestonAdi};
+
+    let model = Heston::new(0.04, 1.5, 0.04, 0.5, -0.7);
+    for is_call in [true, false] {
+        let option = HestonOption::new(is_call, 100.0, 100.0, 0.03, 0.01, 1.0, model);
+        for scheme in [AdiScheme::Douglas, AdiScheme::CraigSneyd] {
+            let grid = HestonAdi::new(80, 40, 40)
+                .with_scheme(scheme)
+                .solve(&option);
+            assert!((grid.price(100.0, 0.04) - option.price()).abs() < 1.5e-2);
+            let bumped = |s: f64| HestonOption::new(is_call, s, 100.0, 0.03, 0.01, 1.0, model);
+            let delta = bumped(100.5).price() - bumped(99.5).price();
+            assert!((grid.delta(100.0, 0.04) - delta).abs() < 3e-3);
+        }
+    }
+}
+
+#[test]
+fn heston_adi_american_put() {
+    use blackscholes::heston::{Heston, HestonOption};
+    use blackscholes::pde::HestonAdi;
+
+    // the benchmark of Ikonen and Toivanen
+    let model = Heston::new(0.0625, 5.0, 0.16, 0.9, 0.1);
+    let expected = [2.0, 1.107641, 0.520030, 0.213668, 0.082036];
+    let option = HestonOption::new(false, 10.0, 10.0, 0.1, 0.0, 0.25, model);
+    let grid = HestonAdi::new(100, 50, 50)
+        .with_exercise(Exercise::American)
+        .solve(&option);
+    for (s, expected) in [8.0, 9.0, 10.0, 11.0, 12.0].into_iter().zip(expected) {
+        assert!((grid.price(s, 0.0625) - expected).abs() < 2e-3);
+        let european = HestonOption::new(false, s, 10.0, 0.1, 0.0, 0.25, model).price();
+        assert!(grid.price(s, 0.0625) >= european);
+    }
+}
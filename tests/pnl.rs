@@ -0,0 +1,31 @@
+use blackscholes::pnl::{self, PathPoint};
+use blackscholes::OptionInputs;
+
+#[test]
+fn small_steps_are_explained_by_greeks() {
+    let option = OptionInputs::new(true, 100.0, 100.0, 0.0, 0.0, 0.5);
+    let path: Vec<PathPoint> = (0..20)
+        .map(|i| {
+            let x = i as f64;
+            PathPoint::new(
+                100.0 + (x * 0.7).sin(),
+                0.2 + 0.002 * (x * 0.3).cos(),
+                0.5 - x / 365.25,
+            )
+        })
+        .collect();
+
+    let steps = pnl::explain(&option, -10.0, &path);
+    assert_eq!(steps.len(), 19);
+    for step in &steps {
+        assert!(step.residual.abs() < 0.01 * step.actual.abs().max(0.01));
+    }
+
+    let total = pnl::total(&steps);
+    let start = OptionInputs::new(true, 100.0, 100.0, 0.0, 0.0, 0.5).with_implied_vol(path[0].vol);
+    let last = path.last().unwrap();
+    let end = OptionInputs::new(true, last.s, 100.0, 0.0, 0.0, last.t).with_implied_vol(last.vol);
+    assert!((total.actual + 10.0 * (end.price() - start.price())).abs() < 1e-9);
+    // short option collects time decay
+    assert!(total.theta > 0.0);
+}
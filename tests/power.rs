@@ -0,0 +1,81 @@
+use blackscholes::power::PowerOption;
+use blackscholes::{quad, OptionInputs};
+
+/// Price by integrating the payoff over the lognormal spot at expiry.
+fn integrated_price(option: &PowerOption) -> f64 {
+    let (vol, t) = (option.implied_vol, option.t);
+    let sign = if option.is_call { 1.0 } else { -1.0 };
+    let payoff = |z: f64| {
+        let s = option.s * ((option.r - option.q - 0.5 * vol * vol) * t + vol * t.sqrt() * z).exp();
+        let value = (sign * (s.powf(option.power) - option.k))
+            .max(0.0)
+            .min(option.cap);
+        value * (-0.5 * z * z).exp()
+    };
+    // split where the payoff kinks, at the strike and where it reaches the cap
+    let z = |level: f64| {
+        let s = level.powf(1.0 / option.power);
+        ((s / option.s).ln() - (option.r - option.q - 0.5 * vol * vol) * t) / (vol * t.sqrt())
+    };
+    let mut kinks = vec![-12.0, 12.0, z(option.k)];
+    if option.cap.is_finite() && option.k + sign * option.cap > 0.0 {
+        kinks.push(z(option.k + sign * option.cap));
+    }
+    kinks.sort_by(f64::total_cmp);
+    let integral: f64 = kinks
+        .windows(2)
+        .map(|w| quad::integrate_legendre(payoff, w[0], w[1], 200))
+        .sum();
+    integral / (2.0 * std::f64::consts::PI).sqrt() * (-option.r * t).exp()
+}
+
+#[test]
+fn reduces_to_vanilla() {
+    for is_call in [true, false] {
+        let power =
+            PowerOption::new(is_call, 100.0, 95.0, 0.05, 0.02, 0.5, 1.0).with_implied_vol(0.25);
+        let vanilla =
+            OptionInputs::new(is_call, 100.0, 95.0, 0.05, 0.02, 0.5).with_implied_vol(0.25);
+        assert!((power.price() - vanilla.price()).abs() < 1e-12);
+        assert!((power.delta() - vanilla.delta()).abs() < 1e-12);
+        assert!((power.vega() - vanilla.vega()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn matches_integrated_payoff() {
+    for (power, k, cap) in [
+        (2.0, 10_000.0, f64::INFINITY),
+        (2.0, 9_500.0, 2_000.0),
+        (0.5, 10.2, f64::INFINITY),
+        (-1.0, 0.011, 0.002),
+        (1.5, 1_000.0, 100.0),
+    ] {
+        for is_call in [true, false] {
+            let option = PowerOption::new(is_call, 100.0, k, 0.05, 0.02, 0.5, power)
+                .with_implied_vol(0.25)
+                .with_cap(cap);
+            let expected = integrated_price(&option);
+            assert!(
+                (option.price() - expected).abs() < 1e-7 * expected.max(1.0),
+                "{expected}"
+            );
+
+            let bumped = |s: f64, vol: f64| {
+                let mut option = option.clone();
+                option.s = s;
+                option.implied_vol = vol;
+                option.price()
+            };
+            let h = 1e-3;
+            let delta = (bumped(100.0 + h, 0.25) - bumped(100.0 - h, 0.25)) / (2.0 * h);
+            let gamma = (bumped(100.0 + h, 0.25) - 2.0 * option.price() + bumped(100.0 - h, 0.25))
+                / (h * h);
+            let vega = (bumped(100.0, 0.25 + 1e-5) - bumped(100.0, 0.25 - 1e-5)) / 2e-3;
+            let scale = expected.max(1.0);
+            assert!((option.delta() - delta).abs() < 1e-6 * scale);
+            assert!((option.gamma() - gamma).abs() < 1e-4 * scale);
+            assert!((option.vega() - vega).abs() < 1e-6 * scale);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use blackscholes::mc::MonteCarlo;
+use blackscholes::qmc::{Sobol, MAX_DIMENSION};
+use blackscholes::OptionInputs;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn sobol_points_stratify_every_dimension() {
+    let mut sobol = Sobol::new(MAX_DIMENSION);
+    let points: Vec<Vec<f64>> = (0..1024).map(|_| sobol.next_point()).collect();
+    assert_eq!(points[0][0], 0.5);
+    assert_eq!(points[1][0], 0.75);
+    assert_eq!(points[2][0], 0.25);
+
+    // with the origin the first 2^m points put exactly one in each interval of width 2^-m
+    for d in 0..MAX_DIMENSION {
+        let mut counts = [0; 1024];
+        counts[0] += 1;
+        for p in &points[..1023] {
+            assert!(p[d] > 0.0 && p[d] < 1.0);
+            counts[(p[d] * 1024.0) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c == 1), "dimension {d}");
+    }
+}
+
+#[test]
+fn quasi_random_converges_faster() {
+    let option = OptionInputs::new(true, 100.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+    let mc = MonteCarlo::new(16383);
+    let quasi = mc.price(&option, &mut Sobol::new(1));
+    let pseudo = mc.price(&option, &mut StdRng::seed_from_u64(11));
+    // a pseudo-random estimate is typically off by its standard error
+    let quasi_error = (quasi.price - quasi.analytic).abs();
+    assert!(quasi_error < 0.01, "{quasi:?}");
+    assert!(quasi_error < 0.1 * pseudo.std_error);
+
+    // paths draw one dimension per step
+    let paths = MonteCarlo::new(8)
+        .with_steps(4)
+        .paths(&option, &mut Sobol::new(4));
+    assert!(paths.iter().all(|p| p.len() == 5));
+}
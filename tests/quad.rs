@@ -0,0 +1,28 @@
+use blackscholes::quad;
+use blackscholes::PI;
+
+#[test]
+fn gaussian_rules_integrate_polynomials_exactly() {
+    let legendre = quad::gauss_legendre(8);
+    assert!((legendre.weights.iter().sum::<f64>() - 2.0).abs() < 1e-13);
+    assert!((quad::integrate_legendre(|x| x.powi(5), 0.0, 1.0, 4) - 1.0 / 6.0).abs() < 1e-14);
+
+    let hermite = quad::gauss_hermite(20);
+    assert!((hermite.weights.iter().sum::<f64>() - PI.sqrt()).abs() < 1e-13);
+    assert!((hermite.integrate(|x| x * x) - 0.5 * PI.sqrt()).abs() < 1e-13);
+    assert!(hermite.nodes.windows(2).all(|w| w[0] < w[1]));
+
+    let laguerre = quad::gauss_laguerre(12);
+    assert!((laguerre.integrate(|x| x.powi(3)) - 6.0).abs() < 1e-10);
+    assert!((laguerre.integrate(|x| x.powi(7)) - 5040.0).abs() < 1e-7);
+}
+
+#[test]
+fn normal_expectation_and_simpson() {
+    let lognormal_mean = quad::normal_expectation(f64::exp, 32);
+    assert!((lognormal_mean - 0.5_f64.exp()).abs() < 1e-13);
+
+    assert!((quad::adaptive_simpson(f64::sin, 0.0, PI, 1e-12) - 2.0).abs() < 1e-11);
+    let kink = quad::adaptive_simpson(|x: f64| x.abs().sqrt(), -1.0, 1.0, 1e-10);
+    assert!((kink - 4.0 / 3.0).abs() < 1e-6);
+}
@@ -0,0 +1,86 @@
+use blackscholes::barrier::{BarrierOption, Direction, Knock};
+use blackscholes::quad;
+use blackscholes::reflection::BrownianMotion;
+
+#[test]
+fn distributions_are_consistent() {
+    for motion in [
+        BrownianMotion::new(0.3, 0.4),
+        BrownianMotion::new(-0.2, 0.25),
+    ] {
+        let t = 0.75;
+        // the joint density integrates to the joint distribution
+        let (x, m): (f64, f64) = (0.1, 0.3);
+        let inner = |level: f64| {
+            quad::integrate_legendre(
+                |y| motion.joint_max_pdf(y, level, t),
+                -4.0,
+                x.min(level),
+                64,
+            )
+        };
+        let integrated =
+            quad::integrate_legendre(inner, 0.0, x, 64) + quad::integrate_legendre(inner, x, m, 64);
+        assert!((integrated - motion.joint_max_cdf(x, m, t)).abs() < 1e-7);
+
+        // the first passage density integrates to the probability of a hit
+        for level in [0.2, -0.15] {
+            let passage =
+                quad::integrate_legendre(|s| motion.first_passage_pdf(level, s), 0.0, t, 200);
+            assert!((passage - motion.hit_probability(level, t)).abs() < 1e-7);
+        }
+
+        // the minimum of the motion is the maximum of its negation
+        let negated = BrownianMotion::new(-motion.drift, motion.vol);
+        assert!((motion.min_cdf(-0.2, t) - (1.0 - negated.max_cdf(0.2, t))).abs() < 1e-15);
+        assert!(
+            (motion.joint_min_sf(-0.1, -0.3, t) - negated.joint_max_cdf(0.1, 0.3, t)).abs() < 1e-15
+        );
+        assert_eq!(
+            (motion.max_cdf(-0.1, t), motion.hit_probability(0.0, t)),
+            (0.0, 1.0)
+        );
+    }
+}
+
+#[test]
+fn bridge_recovers_the_hit_probability() {
+    // averaging the bridge's hit probability over where the motion ends gives the hit
+    // probability over the whole interval
+    let motion = BrownianMotion::new(0.1, 0.3);
+    let (level, t): (f64, f64) = (0.25, 0.5);
+    let sd = motion.vol * t.sqrt();
+    let density = |x: f64| {
+        let z = (x - motion.drift * t) / sd;
+        (-0.5 * z * z).exp() / (sd * (2.0 * std::f64::consts::PI).sqrt())
+    };
+    let weighted = |x: f64| density(x) * motion.bridge_hit_probability(0.0, x, level, t);
+    let integrated = quad::integrate_legendre(weighted, -3.0, level, 96)
+        + quad::integrate_legendre(weighted, level, 3.0, 96);
+    assert!((integrated - motion.hit_probability(level, t)).abs() < 1e-7);
+}
+
+#[test]
+fn touch_probability_prices_a_barrier_rebate() {
+    // without rates a knock-in's rebate is paid when the barrier is never touched
+    let option = BarrierOption::new(
+        true,
+        100.0,
+        100.0,
+        0.0,
+        0.0,
+        1.0,
+        Direction::Up,
+        Knock::In,
+        120.0,
+    )
+    .with_rebate(1.0)
+    .with_implied_vol(0.3);
+    let vanilla_part = BarrierOption {
+        rebate: 0.0,
+        ..option.clone()
+    };
+    let motion = BrownianMotion::log_spot(0.0, 0.0, 0.3);
+    let untouched = 1.0 - motion.hit_probability((120.0_f64 / 100.0).ln(), 1.0);
+    assert!((option.price() - vanilla_part.price() - untouched).abs() < 1e-12);
+}
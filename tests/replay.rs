@@ -0,0 +1,45 @@
+use blackscholes::replay::{replay, Call, Recorder};
+use blackscholes::OptionInputs;
+
+#[test]
+fn log_round_trips_and_replays() {
+    let mut recorder = Recorder::new();
+    let option = OptionInputs::new(true, 100.0, 105.0, 0.03, 0.01, 0.5).with_implied_vol(0.25);
+    let price = recorder.price(&option);
+    assert_eq!(price, option.price());
+    assert_eq!(recorder.evaluate(&option, Call::Vega), option.vega());
+    let put = OptionInputs::new(false, 100.0, 95.0, 0.03, 0.01, 0.5);
+    let vol = recorder.implied_vol(&put, 3.0);
+    assert!(vol > 0.0);
+    // a price below intrinsic admits no vol
+    assert!(recorder.implied_vol(&put, 0.0).is_nan());
+
+    let mut log = Vec::new();
+    recorder.write(&mut log).unwrap();
+    assert_eq!(log.len(), 16 + 57 * 4);
+    let read = Recorder::read(&mut log.as_slice()).unwrap();
+    assert_eq!(read.records.len(), 4);
+    assert_eq!(read.records[..3], recorder.records[..3]);
+    assert!(replay(&read.records, 0.0).is_empty());
+
+    // a changed result is reported
+    let mut records = read.records;
+    records[1].output += 1e-6;
+    let mismatches = replay(&records, 1e-9);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].index, 1);
+    assert_eq!(mismatches[0].replayed, option.vega());
+
+    assert!(Recorder::read(&mut &log[..20]).is_err());
+    assert!(Recorder::read(&mut &b"not a log at all"[..]).is_err());
+
+    // corrupt counts and call codes are errors rather than panics
+    let mut corrupt = log.clone();
+    corrupt[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+    let error = Recorder::read(&mut corrupt.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    let mut corrupt = log.clone();
+    corrupt[16] = 0xfe;
+    let error = Recorder::read(&mut corrupt.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,82 @@
+use blackscholes::risk::{self, RiskBumps, SurfaceRisk, VegaLadder};
+use blackscholes::surface::{SurfaceBump, VolSurface};
+use blackscholes::{Portfolio, Position};
+
+fn surface() -> VolSurface {
+    VolSurface::new(
+        100.0,
+        0.02,
+        0.0,
+        vec![0.25, 2.0],
+        vec![-0.2, 0.0, 0.2],
+        vec![vec![0.3, 0.2, 0.18], vec![0.26, 0.2, 0.19]],
+    )
+}
+
+fn position(surface: &VolSurface, is_call: bool, k: f64, t: f64, quantity: f64) -> Position {
+    Position::new("", surface.option(is_call, k, t), quantity)
+}
+
+#[test]
+fn parallel_bump_matches_vega() {
+    let s = surface();
+    let portfolio = Portfolio::new()
+        .with_position(position(&s, true, 100.0, 0.25, 2.0))
+        .with_position(position(&s, false, 90.0, 2.0, -1.0));
+    assert!((risk::value_on(&portfolio, &s) - portfolio.value()).abs() < 1e-10);
+
+    let risk = SurfaceRisk::new(&portfolio, &s, &RiskBumps::default());
+    assert!((risk.vega - portfolio.vega()).abs() < 1e-3 * portfolio.vega().abs());
+}
+
+#[test]
+fn skew_and_term_separate_exposures() {
+    let s = surface();
+    // long a downside put and short an upside call: long skew
+    let risk_reversal = Portfolio::new()
+        .with_position(position(&s, false, 85.0, 0.25, 1.0))
+        .with_position(position(&s, true, 115.0, 0.25, -1.0));
+    let risk = SurfaceRisk::new(&risk_reversal, &s, &RiskBumps::default());
+    assert!(risk.skew < 0.0);
+
+    // long the back month and short the front month
+    let calendar = Portfolio::new()
+        .with_position(position(&s, true, 100.0, 2.0, 1.0))
+        .with_position(position(&s, true, 100.0, 0.25, -1.0));
+    let risk = SurfaceRisk::new(&calendar, &s, &RiskBumps::default());
+    assert!(risk.term > 0.0);
+
+    let bumped = s.bumped(&SurfaceBump::TermTilt {
+        slope: 0.01,
+        pivot: 1.0,
+    });
+    assert!((bumped.vols[1][1] - 0.21).abs() < 1e-12);
+}
+
+#[test]
+fn vega_ladder_buckets() {
+    let s = surface();
+    let portfolio = Portfolio::new()
+        .with_position(position(&s, true, 100.0, 0.25, 2.0))
+        .with_position(position(&s, false, 80.0, 0.25, 1.0))
+        .with_position(position(&s, false, 100.0, 2.0, -1.0));
+    let ladder = VegaLadder::new(
+        &portfolio,
+        &s,
+        0.01,
+        vec![0.5, 1.0, 5.0],
+        vec![0.25, 0.75, 1.0],
+    );
+
+    // the 80 put is a 90-odd delta call equivalent, the at-the-money options sit in the middle
+    assert!(ladder.vega[0][2] > 0.0);
+    assert!(ladder.vega[0][1] > 0.0);
+    assert!(ladder.vega[2][1] < 0.0);
+    assert_eq!(ladder.vega[1], vec![0.0; 3]);
+    assert_eq!(ladder.vega[0][0], 0.0);
+
+    let bumped = risk::bump_risk(&portfolio, &s, &SurfaceBump::Parallel(0.01));
+    assert!((ladder.total() - bumped).abs() < 1e-10);
+    assert!((ladder.by_expiry().iter().sum::<f64>() - bumped).abs() < 1e-10);
+    assert!((ladder.by_delta()[1] - ladder.vega[0][1] - ladder.vega[2][1]).abs() < 1e-12);
+}
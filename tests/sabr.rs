@@ -0,0 +1,48 @@
+use blackscholes::compare::PricingModel;
+use blackscholes::sabr::Sabr;
+use blackscholes::surface::Smile;
+use blackscholes::OptionInputs;
+
+#[test]
+fn lognormal_without_vol_of_vol_is_flat() {
+    let model = Sabr::new(0.25, 1.0, -0.3, 0.0);
+    for k in [70.0, 100.0, 140.0] {
+        assert!((model.implied_vol(100.0, k, 2.0) - 0.25).abs() < 1e-12);
+    }
+    let contract = OptionInputs::new(true, 100.0, 110.0, 0.03, 0.01, 1.0);
+    let bsm = contract.clone().with_implied_vol(0.25).price();
+    assert!((model.price(&contract) - bsm).abs() < 1e-10);
+}
+
+#[test]
+fn atm_vol_and_continuity() {
+    let (f, t): (f64, f64) = (0.03, 5.0);
+    let model = Sabr::new(0.035, 0.5, -0.25, 0.4);
+    let (alpha, beta, rho, nu) = (model.alpha, model.beta, model.rho, model.nu);
+    let fb = f.powf(1.0 - beta);
+    let atm = alpha / fb
+        * (1.0
+            + ((1.0 - beta).powi(2) / 24.0 * alpha * alpha / (fb * fb)
+                + 0.25 * rho * beta * nu * alpha / fb
+                + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu)
+                * t);
+    assert!((model.atm_vol(f, t) - atm).abs() < 1e-14);
+
+    let near = model.implied_vol(f, f * (1.0 + 1e-7), t);
+    assert!((near - atm).abs() < 1e-7);
+
+    // negative correlation tilts the smile down in strike, vol of vol curves it up
+    let smile = model.smile(f, t);
+    assert!(smile.vol(0.02) > atm && atm > smile.vol(0.04));
+    assert!(smile.vol(0.02) + smile.vol(0.04) > 2.0 * atm);
+    assert!(smile.dvol_dk(f) < 0.0);
+}
+
+#[test]
+fn feeds_bsm_inputs() {
+    let model = Sabr::new(0.4, 0.7, -0.4, 0.6);
+    let option = model.option(false, 100.0, 90.0, 0.02, 0.0, 0.5);
+    let f = 100.0 * (0.02_f64 * 0.5).exp();
+    assert!((option.implied_vol() - model.implied_vol(f, 90.0, 0.5)).abs() < 1e-15);
+    assert!(option.price() > 0.0);
+}
@@ -0,0 +1,55 @@
+use blackscholes::{OptionInputs, Portfolio, Position, ScenarioGrid};
+
+fn straddle() -> Portfolio {
+    let call = OptionInputs::new(true, 100.0, 100.0, 0.05, 0.0, 0.25).with_implied_vol(0.2);
+    let put = OptionInputs::new(false, 100.0, 100.0, 0.05, 0.0, 0.25).with_implied_vol(0.2);
+    Portfolio::new()
+        .with_position(Position::new("C100", call, 1.0).with_multiplier(100.0))
+        .with_position(Position::new("P100", put, 1.0).with_multiplier(100.0))
+}
+
+#[test]
+fn unshifted_scenario_has_zero_pnl() {
+    let cube = ScenarioGrid::new(vec![-0.1, 0.0, 0.1], vec![-0.05, 0.0, 0.05]).run(&straddle());
+    assert_eq!(cube.pnl.len(), 9);
+    assert!(cube.get(1, 1, 0).abs() < 1e-9);
+    // Long straddle: gains on big moves and on vol up
+    assert!(cube.get(0, 1, 0) > 0.0 && cube.get(2, 1, 0) > 0.0);
+    assert!(cube.get(1, 2, 0) > 0.0 && cube.get(1, 0, 0) < 0.0);
+    assert_eq!(cube.worst().unwrap().0.vol_shift, -0.05);
+}
+
+#[test]
+fn csv_has_labeled_axes() {
+    let cube = ScenarioGrid::new(vec![-0.1, 0.1], vec![0.0])
+        .with_time_shifts(vec![0.0, 7.0])
+        .run(&straddle());
+
+    let mut long = Vec::new();
+    cube.write_csv(&mut long).unwrap();
+    let long = String::from_utf8(long).unwrap();
+    let rows: Vec<&str> = long.lines().collect();
+    assert_eq!(rows[0], "time_shift,vol_shift,spot_shift,pnl");
+    assert_eq!(rows.len(), 5);
+    assert!(rows[4].starts_with("7,0,0.1,"));
+
+    let mut slice = Vec::new();
+    cube.write_csv_slice(&mut slice, 1).unwrap();
+    let slice = String::from_utf8(slice).unwrap();
+    assert!(slice.starts_with("vol_shift\\spot_shift,-0.1,0.1\n0,"));
+
+    let mut empty = Vec::new();
+    let error = cube.write_csv_slice(&mut empty, 2).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn vol_shifts_below_zero_are_floored() {
+    // the straddle is worth about its discounted intrinsic value at no vol
+    let cube = ScenarioGrid::new(vec![-0.1], vec![-0.3]).run(&straddle());
+    let pnl = cube.get(0, 0, 0);
+    let value = straddle().value();
+    let intrinsic = 100.0 * (100.0 - 90.0 * (0.05_f64 * 0.25).exp()) * (-0.05_f64 * 0.25).exp();
+    assert!((pnl - (intrinsic - value)).abs() < 1e-6);
+}
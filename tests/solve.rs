@@ -0,0 +1,46 @@
+use blackscholes::solve::{self, SolveError};
+use blackscholes::OptionInputs;
+
+#[test]
+fn brent_finds_and_rejects() {
+    let root = solve::brent(|x| x * x * x - 2.0 * x - 5.0, 2.0, 3.0, 1e-14).unwrap();
+    assert!((root - 2.094_551_481_542_326_5).abs() < 1e-13);
+    assert_eq!(
+        solve::brent(|x| x * x + 1.0, -1.0, 1.0, 1e-12),
+        Err(SolveError::NotBracketed)
+    );
+}
+
+#[test]
+fn newton_and_halley_solve_implied_vol() {
+    let target = OptionInputs::new(true, 100.0, 110.0, 0.03, 0.01, 0.5)
+        .with_implied_vol(0.27)
+        .price();
+    let option =
+        |vol: f64| OptionInputs::new(true, 100.0, 110.0, 0.03, 0.01, 0.5).with_implied_vol(vol);
+
+    // vega is per vol point
+    let vol = solve::newton(
+        |v| {
+            let o = option(v);
+            (o.price() - target, 100.0 * o.vega())
+        },
+        0.05,
+        0.01,
+        2.0,
+        1e-12,
+    )
+    .unwrap();
+    assert!((vol - 0.27).abs() < 1e-10);
+
+    let vol = solve::halley(
+        |v| {
+            let o = option(v);
+            (o.price() - target, 100.0 * o.vega(), 100.0 * o.vomma())
+        },
+        0.3,
+        1e-12,
+    )
+    .unwrap();
+    assert!((vol - 0.27).abs() < 1e-10);
+}
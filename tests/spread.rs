@@ -0,0 +1,94 @@
+use blackscholes::correlation::CorrelationRisk;
+use blackscholes::spread::{samuelson_vol, CalendarSpreadOption, SpreadOption};
+
+fn spread(k: f64, rho: f64) -> SpreadOption {
+    SpreadOption::new(true, 122.0, 120.0, k, 0.1, 0.1, 0.2, 0.2, rho)
+}
+
+#[test]
+fn kirk_reference() {
+    // Haug, The Complete Guide to Option Pricing Formulas, table 5-2: F1 = 122, F2 = 120, K = 3,
+    // T = 0.1, r = 10%, both vols 20%, rho = -0.5 gives 4.7530
+    let option = spread(3.0, -0.5);
+    assert!((option.price() - 4.7530).abs() < 5e-4, "{}", option.price());
+}
+
+#[test]
+fn cega_and_scenarios() {
+    let option = spread(3.0, 0.5);
+    // a spread call loses value as the legs move together
+    assert!(option.cega() < 0.0);
+
+    let h = 0.01;
+    let fd = option.shift_correlation(h).price() - option.shift_correlation(-h).price();
+    assert!((option.cega() - 0.5 * fd).abs() < 1e-5);
+
+    let scenarios = option.correlation_scenarios(&[-0.2, 0.0, 0.2, 1.0]);
+    assert_eq!(scenarios[1].pnl, 0.0);
+    assert!(scenarios[0].pnl > 0.0 && scenarios[2].pnl < 0.0);
+    assert_eq!(option.shift_correlation(1.0).rho, 1.0);
+}
+
+fn calendar(is_call: bool, near: f64, far: f64, k: f64) -> CalendarSpreadOption {
+    CalendarSpreadOption::new(is_call, near, far, k, 0.04, 0.5, 0.35, 0.3, 0.95)
+}
+
+#[test]
+fn calendar_spread_deltas_and_parity() {
+    // contango: the nearby trades below the deferred, struck at a negative spread
+    for is_call in [true, false] {
+        let option = calendar(is_call, 70.0, 72.0, -1.5);
+        let h = 1e-4;
+        let near = (calendar(is_call, 70.0 + h, 72.0, -1.5).price()
+            - calendar(is_call, 70.0 - h, 72.0, -1.5).price())
+            / (2.0 * h);
+        let far = (calendar(is_call, 70.0, 72.0 + h, -1.5).price()
+            - calendar(is_call, 70.0, 72.0 - h, -1.5).price())
+            / (2.0 * h);
+        assert!((option.near_delta() - near).abs() < 1e-6);
+        assert!(
+            (option.far_delta() - far).abs() < 1e-6,
+            "{} {far}",
+            option.far_delta()
+        );
+    }
+
+    let df = (-0.04_f64 * 0.5).exp();
+    let parity =
+        calendar(true, 70.0, 72.0, -1.5).price() - calendar(false, 70.0, 72.0, -1.5).price();
+    assert!((parity - df * (70.0 - 72.0 + 1.5)).abs() < 1e-10);
+
+    // the spread is worth less the more the contracts move together
+    assert!(calendar(true, 70.0, 72.0, -1.5).cega() < 0.0);
+}
+
+#[test]
+fn samuelson_effect() {
+    assert_eq!(samuelson_vol(0.3, 0.0, 1.0, 0.5), 0.3);
+    // the nearby contract is closer to expiry, so its average vol is higher
+    let near = samuelson_vol(0.4, 1.5, 0.6, 0.5);
+    let far = samuelson_vol(0.4, 1.5, 1.1, 0.5);
+    assert!(near > far && near < 0.4);
+}
+
+#[test]
+fn implied_correlation_round_trips() {
+    let strikes = [-2.0, 0.0, 3.0, 6.0];
+    let rhos = [0.3, 0.4, 0.5, 0.65];
+    let prices: Vec<f64> = strikes
+        .iter()
+        .zip(rhos)
+        .map(|(&k, rho)| spread(k, rho).price())
+        .collect();
+    let implied = spread(0.0, 0.0).implied_correlations(&strikes, &prices);
+    for (rho, fitted) in rhos.iter().zip(implied) {
+        assert!((rho - fitted).abs() < 1e-8, "{rho} {fitted}");
+    }
+
+    // below the price at perfect correlation no correlation fits
+    let floor = spread(3.0, 1.0).price();
+    assert!(spread(3.0, 0.0).implied_correlation(0.9 * floor).is_nan());
+
+    let option = calendar(true, 70.0, 72.0, -1.5);
+    assert!((option.implied_correlation(option.price()) - 0.95).abs() < 1e-8);
+}
@@ -0,0 +1,50 @@
+use blackscholes::stir::{price_to_rate, StirModel, StirOption};
+
+#[test]
+fn price_call_is_rate_put() {
+    let option = StirOption::from_rate(false, 4.25, 4.0, 0.04, 0.5);
+    assert!(option.is_call);
+    assert!((option.futures_price - 95.75).abs() < 1e-12);
+    assert!((option.strike - 96.0).abs() < 1e-12);
+    assert!((price_to_rate(option.strike) - 4.0).abs() < 1e-12);
+
+    // the normal model gives the same premium in either space
+    let vol = 0.9;
+    let in_price = option.normal_price(vol);
+    let in_rate = option.normal_rate(vol);
+    assert!(!in_rate.is_call);
+    assert!((in_price.price() - in_rate.price()).abs() < 1e-12);
+    assert!((in_price.delta() + in_rate.delta()).abs() < 1e-12);
+    assert!((option.delta(StirModel::Normal(vol)) - in_price.delta()).abs() < 1e-12);
+}
+
+#[test]
+fn parity_in_price_space() {
+    let (f, k, r, t): (f64, f64, f64, f64) = (95.5, 95.75, 0.04, 1.0);
+    let df = (-r * t).exp();
+    for model in [
+        StirModel::BlackPrice(0.01),
+        StirModel::BlackRate(0.25),
+        StirModel::Normal(1.0),
+    ] {
+        let call = StirOption::new(true, f, k, r, t);
+        let put = StirOption::new(false, f, k, r, t);
+        let parity = call.price(model) - put.price(model);
+        assert!((parity - df * (f - k)).abs() < 1e-10, "{model:?}");
+
+        let delta = call.delta(model) - put.delta(model);
+        assert!((delta - df).abs() < 1e-10, "{model:?}");
+        assert!((call.rate_delta(model) + call.delta(model)).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn rate_delta_by_finite_difference() {
+    let option = StirOption::new(true, 96.0, 96.25, 0.03, 0.75);
+    let model = StirModel::BlackRate(0.3);
+    let h = 1e-4;
+    let up = StirOption::new(true, 96.0 - h, 96.25, 0.03, 0.75).price(model);
+    let down = StirOption::new(true, 96.0 + h, 96.25, 0.03, 0.75).price(model);
+    assert!(((up - down) / (2.0 * h) - option.rate_delta(model)).abs() < 1e-6);
+    assert!(option.price(model) > option.intrinsic());
+}
@@ -0,0 +1,153 @@
+use blackscholes::chain::{OptionChain, OptionQuote};
+use blackscholes::collar::{self, HedgeConstraints};
+use blackscholes::roll;
+use blackscholes::strategy::{IncomeYield, Strategy};
+use blackscholes::OptionInputs;
+
+fn call(k: f64, t: f64) -> OptionInputs {
+    OptionInputs::new(true, 100.0, k, 0.03, 0.0, t).with_implied_vol(0.25)
+}
+
+#[test]
+fn covered_call_greeks_and_payoff() {
+    let c = call(105.0, 0.1);
+    let strategy = Strategy::covered_call(c.clone());
+    assert!((strategy.delta() - (1.0 - c.delta())).abs() < 1e-12);
+    assert!((strategy.premium() + c.price()).abs() < 1e-12);
+    assert_eq!(strategy.payoff(110.0), 105.0);
+    assert_eq!(strategy.payoff(90.0), 90.0);
+}
+
+fn quote(k: f64, t: f64) -> OptionQuote {
+    let p = call(k, t).price();
+    OptionQuote::new(true, k, t, p - 0.05, p + 0.05)
+}
+
+#[test]
+fn roll_candidates_scored() {
+    let current = quote(105.0, 7.0 / 365.0);
+    let chain = OptionChain::new(
+        100.0,
+        0.03,
+        0.0,
+        vec![
+            current.clone(),
+            quote(105.0, 35.0 / 365.0),
+            quote(110.0, 35.0 / 365.0),
+            quote(105.0, 63.0 / 365.0),
+            OptionQuote::new(false, 95.0, 35.0 / 365.0, 1.0, 1.1),
+        ],
+    );
+
+    let candidates = roll::analyze(&chain, &current);
+    assert_eq!(candidates.len(), 3);
+    assert!(candidates
+        .windows(2)
+        .all(|w| w[0].annualized_yield >= w[1].annualized_yield));
+
+    let same_strike = candidates
+        .iter()
+        .find(|c| c.quote.k == 105.0 && c.quote.t == 35.0 / 365.0)
+        .unwrap();
+    assert!(same_strike.net_credit > 0.0);
+    // later expiry at the same OTM strike has more delta to give up
+    assert!(same_strike.delta_change < 0.0);
+    assert_eq!(same_strike.roll.legs.len(), 2);
+    assert!(same_strike.roll.premium() < 0.0);
+    let higher = candidates.iter().find(|c| c.quote.k == 110.0).unwrap();
+    assert!(higher.delta > same_strike.delta);
+}
+
+#[test]
+fn income_yields() {
+    let c = call(105.0, 0.25);
+    let premium = c.price();
+    let cc = IncomeYield::covered_call(&c, 95.0);
+    assert!((cc.static_yield - premium / 95.0).abs() < 1e-12);
+    assert!((cc.if_called_yield - (premium + 10.0) / 95.0).abs() < 1e-12);
+    assert!((cc.annualized_static_yield - 4.0 * cc.static_yield).abs() < 1e-12);
+    assert!((cc.breakeven - (95.0 - premium)).abs() < 1e-12);
+
+    let p = OptionInputs::new(false, 100.0, 95.0, 0.03, 0.0, 0.5).with_implied_vol(0.25);
+    let csp = IncomeYield::cash_secured_put(&p);
+    assert!((csp.annualized_static_yield - 2.0 * p.price() / 95.0).abs() < 1e-12);
+    assert!((csp.breakeven - (95.0 - p.price())).abs() < 1e-12);
+}
+
+fn quote_type(is_call: bool, k: f64, t: f64) -> OptionQuote {
+    let p = OptionInputs::new(is_call, 100.0, k, 0.03, 0.0, t)
+        .with_implied_vol(0.25)
+        .price();
+    OptionQuote::new(is_call, k, t, p - 0.02, p + 0.02)
+}
+
+#[test]
+fn collar_search() {
+    let t = 0.25;
+    let quotes = (80..=120)
+        .step_by(5)
+        .flat_map(|k| {
+            [
+                quote_type(true, k as f64, t),
+                quote_type(false, k as f64, t),
+            ]
+        })
+        .collect();
+    let chain = OptionChain::new(100.0, 0.03, 0.0, quotes);
+
+    let constraints = HedgeConstraints::new(90.0)
+        .with_target_cost(0.0, 1.0)
+        .with_max_delta_give_up(0.4);
+    let found = collar::collars(&chain, t, &constraints);
+    assert!(!found.is_empty());
+    for c in &found {
+        let call = c.call.as_ref().unwrap();
+        assert!(c.put.k >= 90.0 && call.k > c.put.k);
+        assert!(c.net_cost.abs() <= 1.0);
+        assert!(c.delta_give_up <= 0.4);
+    }
+    assert!(found.windows(2).all(|w| w[0].put.k >= w[1].put.k));
+    for c in &found {
+        assert_eq!(c.strategy.legs.len(), 2);
+        assert!((c.strategy.delta() - c.delta).abs() < 1e-15);
+    }
+
+    // zero-cost by default means within a hundredth of a percent of spot, so a put ask and call
+    // bid that differ only by rounding still match
+    let quotes = vec![
+        OptionQuote::new(false, 95.0, t, 2.0, 2.3),
+        OptionQuote::new(true, 110.0, t, 0.1 + 2.2, 2.6),
+    ];
+    let chain = OptionChain::new(100.0, 0.03, 0.0, quotes);
+    let zero_cost = collar::collars(&chain, t, &HedgeConstraints::new(90.0));
+    assert_eq!(zero_cost.len(), 1);
+    assert!(zero_cost[0].net_cost != 0.0 && zero_cost[0].net_cost.abs() < 1e-12);
+
+    let puts = collar::protective_puts(
+        &chain,
+        t,
+        &HedgeConstraints::new(85.0).with_target_cost(1.5, 1.5),
+    );
+    assert!(puts.iter().all(|p| p.put.ask <= 3.0 && p.call.is_none()));
+    assert!(puts.iter().all(|p| p.delta < 1.0));
+}
+
+#[test]
+fn package_implied_vol() {
+    let vol_at =
+        |k: f64, vol: f64| OptionInputs::new(true, 100.0, k, 0.03, 0.0, 0.5).with_implied_vol(vol);
+    // a call spread quoted off its legs' own vols
+    let spread = Strategy::new()
+        .with_leg(vol_at(100.0, 0.20), 1.0)
+        .with_leg(vol_at(110.0, 0.22), -1.0);
+    let vol = spread.implied_vol(spread.premium());
+    assert!(vol > 0.1 && vol < 0.2, "{vol}");
+    assert!((spread.with_vol(vol).premium() - spread.premium()).abs() < 1e-10);
+
+    let straddle = Strategy::new().with_leg(vol_at(100.0, 0.25), 1.0).with_leg(
+        OptionInputs::new(false, 100.0, 100.0, 0.03, 0.0, 0.5).with_implied_vol(0.25),
+        1.0,
+    );
+    assert!((straddle.implied_vol(straddle.premium()) - 0.25).abs() < 1e-10);
+    assert!(straddle.implied_vol(-1.0).is_nan());
+}
@@ -0,0 +1,127 @@
+use blackscholes::surface::{Smile, VolSurface};
+use blackscholes::OptionInputs;
+
+fn skewed() -> VolSurface {
+    VolSurface::new(
+        100.0,
+        0.02,
+        0.0,
+        vec![0.25, 1.0],
+        vec![-0.2, 0.0, 0.2],
+        vec![vec![0.3, 0.2, 0.18], vec![0.26, 0.2, 0.19]],
+    )
+}
+
+#[test]
+fn interpolates_smile_and_total_variance() {
+    let surface = skewed();
+    assert!((surface.vol_at_moneyness(-0.1, 0.25) - 0.25).abs() < 1e-12);
+    assert_eq!(surface.vol_at_moneyness(-0.5, 0.25), 0.3);
+    assert_eq!(surface.vol_at_moneyness(0.0, 0.5), 0.2);
+
+    let w0 = 0.3_f64.powi(2) * 0.25;
+    let w1 = 0.26_f64.powi(2) * 1.0;
+    let expected = ((w0 + (w1 - w0) / 3.0) / 0.5).sqrt();
+    assert!((surface.vol_at_moneyness(-0.2, 0.5) - expected).abs() < 1e-12);
+
+    let f = surface.forward(1.0);
+    assert!((surface.vol(f, 1.0) - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn smile_delta_matches_sticky_moneyness_bump() {
+    // Linear skew in strike, re-centred on spot under sticky moneyness
+    let skew = |k: f64, s: f64| 0.2 - 0.1 * (k / s - 1.0);
+    let (k, t, s) = (95.0, 0.5, 100.0);
+    let price = |s: f64| {
+        OptionInputs::new(false, s, k, 0.02, 0.0, t)
+            .with_implied_vol(skew(k, s))
+            .price()
+    };
+
+    let option = OptionInputs::new(false, s, k, 0.02, 0.0, t).with_implied_vol(skew(k, s));
+    let smile_delta = option.smile_delta(&|k: f64| skew(k, s));
+    let bumped = (price(s + 0.01) - price(s - 0.01)) / 0.02;
+    assert!((smile_delta - bumped).abs() < 1e-6);
+    // Negative skew raises the vol of a fixed strike as spot rallies
+    assert!(smile_delta > option.delta());
+
+    let surface = skewed();
+    let smile = surface.smile(0.25);
+    let f = surface.forward(0.25);
+    let k = 0.95 * f;
+    assert!((smile.dvol_dk(k) + 0.5 / k).abs() < 1e-6);
+}
+
+#[test]
+fn surface_from_model() {
+    use blackscholes::fourier::Gbm;
+    use blackscholes::heston::{Heston, HestonOption};
+
+    let flat = VolSurface::from_model(
+        &Gbm { vol: 0.3 },
+        100.0,
+        0.02,
+        0.01,
+        vec![0.5, 1.0],
+        vec![-0.2, 0.0, 0.2],
+    );
+    assert!(flat.vols.iter().flatten().all(|v| (v - 0.3).abs() < 1e-10));
+
+    let heston = Heston::new(0.04, 1.5, 0.04, 0.6, -0.7);
+    let surface = VolSurface::from_model(
+        &heston,
+        100.0,
+        0.02,
+        0.0,
+        vec![0.25, 1.0],
+        vec![-0.2, 0.0, 0.2],
+    );
+    // negative correlation gives a downward sloping skew
+    assert!(surface
+        .vols
+        .iter()
+        .all(|row| row[0] > row[1] && row[1] > row[2]));
+
+    // the surface reprices the model at its nodes
+    let k = surface.forward(1.0) * 0.2_f64.exp();
+    let model_price = HestonOption::new(true, 100.0, k, 0.02, 0.0, 1.0, heston).price();
+    assert!((surface.option(true, k, 1.0).price() - model_price).abs() < 1e-8);
+}
+
+#[test]
+fn live_surface_refits_locally() {
+    use blackscholes::live::LiveSurface;
+
+    let surface = VolSurface::new(
+        100.0,
+        0.0,
+        0.0,
+        vec![0.25, 1.0],
+        vec![-0.2, -0.1, 0.0, 0.1, 0.2],
+        vec![vec![0.2; 5], vec![0.2; 5]],
+    );
+    let mut live = LiveSurface::new(surface, 0.0);
+
+    // the first tick on a slice sets all of it
+    assert_eq!(live.update(100.0, 0.3, 0.25, 1.0), 0);
+    assert_eq!(live.surface.vols[0], vec![0.25; 5]);
+    assert_eq!(live.surface.vols[1], vec![0.2; 5]);
+
+    // a second tick only refits the nodes on its side of the first
+    let k = 100.0 * 0.1_f64.exp();
+    live.update(k, 0.25, 0.21, 2.0);
+    assert_eq!(live.surface.vols[0][..3], [0.25, 0.25, 0.25]);
+    assert!((live.surface.vols[0][3] - 0.21).abs() < 1e-12);
+    assert_eq!(live.updated[0], vec![1.0, 1.0, 1.0, 2.0, 2.0]);
+
+    // between the two observations the smile is interpolated
+    live.update(100.0 * (-0.1_f64).exp(), 0.25, 0.3, 3.0);
+    assert_eq!(live.updated[0], vec![3.0, 3.0, 1.0, 2.0, 2.0]);
+    assert!((live.surface.vols[0][1] - 0.3).abs() < 1e-12);
+
+    let stale = live.stale_nodes(3.5, 1.0);
+    assert_eq!(stale.len(), 3 + 5);
+    assert!(stale.contains(&(0, 3)) && stale.contains(&(1, 0)));
+    assert_eq!(live.age(1, 0, 3.5), 3.5);
+}
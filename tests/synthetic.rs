@@ -0,0 +1,37 @@
+use blackscholes::surface::VolSurface;
+use blackscholes::synthetic::{ChainGenerator, NoiseModel};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn quotes_bracket_arbitrage_free_prices() {
+    let surface = VolSurface::new(
+        100.0,
+        0.03,
+        0.01,
+        vec![0.1, 0.5],
+        vec![-0.3, 0.0, 0.3],
+        vec![vec![0.35, 0.25, 0.22], vec![0.3, 0.24, 0.22]],
+    );
+    let noise = NoiseModel::new(0.01, 0.01).with_skew(0.8).with_tick(0.05);
+    let generator = ChainGenerator::new(&surface, noise);
+    let strikes: Vec<f64> = (0..21).map(|i| 80.0 + 2.0 * i as f64).collect();
+    let quotes = generator.chain(&mut StdRng::seed_from_u64(7), &strikes, &[0.1, 0.5]);
+
+    assert_eq!(quotes.len(), 84);
+    for q in &quotes {
+        assert!(q.bid <= q.price && q.price <= q.ask);
+        assert!(((q.bid / 0.05).round() * 0.05 - q.bid).abs() < 1e-9);
+    }
+
+    // Call prices are decreasing and convex in strike
+    let calls: Vec<f64> = quotes[..42]
+        .iter()
+        .filter(|q| q.is_call)
+        .map(|q| q.price)
+        .collect();
+    for w in calls.windows(3) {
+        assert!(w[1] < w[0]);
+        assert!(w[0] - 2.0 * w[1] + w[2] > 0.0);
+    }
+}
@@ -0,0 +1,47 @@
+use blackscholes::tick::{round_to_tick, Rounding, TickTable};
+
+#[test]
+fn rounds_to_grid() {
+    assert_eq!(round_to_tick(1.234, 0.05, Rounding::Nearest), 1.25);
+    assert!((round_to_tick(1.234, 0.05, Rounding::Down) - 1.2).abs() < 1e-12);
+    assert_eq!(round_to_tick(1.201, 0.05, Rounding::Up), 1.25);
+    // 0.1 + 0.2 is not exactly 0.3 but is on the grid
+    assert!((round_to_tick(0.1 + 0.2, 0.1, Rounding::Up) - 0.3).abs() < 1e-12);
+}
+
+#[test]
+fn tiered_ticks() {
+    let table = TickTable::new(vec![(3.0, 0.05)], 0.10);
+    assert_eq!(table.tick_at(2.99), 0.05);
+    assert_eq!(table.tick_at(3.0), 0.10);
+    assert!((table.bid(2.98) - 2.95).abs() < 1e-12);
+    assert!((table.ask(2.98) - 3.0).abs() < 1e-12);
+    assert!((table.ask(3.01) - 3.1).abs() < 1e-12);
+    assert!((table.edge_in_ticks(3.03, 3.2) - 1.7).abs() < 1e-12);
+    assert!(table.edge_in_ticks(1.0, 0.9) < 0.0);
+    assert_eq!(TickTable::uniform(0.01).tick_at(100.0), 0.01);
+}
+
+#[test]
+fn quoter_widens_and_rounds_outwards() {
+    use blackscholes::quoting::{Edge, Quoter};
+    use blackscholes::OptionInputs;
+
+    let option = OptionInputs::new(true, 100.0, 105.0, 0.03, 0.0, 0.25).with_implied_vol(0.25);
+    let table = TickTable::new(vec![(3.0, 0.05)], 0.10);
+
+    let market = Quoter::new(Edge::Price(0.12), table.clone()).quote(&option);
+    assert!(market.bid_edge() >= 0.12 && market.ask_edge() >= 0.12);
+    assert!(market.bid_edge() < 0.12 + table.tick_at(market.bid));
+    assert_eq!(table.bid(market.bid), market.bid);
+    assert_eq!(table.ask(market.ask), market.ask);
+
+    let market = Quoter::new(Edge::Vol(0.01), table).quote(&option);
+    assert!(market.bid_vol <= 0.24 + 1e-9 && market.ask_vol >= 0.26 - 1e-9);
+    assert!(market.bid_vol < 0.25 && market.ask_vol > 0.25);
+
+    let far = OptionInputs::new(true, 100.0, 200.0, 0.03, 0.0, 0.1).with_implied_vol(0.2);
+    let market = Quoter::new(Edge::Price(0.05), TickTable::uniform(0.05)).quote(&far);
+    assert_eq!(market.bid, 0.0);
+    assert!(market.bid_vol.is_nan());
+}
@@ -0,0 +1,32 @@
+use blackscholes::binomial::{BinomialTree, Exercise};
+use blackscholes::trinomial::{Parameterization, TrinomialTree};
+use blackscholes::OptionInputs;
+
+#[test]
+fn european_matches_bsm_with_tree_greeks() {
+    let kr = Parameterization::KamradRitchken {
+        lambda: 1.5_f64.sqrt(),
+    };
+    for is_call in [true, false] {
+        let option =
+            OptionInputs::new(is_call, 100.0, 105.0, 0.05, 0.02, 0.75).with_implied_vol(0.3);
+        for parameterization in [Parameterization::Boyle, kr] {
+            let tree = TrinomialTree::new(1000, Exercise::European)
+                .with_parameterization(parameterization);
+            let value = tree.value(&option);
+            assert!((value.price - option.price()).abs() < 5e-3);
+            assert!((value.delta - option.delta()).abs() < 1e-3);
+            assert!((value.gamma - option.gamma()).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn american_put_converges_faster_than_binomial() {
+    let put = OptionInputs::new(false, 100.0, 100.0, 0.08, 0.0, 0.5).with_implied_vol(0.25);
+    let reference = TrinomialTree::new(4000, Exercise::American).price(&put);
+    let binomial = BinomialTree::new(200, Exercise::American).price(&put);
+    let trinomial = TrinomialTree::new(200, Exercise::American).price(&put);
+    assert!((trinomial - reference).abs() < (binomial - reference).abs());
+    assert!(trinomial > put.price() + 0.1);
+}